@@ -0,0 +1,756 @@
+# German strings for the Send UI. See locales/en-US/main.ftl.
+
+accent-color = Akzentfarbe
+accept-encoding = Accept-Encoding
+accept-encoding-hint = Wird gesendet, sofern die Anfrage keinen Accept-Encoding-Header setzt. gzip-, deflate- und br-Antworten werden entpackt.
+accept-encoding-not-sent = Nicht gesendet
+accepts-invalid-certificates = Akzeptiert ungültige und selbstsignierte Zertifikate
+active-environment = Aktive Umgebung
+add-capture-rule = + Erfassungsregel hinzufügen
+add-certificate-pin = Pin hinzufügen
+add-cookie = Cookie hinzufügen
+add-default-header = Standard-Header hinzufügen
+add-file = Datei hinzufügen
+add-header = Header hinzufügen
+add-header-assertion = Header-Prüfung hinzufügen
+add-header-preset = Vorlage hinzufügen
+add-header-row = + Header hinzufügen
+add-parameter = Parameter hinzufügen
+add-pem-client-certificate = PEM-Zertifikat hinzufügen...
+add-pkcs12-client-certificate = PKCS#12-Archiv hinzufügen...
+add-query-parameter = Query-Parameter hinzufügen
+add-session = Sitzung hinzufügen
+add-text-field = Textfeld hinzufügen
+add-token = Token hinzufügen
+add-variable = Variable hinzufügen
+all-collections = Alle Sammlungen
+always-new-connection = Immer eine neue Verbindung öffnen
+always-new-connection-hint = Überspringt gepoolte Verbindungen und TLS-Sitzungen, um eine kalte Anfrage zu messen
+api-key = API-Schlüssel
+api-key-add-to = Hinzufügen zu
+api-key-hint = Der Schlüssel wird beim Senden hinzugefügt und erscheint nicht in den Tabellen Header und Params. Mit einer {"{{"}Variable{"}}"} lässt er sich je Umgebung austauschen.
+api-key-name = Schlüssel
+appearance = Darstellung
+as-received = Wie empfangen
+ask-again-for-all = Wieder fragen
+ask-again-for-all-hint = Mit „Nicht mehr fragen“ ausgeblendete Rückfragen wieder anzeigen
+attach-json-schema = JSON-Schema anhängen...
+attach-json-schema-title = JSON-Schema anhängen
+attempt-number = Versuch { $attempt }
+auth = Auth
+auth-dialog-hint = Anfragen und Ordner mit Erben verwenden diese Authentifizierung.
+auth-dialog-title = Authentifizierung für { $name }
+auth-inherited = Verwendet { $auth } aus Ordner oder Sammlung.
+auth-menu = Authentifizierung…
+auth-overridden-by-header = Ein Authorization-Header im Tab Header überschreibt diese Zugangsdaten.
+auth-type = Typ
+average-latency = Durchschnittliche Latenz
+backoff-ms = Wartezeit (ms)
+basic-auth = Basic Auth
+basic-auth-hint = Beim Senden wird aus diesen Zugangsdaten ein Authorization: Basic-Header berechnet.
+bearer-token = Bearer-Token
+binary-body-preview = <Inhalt von { $file }>
+binary-file-missing = Die Datei kann nicht gelesen werden; die Anfrage wird ohne Body gesendet
+bindings-can-be-changed-in-settings = Tastenkürzel können in den Einstellungen geändert werden.
+body = Body
+body-editor-rows = Zeilen im Body-Editor
+body-from-file = Aus Datei
+body-from-file-hint = Den Body beim Senden aus einer Datei lesen. Die Datei wird unverändert gesendet, ohne Variablenersetzung, und ihr Inhalt wird nicht im Arbeitsbereich gespeichert.
+body-schema-hint = Der Body wird gegen dieses Schema geprüft, und beim Tippen werden Eigenschaftsnamen vorgeschlagen
+browse = Durchsuchen...
+build-client-failed = Client konnte nicht erstellt werden: { $error }
+bulk-edit = Massenbearbeitung
+bulk-edit-headers-hint = Header als Zeilen im Format Key: Value bearbeiten, z. B. um sie aus den Browser-Entwicklertools einzufügen. Mit // beginnende Zeilen sind ausgeschaltet.
+bypass-hosts = Ausgenommene Hosts
+ca-bundle-count = ({ $count } Zertifikate)
+cancel = Abbrechen
+cancel-all = Alle abbrechen
+cannot-format-json = Kein gültiges JSON: { $error }
+capture-listen-failed = Port { $port } konnte nicht geöffnet werden: { $error }
+capture-listening = Lauscht auf 127.0.0.1:{ $port }
+capture-no-environment = keine Umgebung zum Speichern ausgewählt
+capture-rules-hint = Erfassungsregeln speichern die erste Gruppe eines regulären Ausdrucks über den Rohinhalt oder einen Header in der aktiven Umgebung. Nützlich für HTML-, XML- und Textantworten. Durchsucht wird nur das erste MiB des Inhalts.
+capture-traffic = Datenverkehr mitschneiden
+capture-traffic-hint = Stelle den HTTP-Proxy einer App oder eines Browsers auf die Adresse unten ein. Unverschlüsselter HTTP-Verkehr wird aufgezeichnet; HTTPS wird ohne Aufzeichnung durchgereicht.
+capture-traffic-menu = Datenverkehr mitschneiden...
+certificate-pinned = 📌 Zertifikat gepinnt
+certificate-pins = Zertifikat-Pins
+certificate-pins-hint = Anfragen an einen gepinnten Host schlagen fehl, wenn sein Zertifikat zu keinem Pin passt. sha256/<base64> pinnt den öffentlichen Schlüssel, ein hexadezimaler SHA-256-Fingerabdruck das ganze Zertifikat.
+chunked-transfer = Chunked Transfer-Encoding
+chunked-transfer-hint = Sendet den Body ohne Content-Length. Gilt nur für HTTP/1.1; HTTP/2 hat ein eigenes Framing.
+clear = Leeren
+clear-cookies = Cookies löschen
+clear-domain-cookies = Domain leeren
+clear-history = Verlauf löschen
+clear-variables = Variablen leeren
+click-then-press-the-new-key = Klicken und dann die neue Tastenkombination drücken (Esc bricht ab)
+click-to-dismiss = Zum Schließen klicken
+client-certificates = Client-Zertifikate
+client-certificates-hint = Wird passenden Hosts vorgelegt, die gegenseitiges TLS verlangen. PEM-Schlüssel müssen unverschlüsseltes PKCS#8 sein (BEGIN PRIVATE KEY).
+client-id = Client-ID
+client-secret = Client-Secret
+collection-imported = '{ $name }' importiert: { $count } Anfragen
+collection-name = Name der Sammlung:
+collection-runner = Sammlung ausführen
+collections = Sammlungen
+colorblind-friendly = Farbenblind-freundlich
+command-palette = Befehlspalette
+concurrent-users = Gleichzeitige Benutzer
+confirm = Bestätigen
+confirm-clear-variables = Alle { $count } Variablen aus „{ $name }“ entfernen?
+confirm-delete-collection = Die Sammlung „{ $name }“ mit allen Anfragen löschen?
+confirm-delete-environment = Die Umgebung „{ $name }“ löschen?
+confirm-delete-workspace = Den Arbeitsbereich „{ $name }“ löschen? Die gespeicherte Datei bleibt erhalten.
+confirm-import-from-url = Ein Link möchte eine Sammlung von { $url } herunterladen und importieren. Fahre nur fort, wenn du dieser Quelle vertraust.
+confirm-overwrite-file = { $path } existiert bereits. Ersetzen?
+confirmations = Bestätigungen
+connection = Verbindung
+connection-errors = Verbindungsfehler
+connection-new = (neue Verbindung)
+connection-new-hint = Die Zeit enthält DNS-Auflösung, TCP-Verbindungsaufbau und gegebenenfalls den TLS-Handshake
+connection-reused = (wiederverwendete Verbindung)
+connection-reused-hint = Über eine gepoolte Verbindung gesendet; die Zeit enthält keinen DNS-, TCP- oder TLS-Aufbau
+content-encoding-decoded-hint = Empfangene Größe; der angezeigte Body ist entpackt
+content-encoding-not-decoded-hint = Diese Kodierung wurde nicht entpackt; der Body wird unverändert angezeigt
+cookie-domain = Domain
+cookie-domain-count = { $domain } ({ $count })
+cookie-expired = Abgelaufen
+cookie-jar-empty = Keine Cookies gespeichert
+cookie-jar-of = Cookie-Speicher: { $jar }
+cookie-manager = Cookies
+cookie-manager-hint = Cookies sind nach der Domain gruppiert, an die sie gesendet werden. Änderungen gelten ab der nächsten Anfrage.
+cookie-manager-menu = Cookies verwalten...
+cookie-name = Name
+cookie-session = Sitzung
+cookie-value = Wert
+cookies = Cookies
+cookies-received = Von dieser Antwort gesetzt
+copy = Kopieren
+copy-error = 📋 Fehler kopieren
+copy-link = Link kopieren
+create = Erstellen
+cron-sec-min-hour-day-month = Cron (Sek. Min. Std. Tag Monat Wochentag)
+custom = Eigene
+custom-method = Eigene...
+custom-method-hint = z. B. PROPFIND
+custom-user-agent = Eigener User-Agent
+deep-link-missing-id = Dem Anfrage-Link fehlt eine ID
+deep-link-missing-url = Dem Import-Link fehlt eine URL
+deep-link-not-send = Kein send://-Link: { $link }
+deep-link-request-not-found = Keine Anfrage mit der ID { $id } gefunden
+deep-link-unknown-action = Unbekannte Link-Aktion '{ $action }'
+default-headers = Standard-Header
+default-method = Standardmethode
+default-url = Standard-URL
+delay-between-requests = Pause zwischen Anfragen
+delete = Löschen
+delete-environment = Umgebung löschen
+desktop-notification-on-failure = Desktop-Benachrichtigung bei Fehlern
+detect-again = Erneut erkennen
+disable-tls-certificate-verification = TLS-Zertifikatsprüfung deaktivieren
+disable-tls-verification = TLS-Prüfung deaktivieren
+dont-ask-again = Nicht mehr fragen
+doubled-after-each-attempt = Verdoppelt sich nach jedem Versuch
+download-downloading = { $file } wird heruntergeladen
+download-failed = Download fehlgeschlagen: { $error }
+download-in-progress = Es läuft bereits ein Download
+download-interrupted = Download unterbrochen
+download-interrupted-error = Download unterbrochen: { $error }
+download-paused = { $file } pausiert
+download-remainder-get-only = Für den Rest wird die Anfrage erneut gesendet, daher gibt es das nur für GET- und HEAD-Anfragen. Erhöhe stattdessen die maximale Antwortgröße oder aktiviere das Speichern in einer temporären Datei und sende erneut.
+download-remainder-to-file = Rest in Datei herunterladen...
+download-response-title = Antwort herunterladen
+download-saved = { $size } unter { $path } gespeichert
+download-url-failed = { $url } konnte nicht heruntergeladen werden: { $error }
+duration-seconds = Dauer (Sekunden)
+edit = Bearbeiten
+editor = Editor
+elapsed = Vergangen
+endpoint = Endpunkt
+endpoints = Endpunkte
+enter-html-content = HTML-Inhalt eingeben...
+enter-javascript-code = JavaScript-Code eingeben...
+enter-json-data = JSON-Daten eingeben...
+enter-plain-text = Klartext eingeben...
+enter-url-supports-variable = URL eingeben (unterstützt {"{{"}variable{"}}"})...
+enter-xml-content = XML-Inhalt eingeben...
+environment = Umgebung
+environment-label = Umgebung:
+environment-name = Name der Umgebung:
+environment-not-found = Umgebung '{ $name }' nicht gefunden
+error-body-decode = Antwort-Body konnte nicht gelesen werden
+error-body-decode-hint-1 = Die Verbindung ist beim Herunterladen des Inhalts möglicherweise abgebrochen
+error-body-decode-hint-2 = Prüfe das Content-Encoding, das der Server sendet
+error-cause = Ursache
+error-certificate-pin = Zertifikat-Pin stimmt nicht überein
+error-certificate-pin-hint-1 = Etwas zwischen dir und dem Server fängt möglicherweise die Verbindung ab
+error-certificate-pin-hint-2 = Wurde das Zertifikat des Servers ausgetauscht, aktualisiere den Pin in den Arbeitsbereich-Einstellungen
+error-client = HTTP-Client konnte nicht eingerichtet werden
+error-client-hint-1 = Prüfe die Proxy-, Zertifikats- und Netzwerkeinstellungen
+error-connect = Verbindung nicht möglich
+error-connect-timeout = Zeitüberschreitung beim Verbindungsaufbau
+error-connect-timeout-hint-1 = Prüfe, ob der Host von diesem Rechner aus erreichbar ist
+error-connect-timeout-hint-2 = Prüfe die Proxy-Einstellungen unter Einstellungen oder Arbeitsbereich-Einstellungen
+error-connect-timeout-hint-3 = Eine Firewall oder ein VPN blockiert möglicherweise die Verbindung
+error-connection-refused = Verbindung abgelehnt
+error-connection-refused-hint-1 = Stelle sicher, dass der Server läuft und auf diesem Port lauscht
+error-connection-refused-hint-2 = Prüfe Port und Schema (http oder https) in der URL
+error-dns = DNS-Auflösung fehlgeschlagen
+error-dns-hint-1 = Prüfe den Hostnamen in der URL auf Tippfehler
+error-dns-hint-2 = Stelle sicher, dass die Umgebungsvariablen in der URL definiert sind
+error-dns-hint-3 = Prüfe deine Netzwerkverbindung oder DNS-Einstellungen
+error-gave-up = Nach { $attempts } Versuchen aufgegeben
+error-http-version = HTTP-Version nicht unterstützt
+error-http-version-hint-1 = Der Server hat HTTP/2 nicht akzeptiert; setze die HTTP-Version im Einstellungen-Tab der Anfrage auf Auto oder HTTP/1.1
+error-http-version-hint-2 = Klartext-Server (h2c) benötigen HTTP/2 (prior knowledge)
+error-invalid-request = Ungültige Anfrage
+error-invalid-request-hint-1 = Prüfe URL, Header-Namen und Header-Werte
+error-invalid-request-hint-2 = Stelle sicher, dass alle Umgebungsvariablen aufgelöst sind
+error-offline = Offline
+error-offline-hint-1 = Schalte den Offline-Modus in den Einstellungen aus oder speichere ein Beispiel für diese Anfrage
+error-other = Anfrage fehlgeschlagen
+error-rate = Fehlerquote
+error-things-to-try = Lösungsvorschläge
+error-timeout = Zeitüberschreitung der Anfrage
+error-timeout-hint-1 = Erhöhe das Zeitlimit im Einstellungen-Tab der Anfrage
+error-timeout-hint-2 = Der Server ist möglicherweise überlastet; versuche es später erneut
+error-tls = TLS-Fehler
+error-tls-hint-1 = Prüfe, ob das Zertifikat des Servers für diesen Host gültig ist
+error-tls-hint-2 = Füge das CA-Zertifikat in den Arbeitsbereich-Einstellungen hinzu
+error-tls-hint-3 = Nur für lokale Tests: deaktiviere die TLS-Prüfung
+error-too-many-redirects = Zu viele Weiterleitungen
+error-too-many-redirects-hint-1 = Der Server leitet möglicherweise in einer Schleife weiter
+error-too-many-redirects-hint-2 = Deaktiviere das Folgen von Weiterleitungen im Einstellungen-Tab der Anfrage, um die Weiterleitung zu untersuchen
+errors-by-status = Fehler nach Status
+every-seconds = Alle (Sekunden)
+executions = Ausführungen
+expect-continue-headers = Aus den Headern
+expect-continue-hint = Fügt den Header Expect: 100-continue hinzu oder entfernt ihn. Der Body wird in jedem Fall sofort gesendet; der Client wartet nicht auf die 100-Antwort.
+expect-continue-never = Nie senden
+expect-continue-send = Expect: 100-continue senden
+export = Exportieren...
+export-as-script = Als Skript exportieren
+export-collection = Sammlung exportieren...
+export-for-sharing = Zum Teilen exportieren
+export-for-sharing-menu = Zum Teilen exportieren...
+export-load-test-summary-title = Lasttest-Zusammenfassung exportieren
+export-named-title = '{ $name }' exportieren
+export-summary = Zusammenfassung exportieren...
+exported-named = '{ $name }' exportiert
+exported-named-as-script = '{ $name }' als Skript exportiert
+exported-to = Nach { $path } exportiert
+fail-unless-status-is-2xx = Fehlschlagen, wenn der Status nicht 2xx ist
+file = Datei
+file-kind-app-state = App-Zustand
+file-kind-collection = Sammlung
+file-kind-export = Export
+file-kind-load-test-summary = Lasttest-Zusammenfassung
+file-kind-script = Skript
+file-kind-settings = Einstellungen
+file-kind-workspace = Arbeitsbereich
+file-not-found = Datei nicht gefunden
+find = Suchen
+find-in-body = Suchen (Strg+F)
+folder-name = Name des Ordners:
+follow-redirects = Weiterleitungen folgen
+format-json = Formatieren
+generator-iso-timestamp = ISO-8601-Zeitstempel
+generator-random-int = Zufällige Ganzzahl (0-1000)
+generator-random-string = Zufällige Zeichenkette
+generator-timestamp = Unix-Zeitstempel
+generator-uuid = UUID
+header = Header
+header-assertions-hint = Prüfungen der Antwort-Header, ausgeführt nach jedem Senden sowie in Sammlungsläufen und Monitoren. Erwartete Werte unterstützen {"{{"}variable{"}}"}-Syntax.
+header-name = Header-Name
+header-name-hint = Header-Name
+header-preset-saved = Header-Vorlage "{ $name }" gespeichert
+header-presets = Header-Vorlagen
+header-presets-hint = Benannte Header-Sätze, die im Header-Tab zu jeder Anfrage hinzugefügt werden können. Bereits vorhandene Header erhalten den Wert der Vorlage.
+header-value = Header-Wert
+header-value-supports-variable = Header-Wert (unterstützt {"{{"}variable{"}}"})
+headers = Header
+help = Hilfe
+hide-the-mini-sidebar-view-menu = Mini-Seitenleiste ausblenden (über das Menü Ansicht wieder einblenden)
+history = Verlauf
+honor-retry-after-header = Retry-After-Header beachten
+http-version = HTTP-Version
+ignore-cookie-jar = Cookie-Speicher ignorieren
+ignore-cookie-jar-hint = Weder gespeicherte Cookies senden noch die von dieser Antwort gesetzten behalten
+import = Importieren
+import-ca-certificate = CA-Zertifikate importieren...
+import-ca-certificates-title = CA-Zertifikate importieren
+import-client-certificate-title = Client-Zertifikat importieren
+import-collection = Sammlung importieren...
+import-collection-title = Sammlung importieren
+import-curl = curl-Befehl importieren
+import-curl-menu = curl-Befehl importieren...
+import-file-failed = { $path } konnte nicht importiert werden: { $error }
+import-from-curl = Aus curl importieren...
+import-from-openapi = Aus OpenAPI importieren...
+import-from-openapi-hint = Ein OpenAPI-3- oder Swagger-2-Dokument im JSON-Format
+import-from-postman = Aus Postman importieren...
+import-from-postman-hint = Eine aus Postman exportierte Sammlung (JSON v2.0 oder v2.1)
+inherit-auth = Von übergeordnetem Element erben
+insert-dynamic-variable = Dynamische Variable einfügen
+insert-value = Wert einfügen
+interface-binding-unsupported = Die Bindung an eine Netzwerkschnittstelle wird auf dieser Plattform nicht unterstützt
+invalid-and-self-signed-certificates-will = Ungültige und selbstsignierte Zertifikate werden akzeptiert
+invalid-ca-certificate = Ungültiges CA-Zertifikat '{ $name }': { $error }
+invalid-client-certificate = Ungültiges Client-Zertifikat { $name }: { $error }
+invalid-method = "{ $method }" ist keine gültige HTTP-Methode
+invalid-pem-certificate = Ungültiges PEM-Zertifikat in { $name }: { $error }
+invalid-proxy-configuration = Ungültige Proxy-Konfiguration: { $error }
+invalid-source-address = Ungültige Quelladresse '{ $address }': { $error }
+invalid-system-proxy = Ungültiger System-Proxy: { $error }
+ip-preference-auto = Automatisch
+ip-preference-ipv4-only = Nur IPv4
+ip-preference-ipv6-only = Nur IPv6
+ip-preference-prefer-ipv4 = IPv4 bevorzugen
+ip-preference-prefer-ipv6 = IPv6 bevorzugen
+ip-version = IP-Version
+ip-version-label = IP-Version:
+iterations = Durchläufe
+json-syntax-error = ⚠ Zeile { $line }, Spalte { $column }: { $message }
+jwt-claims = Claims
+jwt-expired = (abgelaufen)
+jwt-expires = Läuft ab
+jwt-expires-in = (in { $duration })
+jwt-header = Header
+jwt-issued = Ausgestellt
+jwt-not-before = Nicht vor
+keep-the-sidebar-open = Seitenleiste geöffnet lassen
+key = Schlüssel
+keyboard-shortcuts = Tastenkürzel
+language = Sprache
+language-system = Systemstandard
+larger-responses = Größere Antworten
+latency-limit-ms-0-none = Latenzgrenze (ms, 0 = keine)
+latency-min-max = Latenz min. / max.
+latency-p50-p95-p99 = Latenz p50 / p95 / p99
+layout = Layout
+layout-side-by-side = Nebeneinander
+layout-single = Ein Bereich
+layout-stacked = Anfrage über Antwort
+links = Links
+listen-on-127-0-0-1 = Auf 127.0.0.1 lauschen, Port
+load-file-failed = { $path } konnte nicht geladen werden: { $error }
+load-test = Lasttest
+load-test-current-request = Aktuelle Anfrage
+load-test-limit-count = Anzahl Anfragen
+load-test-limit-duration = Dauer
+load-test-menu = Lasttest...
+load-test-selected-folder = Ausgewählter Ordner
+load-test-summary-exported = Lasttest-Zusammenfassung exportiert
+load-test-target = Ziel: { $name } ({ $count } Anfragen)
+load-workspace = Arbeitsbereich laden...
+load-workspace-title = Arbeitsbereich laden
+loaded-workspace = Geladener Arbeitsbereich
+local-file-paths-in-form-data = Lokale Dateipfade in Form-Data werden entfernt.
+manage-header-presets = Vorlagen verwalten...
+max-concurrent-requests = Max. gleichzeitige Anfragen
+max-history-entries = Max. Verlaufseinträge
+max-redirects = Max. Weiterleitungen
+max-response-size-mb-0-unlimited = Max. Antwortgröße (MB, 0 = unbegrenzt)
+max-wait-secs = Max. Wartezeit (s)
+maximum-tls-version = Höchste TLS-Version
+method-colors = Methodenfarben
+metrics-all-time = Gesamter Zeitraum
+metrics-last-day = Letzte 24 Stunden
+metrics-last-hour = Letzte Stunde
+metrics-last-month = Letzte 30 Tage
+metrics-last-week = Letzte 7 Tage
+mini-sidebar = Mini-Seitenleiste
+minify-json = Minimieren
+minimum-tls-version = Niedrigste TLS-Version
+missing-request-name = Anfrage { $id }
+monitor = Monitor
+monitor-current-request = Aktuelle Anfrage überwachen
+monitor-expected-success = 2xx erwartet, { $status } erhalten
+monitor-failed-notification = Monitor „{ $name }“ fehlgeschlagen
+monitor-failing = ✖ Fehlgeschlagen
+monitor-last-results = Letzte { $count } Ergebnisse
+monitor-not-run-yet = Noch nicht ausgeführt
+monitor-passing = ✔ Erfolgreich
+monitor-selected-folder = Ausgewählten Ordner überwachen
+monitor-too-slow = Dauerte { $time } ms (Grenze { $limit } ms)
+monitors = Monitore
+monitors-menu = Monitore...
+monitors-paused = ⏸ Monitore pausiert
+monitors-paused-offline = Monitore sind pausiert, solange der Offline-Modus an ist
+monitors-run-while-the-app-is = Monitore laufen, solange die App geöffnet ist.
+move-file-failed = Datei konnte nicht an ihren Platz verschoben werden: { $error }
+name = Name
+name-requests-from-url = Anfragen nach URL benennen
+negotiated-http-version = Mit dem Server ausgehandelte HTTP-Version
+network = Netzwerk
+network-interface = Netzwerkschnittstelle:
+network-interface-hint = z. B. eth0, utun3 (leer = automatisch)
+new-collection = Neue Sammlung
+new-environment = Neue Umgebung
+new-folder = Neuer Ordner
+new-request = Neue Anfrage
+new-requests = Neue Anfragen
+new-workspace = Neuer Arbeitsbereich
+no-assertions = Diese Anfrage hat keine Prüfungen. Sie lassen sich im Tab „Tests“ hinzufügen.
+no-auth = Keine Authentifizierung
+no-auth-hint = Diese Anfrage sendet keine eigenen Zugangsdaten. Ein Token aus dem Token-Manager gilt weiterhin.
+no-cookies-received = Diese Antwort hat keine Cookies gesetzt
+no-environment = Keine Umgebung
+no-failures = Keine Fehler.
+no-file-selected = Keine Datei ausgewählt
+no-header-presets = Noch keine Vorlagen
+no-matches = Keine Treffer
+no-matching-commands = Keine passenden Befehle
+no-monitors-yet = Noch keine Monitore.
+no-pem-certificates = Keine PEM-Zertifikate in { $name } gefunden
+no-recent-workspaces = Keine zuletzt verwendeten Arbeitsbereiche
+no-requests-sent-yet = Noch keine Anfragen gesendet.
+no-response = Keine Antwort
+no-response-yet-send-a-request = Noch keine Antwort. Sende eine Anfrage, um die Antwort hier zu sehen.
+no-runs-in-range = Keine Ausführungen in diesem Zeitraum.
+no-session = Keine Sitzung
+no-sessions = Noch keine Sitzungen
+no-system-proxy-detected = Kein System-Proxy erkannt
+no-token = Kein Token
+no-tokens = Noch keine Tokens
+normalized-url = Gesendet als:
+ntlm-auth = Windows (NTLM)
+ntlm-domain = Domäne
+ntlm-hint = Führt einen NTLMv2-Handshake für Intranet-Server wie IIS durch. NTLM über Negotiate sendet denselben Austausch im Negotiate-Schema für Server, die nur Negotiate anbieten. Kerberos wird nicht unterstützt; Server, die Kerberos verlangen, lehnen die Anfrage ab.
+ntlm-over-negotiate = NTLM über Negotiate
+ntlm-scheme = Schema
+oauth-clear-token = Token verwerfen
+oauth-hint = Beim ersten Senden wird ein Token geholt und bis kurz vor Ablauf wiederverwendet.
+oauth-token-cached = Token zwischengespeichert
+oauth-token-expired = Zwischengespeichertes Token abgelaufen; beim nächsten Senden wird ein neues geholt
+oauth-token-expires-in = Token zwischengespeichert, läuft in { $seconds } s ab
+oauth-token-not-fetched = Noch kein Token geholt
+oauth2-client-credentials = OAuth 2.0 Client Credentials
+off = Aus
+offline-capture-skipped = Der Offline-Modus ist an; schalte ihn aus, um Verkehr aufzuzeichnen
+offline-import-skipped = Der Offline-Modus ist an; Import übersprungen
+offline-mode = Offline-Modus
+offline-mode-badge = 📴 Offline-Modus
+offline-mode-hint = Netzwerkzugriffe blockieren und gespeicherte Beispiele liefern
+offline-mode-is-on = Der Offline-Modus ist an
+offline-no-example = Der Offline-Modus ist an und diese Anfrage hat kein gespeichertes Beispiel
+offline-no-saved-example = Offline-Modus: kein gespeichertes Beispiel
+ok = OK
+on = An
+only-hosts-containing = Nur Hosts mit
+open-file-failed = Datei konnte nicht geöffnet werden: { $error }
+open-sample-collection = Beispielsammlung hinzufügen
+open-send = Send öffnen
+override = Überschreiben
+override-application-proxy = Proxy-Einstellungen der App überschreiben
+override-workspace-proxy = Arbeitsbereich-Proxy überschreiben
+override-workspace-retry-policy = Wiederholungsrichtlinie des Arbeitsbereichs überschreiben
+overwrite = Überschreiben
+palette-capture-traffic = Datenverkehr aufzeichnen
+palette-environment = Umgebung: { $name }
+palette-export-collection = Sammlung exportieren
+palette-export-for-sharing = Zum Teilen exportieren
+palette-import-collection = Sammlung importieren
+palette-load-test = Lasttest
+palette-load-workspace = Arbeitsbereich laden
+palette-monitors = Monitore
+palette-new-collection = Neue Sammlung
+palette-new-environment = Neue Umgebung
+palette-new-folder = Neuer Ordner
+palette-new-workspace = Neuer Arbeitsbereich
+palette-offline-mode-off = Offline-Modus ausschalten
+palette-offline-mode-on = Offline-Modus einschalten
+palette-open-request = Öffnen: { $request }
+palette-run-collection = Sammlung ausführen
+palette-save-workspace = Arbeitsbereich speichern
+palette-settings = Einstellungen
+palette-show-collections = Sammlungen anzeigen
+palette-show-environment = Umgebung anzeigen
+palette-show-history = Verlauf anzeigen
+palette-show-tools = Werkzeuge anzeigen
+palette-workspace = Arbeitsbereich: { $name }
+palette-workspace-settings = Arbeitsbereich-Einstellungen
+parameter-name = Parametername
+parameter-name-hint = Parametername
+parameter-value = Parameterwert
+parameter-value-supports-variable = Parameterwert (unterstützt {"{{"}variable{"}}"})
+params = Parameter
+part-content-type-hint = Standard für den Teil
+part-headers = Teil-Header ({ $count })
+pass-rate = Erfolgsquote
+passphrase = Passphrase
+password = Passwort
+paste-a-curl-command = curl-Befehl einfügen:
+path-variables = Pfadvariablen
+path-variables-hint = Aus den Segmenten :name und {"{"}name{"}"} des URL-Pfads
+pause = Pausieren
+pause-monitors = ⏸ Monitore pausieren
+pem-certificate-filter = PEM-Zertifikat
+pem-private-key-filter = Privater PEM-Schlüssel
+persist-cookies = Cookies in der Arbeitsbereichsdatei behalten
+persist-cookies-hint = Cookies bleiben nach einem Neustart erhalten. Für Secure- und HttpOnly-Cookies gibt es die Option darunter. Werte werden in der Datei maskiert, nicht verschlüsselt, und nie geteilt.
+persist-login-cookies = Auch Secure- und HttpOnly-Cookies behalten
+persist-login-cookies-off = Secure- und HttpOnly-Cookies werden nicht gespeichert und sind nach einem Neustart weg.
+persist-login-cookies-warning = ⚠ Login-Cookies werden maskiert, nicht verschlüsselt, in die Arbeitsbereichsdatei geschrieben. Wer die Datei lesen kann, kann sich als du anmelden.
+pin-sidebar-open = Seitenleiste anheften
+pkcs12-archive-filter = PKCS#12-Archiv
+preset-name = Name der Vorlage
+press-keys = Tasten drücken...
+preview-ntlm-handshake = <aus dem NTLM-Handshake beim Senden>
+preview-oauth-token = <wird beim Senden abgerufen>
+preview-request = Anfrage-Vorschau
+preview-url = URL:
+proxy = Proxy
+proxy-auth = Authentifizierung
+proxy-bypassed-for = Umgangen für: { $hosts }
+proxy-ntlm-hint = Benutzername als DOMÄNE\benutzer
+proxy-routes-all = Alle Anfragen laufen über diesen Proxy
+proxy-url = Proxy-URL
+query-string = Query-String
+queue-empty = Es wird nichts gesendet
+queue-in-flight = Läuft
+queue-pending = Wartet
+quit = Beenden
+read-file-failed = { $name } konnte nicht gelesen werden: { $error }
+read-key-failed = Der Schlüssel konnte nicht gelesen werden: { $error }
+read-schema-failed = Schema { $path } konnte nicht gelesen werden: { $error }
+recent-workspaces = Zuletzt verwendete Arbeitsbereiche
+redirect-limit-reached = Beim Weiterleitungslimit angehalten; diese Antwort ist selbst eine Weiterleitung.
+redirects = Weiterleitungen
+redo = Wiederholen
+refresh = Aktualisieren
+register-send-links = send://-Links registrieren
+remove-all-variable-values = Aktuelle Werte aller Variablen entfernen
+remove-schema = Schema entfernen
+request = Anfrage
+request-body = Anfrage-Body
+request-cancelled = Anfrage abgebrochen
+request-failed = Anfrage fehlgeschlagen: { $error }
+request-name = Name der Anfrage:
+request-no-longer-exists = Die Anfrage existiert nicht mehr
+request-queue = Anfragewarteschlange
+request-size-body = Body: { $body }
+request-size-headers = Anfragezeile und Header: { $headers }
+request-size-hint = Ungefähre Größe der zu sendenden Anfrage
+requests = Anfragen
+requests-are-not-sent-responses-come = Anfragen werden nicht gesendet; Antworten stammen aus gespeicherten Beispielen. In den Einstellungen ausschalten
+requests-captured = { $count } Anfragen aufgezeichnet
+requests-per-second-0-unlimited = Anfragen pro Sekunde (0 = unbegrenzt)
+requests-renamed = {$count} Anfragen umbenannt
+reset = Zurücksetzen
+response = Antwort
+response-body-size = Inhalt: { $size }
+response-headers-size = Header: { $size }
+response-retries = ↻ { $count } Wiederholungen
+response-size = Größe: { $size }
+response-spooled = ⓘ Body mit { $size } in einer temporären Datei gespeichert; nur Anfang und Ende werden angezeigt
+response-status = Status: { $status } { $text }
+response-time-ms = Zeit: { $ms } ms
+response-truncated = ⚠ Antwort bei { $size } abgeschnitten
+restore-defaults = Standardwerte wiederherstellen
+resume = Fortsetzen
+resume-monitors = ▶ Monitore fortsetzen
+retries = Wiederholungen
+retry = Erneut versuchen
+retry-icon = ↻ Erneut versuchen
+retry-on = Wiederholen bei:
+retry-with-modified-headers-menu = Mit geänderten Headern wiederholen...
+retry-with-modified-headers-title = Mit geänderten Headern wiederholen
+retrying-in = ⏳ Neuer Versuch in {$seconds} s ({$attempt}/{$max})
+retrying-status-in = ⏳ {$status}, neuer Versuch in {$seconds} s ({$attempt}/{$max})
+revert-changes = Verwerfen
+right-click-a-request-to-copy = Rechtsklick auf eine Anfrage kopiert ihren Link
+run = Ausführen
+run-collection = Sammlung ausführen...
+run-in-background = Im Hintergrund weiterlaufen
+run-in-background-hint = Das Schließen des Fensters minimiert es, solange Monitore geplant sind. Mit Datei > Beenden wird die App beendet.
+run-metrics = Ausführungsmetriken
+run-metrics-menu = Ausführungsmetriken...
+run-metrics-source = Ausführungen von Sammlungen und Monitoren
+run-now = Jetzt ausführen
+running-in-background = Send führt Monitore im Hintergrund weiter aus. Mit Datei > Beenden wird die App beendet.
+runs = Ausführungen
+save = Speichern
+save-as-collection = Als Sammlung speichern
+save-as-example = Als Beispiel speichern
+save-body-as = Body speichern unter...
+save-file-failed = { $what } konnte nicht unter { $path } gespeichert werden: { $error }
+save-headers-as-preset = Header als Vorlage speichern
+save-request-to-collection-first = Speichere die Anfrage zuerst in einer Sammlung
+save-response-body-title = Antwortinhalt speichern
+save-workspace = Arbeitsbereich speichern...
+save-workspace-title = Arbeitsbereich speichern
+saved-example-badge = 📄 Gespeichertes Beispiel
+schedule = Zeitplan
+schema-missing-field = { $path }: Pflichtfeld '{ $name }' fehlt
+schema-not-in-enum = { $path }: muss einer der Werte { $options } sein
+schema-unknown-field = { $path }: unbekanntes Feld
+schema-wrong-type = { $path }: { $expected } erwartet, { $found } gefunden
+scopes = Scopes
+secret-variables-are-blanked = Geheime Variablen werden geleert.
+secret-variables-are-masked-and-blanked = Geheime Variablen werden maskiert und beim Export zum Teilen geleert
+select-a-collection-or-folder-to = Wähle eine Sammlung oder einen Ordner zum Ausführen.
+select-file-title = Datei auswählen
+select-private-key-title = Privaten Schlüssel auswählen
+select-text-in-the-response-body = Zuerst Text im Antwortinhalt markieren
+selected-collection = Ausgewählte Sammlung
+selected-requests = {$count} ausgewählte Anfragen
+send = Senden
+send-and-download = Senden und herunterladen...
+send-in-parallel = ▶ Parallel senden
+send-links-registered = send://-Links öffnen sich jetzt in dieser App
+send-on-new-connection = Über neue Verbindung senden
+send-repeatedly = Wiederholt senden (Runner-Einstellungen)
+send-selected-in-parallel = {$count} ausgewählte parallel senden
+send-this-row = Diesen Eintrag senden; nicht angehakte Einträge bleiben erhalten, werden aber nicht gesendet
+sensitive-headers-are-removed = Sensible Header-Werte (Authorization, Cookie, Tokens) werden entfernt.
+sent-with-every-request-unless-the = Wird mit jeder Anfrage gesendet, sofern die Anfrage denselben Header nicht selbst setzt.
+server-responded-with = Der Server antwortete mit { $status }
+session-cookies = Cookies ({ $count })
+session-label = Sitzung:
+session-token-shared = Gemeinsamer Wert
+session-tokens = Tokens
+sessions = Sitzungen
+sessions-hint = Jede Sitzung hat eigene Cookies und Token-Werte. Von Antworten gesetzte Cookies werden in der aktiven Sitzung gespeichert und bei passenden Anfragen zurückgesendet.
+sessions-menu = Sitzungen...
+settings = Einstellungen
+settings-menu = Einstellungen...
+shortcut-close-request = Anfrage schließen
+shortcut-command-palette = Befehlspalette
+shortcut-new-request = Neue Anfrage
+shortcut-save = Anfrage speichern
+shortcut-send = Anfrage senden
+shortcut-show-shortcuts = Tastenkürzel anzeigen
+shortcut-switch-environment = Umgebung wechseln
+show-compressed-bytes = Empfangene Bytes anzeigen
+signer-algorithm = Algorithmus
+signer-dialog-title = Anfragesignierung für { $name }
+signer-enabled = Anfragen signieren
+signer-headers = Zu setzende Header
+signer-hint = Signiert jede Anfrage dieser Sammlung vor dem Senden. Der zu signierende Text und die Header-Werte können {"{"}method{"}"}, {"{"}url{"}"}, {"{"}host{"}"}, {"{"}path{"}"}, {"{"}query{"}"}, {"{"}body{"}"}, {"{"}body_sha256{"}"}, {"{"}timestamp{"}"}, {"{"}timestamp_ms{"}"}, {"{"}date{"}"}, {"{"}iso_date{"}"}, {"{"}nonce{"}"} und {"{"}header:Name{"}"} verwenden; Header-Werte zusätzlich {"{"}signature{"}"}. Multipart-Bodys gelten als leer.
+signer-menu = Anfragesignierung…
+signer-remove = Signierung entfernen
+signer-secret = Geheimnis
+signer-string-to-sign = Zu signierender Text
+skipped = Übersprungen
+soft-wrap = Zeilen umbrechen
+source-address = Quelladresse:
+source-address-hint = z. B. 192.168.1.20 (leer = automatisch)
+spool-file-failed = Die Antwort konnte nicht in eine temporäre Datei geschrieben werden: { $error }
+start = Starten
+status-auto-save-on = Automatisches Speichern aktiv
+status-badges = Statussymbole anzeigen
+status-badges-hint = Statuscodes mit ✔ ↪ ⚠ ✖ versehen, damit die Statusklasse auch ohne Farben erkennbar ist
+status-colors = Statusfarben
+status-cookies = Cookies: { $count }
+status-cookies-hint = Cookies, die die aktuelle Anfrage an ihren Host sendet
+status-in-flight = { $count } laufend
+status-not-saved = Nicht in einer Datei gespeichert
+status-not-saved-hint = Mit Datei > Arbeitsbereich speichern wird automatisches Speichern aktiviert
+status-proxy = Proxy: { $url }
+status-proxy-off = Kein Proxy
+status-proxy-system = System-Proxy: { $url }
+status-saved = Automatisch gespeichert um { $time }
+stop = Stoppen
+stop-after = Beenden nach
+stream-to-temporary-file = In eine temporäre Datei schreiben
+stream-to-temporary-file-hint = Bodies über der Größengrenze werden vollständig in eine temporäre Datei empfangen; angezeigt werden nur Anfang und Ende. Andernfalls werden sie abgeschnitten.
+supported-on-linux-and-macos = Unterstützt unter Linux und macOS
+switch-environment = Umgebung wechseln
+system-bypass-hosts = System-Ausnahmeliste
+system-proxy-badge = 🔀 System-Proxy: { $url }
+system-proxy-detected = Erkannt aus { $source }; kann in den Einstellungen deaktiviert werden
+system-proxy-found = Erkannt: { $url } (aus { $source })
+tab-accepts-first = Tab fügt den ersten Vorschlag ein
+target = Ziel
+tests = Tests
+text = Text
+theme = Design
+theme-dark = Dunkel
+theme-light = Hell
+theme-system = System
+these-headers-are-used-for-this = Diese Header werden nur für diesen Versuch verwendet.
+this-request-does-not-have-a = Diese Anfrage hat keinen Body
+this-response-was-received-with-certificate = Diese Antwort wurde mit deaktivierter Zertifikatsprüfung empfangen
+throughput = Durchsatz
+timeout-seconds-0-none = Zeitlimit (Sekunden, 0 = keins)
+timing-connect = Verbindungsaufbau
+timing-connect-tls = Verbindungsaufbau + TLS
+timing-connect-tls-hint = Der TLS-Handshake läuft im selben Schritt wie der TCP-Verbindungsaufbau und wird daher nicht separat gemessen
+timing-dns = DNS-Auflösung
+timing-no-connect = Eine offene Verbindung wurde wiederverwendet, daher gab es keine DNS-Auflösung und keinen Verbindungsaufbau
+timing-transfer = Inhalt herunterladen
+timing-waiting = Warten (TTFB)
+tls-not-verified = ⚠ TLS nicht geprüft
+tls-verification-disabled = ⚠ TLS-Prüfung deaktiviert
+tls-versions = TLS-Versionen:
+to = bis
+token-expired = Abgelaufen
+token-expired-hint = In der Token-Verwaltung aktualisieren
+token-expired-toast = Token „{ $name }“ ist abgelaufen
+token-expires = Läuft ab
+token-expires-hint = Leer lassen, um den Ablauf aus einem JWT zu lesen
+token-expires-soon = Läuft in { $minutes } Min. ab
+token-from-collection = Aus Sammlung
+token-header-hint = Aus Token „{ $name }“
+token-invalid-date = Ungültiges Datum
+token-label = Token:
+token-manager = Token-Verwaltung
+token-manager-hint = Anfragen und Sammlungen wählen ein Token über seinen Namen. Es wird als Authorization-Header gesendet, sofern die Anfrage keinen eigenen setzt. Einen neuen Wert hier einfügen, um ihn überall zu aktualisieren.
+token-manager-menu = Token-Verwaltung...
+token-name = Name
+token-no-expiry = Kein Ablauf
+token-scheme = Schema
+token-status = Status
+token-url = Token-URL
+token-valid-until = Gültig bis { $time }
+token-value = Wert
+token-value-hint = Unterstützt {"{{"}variable{"}}"}-Syntax
+tool-base64-decode = Base64 dekodieren
+tool-base64-encode = Base64 kodieren
+tool-invalid-base64 = Kein gültiges Base64: { $error }
+tool-jwt-decode = JWT dekodieren
+tool-jwt-not-json = { $part } ist kein JSON: { $error }
+tool-jwt-parts = Ein JWT besteht aus drei durch Punkte getrennten Teilen
+tool-md5 = MD5
+tool-not-utf8 = { $count } dekodierte Bytes sind kein UTF-8-Text
+tool-sha256 = SHA-256
+tool-url-decode = URL dekodieren
+tool-url-encode = URL kodieren
+tools = Werkzeuge
+tools-input = Eingabe
+tools-output = Ausgabe
+total-requests = Anfragen insgesamt
+trusted-ca-certificates = Vertrauenswürdige CA-Zertifikate:
+type-a-command-or-request-name = Befehl oder Anfragename eingeben...
+undo = Rückgängig
+unsaved-changes = Ungespeicherte Änderungen
+url-not-a-collection = { $url } ist keine Sammlung: { $error }
+use-as-input = Als Eingabe verwenden
+use-manual-proxy = Manuellen Proxy verwenden
+use-response-selection = Auswahl aus Antwort übernehmen
+use-session = Diese Sitzung verwenden
+use-system-proxy-settings = Proxy-Einstellungen des Systems verwenden
+use-whole-response = Ganze Antwort übernehmen
+user-agent-supports-variable = User-Agent (unterstützt {"{{"}variable{"}}"})
+username = Benutzername
+value = Wert
+variable-name = Variablenname
+variable-value = Variablenwert
+variables = Variablen:
+view = Ansicht
+welcome-import = Importieren
+welcome-screen = Startbildschirm
+welcome-start = Loslegen
+welcome-subtitle = Beginne mit einer Anfrage, einer Beispielsammlung oder deinen vorhandenen API-Definitionen.
+welcome-title = Willkommen bei Send
+workspace = Arbeitsbereich
+workspace-default-header-copy-it-to = Standard-Header des Arbeitsbereichs; zum Ändern in diese Anfrage kopieren
+workspace-default-value = Arbeitsbereich-Standard ({ $value })
+workspace-loaded = Arbeitsbereich geladen
+workspace-name = Name des Arbeitsbereichs:
+workspace-saved-to = Arbeitsbereich unter { $path } gespeichert
+workspace-settings = Arbeitsbereich-Einstellungen...
+workspace-settings-title = Arbeitsbereich-Einstellungen - { $name }
+workspaces = Arbeitsbereiche:
+write-file-failed = Datei konnte nicht geschrieben werden: { $error }
@@ -0,0 +1,760 @@
+# English strings for the Send UI, in Fluent syntax (https://projectfluent.org).
+#
+# To add a language, copy this file to locales/<language-tag>/main.ftl,
+# translate the values (keep the ids), and add the language to
+# `Language` in src/main.rs. Missing ids fall back to English.
+
+accent-color = Accent color
+accept-encoding = Accept-Encoding
+accept-encoding-hint = Sent unless the request sets an Accept-Encoding header. gzip, deflate and br responses are decompressed.
+accept-encoding-not-sent = Not sent
+accepts-invalid-certificates = Accepts invalid and self-signed certificates
+active-environment = Active Environment
+add-capture-rule = + Add Capture Rule
+add-certificate-pin = Add Pin
+add-cookie = Add Cookie
+add-default-header = Add Default Header
+add-file = Add File
+add-header = Add Header
+add-header-assertion = Add Header Assertion
+add-header-preset = Add Preset
+add-header-row = + Add header
+add-parameter = Add Parameter
+add-pem-client-certificate = Add PEM Certificate...
+add-pkcs12-client-certificate = Add PKCS#12 Archive...
+add-query-parameter = Add Query Parameter
+add-session = Add Session
+add-text-field = Add Text Field
+add-token = Add Token
+add-variable = Add Variable
+all-collections = All collections
+always-new-connection = Always open a new connection
+always-new-connection-hint = Skips pooled connections and TLS session reuse, to time a cold request
+api-key = API Key
+api-key-add-to = Add to
+api-key-hint = The key is added to the request when it is sent and stays out of the Headers and Params tables. Use a {"{{"}variable{"}}"} to swap it per environment.
+api-key-name = Key
+appearance = Appearance
+as-received = As received
+ask-again-for-all = Ask Again
+ask-again-for-all-hint = Bring back prompts hidden with "Don't ask again"
+attach-json-schema = Attach JSON Schema...
+attach-json-schema-title = Attach JSON Schema
+attempt-number = Attempt { $attempt }
+auth = Auth
+auth-dialog-hint = Requests and folders set to Inherit use this auth.
+auth-dialog-title = Auth for { $name }
+auth-inherited = Uses { $auth } from its folder or collection.
+auth-menu = Auth…
+auth-overridden-by-header = An Authorization header on the Headers tab overrides these credentials.
+auth-type = Type
+average-latency = Average latency
+backoff-ms = Backoff (ms)
+basic-auth = Basic Auth
+basic-auth-hint = An Authorization: Basic header is computed from these credentials when the request is sent.
+bearer-token = Bearer Token
+binary-body-preview = <contents of { $file }>
+binary-file-missing = The file can't be read; the request will be sent without a body
+bindings-can-be-changed-in-settings = Bindings can be changed in Settings.
+body = Body
+body-editor-rows = Body editor rows
+body-from-file = From file
+body-from-file-hint = Read the body from a file when the request is sent. The file is sent as is, without variable substitution, and its contents are not saved in the workspace.
+body-schema-hint = The body is checked against this schema, and property names are suggested while typing
+browse = Browse...
+build-client-failed = Failed to build client: { $error }
+bulk-edit = Bulk Edit
+bulk-edit-headers-hint = Edit the headers as Key: Value lines, e.g. to paste them from browser devtools. Lines starting with // are switched off.
+bypass-hosts = Bypass hosts
+ca-bundle-count = ({ $count } certificates)
+cancel = Cancel
+cancel-all = Cancel All
+cannot-format-json = Not valid JSON: { $error }
+capture-listen-failed = Failed to listen on port { $port }: { $error }
+capture-listening = Listening on 127.0.0.1:{ $port }
+capture-no-environment = no environment is selected to store it in
+capture-rules-hint = Capture rules store the first capture group of a regex, run over the raw body or a header, in the active environment. Useful for HTML, XML and plain-text responses. Only the first MiB of the body is searched.
+capture-traffic = Capture Traffic
+capture-traffic-hint = Point an app or browser's HTTP proxy at the address below. Plain HTTP traffic is recorded; HTTPS is passed through unrecorded.
+capture-traffic-menu = Capture Traffic...
+certificate-pinned = 📌 Certificate pinned
+certificate-pins = Certificate pins
+certificate-pins-hint = Requests to a pinned host fail unless its certificate matches one of the pins. Use sha256/<base64> for a public key pin, or the certificate's SHA-256 fingerprint in hex.
+chunked-transfer = Chunked transfer encoding
+chunked-transfer-hint = Sends the body without a Content-Length. Only applies to HTTP/1.1; HTTP/2 has its own framing.
+clear = Clear
+clear-cookies = Clear Cookies
+clear-domain-cookies = Clear Domain
+clear-history = Clear History
+clear-variables = Clear Variables
+click-then-press-the-new-key = Click, then press the new key combination (Esc cancels)
+click-to-dismiss = Click to dismiss
+client-certificates = Client certificates
+client-certificates-hint = Presented to matching hosts that ask for mutual TLS. PEM keys must be unencrypted PKCS#8 (BEGIN PRIVATE KEY).
+client-id = Client ID
+client-secret = Client Secret
+collection-imported = Imported '{ $name }': { $count } requests
+collection-name = Collection Name:
+collection-runner = Collection Runner
+collections = Collections
+colorblind-friendly = Colorblind-friendly
+command-palette = Command Palette
+concurrent-users = Concurrent users
+confirm = Confirm
+confirm-clear-variables = Remove all { $count } variables from "{ $name }"?
+confirm-delete-collection = Delete the collection "{ $name }" and all of its requests?
+confirm-delete-environment = Delete the environment "{ $name }"?
+confirm-delete-workspace = Delete the workspace "{ $name }"? Its saved file is kept.
+confirm-import-from-url = A link asks to download and import a collection from { $url }. Only continue if you trust this source.
+confirm-overwrite-file = { $path } already exists. Replace it?
+confirmations = Confirmations
+connection = Connection
+connection-errors = Connection errors
+connection-new = (new connection)
+connection-new-hint = The time includes DNS lookup, TCP connect and any TLS handshake
+connection-reused = (reused connection)
+connection-reused-hint = Sent over a pooled connection, so the time has no DNS, TCP or TLS setup
+content-encoding-decoded-hint = Size as received; the body shown is decompressed
+content-encoding-not-decoded-hint = This encoding was not decompressed; the body is shown as received
+cookie-domain = Domain
+cookie-domain-count = { $domain } ({ $count })
+cookie-expired = Expired
+cookie-jar-empty = No cookies stored
+cookie-jar-of = Cookie jar: { $jar }
+cookie-manager = Cookies
+cookie-manager-hint = Cookies are grouped by the domain they are sent to. Changes apply to the next request.
+cookie-manager-menu = Manage Cookies...
+cookie-name = Name
+cookie-session = Session
+cookie-value = Value
+cookies = Cookies
+cookies-received = Set by this response
+copy = Copy
+copy-error = 📋 Copy error
+copy-link = Copy Link
+create = Create
+cron-sec-min-hour-day-month = Cron (sec min hour day month weekday)
+custom = Custom
+custom-method = Custom...
+custom-method-hint = e.g. PROPFIND
+custom-user-agent = Custom User-Agent
+deep-link-missing-id = Request link is missing an id
+deep-link-missing-url = Import link is missing a url
+deep-link-not-send = Not a send:// link: { $link }
+deep-link-request-not-found = No request with id { $id } was found
+deep-link-unknown-action = Unknown link action '{ $action }'
+default-headers = Default Headers
+default-method = Default method
+default-url = Default URL
+delay-between-requests = Delay between requests
+delete = Delete
+delete-environment = Delete environment
+desktop-notification-on-failure = Desktop notification on failure
+detect-again = Detect again
+disable-tls-certificate-verification = Disable TLS certificate verification
+disable-tls-verification = Disable TLS verification
+dont-ask-again = Don't ask again
+doubled-after-each-attempt = Doubled after each attempt
+download-downloading = Downloading { $file }
+download-failed = Download failed: { $error }
+download-in-progress = A download is already in progress
+download-interrupted = Download interrupted
+download-interrupted-error = Download interrupted: { $error }
+download-paused = Paused { $file }
+download-remainder-get-only = Getting the rest sends the request again, so it is only offered for GET and HEAD requests. Raise the max response size, or turn on streaming to a temporary file, and send again instead.
+download-remainder-to-file = Download remainder to file...
+download-response-title = Download Response
+download-saved = Saved { $size } to { $path }
+download-url-failed = Failed to download { $url }: { $error }
+duration-seconds = Duration (seconds)
+edit = Edit
+editor = Editor
+elapsed = Elapsed
+endpoint = Endpoint
+endpoints = Endpoints
+enter-html-content = Enter HTML content...
+enter-javascript-code = Enter JavaScript code...
+enter-json-data = Enter JSON data...
+enter-plain-text = Enter plain text...
+enter-url-supports-variable = Enter URL (supports {"{{"}variable{"}}"})...
+enter-xml-content = Enter XML content...
+environment = Environment
+environment-label = Environment:
+environment-name = Environment Name:
+environment-not-found = Environment '{ $name }' not found
+error-body-decode = Could not read the response body
+error-body-decode-hint-1 = The connection may have dropped while the body was downloading
+error-body-decode-hint-2 = Check the Content-Encoding the server sends
+error-cause = Cause
+error-certificate-pin = Certificate pin mismatch
+error-certificate-pin-hint-1 = Something between you and the server may be intercepting the connection
+error-certificate-pin-hint-2 = If the server's certificate was rotated, update the pin in Workspace Settings
+error-client = Could not set up the HTTP client
+error-client-hint-1 = Check the proxy, certificate and network settings
+error-connect = Could not connect
+error-connect-timeout = Connection timed out
+error-connect-timeout-hint-1 = Check that the host is reachable from this machine
+error-connect-timeout-hint-2 = Check the proxy settings in Settings or Workspace Settings
+error-connect-timeout-hint-3 = A firewall or VPN may be blocking the connection
+error-connection-refused = Connection refused
+error-connection-refused-hint-1 = Make sure the server is running and listening on this port
+error-connection-refused-hint-2 = Check the port and scheme (http vs https) in the URL
+error-dns = DNS lookup failed
+error-dns-hint-1 = Check the host name in the URL for typos
+error-dns-hint-2 = Make sure environment variables in the URL are defined
+error-dns-hint-3 = Check your network connection or DNS settings
+error-gave-up = Gave up after { $attempts } attempts
+error-http-version = HTTP version not supported
+error-http-version-hint-1 = The server didn't agree to HTTP/2; set the HTTP version to Auto or HTTP/1.1 in the request's Settings tab
+error-http-version-hint-2 = Cleartext (h2c) servers need HTTP/2 (prior knowledge)
+error-invalid-request = Invalid request
+error-invalid-request-hint-1 = Check the URL, header names and header values
+error-invalid-request-hint-2 = Make sure all environment variables are resolved
+error-offline = Offline
+error-offline-hint-1 = Turn off offline mode in Settings, or save an example for this request
+error-other = Request failed
+error-rate = Error rate
+error-things-to-try = Things to try
+error-timeout = Request timed out
+error-timeout-hint-1 = Increase the timeout in the request's Settings tab
+error-timeout-hint-2 = The server may be overloaded; try again later
+error-tls = TLS error
+error-tls-hint-1 = Check that the server's certificate is valid for this host
+error-tls-hint-2 = Add the CA certificate in Workspace Settings
+error-tls-hint-3 = For local testing only, disable TLS verification
+error-too-many-redirects = Too many redirects
+error-too-many-redirects-hint-1 = The server may be redirecting in a loop
+error-too-many-redirects-hint-2 = Disable redirect following in the request's Settings tab to inspect the redirect
+errors-by-status = Errors by status
+every-seconds = Every (seconds)
+executions = Executions
+expect-continue-headers = From headers
+expect-continue-hint = Adds or removes the Expect: 100-continue header. The body is sent right away either way; the client does not wait for the 100 response.
+expect-continue-never = Never send
+expect-continue-send = Send Expect: 100-continue
+export = Export...
+export-as-script = Export as Script
+export-collection = Export Collection...
+export-for-sharing = Export for Sharing
+export-for-sharing-menu = Export for Sharing...
+export-load-test-summary-title = Export Load Test Summary
+export-named-title = Export '{ $name }'
+export-summary = Export Summary...
+exported-named = Exported '{ $name }'
+exported-named-as-script = Exported '{ $name }' as a script
+exported-to = Exported to { $path }
+fail-unless-status-is-2xx = Fail unless status is 2xx
+file = File
+file-kind-app-state = app state
+file-kind-collection = collection
+file-kind-export = export
+file-kind-load-test-summary = load test summary
+file-kind-script = script
+file-kind-settings = settings
+file-kind-workspace = workspace
+file-not-found = File not found
+find = Find
+find-in-body = Find (Ctrl+F)
+folder-name = Folder Name:
+follow-redirects = Follow redirects
+format-json = Format
+generator-iso-timestamp = ISO 8601 timestamp
+generator-random-int = Random integer (0-1000)
+generator-random-string = Random string
+generator-timestamp = Unix timestamp
+generator-uuid = UUID
+header = Header
+header-assertions-hint = Checks on response headers, run after every send and in collection runs and monitors. Expected values support {"{{"}variable{"}}"} syntax.
+header-name = Header Name
+header-name-hint = Header name
+header-preset-saved = Saved header preset "{ $name }"
+header-presets = Header Presets
+header-presets-hint = Named header sets the Headers tab can add to any request. Headers the request already has get the preset's value.
+header-value = Header Value
+header-value-supports-variable = Header value (supports {"{{"}variable{"}}"})
+headers = Headers
+help = Help
+hide-the-mini-sidebar-view-menu = Hide the mini sidebar (View menu brings it back)
+history = History
+honor-retry-after-header = Honor Retry-After header
+http-version = HTTP version
+ignore-cookie-jar = Ignore cookie jar
+ignore-cookie-jar-hint = Neither send stored cookies nor keep the ones this response sets
+import = Import
+import-ca-certificate = Import CA Certificates...
+import-ca-certificates-title = Import CA Certificates
+import-client-certificate-title = Import Client Certificate
+import-collection = Import Collection...
+import-collection-title = Import Collection
+import-curl = Import curl Command
+import-curl-menu = Import curl Command...
+import-file-failed = Failed to import { $path }: { $error }
+import-from-curl = Import from curl...
+import-from-openapi = Import from OpenAPI...
+import-from-openapi-hint = An OpenAPI 3 or Swagger 2 document in JSON
+import-from-postman = Import from Postman...
+import-from-postman-hint = A collection exported from Postman (v2.0 or v2.1 JSON)
+inherit-auth = Inherit from parent
+insert-dynamic-variable = Insert Dynamic Variable
+insert-value = Insert Value
+interface-binding-unsupported = Binding to a network interface is not supported on this platform
+invalid-and-self-signed-certificates-will = Invalid and self-signed certificates will be accepted
+invalid-ca-certificate = Invalid CA certificate '{ $name }': { $error }
+invalid-client-certificate = Invalid client certificate { $name }: { $error }
+invalid-method = "{ $method }" is not a valid HTTP method
+invalid-pem-certificate = Invalid PEM certificate in { $name }: { $error }
+invalid-proxy-configuration = Invalid proxy configuration: { $error }
+invalid-source-address = Invalid source address '{ $address }': { $error }
+invalid-system-proxy = Invalid system proxy: { $error }
+ip-preference-auto = Auto
+ip-preference-ipv4-only = IPv4 only
+ip-preference-ipv6-only = IPv6 only
+ip-preference-prefer-ipv4 = Prefer IPv4
+ip-preference-prefer-ipv6 = Prefer IPv6
+ip-version = IP version
+ip-version-label = IP version:
+iterations = Iterations
+json-syntax-error = ⚠ Line { $line }, column { $column }: { $message }
+jwt-claims = Claims
+jwt-expired = (expired)
+jwt-expires = Expires
+jwt-expires-in = (in { $duration })
+jwt-header = Header
+jwt-issued = Issued
+jwt-not-before = Not before
+keep-the-sidebar-open = Keep the sidebar open
+key = Key
+keyboard-shortcuts = Keyboard Shortcuts
+language = Language
+language-system = System default
+larger-responses = Larger responses
+latency-limit-ms-0-none = Latency limit (ms, 0 = none)
+latency-min-max = Latency min / max
+latency-p50-p95-p99 = Latency p50 / p95 / p99
+layout = Layout
+layout-side-by-side = Side by side
+layout-single = Single pane
+layout-stacked = Request above response
+links = Links
+listen-on-127-0-0-1 = Listen on 127.0.0.1 port
+load-file-failed = Failed to load { $path }: { $error }
+load-test = Load Test
+load-test-current-request = Current request
+load-test-limit-count = Request count
+load-test-limit-duration = Duration
+load-test-menu = Load Test...
+load-test-selected-folder = Selected folder
+load-test-summary-exported = Load test summary exported
+load-test-target = Target: { $name } ({ $count } requests)
+load-workspace = Load Workspace...
+load-workspace-title = Load Workspace
+loaded-workspace = Loaded Workspace
+local-file-paths-in-form-data = Local file paths in form-data are scrubbed.
+manage-header-presets = Manage Presets...
+max-concurrent-requests = Max concurrent requests
+max-history-entries = Max history entries
+max-redirects = Max redirects
+max-response-size-mb-0-unlimited = Max response size (MB, 0 = unlimited)
+max-wait-secs = Max wait (s)
+maximum-tls-version = Maximum TLS version
+method-colors = Method colors
+metrics-all-time = All time
+metrics-last-day = Last 24 hours
+metrics-last-hour = Last hour
+metrics-last-month = Last 30 days
+metrics-last-week = Last 7 days
+mini-sidebar = Mini sidebar
+minify-json = Minify
+minimum-tls-version = Minimum TLS version
+missing-request-name = Request { $id }
+monitor = Monitor
+monitor-current-request = Monitor Current Request
+monitor-expected-success = Expected 2xx, got { $status }
+monitor-failed-notification = Monitor "{ $name }" failed
+monitor-failing = ✖ Failing
+monitor-last-results = Last { $count } results
+monitor-not-run-yet = Not run yet
+monitor-passing = ✔ Passing
+monitor-selected-folder = Monitor Selected Folder
+monitor-too-slow = Took { $time } ms (limit { $limit } ms)
+monitors = Monitors
+monitors-menu = Monitors...
+monitors-paused = ⏸ Monitors paused
+monitors-paused-offline = Monitors are paused while offline mode is on
+monitors-run-while-the-app-is = Monitors run while the app is open.
+move-file-failed = Failed to move file into place: { $error }
+name = Name
+name-requests-from-url = Name Requests from URL
+negotiated-http-version = HTTP version negotiated with the server
+network = Network
+network-interface = Network interface:
+network-interface-hint = e.g. eth0, utun3 (empty = automatic)
+new-collection = New Collection
+new-environment = New Environment
+new-folder = New Folder
+new-request = New Request
+new-requests = New Requests
+new-workspace = New Workspace
+no-assertions = This request has no assertions. Add them in the Tests tab.
+no-auth = No Auth
+no-auth-hint = This request sends no credentials of its own. A Token Manager token still applies.
+no-cookies-received = This response set no cookies
+no-environment = No Environment
+no-failures = No failures.
+no-file-selected = No file selected
+no-header-presets = No presets yet
+no-matches = No matches
+no-matching-commands = No matching commands
+no-monitors-yet = No monitors yet.
+no-pem-certificates = No PEM certificates found in { $name }
+no-recent-workspaces = No recent workspaces
+no-requests-sent-yet = No requests sent yet.
+no-response = No response
+no-response-yet-send-a-request = No response yet. Send a request to see the response here.
+no-runs-in-range = No runs in this range.
+no-session = No Session
+no-sessions = No sessions yet
+no-system-proxy-detected = No system proxy detected
+no-token = No token
+no-tokens = No tokens yet
+normalized-url = Sent as:
+ntlm-auth = Windows (NTLM)
+ntlm-domain = Domain
+ntlm-hint = Sends an NTLMv2 handshake for intranet servers such as IIS. NTLM over Negotiate sends the same exchange under the Negotiate scheme for servers that only offer Negotiate. Kerberos isn't supported, so servers that require it will reject the request.
+ntlm-over-negotiate = NTLM over Negotiate
+ntlm-scheme = Scheme
+oauth-clear-token = Clear Token
+oauth-hint = A token is fetched on the first send and reused until shortly before it expires.
+oauth-token-cached = Token cached
+oauth-token-expired = Cached token expired; a new one is fetched on the next send
+oauth-token-expires-in = Token cached, expires in { $seconds }s
+oauth-token-not-fetched = No token fetched yet
+oauth2-client-credentials = OAuth 2.0 Client Credentials
+off = Off
+offline-capture-skipped = Offline mode is on; turn it off to capture traffic
+offline-import-skipped = Offline mode is on; import skipped
+offline-mode = Offline mode
+offline-mode-badge = 📴 Offline mode
+offline-mode-hint = Block network calls and serve saved examples
+offline-mode-is-on = Offline mode is on
+offline-no-example = Offline mode is on and this request has no saved example
+offline-no-saved-example = Offline mode: no saved example
+ok = OK
+on = On
+only-hosts-containing = Only hosts containing
+open-file-failed = Failed to open file: { $error }
+open-sample-collection = Add Sample Collection
+open-send = Open Send
+override = Override
+override-application-proxy = Override application proxy settings
+override-workspace-proxy = Override workspace proxy
+override-workspace-retry-policy = Override workspace retry policy
+overwrite = Overwrite
+palette-capture-traffic = Capture traffic
+palette-environment = Environment: { $name }
+palette-export-collection = Export collection
+palette-export-for-sharing = Export for sharing
+palette-import-collection = Import collection
+palette-load-test = Load test
+palette-load-workspace = Load workspace
+palette-monitors = Monitors
+palette-new-collection = New collection
+palette-new-environment = New environment
+palette-new-folder = New folder
+palette-new-workspace = New workspace
+palette-offline-mode-off = Turn offline mode off
+palette-offline-mode-on = Turn offline mode on
+palette-open-request = Open: { $request }
+palette-run-collection = Run collection
+palette-save-workspace = Save workspace
+palette-settings = Settings
+palette-show-collections = Show collections
+palette-show-environment = Show environment
+palette-show-history = Show history
+palette-show-tools = Show tools
+palette-workspace = Workspace: { $name }
+palette-workspace-settings = Workspace settings
+parameter-name = Parameter Name
+parameter-name-hint = Parameter name
+parameter-value = Parameter Value
+parameter-value-supports-variable = Parameter value (supports {"{{"}variable{"}}"})
+params = Params
+part-content-type-hint = Default for the part
+part-headers = Part headers ({ $count })
+pass-rate = Pass rate
+passphrase = Passphrase
+password = Password
+paste-a-curl-command = Paste a curl command:
+path-variables = Path Variables
+path-variables-hint = Taken from the :name and {"{"}name{"}"} segments of the URL path
+pause = Pause
+pause-monitors = ⏸ Pause Monitors
+pem-certificate-filter = PEM Certificate
+pem-private-key-filter = PEM Private Key
+persist-cookies = Keep cookies in the workspace file
+persist-cookies-hint = Cookies survive restarts. Secure and HttpOnly ones need the option below. Values are masked in the file, not encrypted, and are never shared.
+persist-login-cookies = Also keep Secure and HttpOnly cookies
+persist-login-cookies-off = Secure and HttpOnly cookies are not saved and are gone after a restart.
+persist-login-cookies-warning = ⚠ Login cookies are written to the workspace file masked, not encrypted. Anyone who can read the file can sign in as you.
+pin-sidebar-open = Pin sidebar open
+pkcs12-archive-filter = PKCS#12 Archive
+preset-name = Preset name
+press-keys = Press keys...
+preview-ntlm-handshake = <from the NTLM handshake when sent>
+preview-oauth-token = <fetched when sent>
+preview-request = Preview Request
+preview-url = URL:
+proxy = Proxy
+proxy-auth = Authentication
+proxy-bypassed-for = Bypassed for: { $hosts }
+proxy-ntlm-hint = Username as DOMAIN\user
+proxy-routes-all = All requests are routed through this proxy
+proxy-url = Proxy URL
+query-string = Query string
+queue-empty = Nothing is being sent
+queue-in-flight = In flight
+queue-pending = Queued
+quit = Quit
+read-file-failed = Failed to read { $name }: { $error }
+read-key-failed = Failed to read the key: { $error }
+read-schema-failed = Failed to read schema { $path }: { $error }
+recent-workspaces = Recent Workspaces
+redirect-limit-reached = Stopped at the redirect limit; this response is itself a redirect.
+redirects = Redirects
+redo = Redo
+refresh = Refresh
+register-send-links = Register send:// links
+remove-all-variable-values = Remove current values of all variables
+remove-schema = Remove Schema
+request = Request
+request-body = Request body
+request-cancelled = Request cancelled
+request-failed = Request failed: { $error }
+request-name = Request Name:
+request-no-longer-exists = Request no longer exists
+request-queue = Request Queue
+request-size-body = Body: { $body }
+request-size-headers = Request line and headers: { $headers }
+request-size-hint = Approximate size of the request to be sent
+requests = Requests
+requests-are-not-sent-responses-come = Requests are not sent; responses come from saved examples. Turn off in Settings
+requests-captured = { $count } requests captured
+requests-per-second-0-unlimited = Requests per second (0 = unlimited)
+requests-renamed = Renamed {$count} requests
+reset = Reset
+response = Response
+response-body-size = Body: { $size }
+response-headers-size = Headers: { $size }
+response-retries = ↻ { $count } retries
+response-size = Size: { $size }
+response-spooled = ⓘ Body of { $size } saved to a temporary file; only its start and end are shown
+response-status = Status: { $status } { $text }
+response-time-ms = Time: { $ms }ms
+response-truncated = ⚠ Response truncated at { $size }
+restore-defaults = Restore Defaults
+resume = Resume
+resume-monitors = ▶ Resume Monitors
+retries = Retries
+retry = Retry
+retry-icon = ↻ Retry
+retry-on = Retry on:
+retry-with-modified-headers-menu = Retry with modified headers...
+retry-with-modified-headers-title = Retry with Modified Headers
+retrying-in = ⏳ Retrying in {$seconds} s ({$attempt}/{$max})
+retrying-status-in = ⏳ {$status}, retrying in {$seconds} s ({$attempt}/{$max})
+revert-changes = Revert
+right-click-a-request-to-copy = Right-click a request to copy its link
+run = Run
+run-collection = Run Collection...
+run-in-background = Keep running in the background
+run-in-background-hint = Closing the window minimizes it while monitors are scheduled. Use File > Quit to exit.
+run-metrics = Run Metrics
+run-metrics-menu = Run Metrics...
+run-metrics-source = Collection runner and monitor executions
+run-now = Run Now
+running-in-background = Send keeps running monitors in the background. Use File > Quit to exit.
+runs = Runs
+save = Save
+save-as-collection = Save as Collection
+save-as-example = Save as Example
+save-body-as = Save Body As...
+save-file-failed = Failed to save { $what } to { $path }: { $error }
+save-headers-as-preset = Save Headers as Preset
+save-request-to-collection-first = Save the request to a collection first
+save-response-body-title = Save Response Body
+save-workspace = Save Workspace...
+save-workspace-title = Save Workspace
+saved-example-badge = 📄 Saved example
+schedule = Schedule
+schema-missing-field = { $path }: missing required field '{ $name }'
+schema-not-in-enum = { $path }: must be one of { $options }
+schema-unknown-field = { $path }: unknown field
+schema-wrong-type = { $path }: expected { $expected }, found { $found }
+scopes = Scopes
+secret-variables-are-blanked = Secret variables are blanked.
+secret-variables-are-masked-and-blanked = Secret variables are masked and blanked when exporting for sharing
+select-a-collection-or-folder-to = Select a collection or folder to run.
+select-file-title = Select File
+select-private-key-title = Select the Private Key
+select-text-in-the-response-body = Select text in the response body first
+selected-collection = Selected Collection
+selected-requests = {$count} selected requests
+send = Send
+send-and-download = Send and Download...
+send-in-parallel = ▶ Send in Parallel
+send-links-registered = send:// links now open in this app
+send-on-new-connection = Send on a New Connection
+send-repeatedly = Send Repeatedly (Runner Settings)
+send-selected-in-parallel = Send {$count} Selected in Parallel
+send-this-row = Send this entry; unchecked entries are kept but not sent
+sensitive-headers-are-removed = Sensitive header values (Authorization, Cookie, tokens) are removed.
+sent-with-every-request-unless-the = Sent with every request unless the request sets the same header.
+server-responded-with = Server responded with { $status }
+session-cookies = Cookies ({ $count })
+session-label = Session:
+session-token-shared = Shared value
+session-tokens = Tokens
+sessions = Sessions
+sessions-hint = Each session keeps its own cookies and token values. Cookies set by responses are stored in the active session and sent back on matching requests.
+sessions-menu = Sessions...
+settings = Settings
+settings-menu = Settings...
+shortcut-close-request = Close request
+shortcut-command-palette = Command palette
+shortcut-new-request = New request
+shortcut-save = Save request
+shortcut-send = Send request
+shortcut-show-shortcuts = Show shortcuts
+shortcut-switch-environment = Switch environment
+show-compressed-bytes = Show bytes as received
+signer-algorithm = Algorithm
+signer-dialog-title = Request signing for { $name }
+signer-enabled = Sign requests
+signer-headers = Headers to set
+signer-hint = Signs every request in this collection before it is sent. The string to sign and header values may use {"{"}method{"}"}, {"{"}url{"}"}, {"{"}host{"}"}, {"{"}path{"}"}, {"{"}query{"}"}, {"{"}body{"}"}, {"{"}body_sha256{"}"}, {"{"}timestamp{"}"}, {"{"}timestamp_ms{"}"}, {"{"}date{"}"}, {"{"}iso_date{"}"}, {"{"}nonce{"}"} and {"{"}header:Name{"}"}; header values also get {"{"}signature{"}"}. Multipart bodies count as empty.
+signer-menu = Request Signing…
+signer-remove = Remove Signer
+signer-secret = Secret
+signer-string-to-sign = String to sign
+skipped = Skipped
+soft-wrap = Wrap lines
+source-address = Source address:
+source-address-hint = e.g. 192.168.1.20 (empty = automatic)
+spool-file-failed = Failed to write the response to a temporary file: { $error }
+start = Start
+status-auto-save-on = Auto-save on
+status-badges = Show status icons
+status-badges-hint = Prefix status codes with ✔ ↪ ⚠ ✖ so the status class is visible without relying on color
+status-colors = Status colors
+status-cookies = Cookies: { $count }
+status-cookies-hint = Cookies the current request sends to its host
+status-in-flight = { $count } in flight
+status-not-saved = Not saved to a file
+status-not-saved-hint = Use File > Save Workspace to enable auto-save
+status-proxy = Proxy: { $url }
+status-proxy-off = No proxy
+status-proxy-system = System proxy: { $url }
+status-saved = Auto-saved at { $time }
+stop = Stop
+stop-after = Stop after
+stream-to-temporary-file = Stream to a temporary file
+stream-to-temporary-file-hint = Bodies over the size limit are received in full into a temporary file, and only their start and end are shown. Otherwise they are truncated.
+supported-on-linux-and-macos = Supported on Linux and macOS
+switch-environment = Switch Environment
+system-bypass-hosts = System bypass list
+system-proxy-badge = 🔀 System proxy: { $url }
+system-proxy-detected = Detected from { $source }; can be disabled in Settings
+system-proxy-found = Detected: { $url } (from { $source })
+tab-accepts-first = Tab inserts the first suggestion
+target = Target
+tests = Tests
+text = Text
+theme = Theme
+theme-dark = Dark
+theme-light = Light
+theme-system = System
+these-headers-are-used-for-this = These headers are used for this retry only.
+this-request-does-not-have-a = This request does not have a body
+this-response-was-received-with-certificate = This response was received with certificate verification disabled
+throughput = Throughput
+timeout-seconds-0-none = Timeout (seconds, 0 = none)
+timing-connect = Connect
+timing-connect-tls = Connect + TLS
+timing-connect-tls-hint = The TLS handshake runs in the same step as the TCP connect, so it isn't timed on its own
+timing-dns = DNS lookup
+timing-no-connect = Reused an open connection, so there was no DNS lookup or connect
+timing-transfer = Content download
+timing-waiting = Waiting (TTFB)
+tls-not-verified = ⚠ TLS not verified
+tls-verification-disabled = ⚠ TLS verification disabled
+tls-versions = TLS versions:
+to = to
+token-expired = Expired
+token-expired-hint = Refresh it in the Token Manager
+token-expired-toast = Token "{ $name }" has expired
+token-expires = Expires
+token-expires-hint = Leave empty to read the expiry from a JWT
+token-expires-soon = Expires in { $minutes } min
+token-from-collection = From collection
+token-header-hint = From token "{ $name }"
+token-invalid-date = Invalid date
+token-label = Token:
+token-manager = Token Manager
+token-manager-hint = Requests and collections pick a token by name. It is sent as the Authorization header unless the request sets one itself. Paste a new value here to refresh it everywhere.
+token-manager-menu = Token Manager...
+token-name = Name
+token-no-expiry = No expiry
+token-scheme = Scheme
+token-status = Status
+token-url = Token URL
+token-valid-until = Valid until { $time }
+token-value = Value
+token-value-hint = Supports {"{{"}variable{"}}"} syntax
+tool-base64-decode = Base64 Decode
+tool-base64-encode = Base64 Encode
+tool-invalid-base64 = Not valid Base64: { $error }
+tool-jwt-decode = Decode JWT
+tool-jwt-not-json = { $part } is not JSON: { $error }
+tool-jwt-parts = A JWT has three dot-separated parts
+tool-md5 = MD5
+tool-not-utf8 = Decoded { $count } bytes that are not UTF-8 text
+tool-sha256 = SHA-256
+tool-url-decode = URL Decode
+tool-url-encode = URL Encode
+tools = Tools
+tools-input = Input
+tools-output = Output
+total-requests = Total requests
+trusted-ca-certificates = Trusted CA certificates:
+type-a-command-or-request-name = Type a command or request name...
+undo = Undo
+unsaved-changes = Unsaved changes
+url-not-a-collection = { $url } is not a collection: { $error }
+use-as-input = Use as Input
+use-manual-proxy = Use manual proxy
+use-response-selection = Use Response Selection
+use-session = Use this session
+use-system-proxy-settings = Use system proxy settings
+use-whole-response = Use Whole Response
+user-agent-supports-variable = User-Agent (supports {"{{"}variable{"}}"})
+username = Username
+value = Value
+variable-name = Variable name
+variable-value = Variable value
+variables = Variables:
+view = View
+welcome-import = Import
+welcome-screen = Welcome Screen
+welcome-start = Start
+welcome-subtitle = Start with a request, a sample collection or your existing API definitions.
+welcome-title = Welcome to Send
+workspace = Workspace
+workspace-default-header-copy-it-to = Workspace default header; copy it to this request to change it
+workspace-default-value = Workspace default ({ $value })
+workspace-loaded = Workspace loaded
+workspace-name = Workspace Name:
+workspace-saved-to = Workspace saved to { $path }
+workspace-settings = Workspace Settings...
+workspace-settings-title = Workspace Settings - { $name }
+workspaces = Workspaces:
+write-file-failed = Failed to write file: { $error }
@@ -1,2093 +1,20680 @@
+use base64::Engine;
 use eframe::{Result as EframeResult, egui};
 use egui::{Color32, RichText, ScrollArea, TextEdit, Ui};
-use egui_extras::syntax_highlighting::{CodeTheme, highlight};
-use egui_extras::{Size, StripBuilder, TableBuilder};
-use reqwest::{Method, header::HeaderMap};
-use rfd;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use hmac::{Hmac, Mac};
+use md4::Md4;
+use md5::{Digest, Md5};
+use regex::Regex;
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::mpsc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::runtime::Runtime;
+use unic_langid::LanguageIdentifier;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct HttpRequest {
     id: String,
     name: String,
     method: String,
     url: String,
-    headers: Vec<(String, String)>,
+    headers: Vec<KeyValue>,
     body: String,
     body_type: BodyType,
     form_data: Vec<FormDataEntry>,
-    url_encoded_data: Vec<(String, String)>,
-    query_params: Vec<(String, String)>,
+    url_encoded_data: Vec<KeyValue>,
+    query_params: Vec<KeyValue>,
+    // Older files kept the query params out of the URL and appended them at send time
+    #[serde(default)]
+    query_in_url: bool,
+    // Values for the :name and {name} segments of the URL path, kept in URL order
+    #[serde(default)]
+    path_variables: Vec<(String, String)>,
+    #[serde(default)]
+    settings: RequestSettings,
+    #[serde(default)]
+    example: Option<SavedExample>,
+    // JSON Schema of the body, from an OpenAPI import or attached by hand
+    #[serde(default)]
+    body_schema: Option<serde_json::Value>,
+    // Token Manager entry to send; None falls back to the collection's
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    assertions: Vec<HeaderAssertion>,
+    #[serde(default)]
+    captures: Vec<CaptureRule>,
+    #[serde(default)]
+    auth: RequestAuth,
+    #[serde(default)]
+    binary_file: String,
+    // Raw bodies read from this file at send time instead of `body`
+    #[serde(default)]
+    body_file: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-enum BodyType {
-    None,
-    Raw,
-    Json,
-    FormData,
-    UrlEncoded,
-}
+impl HttpRequest {
+    // Byte ranges and names of the path variables in a URL. {{variables}} and the host's port
+    // aren't path variables, and neither is anything after the query or fragment.
+    fn path_variable_spans(url: &str) -> Vec<(std::ops::Range<usize>, String)> {
+        let start = url.find("://").map_or(0, |scheme| {
+            url[scheme + 3..]
+                .find('/')
+                .map_or(url.len(), |i| scheme + 3 + i)
+        });
+        let end = url[start..]
+            .find(['?', '#'])
+            .map_or(url.len(), |i| start + i);
+        let is_name = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+        let mut spans = vec![];
+        let mut offset = start;
+        for segment in url[start..end].split('/') {
+            if let Some(rest) = segment.strip_prefix(':') {
+                let len = rest.find(|c| !is_name(c)).unwrap_or(rest.len());
+                if len > 0 {
+                    spans.push((offset..offset + 1 + len, rest[..len].to_string()));
+                }
+            } else {
+                let mut i = 0;
+                while let Some(open) = segment[i..].find('{').map(|at| i + at) {
+                    if segment[open..].starts_with("{{") {
+                        i = segment[open..]
+                            .find("}}")
+                            .map_or(segment.len(), |at| open + at + 2);
+                        continue;
+                    }
+                    let name_len = segment[open + 1..]
+                        .find(|c| !is_name(c))
+                        .unwrap_or(segment.len() - open - 1);
+                    if name_len > 0 && segment[open + 1 + name_len..].starts_with('}') {
+                        let name = &segment[open + 1..open + 1 + name_len];
+                        spans.push((
+                            offset + open..offset + open + name_len + 2,
+                            name.to_string(),
+                        ));
+                    }
+                    i = open + 1;
+                }
+            }
+            offset += segment.len() + 1;
+        }
+        spans
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-enum FormDataEntry {
-    Text {
-        key: String,
-        value: String,
-    },
-    File {
-        key: String,
-        file_path: String,
-        file_name: String,
-    },
+    // Matches the Path Variables table to the URL, keeping the values of names still in it
+    fn sync_path_variables(&mut self) {
+        let mut variables: Vec<(String, String)> = vec![];
+        for (_, name) in Self::path_variable_spans(&self.url) {
+            if variables.iter().any(|(existing, _)| *existing == name) {
+                continue;
+            }
+            let value = self
+                .path_variables
+                .iter()
+                .find(|(existing, _)| *existing == name)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default();
+            variables.push((name, value));
+        }
+        self.path_variables = variables;
+    }
+
+    // Where the query string is in a URL, without its '?'
+    fn query_range(url: &str) -> Option<std::ops::Range<usize>> {
+        let start = url.find('?')?;
+        if url[..start].contains('#') {
+            return None;
+        }
+        let end = url[start..].find('#').map_or(url.len(), |i| start + i);
+        Some(start + 1..end)
+    }
+
+    // The Params table for a URL's query string, decoded by form rules ('+' is a space)
+    fn query_pairs(url: &str) -> Vec<KeyValue> {
+        Self::raw_query_pairs(url)
+            .into_iter()
+            .map(|(_, row)| row)
+            .collect()
+    }
+
+    // Each pair of a URL's query string as written, with its decoded row
+    fn raw_query_pairs(url: &str) -> Vec<(&str, KeyValue)> {
+        let decode = |text: &str| {
+            let text = text.replace('+', " ");
+            urlencoding::decode(&text).map_or_else(|_| text.clone(), |text| text.into_owned())
+        };
+        let Some(query) = Self::query_range(url) else {
+            return vec![];
+        };
+        url[query]
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (pair, KeyValue::new(decode(key), decode(value)))
+            })
+            .collect()
+    }
+
+    // Escapes what would end a query key or value, leaving {{variables}} as typed
+    fn encode_query_text(text: &str, is_key: bool) -> String {
+        let mut encoded = String::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            if rest.starts_with("{{")
+                && let Some(end) = rest.find("}}")
+            {
+                encoded.push_str(&rest[..end + 2]);
+                rest = &rest[end + 2..];
+                continue;
+            }
+            let c = rest.chars().next().unwrap_or_default();
+            match c {
+                '%' | '&' | '#' | '+' | ' ' => encoded.push_str(&urlencoding::encode(&rest[..1])),
+                '=' if is_key => encoded.push_str("%3D"),
+                c => encoded.push(c),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        encoded
+    }
+
+    // URL edited: the Params table follows its query string. Switched-off params aren't in
+    // the URL, so they keep their rows.
+    fn sync_query_params(&mut self) {
+        let mut params = Self::query_pairs(&self.url);
+        for (i, row) in self.query_params.iter().enumerate() {
+            if !row.enabled {
+                params.insert(i.min(params.len()), row.clone());
+            }
+        }
+        self.query_params = params;
+    }
+
+    // Params table edited: the URL's query string is rewritten from it. Pairs whose row
+    // wasn't changed keep the text they had, e.g. "a+b" or a bare "flag".
+    fn sync_url_query(&mut self) {
+        let (base, fragment) = match Self::query_range(&self.url) {
+            Some(query) => (&self.url[..query.start - 1], &self.url[query.end..]),
+            None => self
+                .url
+                .split_at(self.url.find('#').unwrap_or(self.url.len())),
+        };
+        let mut unchanged = Self::raw_query_pairs(&self.url);
+        let pairs: Vec<String> = KeyValue::enabled(&self.query_params)
+            .filter(|(key, value)| !key.is_empty() || !value.is_empty())
+            .map(|(key, value)| {
+                match unchanged
+                    .iter()
+                    .position(|(_, row)| row.key == *key && row.value == *value)
+                {
+                    Some(i) => unchanged.remove(i).0.to_string(),
+                    None => format!(
+                        "{}={}",
+                        Self::encode_query_text(key, true),
+                        Self::encode_query_text(value, false)
+                    ),
+                }
+            })
+            .collect();
+        let query = if pairs.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", pairs.join("&"))
+        };
+        self.url = format!("{}{}{}", base, query, fragment);
+    }
+
+    fn move_query_params_into_url(&mut self) {
+        let mut params = Self::query_pairs(&self.url);
+        params.extend(
+            self.query_params
+                .drain(..)
+                .filter(|row| !row.key.trim().is_empty()),
+        );
+        self.query_params = params;
+        self.sync_url_query();
+        self.query_in_url = true;
+    }
+
+    // The URL with each path variable that has a value replaced by `encode(value)`
+    fn url_with_path_variables(&self, encode: impl Fn(&str) -> String) -> String {
+        let mut url = self.url.clone();
+        for (range, name) in Self::path_variable_spans(&self.url).into_iter().rev() {
+            if let Some((_, value)) = self
+                .path_variables
+                .iter()
+                .find(|(existing, value)| *existing == name && !value.is_empty())
+            {
+                url.replace_range(range, &encode(value));
+            }
+        }
+        url
+    }
 }
 
-#[derive(Debug, Clone)]
-struct HttpResponse {
-    status: u16,
-    status_text: String,
-    headers: HashMap<String, String>,
-    body: String,
-    time: u128,
-    body_size: usize,
-    headers_size: usize,
+// A header, query param or url-encoded field. Switched-off rows stay in the table but aren't
+// sent. Saved as [key, value], with a third `false` while switched off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "KeyValueRow", into = "KeyValueRow")]
+struct KeyValue {
+    key: String,
+    value: String,
+    enabled: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Folder {
-    id: String,
-    name: String,
-    requests: Vec<HttpRequest>,
-    folders: Vec<Folder>,
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum KeyValueRow {
+    Disabled(String, String, bool),
+    Enabled(String, String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Collection {
-    id: String,
-    name: String,
-    root_folder: Folder,
+impl From<KeyValueRow> for KeyValue {
+    fn from(row: KeyValueRow) -> Self {
+        match row {
+            KeyValueRow::Disabled(key, value, enabled) => Self {
+                key,
+                value,
+                enabled,
+            },
+            KeyValueRow::Enabled(key, value) => Self::new(key, value),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Environment {
-    name: String,
-    variables: Vec<(String, String)>,
+impl From<KeyValue> for KeyValueRow {
+    fn from(row: KeyValue) -> Self {
+        if row.enabled {
+            Self::Enabled(row.key, row.value)
+        } else {
+            Self::Disabled(row.key, row.value, false)
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct AppStorage {
-    collections: Vec<Collection>,
-    environments: Vec<Environment>,
+impl KeyValue {
+    fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            enabled: true,
+        }
+    }
+
+    fn pair(&self) -> (String, String) {
+        (self.key.clone(), self.value.clone())
+    }
+
+    // The rows that are sent, as (key, value)
+    fn enabled(rows: &[KeyValue]) -> impl Iterator<Item = (&String, &String)> {
+        rows.iter()
+            .filter(|row| row.enabled)
+            .map(|row| (&row.key, &row.value))
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct AppCache {
-    current_workspace: usize,
-    workspaces: Vec<Workspace>,
-    selected_sidebar_item: Option<SidebarItem>,
-    request_tab: RequestTab,
-    response_tab: ResponseTab,
-    raw_body_type: RawBodyType,
+// Credentials the Authorization header is computed from; values support {{variable}} syntax
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RequestAuth {
+    // Use the nearest folder's or the collection's auth
+    #[default]
+    Inherit,
+    None,
+    Basic {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    #[serde(rename = "apikey")]
+    ApiKey {
+        key: String,
+        value: String,
+        placement: ApiKeyPlacement,
+    },
+    // OAuth 2.0 client credentials grant; the token is fetched when a request needs it
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        scopes: String,
+    },
+    // Windows integrated auth against IIS. `negotiate` sends the NTLM exchange under the
+    // Negotiate scheme ("NTLM over Negotiate"); there is no Kerberos/SPNEGO support, so
+    // servers that only accept Kerberos reject it.
+    #[serde(rename = "ntlm")]
+    Ntlm {
+        username: String,
+        password: String,
+        domain: String,
+        negotiate: bool,
+    },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Workspace {
-    name: String,
-    file_path: Option<std::path::PathBuf>,
-    collections: Vec<Collection>,
-    environments: Vec<Environment>,
-    selected_collection: Option<usize>,
-    selected_folder_path: Vec<usize>, // Path to selected folder within collection
-    selected_request: Option<usize>,
-    selected_environment: Option<usize>,
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+enum ApiKeyPlacement {
+    #[default]
+    Header,
+    Query,
 }
 
-struct SendApp {
-    // Workspaces
-    workspaces: Vec<Workspace>,
-    current_workspace: usize,
-    // Current request
-    current_request: HttpRequest,
-    // Response
-    current_response: Option<HttpResponse>,
-    is_loading: bool,
-    // UI State
-    selected_sidebar_item: Option<SidebarItem>,
-    request_tab: RequestTab,
-    raw_body_type: RawBodyType,
-    response_tab: ResponseTab,
-    // Runtime for async operations
-    runtime: Runtime,
-    response_receiver: Option<mpsc::Receiver<Result<HttpResponse, String>>>,
-    // Dialogs
-    new_collection_dialog: bool,
-    new_collection_name: String,
-    new_request_dialog: bool,
-    new_request_name: String,
-    new_workspace_dialog: bool,
-    new_workspace_name: String,
-    new_environment_dialog: bool,
-    new_environment_name: String,
-    new_folder_dialog: bool,
-    new_folder_name: String,
+impl RequestAuth {
+    // One blank value per kind, in the order the Auth tab lists them
+    fn kinds() -> [RequestAuth; 7] {
+        [
+            RequestAuth::Inherit,
+            RequestAuth::None,
+            RequestAuth::Basic {
+                username: String::new(),
+                password: String::new(),
+            },
+            RequestAuth::Bearer {
+                token: String::new(),
+            },
+            RequestAuth::ApiKey {
+                key: String::new(),
+                value: String::new(),
+                placement: ApiKeyPlacement::Header,
+            },
+            RequestAuth::OAuth2 {
+                token_url: String::new(),
+                client_id: String::new(),
+                client_secret: String::new(),
+                scopes: String::new(),
+            },
+            RequestAuth::Ntlm {
+                username: String::new(),
+                password: String::new(),
+                domain: String::new(),
+                negotiate: false,
+            },
+        ]
+    }
+
+    fn label(&self) -> String {
+        match self {
+            RequestAuth::Inherit => tr("inherit-auth"),
+            RequestAuth::None => tr("no-auth"),
+            RequestAuth::Basic { .. } => tr("basic-auth"),
+            RequestAuth::Bearer { .. } => tr("bearer-token"),
+            RequestAuth::ApiKey { .. } => tr("api-key"),
+            RequestAuth::OAuth2 { .. } => tr("oauth2-client-credentials"),
+            RequestAuth::Ntlm { .. } => tr("ntlm-auth"),
+        }
+    }
+
+    // Whether this auth sends an Authorization header in place of a Token Manager token
+    fn sets_authorization(&self) -> bool {
+        matches!(
+            self,
+            RequestAuth::Basic { .. }
+                | RequestAuth::Bearer { .. }
+                | RequestAuth::OAuth2 { .. }
+                | RequestAuth::Ntlm { .. }
+        )
+    }
+
+    // The key name and value of an API key sent in the given place
+    fn api_key(&self, placement: ApiKeyPlacement) -> Option<(&str, &str)> {
+        match self {
+            RequestAuth::ApiKey {
+                key,
+                value,
+                placement: sent_in,
+            } if *sent_in == placement && !key.trim().is_empty() => Some((key.trim(), value)),
+            _ => None,
+        }
+    }
+
+    fn basic_header(username: &str, password: &str) -> String {
+        format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password))
+        )
+    }
+
+    // Blanks the credentials for Export for Sharing. No catch-all arm, so a new auth type has
+    // to say what it keeps.
+    fn scrub_for_sharing(&mut self) {
+        match self {
+            RequestAuth::Basic { password, .. } => password.clear(),
+            RequestAuth::Bearer { token } => token.clear(),
+            RequestAuth::ApiKey { value, .. } => value.clear(),
+            RequestAuth::OAuth2 { client_secret, .. } => client_secret.clear(),
+            RequestAuth::Ntlm { password, .. } => password.clear(),
+            RequestAuth::Inherit | RequestAuth::None => {}
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-enum ResponseTab {
-    Body,
-    Headers,
-    Cookies,
+// Access tokens from client credentials grants, shared by every request that uses the
+// same token URL, client and scopes. A tokio mutex so concurrent sends wait for one fetch.
+type OAuthTokenCache = Arc<tokio::sync::Mutex<HashMap<String, CachedToken>>>;
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    // The Authorization header value, e.g. "Bearer abc"
+    header: String,
+    expires_at: Option<Instant>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-enum SidebarItem {
-    Collections,
-    Environment,
+impl CachedToken {
+    // Refreshed a little early so it doesn't expire while a request is in flight
+    fn is_fresh(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| Instant::now() + Duration::from_secs(30) < expires_at)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-enum RequestTab {
-    Params,
-    Headers,
-    Body,
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    token_type: String,
+    expires_in: Option<u64>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-enum RawBodyType {
-    Text,
-    JavaScript,
-    JSON,
-    HTML,
-    XML,
+#[derive(Debug, Clone)]
+struct ClientCredentials {
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scopes: String,
+    cache: OAuthTokenCache,
 }
 
-impl Default for SendApp {
-    fn default() -> Self {
-        let default_workspace = Workspace {
-            name: "Default Workspace".to_string(),
-            file_path: None,
-            collections: vec![Collection {
-                id: Uuid::new_v4().to_string(),
-                name: "Default Collection".to_string(),
-                root_folder: Folder {
-                    id: Uuid::new_v4().to_string(),
-                    name: "Root".to_string(),
-                    requests: vec![],
-                    folders: vec![],
-                },
-            }],
-            environments: vec![Environment {
-                name: "Default".to_string(),
-                variables: vec![],
-            }],
-            selected_collection: Some(0),
-            selected_folder_path: vec![],
-            selected_request: None,
-            selected_environment: Some(0),
+impl ClientCredentials {
+    fn cache_key(&self) -> String {
+        format!(
+            "{}\n{}\n{}\n{}",
+            self.token_url, self.client_id, self.client_secret, self.scopes
+        )
+    }
+
+    // The Authorization header value, from the cache or a new token. `refresh` skips the cache.
+    async fn authorization(
+        &self,
+        client: &reqwest::Client,
+        refresh: bool,
+    ) -> reqwest::Result<String> {
+        let mut cache = self.cache.lock().await;
+        let key = self.cache_key();
+        if !refresh && let Some(token) = cache.get(&key).filter(|token| token.is_fresh()) {
+            return Ok(token.header.clone());
+        }
+        let mut form = vec![("grant_type", "client_credentials")];
+        if !self.scopes.trim().is_empty() {
+            form.push(("scope", self.scopes.trim()));
+        }
+        let response: TokenResponse = client
+            .post(&self.token_url)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&form)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let scheme = if response.token_type.is_empty()
+            || response.token_type.eq_ignore_ascii_case("bearer")
+        {
+            "Bearer"
+        } else {
+            response.token_type.as_str()
         };
+        let token = CachedToken {
+            header: format!("{} {}", scheme, response.access_token),
+            expires_at: response
+                .expires_in
+                .map(|secs| Instant::now() + Duration::from_secs(secs)),
+        };
+        let header = token.header.clone();
+        cache.insert(key, token);
+        Ok(header)
+    }
+}
 
-        // Try to load from cache first
-        if let Some(cache) = Self::load_cache() {
-            let workspaces = if cache.workspaces.is_empty() {
-                vec![default_workspace]
-            } else {
-                cache.workspaces
-            };
+// NTLMv2 handshake (MS-NLMP): a negotiate message, the server's challenge in a 401, then
+// an authenticate message on the same connection
+#[derive(Debug, Clone)]
+struct NtlmCredentials {
+    username: String,
+    password: String,
+    domain: String,
+    scheme: &'static str,
+}
 
-            let current_workspace = if cache.current_workspace < workspaces.len() {
-                cache.current_workspace
-            } else {
-                0
-            };
+impl NtlmCredentials {
+    const SIGNATURE: &'static [u8] = b"NTLMSSP\0";
+    // Unicode, OEM, request target, NTLM, always sign, extended session security, 128, 56
+    const FLAGS: u32 = 0xa008_8207;
 
-            Self {
-                workspaces,
-                current_workspace,
-                current_request: HttpRequest {
-                    id: Uuid::new_v4().to_string(),
-                    name: "New Request".to_string(),
-                    method: "GET".to_string(),
-                    url: "https://httpbin.org/get".to_string(),
-                    headers: vec![],
-                    body: String::new(),
-                    body_type: BodyType::None,
-                    form_data: vec![],
-                    url_encoded_data: vec![],
-                    query_params: vec![],
-                },
-                current_response: None,
-                is_loading: false,
-                selected_sidebar_item: cache.selected_sidebar_item,
-                request_tab: cache.request_tab,
-                raw_body_type: cache.raw_body_type,
-                response_tab: cache.response_tab,
-                runtime: Runtime::new().unwrap(),
-                response_receiver: None,
-                new_collection_dialog: false,
-                new_collection_name: String::new(),
-                new_request_dialog: false,
-                new_request_name: String::new(),
-                new_workspace_dialog: false,
-                new_workspace_name: String::new(),
-                new_environment_dialog: false,
-                new_environment_name: String::new(),
-                new_folder_dialog: false,
-                new_folder_name: String::new(),
-            }
-        } else {
-            // Default configuration if no cache exists
-            Self {
-                workspaces: vec![default_workspace],
-                current_workspace: 0,
-                current_request: HttpRequest {
-                    id: Uuid::new_v4().to_string(),
-                    name: "New Request".to_string(),
-                    method: "GET".to_string(),
-                    url: "https://httpbin.org/get".to_string(),
-                    headers: vec![],
-                    body: String::new(),
-                    body_type: BodyType::None,
-                    form_data: vec![],
-                    url_encoded_data: vec![],
-                    query_params: vec![],
-                },
-                current_response: None,
-                is_loading: false,
-                selected_sidebar_item: None,
-                request_tab: RequestTab::Params,
-                raw_body_type: RawBodyType::JSON,
-                response_tab: ResponseTab::Body,
-                runtime: Runtime::new().unwrap(),
-                response_receiver: None,
-                new_collection_dialog: false,
-                new_collection_name: String::new(),
-                new_request_dialog: false,
-                new_request_name: String::new(),
-                new_workspace_dialog: false,
-                new_workspace_name: String::new(),
-                new_environment_dialog: false,
-                new_environment_name: String::new(),
-                new_folder_dialog: false,
-                new_folder_name: String::new(),
-            }
+    fn new(username: &str, password: &str, domain: &str, negotiate: bool) -> Self {
+        // Accept DOMAIN\user when the domain is left empty
+        let (domain, username) = match username.split_once('\\') {
+            Some((user_domain, username)) if domain.trim().is_empty() => (user_domain, username),
+            Some((_, username)) => (domain, username),
+            None => (domain, username),
+        };
+        Self {
+            username: username.trim().to_string(),
+            password: password.to_string(),
+            domain: domain.trim().to_string(),
+            scheme: if negotiate { "Negotiate" } else { "NTLM" },
         }
     }
-}
 
-impl eframe::App for SendApp {
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {
-        self.save_cache();
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
     }
 
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Check for response
-        if let Some(receiver) = &self.response_receiver {
-            if let Ok(result) = receiver.try_recv() {
-                match result {
-                    Ok(response) => {
-                        self.current_response = Some(response);
-                        self.is_loading = false;
-                    }
-                    Err(error) => {
-                        let error_body_size = error.len();
-                        self.current_response = Some(HttpResponse {
-                            status: 0,
-                            status_text: "Error".to_string(),
-                            headers: HashMap::new(),
-                            body: error,
-                            time: 0,
-                            body_size: error_body_size,
-                            headers_size: 0,
-                        });
-                        self.is_loading = false;
-                    }
-                }
-                self.response_receiver = None;
-            }
-        }
-
-        // Top panel
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            egui::menu::bar(ui, |ui| {
-                ui.menu_button("File", |ui| {
-                    if ui.button("New Workspace").clicked() {
-                        self.new_workspace_dialog = true;
-                        ui.close_menu();
-                    }
-                    ui.separator();
-                    if ui.button("New Collection").clicked() {
-                        self.new_collection_dialog = true;
-                        ui.close_menu();
-                    }
-                    if ui.button("New Folder").clicked() {
-                        self.new_folder_dialog = true;
-                        ui.close_menu();
-                    }
-                    if ui.button("New Request").clicked() {
-                        self.new_request_dialog = true;
-                        ui.close_menu();
-                    }
-                    if ui.button("New Environment").clicked() {
-                        self.new_environment_dialog = true;
-                        ui.close_menu();
-                    }
-                    ui.separator();
-                    if ui.button("Save Workspace...").clicked() {
-                        self.save_to_file();
-                        ui.close_menu();
-                    }
-                    if ui.button("Load Workspace...").clicked() {
-                        self.load_from_file();
-                        ui.close_menu();
-                    }
-                    ui.separator();
-                    if ui.button("Export Collection...").clicked() {
-                        self.export_collection();
-                        ui.close_menu();
-                    }
-                    if ui.button("Import Collection...").clicked() {
-                        self.import_collection();
-                        ui.close_menu();
-                    }
-                });
-                ui.menu_button("View", |ui| {
-                    if ui.button("Collections").clicked() {
-                        if self.selected_sidebar_item == Some(SidebarItem::Collections) {
-                            self.selected_sidebar_item = None;
-                        } else {
-                            self.selected_sidebar_item = Some(SidebarItem::Collections);
-                        }
-                        ui.close_menu();
-                    }
-                    if ui.button("Environment").clicked() {
-                        if self.selected_sidebar_item == Some(SidebarItem::Environment) {
-                            self.selected_sidebar_item = None;
-                        } else {
-                            self.selected_sidebar_item = Some(SidebarItem::Environment);
-                        }
-                        ui.close_menu();
-                    }
-                });
+    // NTOWFv1: MD4 of the UTF-16 password
+    fn nt_hash(password: &str) -> [u8; 16] {
+        Md4::digest(Self::utf16(password)).into()
+    }
 
-                ui.separator();
+    fn hmac_md5(key: &[u8], data: &[u8]) -> [u8; 16] {
+        let mut mac = Hmac::<Md5>::new_from_slice(key).expect("HMAC takes keys of any length");
+        mac.update(data);
+        mac.finalize().into_bytes().into()
+    }
 
-                // Workspace tabs
-                ui.horizontal(|ui| {
-                    ui.label("Workspaces:");
-                    for (idx, workspace) in self.workspaces.iter().enumerate() {
-                        let selected = idx == self.current_workspace;
-                        if ui.selectable_label(selected, &workspace.name).clicked() {
-                            self.current_workspace = idx;
-                            self.save_cache();
-                        }
-                    }
-                });
-            });
-        });
+    // NTOWFv2: the key both responses are computed with
+    fn response_key(&self) -> [u8; 16] {
+        let identity = Self::utf16(&format!("{}{}", self.username.to_uppercase(), self.domain));
+        Self::hmac_md5(&Self::nt_hash(&self.password), &identity)
+    }
 
-        // Mini sidebar
-        egui::SidePanel::left("mini_sidebar")
-            .exact_width(50.0)
-            .resizable(false)
-            .show(ctx, |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.add_space(10.0);
+    // The NTLMv2 response: NTProofStr followed by the blob it was computed over
+    fn nt_response(
+        key: &[u8; 16],
+        server_challenge: &[u8],
+        client_challenge: &[u8; 8],
+        filetime: &[u8; 8],
+        target_info: &[u8],
+    ) -> Vec<u8> {
+        let mut blob = vec![1, 1, 0, 0, 0, 0, 0, 0];
+        blob.extend_from_slice(filetime);
+        blob.extend_from_slice(client_challenge);
+        blob.extend_from_slice(&[0; 4]);
+        blob.extend_from_slice(target_info);
+        blob.extend_from_slice(&[0; 4]);
+        let proof = Self::hmac_md5(key, &[server_challenge, &blob].concat());
+        [&proof[..], &blob].concat()
+    }
 
-                    // Collections button
-                    let collections_selected =
-                        self.selected_sidebar_item == Some(SidebarItem::Collections);
-                    let collections_button = egui::Button::new("📁")
-                        .min_size(egui::Vec2::new(40.0, 40.0))
-                        .fill(if collections_selected {
-                            egui::Color32::from_gray(80)
-                        } else {
-                            egui::Color32::TRANSPARENT
-                        });
+    fn header(&self, message: &[u8]) -> String {
+        format!(
+            "{} {}",
+            self.scheme,
+            base64::engine::general_purpose::STANDARD.encode(message)
+        )
+    }
 
-                    if ui.add(collections_button).clicked() {
-                        if collections_selected {
-                            self.selected_sidebar_item = None;
-                        } else {
-                            self.selected_sidebar_item = Some(SidebarItem::Collections);
-                        }
-                        self.save_cache();
-                    }
+    fn negotiate_message(&self) -> Vec<u8> {
+        let mut message = Self::SIGNATURE.to_vec();
+        message.extend_from_slice(&1u32.to_le_bytes());
+        message.extend_from_slice(&Self::FLAGS.to_le_bytes());
+        // Empty domain and workstation fields
+        message.extend_from_slice(&[0; 16]);
+        message
+    }
 
-                    ui.add_space(5.0);
+    // The challenge message from a 401's WWW-Authenticate header
+    fn challenge(&self, response: &reqwest::Response) -> Option<Vec<u8>> {
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return None;
+        }
+        response
+            .headers()
+            .get_all(reqwest::header::WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(Self::challenge_token)
+    }
 
-                    // Environment button
-                    let environment_selected =
-                        self.selected_sidebar_item == Some(SidebarItem::Environment);
-                    let environment_button = egui::Button::new("🌍")
-                        .min_size(egui::Vec2::new(40.0, 40.0))
-                        .fill(if environment_selected {
-                            egui::Color32::from_gray(80)
-                        } else {
-                            egui::Color32::TRANSPARENT
-                        });
+    // The challenge message in a WWW-Authenticate or Proxy-Authenticate value
+    fn challenge_token(value: &str) -> Option<Vec<u8>> {
+        let (scheme, token) = value.trim().split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("NTLM") && !scheme.eq_ignore_ascii_case("Negotiate") {
+            return None;
+        }
+        base64::engine::general_purpose::STANDARD
+            .decode(token.trim())
+            .ok()
+            .filter(|message| message.len() >= 32 && message.starts_with(Self::SIGNATURE))
+    }
 
-                    if ui.add(environment_button).clicked() {
-                        if environment_selected {
-                            self.selected_sidebar_item = None;
-                        } else {
-                            self.selected_sidebar_item = Some(SidebarItem::Environment);
-                        }
-                        self.save_cache();
-                    }
-                });
-            });
+    fn authenticate_message(&self, challenge: &[u8]) -> Option<Vec<u8>> {
+        let field = |offset: usize| -> Option<&[u8]> {
+            let length = u16::from_le_bytes(challenge.get(offset..offset + 2)?.try_into().ok()?);
+            let start = u32::from_le_bytes(challenge.get(offset + 4..offset + 8)?.try_into().ok()?);
+            challenge.get(start as usize..start as usize + length as usize)
+        };
+        let server_challenge = challenge.get(24..32)?;
+        let flags = u32::from_le_bytes(challenge.get(20..24)?.try_into().ok()?);
+        let target_info = if challenge.len() >= 48 {
+            field(40)?
+        } else {
+            &[]
+        };
 
-        // Expandable sidebar panel
-        if let Some(selected_item) = self.selected_sidebar_item.clone() {
-            egui::SidePanel::left("expandable_sidebar")
-                .min_width(250.0)
-                .max_width(400.0)
-                .show(ctx, |ui| match selected_item {
-                    SidebarItem::Collections => {
-                        ui.heading("Collections");
-                        ui.separator();
-                        self.draw_collections_panel(ui);
-                    }
-                    SidebarItem::Environment => {
-                        ui.heading("Environment");
-                        ui.separator();
-                        self.draw_environment_panel(ui);
-                    }
-                });
+        // MsvAvTimestamp from the target info, when the server sends one
+        let mut timestamp = None;
+        let mut pairs = target_info;
+        while pairs.len() >= 4 {
+            let id = u16::from_le_bytes([pairs[0], pairs[1]]);
+            let length = u16::from_le_bytes([pairs[2], pairs[3]]) as usize;
+            let value = pairs.get(4..4 + length)?;
+            match id {
+                0 => break,
+                7 => timestamp = value.try_into().ok(),
+                _ => {}
+            }
+            pairs = &pairs[4 + length..];
         }
-
-        // Central panel
-        egui::CentralPanel::default().show(ctx, |ui| {
-            StripBuilder::new(ui)
-                .size(Size::remainder().at_least(300.0))
-                .size(Size::remainder().at_least(200.0))
-                .vertical(|mut strip| {
-                    strip.cell(|ui| {
-                        self.draw_request_panel(ui);
-                    });
-                    strip.cell(|ui| {
-                        self.draw_response_panel(ui);
-                    });
-                });
+        let filetime = timestamp.unwrap_or_else(|| {
+            let seconds = chrono::Utc::now().timestamp() + 11_644_473_600;
+            (seconds as u64 * 10_000_000).to_le_bytes()
         });
+        let mut client_challenge = [0u8; 8];
+        ring::rand::SecureRandom::fill(&ring::rand::SystemRandom::new(), &mut client_challenge)
+            .ok()?;
 
-        // Dialogs
-        self.draw_dialogs(ctx);
+        let v2_hash = self.response_key();
+        let nt_response = Self::nt_response(
+            &v2_hash,
+            server_challenge,
+            &client_challenge,
+            &filetime,
+            target_info,
+        );
+        // Servers that send a timestamp expect an empty LMv2 response
+        let lm_response = if timestamp.is_some() {
+            vec![0; 24]
+        } else {
+            let lm_proof =
+                Self::hmac_md5(&v2_hash, &[server_challenge, &client_challenge].concat());
+            [&lm_proof[..], &client_challenge].concat()
+        };
+
+        let domain = Self::utf16(&self.domain);
+        let username = Self::utf16(&self.username);
+        let workstation = Self::utf16("SEND");
+        let payloads: [&[u8]; 6] = [
+            &lm_response,
+            &nt_response,
+            &domain,
+            &username,
+            &workstation,
+            &[],
+        ];
+        let mut message = Self::SIGNATURE.to_vec();
+        message.extend_from_slice(&3u32.to_le_bytes());
+        let mut offset = 64;
+        for payload in payloads {
+            message.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+            message.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+            message.extend_from_slice(&(offset as u32).to_le_bytes());
+            offset += payload.len();
+        }
+        message.extend_from_slice(&(flags & Self::FLAGS | 1).to_le_bytes());
+        for payload in payloads {
+            message.extend_from_slice(payload);
+        }
+        Some(message)
     }
 }
 
-impl RawBodyType {
-    fn get_content_type(&self) -> &'static str {
-        match self {
-            RawBodyType::Text => "text/plain",
-            RawBodyType::JavaScript => "application/javascript",
-            RawBodyType::JSON => "application/json",
-            RawBodyType::HTML => "text/html",
-            RawBodyType::XML => "application/xml",
+// Signs every request in a collection for HMAC schemes no auth type covers. Runs on the
+// resolved request: the string to sign and the header values are templates with
+// {method}, {url}, {host}, {path}, {query}, {body}, {body_sha256}, {timestamp},
+// {timestamp_ms}, {date}, {iso_date}, {nonce} and {header:Name}; header values also get
+// {signature}. {{variables}} are resolved first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct RequestSigner {
+    enabled: bool,
+    algorithm: SignatureAlgorithm,
+    encoding: SignatureEncoding,
+    secret: String,
+    string_to_sign: String,
+    // Set on the request, replacing headers of the same name
+    headers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+enum SignatureAlgorithm {
+    #[default]
+    HmacSha256,
+    HmacSha384,
+    HmacSha512,
+    HmacSha1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+enum SignatureEncoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+impl Default for RequestSigner {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            algorithm: SignatureAlgorithm::HmacSha256,
+            encoding: SignatureEncoding::Hex,
+            secret: String::new(),
+            string_to_sign: "{method}\n{path}\n{timestamp}\n{body_sha256}".to_string(),
+            headers: vec![
+                ("X-Timestamp".to_string(), "{timestamp}".to_string()),
+                ("X-Signature".to_string(), "{signature}".to_string()),
+            ],
         }
     }
 }
 
-impl SendApp {
-    fn get_cache_dir() -> std::path::PathBuf {
-        let mut cache_dir = dirs::cache_dir().unwrap_or_else(|| std::env::current_dir().unwrap());
-        cache_dir.push("send");
-        cache_dir
-    }
+impl SignatureAlgorithm {
+    const ALL: [SignatureAlgorithm; 4] = [
+        SignatureAlgorithm::HmacSha256,
+        SignatureAlgorithm::HmacSha384,
+        SignatureAlgorithm::HmacSha512,
+        SignatureAlgorithm::HmacSha1,
+    ];
 
-    fn get_cache_file_path() -> std::path::PathBuf {
-        let mut cache_path = Self::get_cache_dir();
-        cache_path.push("send.json");
-        cache_path
+    fn label(&self) -> &'static str {
+        match self {
+            SignatureAlgorithm::HmacSha256 => "HMAC-SHA256",
+            SignatureAlgorithm::HmacSha384 => "HMAC-SHA384",
+            SignatureAlgorithm::HmacSha512 => "HMAC-SHA512",
+            SignatureAlgorithm::HmacSha1 => "HMAC-SHA1",
+        }
     }
 
-    fn save_cache(&self) {
-        let cache = AppCache {
-            current_workspace: self.current_workspace,
-            workspaces: self.workspaces.clone(),
-            selected_sidebar_item: self.selected_sidebar_item.clone(),
-            request_tab: self.request_tab.clone(),
-            response_tab: self.response_tab.clone(),
-            raw_body_type: self.raw_body_type.clone(),
+    fn sign(&self, secret: &[u8], message: &[u8]) -> Vec<u8> {
+        let algorithm = match self {
+            SignatureAlgorithm::HmacSha256 => ring::hmac::HMAC_SHA256,
+            SignatureAlgorithm::HmacSha384 => ring::hmac::HMAC_SHA384,
+            SignatureAlgorithm::HmacSha512 => ring::hmac::HMAC_SHA512,
+            SignatureAlgorithm::HmacSha1 => ring::hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
         };
+        ring::hmac::sign(&ring::hmac::Key::new(algorithm, secret), message)
+            .as_ref()
+            .to_vec()
+    }
+}
 
-        if let Ok(json) = serde_json::to_string_pretty(&cache) {
-            let cache_path = Self::get_cache_file_path();
-            if let Some(parent) = cache_path.parent() {
-                let _ = std::fs::create_dir_all(parent);
+impl RequestSigner {
+    fn sign(&self, request: &mut ResolvedRequest, resolve: impl Fn(&str) -> String) {
+        let (method, full_url, body) = (
+            request.method.clone(),
+            request.url.clone(),
+            request.sent_body(),
+        );
+        let url = reqwest::Url::parse(&full_url).ok();
+        let now = chrono::Utc::now();
+        let nonce = Uuid::new_v4().simple().to_string();
+        let body_sha256 = Tool::hex(ring::digest::digest(&ring::digest::SHA256, &body).as_ref());
+        let body = String::from_utf8_lossy(&body).into_owned();
+        let fill = |template: &str, signature: &str, headers: &[(String, String)]| {
+            let mut filled = String::new();
+            let mut rest = template;
+            while let Some(start) = rest.find('{')
+                && let Some(end) = rest[start..].find('}')
+            {
+                filled.push_str(&rest[..start]);
+                let name = &rest[start + 1..start + end];
+                let value = match name {
+                    "method" => Some(method.clone()),
+                    "url" => Some(full_url.clone()),
+                    "host" => url
+                        .as_ref()
+                        .and_then(|url| url.host_str())
+                        .map(str::to_string),
+                    "path" => url.as_ref().map(|url| url.path().to_string()),
+                    "query" => url
+                        .as_ref()
+                        .map(|url| url.query().unwrap_or_default().to_string()),
+                    "body" => Some(body.clone()),
+                    "body_sha256" => Some(body_sha256.clone()),
+                    "timestamp" => Some(now.timestamp().to_string()),
+                    "timestamp_ms" => Some(now.timestamp_millis().to_string()),
+                    "date" => Some(now.format("%a, %d %b %Y %H:%M:%S GMT").to_string()),
+                    "iso_date" => Some(now.format("%Y%m%dT%H%M%SZ").to_string()),
+                    "nonce" => Some(nonce.clone()),
+                    "signature" => Some(signature.to_string()),
+                    _ => name.strip_prefix("header:").and_then(|header| {
+                        headers
+                            .iter()
+                            .find(|(key, _)| key.trim().eq_ignore_ascii_case(header.trim()))
+                            .map(|(_, value)| value.clone())
+                    }),
+                };
+                match value {
+                    Some(value) => filled.push_str(&value),
+                    None => filled.push_str(&rest[start..start + end + 1]),
+                }
+                rest = &rest[start + end + 1..];
             }
-            let _ = std::fs::write(cache_path, json);
-        }
-    }
+            filled.push_str(rest);
+            filled
+        };
 
-    fn load_cache() -> Option<AppCache> {
-        let cache_path = Self::get_cache_file_path();
-        if let Ok(content) = std::fs::read_to_string(cache_path) {
-            if let Ok(cache) = serde_json::from_str::<AppCache>(&content) {
-                return Some(cache);
+        // Headers without {signature} are set first so the string to sign can include them
+        let (signed, unsigned): (Vec<_>, Vec<_>) = self
+            .headers
+            .iter()
+            .filter(|(key, _)| !key.trim().is_empty())
+            .partition(|(_, value)| value.contains("{signature}"));
+        let set_headers = |target: &mut Vec<(String, String)>,
+                           headers: Vec<&(String, String)>,
+                           signature: &str| {
+            for (key, template) in headers {
+                let value = fill(&resolve(template), signature, target);
+                target.retain(|(name, _)| !name.trim().eq_ignore_ascii_case(key.trim()));
+                target.push((key.trim().to_string(), value));
             }
-        }
-        None
+        };
+        request.credential_headers.extend(
+            self.headers
+                .iter()
+                .filter(|(key, _)| !key.trim().is_empty())
+                .map(|(key, _)| key.trim().to_string()),
+        );
+        set_headers(&mut request.headers, unsigned, "");
+        let message = fill(&resolve(&self.string_to_sign), "", &request.headers);
+        let signature = self
+            .algorithm
+            .sign(resolve(&self.secret).as_bytes(), message.as_bytes());
+        let signature = match self.encoding {
+            SignatureEncoding::Hex => Tool::hex(&signature),
+            SignatureEncoding::Base64 => {
+                base64::engine::general_purpose::STANDARD.encode(signature)
+            }
+        };
+        set_headers(&mut request.headers, signed, &signature);
     }
+}
 
-    fn format_size(size: usize) -> String {
-        if size < 1024 {
-            format!("{} B", size)
-        } else if size < 1024 * 1024 {
-            format!("{:.1} KB", size as f64 / 1024.0)
-        } else if size < 1024 * 1024 * 1024 {
-            format!("{:.1} MB", size as f64 / (1024.0 * 1024.0))
-        } else {
-            format!("{:.1} GB", size as f64 / (1024.0 * 1024.0 * 1024.0))
-        }
-    }
+// A no-code check on a response header, run after every send and in collection runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct HeaderAssertion {
+    enabled: bool,
+    header: String,
+    check: HeaderCheck,
+    // Supports {{variable}} syntax
+    expected: String,
+}
 
-    fn current_workspace(&self) -> &Workspace {
-        &self.workspaces[self.current_workspace]
+impl Default for HeaderAssertion {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            header: String::new(),
+            check: HeaderCheck::Exists,
+            expected: String::new(),
+        }
     }
+}
 
-    fn current_workspace_mut(&mut self) -> &mut Workspace {
-        &mut self.workspaces[self.current_workspace]
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum HeaderCheck {
+    Exists,
+    Equals,
+    Matches,
+    LessThan,
+    AtMost,
+    GreaterThan,
+    AtLeast,
+}
 
-    fn get_folder_by_path_mut<'a>(
-        collection: &'a mut Collection,
-        folder_path: &[usize],
-    ) -> Option<&'a mut Folder> {
-        let mut current_folder = &mut collection.root_folder;
+impl HeaderCheck {
+    const ALL: [HeaderCheck; 7] = [
+        HeaderCheck::Exists,
+        HeaderCheck::Equals,
+        HeaderCheck::Matches,
+        HeaderCheck::LessThan,
+        HeaderCheck::AtMost,
+        HeaderCheck::GreaterThan,
+        HeaderCheck::AtLeast,
+    ];
 
-        for &folder_idx in folder_path {
-            if folder_idx < current_folder.folders.len() {
-                current_folder = &mut current_folder.folders[folder_idx];
-            } else {
-                return None;
-            }
+    fn label(&self) -> &'static str {
+        match self {
+            HeaderCheck::Exists => "exists",
+            HeaderCheck::Equals => "equals",
+            HeaderCheck::Matches => "matches",
+            HeaderCheck::LessThan => "<",
+            HeaderCheck::AtMost => "≤",
+            HeaderCheck::GreaterThan => ">",
+            HeaderCheck::AtLeast => "≥",
         }
-        Some(current_folder)
     }
+}
 
-    fn get_folder_by_path<'a>(
-        collection: &'a Collection,
-        folder_path: &[usize],
-    ) -> Option<&'a Folder> {
-        let mut current_folder = &collection.root_folder;
+impl HeaderAssertion {
+    fn describe(&self) -> String {
+        match self.check {
+            HeaderCheck::Exists => format!("{} exists", self.header.trim()),
+            check => format!("{} {} {}", self.header.trim(), check.label(), self.expected),
+        }
+    }
 
-        for &folder_idx in folder_path {
-            if folder_idx < current_folder.folders.len() {
-                current_folder = &current_folder.folders[folder_idx];
+    // Checks the header's value, or None when the response doesn't have it
+    fn evaluate(&self, actual: Option<&str>) -> Result<(), String> {
+        let Some(actual) = actual else {
+            return Err("header missing".to_string());
+        };
+        let compare = |holds: fn(f64, f64) -> bool| {
+            let value = actual
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("\"{}\" is not a number", actual))?;
+            let expected = self
+                .expected
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("expected value \"{}\" is not a number", self.expected))?;
+            if holds(value, expected) {
+                Ok(())
             } else {
-                return None;
+                Err(format!("was {}", actual))
+            }
+        };
+        match self.check {
+            HeaderCheck::Exists => Ok(()),
+            HeaderCheck::Equals if actual == self.expected => Ok(()),
+            HeaderCheck::Equals => Err(format!("was \"{}\"", actual)),
+            HeaderCheck::Matches => {
+                let regex =
+                    Regex::new(&self.expected).map_err(|e| format!("invalid pattern: {}", e))?;
+                if regex.is_match(actual) {
+                    Ok(())
+                } else {
+                    Err(format!("was \"{}\"", actual))
+                }
             }
+            HeaderCheck::LessThan => compare(|value, expected| value < expected),
+            HeaderCheck::AtMost => compare(|value, expected| value <= expected),
+            HeaderCheck::GreaterThan => compare(|value, expected| value > expected),
+            HeaderCheck::AtLeast => compare(|value, expected| value >= expected),
         }
-        Some(current_folder)
     }
 
-    fn save_current_request(&mut self) {
-        let current_request = self.current_request.clone();
-        let current_workspace_idx = self.current_workspace;
-        let collection_idx = self.workspaces[current_workspace_idx].selected_collection;
-        let request_idx = self.workspaces[current_workspace_idx].selected_request;
-        let folder_path = self.workspaces[current_workspace_idx]
-            .selected_folder_path
-            .clone();
+    fn check_all(
+        assertions: &[HeaderAssertion],
+        header: impl Fn(&str) -> Option<String>,
+    ) -> Vec<(String, Result<(), String>)> {
+        assertions
+            .iter()
+            .map(|assertion| {
+                let actual = header(assertion.header.trim());
+                (assertion.describe(), assertion.evaluate(actual.as_deref()))
+            })
+            .collect()
+    }
 
-        if let (Some(collection_idx), Some(request_idx)) = (collection_idx, request_idx) {
-            if collection_idx < self.workspaces[current_workspace_idx].collections.len() {
-                if let Some(folder) = Self::get_folder_by_path_mut(
-                    &mut self.workspaces[current_workspace_idx].collections[collection_idx],
-                    &folder_path,
-                ) {
-                    if request_idx < folder.requests.len() {
-                        folder.requests[request_idx] = current_request;
-                        self.auto_save_workspace();
-                    }
-                }
-            }
-        }
+    // Messages for the assertions that don't hold against a live response
+    fn failures(
+        assertions: &[HeaderAssertion],
+        headers: &reqwest::header::HeaderMap,
+    ) -> Vec<String> {
+        Self::check_all(assertions, |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        })
+        .into_iter()
+        .filter_map(|(description, result)| result.err().map(|e| format!("{}: {}", description, e)))
+        .collect()
     }
+}
 
-    fn set_content_type_header(&mut self, content_type: &str) {
-        // Find existing Content-Type header (case-insensitive)
-        let content_type_index = self
-            .current_request
-            .headers
-            .iter()
-            .position(|(key, _)| key.to_lowercase() == "content-type");
+// Stores part of a response in an environment variable: the first capture group of a
+// pattern run over the raw body or one header, for responses JSON tools can't read
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct CaptureRule {
+    enabled: bool,
+    source: CaptureSource,
+    // Used when the source is a header
+    header: String,
+    pattern: String,
+    variable: String,
+}
 
-        if let Some(index) = content_type_index {
-            // Update existing Content-Type header
-            self.current_request.headers[index].1 = content_type.to_string();
-        } else {
-            // Add new Content-Type header
-            self.current_request
-                .headers
-                .push(("Content-Type".to_string(), content_type.to_string()));
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+enum CaptureSource {
+    #[default]
+    Body,
+    Header,
+}
+
+impl Default for CaptureRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            source: CaptureSource::Body,
+            header: String::new(),
+            pattern: String::new(),
+            variable: String::new(),
         }
     }
+}
 
-    fn remove_content_type_header(&mut self) {
-        // Remove Content-Type header when body type is None
-        self.current_request
-            .headers
-            .retain(|(key, _)| key.to_lowercase() != "content-type");
-    }
+const CAPTURE_BODY_LIMIT: usize = 1024 * 1024;
 
-    fn auto_save_workspace(&self) {
-        let workspace = self.current_workspace();
-        if let Some(path) = &workspace.file_path {
-            let data = AppStorage {
-                collections: workspace.collections.clone(),
-                environments: workspace.environments.clone(),
-            };
-            if let Ok(json) = serde_json::to_string_pretty(&data) {
-                let _ = std::fs::write(path, json);
-            }
+impl CaptureRule {
+    fn describe(&self) -> String {
+        match self.source {
+            CaptureSource::Body => format!("{} ← body /{}/", self.variable.trim(), self.pattern),
+            CaptureSource::Header => format!(
+                "{} ← {} /{}/",
+                self.variable.trim(),
+                self.header.trim(),
+                self.pattern
+            ),
         }
     }
 
-    fn resolve_value(&self, input: &str) -> String {
-        let mut result = input.to_string();
-        let workspace = self.current_workspace();
-        if let Some(env_idx) = workspace.selected_environment {
-            if env_idx < workspace.environments.len() {
-                let env = &workspace.environments[env_idx];
-                for (key, value) in &env.variables {
-                    let placeholder = format!("{{{{{}}}}}", key);
-                    result = result.replace(&placeholder, value);
-                }
-            }
-        }
-        result
+    // The first capture group, or the whole match when the pattern has no groups. Rules run
+    // on the UI thread, so only the start of a large body is searched.
+    fn apply(&self, body: &str, header: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+        let regex = Regex::new(&self.pattern).map_err(|e| format!("invalid pattern: {}", e))?;
+        let text = match self.source {
+            CaptureSource::Body => body[..body.floor_char_boundary(CAPTURE_BODY_LIMIT)].to_string(),
+            CaptureSource::Header => header(self.header.trim())
+                .ok_or_else(|| format!("header {} missing", self.header.trim()))?,
+        };
+        let groups = regex.captures(&text).ok_or("no match")?;
+        let index = if groups.len() > 1 { 1 } else { 0 };
+        groups
+            .get(index)
+            .map(|group| group.as_str().to_string())
+            .ok_or_else(|| "the capture group didn't match".to_string())
     }
+}
 
-    fn save_to_file(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .set_title("Save Workspace")
-            .add_filter("JSON", &["json"])
-            .save_file()
-        {
-            let workspace = self.current_workspace_mut();
-            let data = AppStorage {
-                collections: workspace.collections.clone(),
-                environments: workspace.environments.clone(),
-            };
-            let json = serde_json::to_string_pretty(&data).unwrap();
-            if std::fs::write(&path, json).is_ok() {
-                workspace.file_path = Some(path);
-            }
-        }
-    }
+// A response stored with the request, served instead of the network in offline mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SavedExample {
+    status: u16,
+    status_text: String,
+    headers: HashMap<String, String>,
+    body: String,
+}
 
-    fn load_from_file(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .set_title("Load Workspace")
-            .add_filter("JSON", &["json"])
-            .pick_file()
-        {
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(storage) = serde_json::from_str::<AppStorage>(&content) {
-                    let workspace_name = path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("Loaded Workspace")
-                        .to_string();
-
-                    let selected_collection = if !storage.collections.is_empty() {
-                        Some(0)
-                    } else {
-                        None
-                    };
-                    let selected_environment = if !storage.environments.is_empty() {
-                        Some(0)
-                    } else {
-                        None
-                    };
+// Everything that affects how the shared reqwest client is built
+#[derive(Debug, Clone, PartialEq)]
+struct ClientConfig {
+    timeout_secs: u64,
+    proxy: Option<ProxySettings>,
+    system_proxy: Option<SystemProxy>,
+    skip_tls_verification: bool,
+    ca_certificates: Vec<CaCertificate>,
+    client_certificates: Vec<ClientCertificate>,
+    min_tls_version: TlsVersion,
+    max_tls_version: TlsVersion,
+    user_agent: String,
+    ip_preference: IpPreference,
+    http_version: HttpVersion,
+    local_address: String,
+    interface: String,
+}
 
-                    let new_workspace = Workspace {
-                        name: workspace_name,
-                        file_path: Some(path),
-                        collections: storage.collections,
-                        environments: storage.environments,
-                        selected_collection,
-                        selected_folder_path: vec![],
-                        selected_request: None,
-                        selected_environment,
-                    };
+const CACHED_CLIENTS_PER_WORKSPACE: usize = 4;
 
-                    self.workspaces.push(new_workspace);
-                    self.current_workspace = self.workspaces.len() - 1;
-                    self.save_cache();
-                }
-            }
+struct CachedClients {
+    config: ClientConfig,
+    clients: HttpClients,
+    // The NTLM relay the clients' proxy points at, shared by clients using the same proxy and
+    // stopped once none of them are left
+    relay: Option<Arc<NtlmProxyRelay>>,
+}
+
+// The shared client plus one per client certificate, since a TLS connector presents a
+// single identity
+#[derive(Clone)]
+struct HttpClients {
+    default: reqwest::Client,
+    identities: Vec<(String, reqwest::Client)>,
+    connections: Arc<std::sync::Mutex<ConnectionLog>>,
+}
+
+// Idle pooled connections are closed after this long, so log entries older than it are dropped
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+// Connections are keyed by local and remote address
+#[derive(Debug, Default)]
+struct ConnectionLog {
+    // Connections no response has come back on yet; the first one takes the entry
+    opened: HashMap<(SocketAddr, SocketAddr), OpenedConnection>,
+    // Latest lookup time for each host name, until a response from the host takes it
+    dns_times: HashMap<String, Duration>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OpenedConnection {
+    at: Instant,
+    // DNS and the TLS handshake included
+    connect: Duration,
+}
+
+// How the final response's connection came about; the durations are only known for new ones
+#[derive(Debug, Clone, Copy)]
+struct ConnectionInfo {
+    reused: bool,
+    dns: Option<Duration>,
+    connect: Option<Duration>,
+}
+
+// Times opening connections for the connection log. reqwest's connector runs the TLS
+// handshake in the same call as the TCP connect, so the two are timed together.
+#[derive(Clone)]
+struct ConnectTimingLayer {
+    connections: Arc<std::sync::Mutex<ConnectionLog>>,
+}
+
+impl<S> tower_layer::Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTimingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTimingService {
+            inner,
+            connections: self.connections.clone(),
         }
     }
+}
 
-    fn export_collection(&self) {
-        let workspace = self.current_workspace();
-        if let Some(idx) = workspace.selected_collection {
-            if let Some(collection) = workspace.collections.get(idx) {
-                if let Some(path) = rfd::FileDialog::new()
-                    .set_title(&format!("Export '{}'", collection.name))
-                    .add_filter("JSON", &["json"])
-                    .save_file()
-                {
-                    let json = serde_json::to_string_pretty(collection).unwrap();
-                    std::fs::write(path, json).ok();
-                }
-            }
-        }
+#[derive(Clone)]
+struct ConnectTimingService<S> {
+    inner: S,
+    connections: Arc<std::sync::Mutex<ConnectionLog>>,
+}
+
+impl<S, R> tower_service::Service<R> for ConnectTimingService<S>
+where
+    S: tower_service::Service<R>,
+    S::Future: Send + 'static,
+    S::Response: hyper_util::client::legacy::connect::Connection,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
     }
 
-    fn import_collection(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .set_title("Import Collection")
-            .add_filter("JSON", &["json"])
-            .pick_file()
-        {
-            if let Ok(content) = std::fs::read_to_string(path) {
-                if let Ok(collection) = serde_json::from_str::<Collection>(&content) {
-                    self.current_workspace_mut().collections.push(collection);
-                    self.auto_save_workspace();
-                }
+    fn call(&mut self, request: R) -> Self::Future {
+        let started = Instant::now();
+        let connecting = self.inner.call(request);
+        let connections = self.connections.clone();
+        Box::pin(async move {
+            let connection = connecting.await?;
+            let finished = Instant::now();
+            let mut extensions = http::Extensions::new();
+            hyper_util::client::legacy::connect::Connection::connected(&connection)
+                .get_extras(&mut extensions);
+            if let Some(info) = extensions.get::<hyper_util::client::legacy::connect::HttpInfo>()
+                && let Ok(mut connections) = connections.lock()
+            {
+                connections
+                    .opened
+                    .retain(|_, opened| opened.at.elapsed() < POOL_IDLE_TIMEOUT);
+                connections.opened.insert(
+                    (info.local_addr(), info.remote_addr()),
+                    OpenedConnection {
+                        at: finished,
+                        connect: finished - started,
+                    },
+                );
             }
-        }
+            Ok(connection)
+        })
     }
+}
 
-    fn draw_collections_panel(&mut self, ui: &mut Ui) {
-        let current_workspace_idx = self.current_workspace;
-        let mut selected_collection = None;
-        let mut selected_folder_path = None;
-        let mut selected_request = None;
-        let mut new_current_request = None;
+impl HttpClients {
+    fn note_connection(&self, response: &reqwest::Response) -> Option<ConnectionInfo> {
+        let info = response
+            .extensions()
+            .get::<hyper_util::client::legacy::connect::HttpInfo>()?;
+        let key = (info.local_addr(), info.remote_addr());
+        let mut connections = self.connections.lock().ok()?;
+        let dns = response
+            .url()
+            .host_str()
+            .and_then(|host| connections.dns_times.remove(host));
+        let Some(opened) = connections.opened.remove(&key) else {
+            return Some(ConnectionInfo {
+                reused: true,
+                dns: None,
+                connect: None,
+            });
+        };
+        Some(ConnectionInfo {
+            reused: false,
+            dns,
+            connect: Some(opened.connect.saturating_sub(dns.unwrap_or_default())),
+        })
+    }
 
-        ScrollArea::vertical().show(ui, |ui| {
-            let workspace = &self.workspaces[current_workspace_idx];
-            let selected_folder_path_copy = workspace.selected_folder_path.clone();
-            let selected_request_copy = workspace.selected_request;
-            let selected_collection_copy = workspace.selected_collection;
+    fn for_url(&self, url: &str) -> &reqwest::Client {
+        let Some(host) = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+        else {
+            return &self.default;
+        };
+        self.identities
+            .iter()
+            .find(|(pattern, _)| CertificatePin::host_matches(pattern, &host))
+            .map_or(&self.default, |(_, client)| client)
+    }
+}
 
-            for (collection_idx, collection) in workspace.collections.iter().enumerate() {
-                let is_selected = selected_collection_copy == Some(collection_idx);
-                let response = ui.selectable_label(is_selected, &collection.name);
-                if response.clicked() {
-                    selected_collection = Some(collection_idx);
-                    selected_folder_path = Some(vec![]);
-                    selected_request = None;
-                }
-                if is_selected {
-                    ui.indent("collection_content", |ui| {
-                        let (sel_folder_path, sel_request, new_request) = self
-                            .draw_folder_contents(
-                                ui,
-                                &collection.root_folder,
-                                vec![],
-                                &selected_folder_path_copy,
-                                selected_request_copy,
-                            );
-                        if let Some(path) = sel_folder_path {
-                            selected_folder_path = Some(path);
-                        }
-                        if let Some(req_idx) = sel_request {
-                            selected_request = Some(req_idx);
-                        }
-                        if let Some(request) = new_request {
-                            new_current_request = Some(request);
-                        }
-                    });
-                }
-            }
-        });
+#[derive(Debug, Clone)]
+struct ResolvedRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    // Credentials the API key auth and the signer set under any name; send_resolved drops
+    // them with the other sensitive headers when a redirect leaves the origin
+    credential_headers: Vec<String>,
+    body: String,
+    body_type: BodyType,
+    form_data: Vec<FormDataEntry>,
+    url_encoded_data: Vec<(String, String)>,
+    // Streamed as the body instead of `body` when set
+    body_file: String,
+    // Adds the Authorization header when the request is built
+    oauth: Option<ClientCredentials>,
+    // Sent after an NTLM handshake in send_resolved
+    ntlm: Option<NtlmCredentials>,
+    // Redirects send_resolved follows; None returns the 3xx itself
+    max_redirects: Option<usize>,
+    http_version: HttpVersion,
+    // File parts streamed so far; reset each time the body is built
+    upload: Arc<UploadProgress>,
+    chunked: bool,
+    expect_continue: ExpectContinue,
+    cookie_jar: Option<CookieJar>,
+}
 
-        if let Some(collection_idx) = selected_collection {
-            self.workspaces[current_workspace_idx].selected_collection = Some(collection_idx);
-            if let Some(folder_path) = selected_folder_path {
-                self.workspaces[current_workspace_idx].selected_folder_path = folder_path;
+impl ResolvedRequest {
+    // The bytes build_request sends as the body: the encoded form, the file's contents or
+    // the raw text. A multipart body only gets its boundary once built, so it counts as empty.
+    fn sent_body(&self) -> Vec<u8> {
+        match self.body_type {
+            BodyType::FormData if !self.form_data.is_empty() => vec![],
+            BodyType::UrlEncoded if !self.url_encoded_data.is_empty() => {
+                // Encoded the way RequestBuilder::form does it
+                reqwest::Url::parse_with_params(
+                    "http://localhost/",
+                    self.url_encoded_data
+                        .iter()
+                        .filter(|(key, _)| !key.trim().is_empty()),
+                )
+                .ok()
+                .and_then(|url| url.query().map(|query| query.as_bytes().to_vec()))
+                .unwrap_or_default()
             }
-            if selected_request.is_none() {
-                self.workspaces[current_workspace_idx].selected_request = None;
+            BodyType::Binary | BodyType::Raw | BodyType::Json
+                if !self.body_file.trim().is_empty() =>
+            {
+                std::fs::read(&self.body_file).unwrap_or_default()
             }
-        }
-        if let Some(request_idx) = selected_request {
-            self.workspaces[current_workspace_idx].selected_request = Some(request_idx);
-        }
-        if let Some(request) = new_current_request {
-            self.current_request = request;
+            _ if !self.body.trim().is_empty() => self.body.as_bytes().to_vec(),
+            _ => vec![],
         }
     }
+}
 
-    fn draw_folder_contents(
-        &self,
-        ui: &mut Ui,
-        folder: &Folder,
-        current_path: Vec<usize>,
-        selected_folder_path: &[usize],
-        selected_request: Option<usize>,
-    ) -> (Option<Vec<usize>>, Option<usize>, Option<HttpRequest>) {
-        let mut result_folder_path = None;
-        let mut result_request = None;
-        let mut result_request_data = None;
-
-        // Draw subfolders first
-        for (folder_idx, subfolder) in folder.folders.iter().enumerate() {
-            let mut subfolder_path = current_path.clone();
-            subfolder_path.push(folder_idx);
-
-            let is_selected_folder = selected_folder_path == subfolder_path;
+#[derive(Debug, Default)]
+struct UploadProgress {
+    sent: AtomicU64,
+    total: AtomicU64,
+}
 
-            ui.horizontal(|ui| {
-                ui.label("📁");
-                if ui
-                    .selectable_label(is_selected_folder, &subfolder.name)
-                    .clicked()
-                {
-                    result_folder_path = Some(subfolder_path.clone());
-                }
-            });
+// Counts bytes as a file part is read into the request body
+struct ProgressReader<R> {
+    inner: R,
+    progress: Arc<UploadProgress>,
+}
 
-            if is_selected_folder {
-                ui.indent(format!("folder_{}", folder_idx), |ui| {
-                    let (sub_folder_path, sub_request, sub_request_data) = self
-                        .draw_folder_contents(
-                            ui,
-                            subfolder,
-                            subfolder_path,
-                            selected_folder_path,
-                            selected_request,
-                        );
-                    if sub_folder_path.is_some() {
-                        result_folder_path = sub_folder_path;
-                    }
-                    if sub_request.is_some() {
-                        result_request = sub_request;
-                        result_request_data = sub_request_data;
-                    }
-                });
-            }
+impl<R: tokio::io::AsyncRead + Unpin> tokio::io::AsyncRead for ProgressReader<R> {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let poll = std::pin::Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let std::task::Poll::Ready(Ok(())) = poll {
+            self.progress
+                .sent
+                .fetch_add((buf.filled().len() - before) as u64, Ordering::Relaxed);
         }
+        poll
+    }
+}
 
-        // Draw requests in current folder
-        let is_current_folder_selected = selected_folder_path == current_path;
-        if is_current_folder_selected {
-            for (request_idx, request) in folder.requests.iter().enumerate() {
-                let selected_req = selected_request == Some(request_idx);
-                let method_color = match request.method.as_str() {
-                    "GET" => Color32::from_rgb(0, 128, 0),
-                    "POST" => Color32::from_rgb(255, 165, 0),
-                    "PUT" => Color32::from_rgb(0, 0, 255),
-                    "DELETE" => Color32::from_rgb(255, 0, 0),
-                    _ => Color32::GRAY,
-                };
-                ui.horizontal(|ui| {
-                    ui.label(RichText::new(&request.method).color(method_color));
-                    if ui.selectable_label(selected_req, &request.name).clicked() {
-                        result_request = Some(request_idx);
-                        result_request_data = Some(request.clone());
-                    }
-                });
-            }
-        }
+// Per-request overrides; `None` inherits the workspace setting
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct RequestSettings {
+    skip_tls_verification: Option<bool>,
+    min_tls_version: Option<TlsVersion>,
+    max_tls_version: Option<TlsVersion>,
+    retry: Option<RetryPolicy>,
+    user_agent: Option<String>,
+    ip_preference: Option<IpPreference>,
+    http_version: Option<HttpVersion>,
+    follow_redirects: Option<bool>,
+    max_redirects: Option<usize>,
+    proxy: Option<ProxySettings>,
+    // Sends on a client of its own, so nothing is reused from earlier requests
+    fresh_connection: bool,
+    accept_encoding: AcceptEncoding,
+    // Streams the body without a Content-Length; HTTP/1.1 only
+    chunked: bool,
+    expect_continue: ExpectContinue,
+    // Neither sends nor keeps cookies from the jar
+    ignore_cookie_jar: bool,
+}
 
-        (result_folder_path, result_request, result_request_data)
-    }
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum ExpectContinue {
+    // Whatever the request's headers say
+    #[default]
+    Headers,
+    Send,
+    Never,
+}
 
-    fn draw_environment_panel(&mut self, ui: &mut Ui) {
-        let current_workspace_idx = self.current_workspace;
-        let mut env_changed = false;
+// Accept-Encoding added unless the request sets the header itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum AcceptEncoding {
+    #[default]
+    Unset,
+    GzipDeflate,
+    Gzip,
+    Deflate,
+    Brotli,
+    Identity,
+}
 
-        // Environment selector and management
-        let workspace = &mut self.workspaces[current_workspace_idx];
-        ui.horizontal(|ui| {
-            let selected_text = if let Some(env_idx) = workspace.selected_environment {
-                if env_idx < workspace.environments.len() {
-                    workspace.environments[env_idx].name.clone()
-                } else {
-                    "No Environment".to_string()
-                }
-            } else {
-                "No Environment".to_string()
-            };
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum HttpVersion {
+    #[default]
+    Auto,
+    Http1,
+    // Fails unless the server picks h2 during the TLS handshake
+    Http2,
+    // h2 from the first byte, which also allows cleartext h2c
+    Http2PriorKnowledge,
+}
 
-            egui::ComboBox::from_label("Active Environment")
-                .selected_text(selected_text)
-                .show_ui(ui, |ui| {
-                    ui.selectable_value(
-                        &mut workspace.selected_environment,
-                        None,
-                        "No Environment",
-                    );
-                    for (idx, env) in workspace.environments.iter().enumerate() {
-                        ui.selectable_value(
-                            &mut workspace.selected_environment,
-                            Some(idx),
-                            &env.name,
-                        );
-                    }
-                });
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+enum IpPreference {
+    #[default]
+    Auto,
+    PreferIpv4,
+    PreferIpv6,
+    Ipv4Only,
+    Ipv6Only,
+}
 
-            if ui.button("New Environment").clicked() {
-                self.new_environment_dialog = true;
+// Resolves through the system resolver, then orders or filters addresses by family,
+// and records how long the lookup took
+struct IpPreferenceResolver {
+    preference: IpPreference,
+    connections: Arc<std::sync::Mutex<ConnectionLog>>,
+}
+
+impl reqwest::dns::Resolve for IpPreferenceResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let preference = self.preference.clone();
+        let connections = self.connections.clone();
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let started = Instant::now();
+            let mut addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Ok(mut connections) = connections.lock() {
+                connections
+                    .dns_times
+                    .insert(host.clone(), started.elapsed());
             }
-        });
-        ui.separator();
-        // Variables
-        if let Some(env_idx) = workspace.selected_environment {
-            if env_idx < workspace.environments.len() {
-                ui.label("Variables:");
-                ScrollArea::vertical().show(ui, |ui| {
-                    let workspace = &mut self.workspaces[current_workspace_idx];
-                    let env = &mut workspace.environments[env_idx];
-                    let mut to_remove = Vec::new();
-
-                    // Table header
-                    ui.horizontal(|ui| {
-                        ui.label("Key");
-                        ui.add_space(150.0);
-                        ui.label("Value");
-                    });
-                    ui.separator();
+            match preference {
+                IpPreference::Auto => {}
+                IpPreference::PreferIpv4 => addrs.sort_by_key(|addr| !addr.is_ipv4()),
+                IpPreference::PreferIpv6 => addrs.sort_by_key(|addr| !addr.is_ipv6()),
+                IpPreference::Ipv4Only => addrs.retain(|addr| addr.is_ipv4()),
+                IpPreference::Ipv6Only => addrs.retain(|addr| addr.is_ipv6()),
+            }
+            if addrs.is_empty() {
+                return Err(format!(
+                    "No addresses found for {} matching {}",
+                    host,
+                    preference.label()
+                )
+                .into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
 
-                    // Pre-calculate duplicate information to avoid borrow checker issues
-                    let mut duplicate_keys = Vec::new();
-                    for (i, (key, _)) in env.variables.iter().enumerate() {
-                        let is_duplicate = !key.trim().is_empty()
-                            && env
-                                .variables
-                                .iter()
-                                .enumerate()
-                                .filter(|(idx, (k, _))| *idx != i && k.trim() == key.trim())
-                                .count()
-                                > 0;
-                        duplicate_keys.push(is_duplicate);
-                    }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct RetryPolicy {
+    max_retries: u32,
+    backoff_ms: u64,
+    retry_on_connection_error: bool,
+    retry_on_429: bool,
+    retry_on_5xx: bool,
+    honor_retry_after: bool,
+    // Longer Retry-After values are cut down to this
+    max_retry_after_secs: u64,
+}
 
-                    for (i, (key, value)) in env.variables.iter_mut().enumerate() {
-                        ui.horizontal(|ui| {
-                            let is_duplicate = duplicate_keys.get(i).copied().unwrap_or(false);
+// A retry the send loop is waiting on, shown as a countdown
+#[derive(Debug, Clone, Copy)]
+struct RetryWait {
+    until: Instant,
+    // 0 when the attempt failed without a response
+    status: u16,
+    attempt: u32,
+    max_retries: u32,
+}
 
-                            let key_color = if is_duplicate && !key.trim().is_empty() {
-                                Color32::from_rgb(255, 100, 100) // Red for duplicates
-                            } else if key.trim().is_empty() {
-                                Color32::from_rgb(150, 150, 150) // Gray for empty
-                            } else {
-                                Color32::WHITE // Normal
-                            };
+impl RetryWait {
+    fn label(&self) -> String {
+        let mut args = FluentArgs::new();
+        let remaining = self.until.saturating_duration_since(Instant::now());
+        args.set("seconds", remaining.as_secs_f32().ceil() as u64);
+        args.set("attempt", self.attempt);
+        args.set("max", self.max_retries);
+        if self.status == 0 {
+            tr_args("retrying-in", &args)
+        } else {
+            args.set("status", self.status);
+            tr_args("retrying-status-in", &args)
+        }
+    }
+}
 
-                            let mut key_edit = TextEdit::singleline(key)
-                                .hint_text("Variable name")
-                                .desired_width(150.0);
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            backoff_ms: 500,
+            retry_on_connection_error: true,
+            retry_on_429: true,
+            retry_on_5xx: true,
+            honor_retry_after: true,
+            max_retry_after_secs: 60,
+        }
+    }
+}
 
-                            if is_duplicate {
-                                key_edit = key_edit.text_color(key_color);
-                            }
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+enum TlsVersion {
+    #[default]
+    Any,
+    Tls1_0,
+    Tls1_1,
+    Tls1_2,
+    Tls1_3,
+}
 
-                            let key_response = ui.add(key_edit);
-                            let value_response = ui.add(
-                                TextEdit::singleline(value)
-                                    .hint_text("Variable value")
-                                    .desired_width(200.0),
-                            );
-
-                            if is_duplicate && !key.trim().is_empty() {
-                                ui.colored_label(Color32::from_rgb(255, 100, 100), "⚠");
-                            }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+enum BodyType {
+    None,
+    Raw,
+    Json,
+    FormData,
+    UrlEncoded,
+    // The file at binary_file, sent as is
+    Binary,
+}
 
-                            if key_response.changed() || value_response.changed() {
-                                env_changed = true;
-                            }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+enum FormDataEntry {
+    Text {
+        key: String,
+        value: String,
+        // Empty leaves the part without a Content-Type (text/plain to the server)
+        #[serde(default)]
+        content_type: String,
+        #[serde(default)]
+        headers: Vec<(String, String)>,
+        #[serde(default = "enabled_by_default")]
+        enabled: bool,
+    },
+    File {
+        key: String,
+        file_path: String,
+        file_name: String,
+        // Empty sends the file as application/octet-stream
+        #[serde(default)]
+        content_type: String,
+        #[serde(default)]
+        headers: Vec<(String, String)>,
+        #[serde(default = "enabled_by_default")]
+        enabled: bool,
+    },
+}
 
-                            if ui.button("🗑").clicked() {
-                                to_remove.push(i);
-                            }
-                        });
-                    }
+// For switches that were always on in files saved before they existed, e.g. rows that
+// were all sent before they could be switched off
+fn enabled_by_default() -> bool {
+    true
+}
 
-                    // Remove variables
-                    if !to_remove.is_empty() {
-                        for &i in to_remove.iter().rev() {
-                            env.variables.remove(i);
-                        }
-                        env_changed = true;
-                    }
+impl FormDataEntry {
+    fn text(key: String, value: String) -> Self {
+        Self::Text {
+            key,
+            value,
+            content_type: String::new(),
+            headers: vec![],
+            enabled: true,
+        }
+    }
 
-                    // Add new variable button
-                    if ui.button("Add Variable").clicked() {
-                        env.variables.push(("".to_string(), "".to_string()));
-                        env_changed = true;
-                    }
-                });
-            }
+    fn file(key: String, file_path: String) -> Self {
+        let file_name = std::path::Path::new(&file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self::File {
+            key,
+            file_path,
+            file_name,
+            content_type: String::new(),
+            headers: vec![],
+            enabled: true,
         }
+    }
 
-        if env_changed {
-            self.auto_save_workspace();
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Text { enabled, .. } | Self::File { enabled, .. } => *enabled,
         }
     }
 
-    fn draw_request_panel(&mut self, ui: &mut Ui) {
-        // Migrate old JSON body type to Raw with JSON sub-type for consistency
-        if self.current_request.body_type == BodyType::Json {
-            self.current_request.body_type = BodyType::Raw;
-            self.raw_body_type = RawBodyType::JSON;
+    fn enabled_mut(&mut self) -> &mut bool {
+        match self {
+            Self::Text { enabled, .. } | Self::File { enabled, .. } => enabled,
         }
+    }
 
-        ui.heading("Request");
-        ui.separator();
-        // Method and URL
-        ui.horizontal(|ui| {
-            let method_response = egui::ComboBox::from_id_source("method")
-                .selected_text(&self.current_request.method)
-                .width(80.0)
-                .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.current_request.method, "GET".to_string(), "GET");
-                    ui.selectable_value(
-                        &mut self.current_request.method,
-                        "POST".to_string(),
-                        "POST",
-                    );
-                    ui.selectable_value(&mut self.current_request.method, "PUT".to_string(), "PUT");
-                    ui.selectable_value(
-                        &mut self.current_request.method,
-                        "DELETE".to_string(),
-                        "DELETE",
-                    );
-                    ui.selectable_value(
-                        &mut self.current_request.method,
-                        "PATCH".to_string(),
-                        "PATCH",
-                    );
-                    ui.selectable_value(
-                        &mut self.current_request.method,
-                        "HEAD".to_string(),
-                        "HEAD",
-                    );
-                    ui.selectable_value(
-                        &mut self.current_request.method,
-                        "OPTIONS".to_string(),
-                        "OPTIONS",
-                    );
-                });
-            if method_response.response.changed() {
-                self.save_current_request();
-            }
-            let url_response = ui.add(
-                TextEdit::singleline(&mut self.current_request.url)
-                    .hint_text("Enter URL (supports {{variable}})...")
-                    .desired_width(ui.available_width() - 80.0),
-            );
-            if url_response.changed() {
-                self.save_current_request();
-            }
-            if ui
-                .button(if self.is_loading { "⏸" } else { "Send" })
-                .clicked()
-                && !self.is_loading
-            {
-                self.send_request();
+    // The per-part options both kinds share
+    fn part_options(&self) -> (&str, &[(String, String)]) {
+        match self {
+            Self::Text {
+                content_type,
+                headers,
+                ..
             }
-        });
+            | Self::File {
+                content_type,
+                headers,
+                ..
+            } => (content_type, headers),
+        }
+    }
 
-        // Environment indicator
-        ui.horizontal(|ui| {
-            ui.label("Environment:");
-            let workspace = self.current_workspace();
-            if let Some(env_idx) = workspace.selected_environment {
-                if env_idx < workspace.environments.len() {
-                    ui.colored_label(
-                        Color32::from_rgb(0, 128, 255),
-                        &workspace.environments[env_idx].name,
-                    );
-                } else {
-                    ui.colored_label(Color32::GRAY, "No Environment");
-                }
-            } else {
-                ui.colored_label(Color32::GRAY, "No Environment");
+    fn part_options_mut(&mut self) -> (&mut String, &mut Vec<(String, String)>) {
+        match self {
+            Self::Text {
+                content_type,
+                headers,
+                ..
             }
-        });
-        ui.separator();
+            | Self::File {
+                content_type,
+                headers,
+                ..
+            } => (content_type, headers),
+        }
+    }
 
-        // Request tabs (Postman style)
-        ui.horizontal(|ui| {
-            if ui
-                .selectable_value(&mut self.request_tab, RequestTab::Params, "Params")
-                .changed()
-            {
-                self.save_cache();
-            }
-            if ui
-                .selectable_value(&mut self.request_tab, RequestTab::Headers, "Headers")
-                .changed()
-            {
-                self.save_cache();
-            }
-            if ui
-                .selectable_value(&mut self.request_tab, RequestTab::Body, "Body")
-                .changed()
+    // Content-Type and extra headers as they go in the part, skipping anything invalid
+    fn apply_part_options(
+        &self,
+        mut part: reqwest::multipart::Part,
+    ) -> reqwest::Result<reqwest::multipart::Part> {
+        let (content_type, headers) = self.part_options();
+        if !content_type.trim().is_empty() {
+            part = part.mime_str(content_type.trim())?;
+        }
+        let mut map = reqwest::header::HeaderMap::new();
+        for (key, value) in headers {
+            if let Ok(name) = reqwest::header::HeaderName::from_bytes(key.trim().as_bytes())
+                && let Ok(value) = reqwest::header::HeaderValue::from_str(value)
             {
-                self.save_cache();
+                map.append(name, value);
             }
-        });
-        ui.separator();
+        }
+        Ok(if map.is_empty() {
+            part
+        } else {
+            part.headers(map)
+        })
+    }
 
-        // Tab content
-        match self.request_tab {
-            RequestTab::Params => {
-                self.draw_query_params_panel(ui);
-            }
-            RequestTab::Headers => {
-                self.draw_headers_panel(ui);
-            }
-            RequestTab::Body => {
-                self.draw_body_panel(ui);
+    // The preview lines after content-disposition
+    fn part_header_lines(&self) -> String {
+        let (content_type, headers) = self.part_options();
+        let mut lines = String::new();
+        if !content_type.trim().is_empty() {
+            lines.push_str(&format!("content-type: {}\n", content_type.trim()));
+        }
+        for (key, value) in headers {
+            if !key.trim().is_empty() {
+                lines.push_str(&format!("{}: {}\n", key.trim().to_lowercase(), value));
             }
         }
+        lines
     }
+}
 
-    fn draw_headers_panel(&mut self, ui: &mut Ui) {
-        ScrollArea::vertical().show(ui, |ui| {
-            let mut to_remove = Vec::new();
-            let mut headers_changed = false;
+// Find bar state of a code editor, kept in egui memory under the editor's id
+#[derive(Debug, Clone, Default)]
+struct CodeFind {
+    open: bool,
+    query: String,
+    current: usize,
+    // Selects and scrolls to the current match on the next frame
+    reveal: bool,
+}
 
-            // Table header
-            ui.horizontal(|ui| {
-                ui.label("Header Name");
-                ui.add_space(150.0);
-                ui.label("Header Value");
-            });
-            ui.separator();
+impl CodeFind {
+    const MAX_MATCHES: usize = 10_000;
 
-            for (i, (key, value)) in self.current_request.headers.iter_mut().enumerate() {
-                ui.horizontal(|ui| {
-                    let key_response = ui.add(
-                        TextEdit::singleline(key)
-                            .hint_text("Header name")
-                            .desired_width(200.0),
-                    );
-                    let value_response = ui.add(
-                        TextEdit::singleline(value)
-                            .hint_text("Header value (supports {{variable}})")
-                            .desired_width(300.0),
-                    );
-                    if key_response.changed() || value_response.changed() {
-                        headers_changed = true;
+    // Byte ranges of every case-insensitive match
+    fn matches(text: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+        if query.is_empty() {
+            return vec![];
+        }
+        // ASCII lowercasing keeps byte offsets, so ranges index the original text
+        let haystack = text.to_ascii_lowercase();
+        haystack
+            .match_indices(&query.to_ascii_lowercase())
+            .take(Self::MAX_MATCHES)
+            .map(|(start, found)| start..start + found.len())
+            .collect()
+    }
+
+    // The bracket next to the cursor (after it first) and its partner, as byte offsets
+    fn bracket_pair(text: &str, cursor: usize) -> Option<(usize, Option<usize>)> {
+        const PAIRS: [(u8, u8); 3] = [(b'(', b')'), (b'[', b']'), (b'{', b'}')];
+        let bytes = text.as_bytes();
+        let at = text
+            .char_indices()
+            .nth(cursor)
+            .map_or(text.len(), |(index, _)| index);
+        let is_bracket = |index: usize| {
+            PAIRS
+                .iter()
+                .any(|(open, close)| bytes[index] == *open || bytes[index] == *close)
+        };
+        let position = if at < bytes.len() && is_bracket(at) {
+            at
+        } else if at > 0 && is_bracket(at - 1) {
+            at - 1
+        } else {
+            return None;
+        };
+        let bracket = bytes[position];
+        let mut depth = 0usize;
+        for (open, close) in PAIRS {
+            if bracket == open {
+                for (index, &byte) in bytes.iter().enumerate().skip(position) {
+                    if byte == open {
+                        depth += 1;
+                    } else if byte == close {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some((position, Some(index)));
+                        }
                     }
-                    if ui.button("🗑").clicked() {
-                        to_remove.push(i);
+                }
+            } else if bracket == close {
+                for index in (0..=position).rev() {
+                    if bytes[index] == close {
+                        depth += 1;
+                    } else if bytes[index] == open {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some((position, Some(index)));
+                        }
                     }
-                });
+                }
             }
+        }
+        Some((position, None))
+    }
+}
 
-            // Remove headers
-            if !to_remove.is_empty() {
-                for &i in to_remove.iter().rev() {
-                    self.current_request.headers.remove(i);
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JsonLayout {
+    AsReceived,
+    Pretty,
+    Minified,
+}
+
+impl JsonLayout {
+    // Re-serializes JSON keeping key order. {{variables}} are swapped for placeholder strings
+    // while it is parsed, so a body that only parses once they are filled in can be formatted.
+    fn apply(self, body: &str) -> Result<String, serde_json::Error> {
+        const MARK: char = '\u{E000}';
+        let mut masked = String::with_capacity(body.len());
+        let mut variables = vec![];
+        let (mut in_string, mut escaped) = (false, false);
+        let mut rest = body;
+        while let Some(c) = rest.chars().next() {
+            if !escaped
+                && rest.starts_with("{{")
+                && let Some(end) = rest.find("}}")
+            {
+                let token = format!("{}{}{}", MARK, variables.len(), MARK);
+                // Outside a string the placeholder needs quotes of its own
+                if in_string {
+                    masked.push_str(&token);
+                } else {
+                    masked.push_str(&format!("\"{}\"", token));
                 }
-                headers_changed = true;
+                variables.push((token, &rest[..end + 2], !in_string));
+                rest = &rest[end + 2..];
+                continue;
             }
-
-            // Add new header button
-            if ui.button("Add Header").clicked() {
-                self.current_request
-                    .headers
-                    .push((String::new(), String::new()));
-                headers_changed = true;
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    in_string = false;
+                }
+            } else if c == '"' {
+                in_string = true;
             }
+            masked.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
 
-            if headers_changed {
-                self.save_current_request();
+        let value = serde_json::from_str::<serde_json::Value>(&masked)?;
+        let mut text = match self {
+            JsonLayout::Minified => serde_json::to_string(&value)?,
+            _ => serde_json::to_string_pretty(&value)?,
+        };
+        for (token, variable, quoted) in variables {
+            if quoted {
+                text = text.replacen(&format!("\"{}\"", token), variable, 1);
+            } else {
+                text = text.replacen(&token, variable, 1);
             }
-        });
+        }
+        Ok(text)
     }
+}
 
-    fn draw_body_panel(&mut self, ui: &mut Ui) {
-        // Body type tabs (Postman style)
-        ui.horizontal(|ui| {
-            if ui
-                .selectable_value(&mut self.current_request.body_type, BodyType::None, "none")
-                .changed()
-            {
-                self.remove_content_type_header();
-                self.save_current_request();
-            }
-            if ui
-                .selectable_value(
-                    &mut self.current_request.body_type,
-                    BodyType::FormData,
-                    "form-data",
-                )
-                .changed()
-            {
-                // Form data uses multipart/form-data, but this is set automatically by reqwest
-                self.remove_content_type_header();
-                self.save_current_request();
-            }
-            if ui
-                .selectable_value(
-                    &mut self.current_request.body_type,
-                    BodyType::UrlEncoded,
-                    "x-www-form-urlencoded",
-                )
-                .changed()
-            {
-                self.set_content_type_header("application/x-www-form-urlencoded");
-                self.save_current_request();
+// Where a JSON body stops parsing; line and column are 1-based, column in characters
+#[derive(Debug, Clone, PartialEq)]
+struct JsonSyntaxError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl JsonSyntaxError {
+    fn check(body: &str) -> Option<Self> {
+        if body.trim().is_empty() {
+            return None;
+        }
+        // Each {{variable}} becomes a 0 padded with spaces, so an unquoted one still parses
+        // and positions line up with the editor
+        let mut masked = String::with_capacity(body.len());
+        let mut rest = body;
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+            masked.push_str(&rest[..start]);
+            let placeholder = &rest[start..start + end + 2];
+            if placeholder.contains('\n') {
+                masked.push_str(&rest[start..start + 2]);
+                rest = &rest[start + 2..];
+                continue;
             }
-            if ui
-                .selectable_value(&mut self.current_request.body_type, BodyType::Raw, "raw")
-                .changed()
-            {
-                // Set Content-Type based on current raw body type
-                let content_type = self.raw_body_type.get_content_type();
-                self.set_content_type_header(content_type);
-                self.save_current_request();
+            masked.push('0');
+            masked.push_str(&" ".repeat(placeholder.chars().count() - 1));
+            rest = &rest[start + end + 2..];
+        }
+        masked.push_str(rest);
+
+        let error = serde_json::from_str::<serde_json::Value>(&masked).err()?;
+        let line_text = masked
+            .lines()
+            .nth(error.line().saturating_sub(1))
+            .unwrap_or_default();
+        // serde_json counts bytes within the line
+        let mut byte_column = error.column().min(line_text.len());
+        while !line_text.is_char_boundary(byte_column) {
+            byte_column -= 1;
+        }
+        let message = error.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(message, _)| message)
+            .to_string();
+        Some(Self {
+            line: error.line().max(1),
+            column: line_text[..byte_column].chars().count().max(1),
+            message,
+        })
+    }
+
+    // Character offset of the error in the body, for placing the marker
+    fn char_index(&self, body: &str) -> usize {
+        let mut index = 0;
+        for (number, line) in body.split('\n').enumerate() {
+            let length = line.chars().count();
+            if number + 1 == self.line {
+                return index + (self.column - 1).min(length);
             }
-        });
+            index += length + 1;
+        }
+        body.chars().count()
+    }
+}
 
-        // Raw sub-tabs (shown when Raw is selected)
-        if self.current_request.body_type == BodyType::Raw {
-            ui.horizontal(|ui| {
-                ui.label("  "); // Indent for sub-tabs
+#[derive(Debug, Clone)]
+struct HttpResponse {
+    status: u16,
+    status_text: String,
+    headers: HashMap<String, String>,
+    body: String,
+    timing: ResponseTiming,
+    body_size: usize,
+    headers_size: usize,
+    tls_verification_disabled: bool,
+    attempts: Vec<AttemptRecord>,
+    remote_addr: Option<String>,
+    // Raw bytes received before the size limit was hit
+    truncated_bytes: Option<Arc<Vec<u8>>>,
+    from_example: bool,
+    // Every Set-Cookie header; the headers map keeps only one
+    set_cookies: Vec<String>,
+    redirects: RedirectChain,
+    // Protocol the server answered with, e.g. "HTTP/2"
+    version: String,
+    connection_reused: Option<bool>,
+    // Content-Encoding the server used
+    content_encoding: Option<String>,
+    // Bytes as received, kept when the body was decompressed
+    encoded_body: Option<Arc<Vec<u8>>>,
+    // Set when the body went to a temporary file; `body` then holds only its start and end
+    spooled: Option<Arc<SpooledBody>>,
+    // The body as formatted or minified from the Body tab; None shows it as received
+    reformatted_body: Option<String>,
+}
 
-                let mut raw_type_changed = false;
+// Milliseconds spent in each phase of the final attempt. DNS and connect are only known when
+// the request opened a new connection; waiting covers the rest up to the response headers.
+#[derive(Debug, Clone, Copy, Default)]
+struct ResponseTiming {
+    total: u128,
+    dns: Option<u128>,
+    // TCP, plus the TLS handshake for HTTPS
+    connect: Option<u128>,
+    waiting: u128,
+    transfer: u128,
+    secure: bool,
+}
 
-                if ui
-                    .selectable_value(&mut self.raw_body_type, RawBodyType::Text, "Text")
-                    .changed()
-                {
-                    raw_type_changed = true;
-                }
-                if ui
-                    .selectable_value(
-                        &mut self.raw_body_type,
-                        RawBodyType::JavaScript,
-                        "JavaScript",
-                    )
-                    .changed()
-                {
-                    raw_type_changed = true;
+impl ResponseTiming {
+    fn new(
+        headers: Duration,
+        total: Duration,
+        connection: Option<&ConnectionInfo>,
+        secure: bool,
+    ) -> Self {
+        let dns = connection.and_then(|connection| connection.dns);
+        let connect = connection.and_then(|connection| connection.connect);
+        let setup = dns.unwrap_or_default() + connect.unwrap_or_default();
+        Self {
+            total: total.as_millis(),
+            dns: dns.map(|dns| dns.as_millis()),
+            connect: connect.map(|connect| connect.as_millis()),
+            waiting: headers.saturating_sub(setup).as_millis(),
+            transfer: total.saturating_sub(headers).as_millis(),
+            secure,
+        }
+    }
+
+    fn phases(&self) -> Vec<(String, u128, Color32)> {
+        let mut phases = Vec::new();
+        if let Some(dns) = self.dns {
+            phases.push((tr("timing-dns"), dns, Color32::from_rgb(0, 150, 136)));
+        }
+        if let Some(connect) = self.connect {
+            let label = if self.secure {
+                tr("timing-connect-tls")
+            } else {
+                tr("timing-connect")
+            };
+            phases.push((label, connect, Color32::from_rgb(255, 152, 0)));
+        }
+        phases.push((
+            tr("timing-waiting"),
+            self.waiting,
+            Color32::from_rgb(76, 175, 80),
+        ));
+        phases.push((
+            tr("timing-transfer"),
+            self.transfer,
+            Color32::from_rgb(33, 150, 243),
+        ));
+        phases
+    }
+}
+
+const SPOOL_PREVIEW_HEAD: usize = 256 * 1024;
+const SPOOL_PREVIEW_TAIL: usize = 64 * 1024;
+
+// A response body too large to keep in memory, removed once no response refers to it
+#[derive(Debug)]
+struct SpooledBody {
+    path: std::path::PathBuf,
+    size: u64,
+}
+
+impl Drop for SpooledBody {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+// Receives the rest of a large body while keeping the last bytes for the preview
+struct ResponseSpool {
+    file: tokio::fs::File,
+    body: SpooledBody,
+    tail: Vec<u8>,
+}
+
+impl ResponseSpool {
+    async fn create(head: &[u8]) -> std::io::Result<Self> {
+        let path = std::env::temp_dir().join(format!("send-response-{}.tmp", Uuid::new_v4()));
+        let file = tokio::fs::File::create(&path).await?;
+        let mut spool = Self {
+            file,
+            body: SpooledBody { path, size: 0 },
+            tail: Vec::new(),
+        };
+        spool.write(head).await?;
+        Ok(spool)
+    }
+
+    async fn write(&mut self, chunk: &[u8]) -> std::io::Result<()> {
+        self.file.write_all(chunk).await?;
+        self.body.size += chunk.len() as u64;
+        self.tail.extend_from_slice(chunk);
+        if self.tail.len() > SPOOL_PREVIEW_TAIL * 2 {
+            self.tail.drain(..self.tail.len() - SPOOL_PREVIEW_TAIL);
+        }
+        Ok(())
+    }
+
+    // The head is what was buffered before spooling started
+    async fn finish(mut self, head: &[u8]) -> std::io::Result<(String, SpooledBody)> {
+        self.file.flush().await?;
+        let head = &head[..head.len().min(SPOOL_PREVIEW_HEAD)];
+        let tail = &self.tail[self.tail.len().saturating_sub(SPOOL_PREVIEW_TAIL)..];
+        let omitted = self
+            .body
+            .size
+            .saturating_sub((head.len() + tail.len()) as u64);
+        let preview = format!(
+            "{}\n\n… {} not shown …\n\n{}",
+            String::from_utf8_lossy(head),
+            SendApp::format_size(omitted as usize),
+            String::from_utf8_lossy(tail)
+        );
+        Ok((preview, self.body))
+    }
+}
+
+// Responses that redirected on the way to the final one, kept in the response's extensions
+#[derive(Debug, Clone, Default)]
+struct RedirectChain {
+    hops: Vec<RedirectHop>,
+    // The redirect limit was reached, so the response is itself a redirect
+    limit_reached: bool,
+}
+
+#[derive(Debug, Clone)]
+struct RedirectHop {
+    method: String,
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+}
+
+struct DownloadState {
+    path: std::path::PathBuf,
+    receiver: mpsc::Receiver<DownloadEvent>,
+    downloaded: u64,
+    total: Option<u64>,
+    result: Option<Result<u64, String>>,
+    request: ResolvedRequest,
+    clients: HttpClients,
+    // The server advertised Accept-Ranges, so the partial file can be continued
+    resumable: bool,
+    // Checked between chunks; the partial file is kept for resuming
+    pause: Arc<AtomicBool>,
+    paused: bool,
+}
+
+enum DownloadEvent {
+    Progress {
+        downloaded: u64,
+        total: Option<u64>,
+    },
+    Paused,
+    Finished(Result<u64, String>),
+    // Send and Download asks where to save once the headers are in; None reads the body as usual
+    ChooseFile {
+        suggested: String,
+        reply: tokio::sync::oneshot::Sender<Option<std::path::PathBuf>>,
+    },
+}
+
+// Written next to "<file>.part" so an interrupted download can be continued later,
+// even after a restart, as long as the same file is picked again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PartialDownload {
+    url: String,
+    // ETag or Last-Modified, sent as If-Range so a changed file starts over
+    validator: Option<String>,
+}
+
+impl PartialDownload {
+    fn paths(path: &std::path::Path) -> (std::path::PathBuf, std::path::PathBuf) {
+        let mut part = path.as_os_str().to_owned();
+        part.push(".part");
+        let mut meta = part.clone();
+        meta.push(".json");
+        (part.into(), meta.into())
+    }
+
+    fn load(path: &std::path::Path) -> Option<Self> {
+        let (_, meta) = Self::paths(path);
+        serde_json::from_str(&std::fs::read_to_string(meta).ok()?).ok()
+    }
+}
+
+struct CaptureState {
+    port: u16,
+    receiver: mpsc::Receiver<CapturedExchange>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+// A plain HTTP request/response pair seen by the capture proxy
+#[derive(Debug, Clone)]
+struct CapturedExchange {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: String,
+    response: SavedExample,
+}
+
+struct LoadTestState {
+    target: String,
+    started: Instant,
+    finished_at: Option<Instant>,
+    receiver: mpsc::Receiver<LoadTestEvent>,
+    cancel: Arc<AtomicBool>,
+    // Latencies in ms of every completed request, including failures
+    latencies: Vec<u128>,
+    errors: usize,
+    // Completed requests per elapsed second, for the throughput chart
+    per_second: Vec<usize>,
+}
+
+enum LoadTestEvent {
+    Completed {
+        elapsed_ms: u128,
+        latency: u128,
+        failed: bool,
+    },
+    Finished,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum LoadTestLimit {
+    Duration,
+    Count,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+struct Toast {
+    kind: ToastKind,
+    message: String,
+    shown_at: Instant,
+}
+
+// A row of the collections tree: collection index, folder path, request index
+#[derive(Debug, Clone, PartialEq)]
+enum TreeNode {
+    Collection(usize),
+    Folder(usize, Vec<usize>),
+    Request(usize, Vec<usize>, usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum RunSource {
+    Runner,
+    Monitor,
+}
+
+// A request executed by the collection runner or a monitor, kept for the metrics dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RunRecord {
+    // Seconds since the Unix epoch
+    timestamp: i64,
+    source: RunSource,
+    collection: String,
+    endpoint: String,
+    // 0 when no response arrived
+    status: u16,
+    time: u128,
+    passed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MetricsRange {
+    Hour,
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl MetricsRange {
+    const ALL: [MetricsRange; 5] = [
+        MetricsRange::Hour,
+        MetricsRange::Day,
+        MetricsRange::Week,
+        MetricsRange::Month,
+        MetricsRange::All,
+    ];
+
+    fn seconds(self) -> Option<i64> {
+        match self {
+            MetricsRange::Hour => Some(3600),
+            MetricsRange::Day => Some(24 * 3600),
+            MetricsRange::Week => Some(7 * 24 * 3600),
+            MetricsRange::Month => Some(30 * 24 * 3600),
+            MetricsRange::All => None,
+        }
+    }
+
+    fn label(self) -> String {
+        tr(match self {
+            MetricsRange::Hour => "metrics-last-hour",
+            MetricsRange::Day => "metrics-last-day",
+            MetricsRange::Week => "metrics-last-week",
+            MetricsRange::Month => "metrics-last-month",
+            MetricsRange::All => "metrics-all-time",
+        })
+    }
+}
+
+// One step from a JSON document's root towards a value
+#[derive(Debug, Clone, PartialEq)]
+enum JsonStep {
+    Key(String),
+    Item,
+}
+
+// Where the cursor sits in a JSON body being edited
+#[derive(Debug, Clone, PartialEq)]
+enum JsonCursor {
+    // Typing a property name of the object at `path`; `start` is where the name begins
+    Key {
+        path: Vec<JsonStep>,
+        prefix: String,
+        start: usize,
+        present: Vec<String>,
+    },
+    // Editing the value at `path`
+    Value(Vec<JsonStep>),
+    Other,
+}
+
+// Destructive operations that ask first, unless the user chose "don't ask again"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum ConfirmKind {
+    DeleteCollection,
+    DeleteEnvironment,
+    ClearVariables,
+    DeleteWorkspace,
+    OverwriteFile,
+    // A send://import link; always asks, since any web page can open one
+    ImportFromUrl,
+}
+
+enum PendingAction {
+    DeleteTreeNode(TreeNode),
+    DeleteEnvironment(usize),
+    ClearVariables(usize),
+    DeleteWorkspace(usize),
+    ImportFromUrl(String),
+    WriteFile {
+        path: std::path::PathBuf,
+        contents: String,
+        what: &'static str,
+        success: String,
+    },
+}
+
+struct Confirmation {
+    kind: ConfirmKind,
+    message: String,
+    action: PendingAction,
+    dont_ask_again: bool,
+}
+
+// State restored by undo/redo: the edited request plus the workspace's collections
+#[derive(Clone)]
+struct UndoSnapshot {
+    workspace: usize,
+    request: HttpRequest,
+    // Shared by consecutive snapshots until the collections are edited
+    collections: Arc<Vec<Collection>>,
+    collections_generation: u64,
+    selected_collection: Option<usize>,
+    selected_folder_path: Vec<usize>,
+    selected_request: Option<usize>,
+}
+
+enum DeepLink {
+    Request(String),
+    Import(String),
+}
+
+struct RunnerState {
+    target: String,
+    collection: String,
+    results: Vec<RunnerResult>,
+    receiver: mpsc::Receiver<RunnerEvent>,
+    cancel: Arc<AtomicBool>,
+    // Per-request cancel flags, checked before a queued request is sent
+    skip: Arc<Vec<AtomicBool>>,
+    finished: bool,
+    max_concurrency: usize,
+    // Shared by every request of the run, so a login early on carries over
+    cookie_jar: CookieJar,
+}
+
+struct RunnerResult {
+    name: String,
+    method: String,
+    url: String,
+    // None while the request is still queued or in flight
+    outcome: Option<Result<u16, String>>,
+    time: u128,
+    // Header assertions that didn't hold
+    failures: Vec<String>,
+    // Set once the request is in flight
+    task: Option<tokio::task::AbortHandle>,
+    retry_wait: Option<RetryWait>,
+}
+
+impl RunnerResult {
+    fn passed(&self) -> bool {
+        matches!(self.outcome, Some(Ok(status)) if (200..300).contains(&status))
+            && self.failures.is_empty()
+    }
+}
+
+enum RunnerEvent {
+    Started {
+        index: usize,
+        task: tokio::task::AbortHandle,
+    },
+    Waiting {
+        index: usize,
+        wait: RetryWait,
+    },
+    Completed {
+        index: usize,
+        result: Result<u16, String>,
+        time: u128,
+        failures: Vec<String>,
+    },
+    Finished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RequestErrorKind {
+    Dns,
+    ConnectionRefused,
+    ConnectTimeout,
+    Connect,
+    Timeout,
+    Tls,
+    CertificatePin,
+    TooManyRedirects,
+    HttpVersion,
+    BodyDecode,
+    InvalidRequest,
+    Client,
+    Offline,
+    Other,
+}
+
+// Why a request produced no response, with the error's source chain outermost first
+#[derive(Debug, Clone)]
+struct RequestError {
+    kind: RequestErrorKind,
+    causes: Vec<String>,
+    attempts: u32,
+    // The attempts before the last one, when it was retried
+    retried: Vec<AttemptRecord>,
+}
+
+// A failed attempt that was retried before the final response
+#[derive(Debug, Clone)]
+struct AttemptRecord {
+    status: u16,
+    time: u128,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Folder {
+    id: String,
+    name: String,
+    requests: Vec<HttpRequest>,
+    folders: Vec<Folder>,
+    #[serde(default)]
+    auth: RequestAuth,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Collection {
+    id: String,
+    name: String,
+    root_folder: Folder,
+    // Token Manager entry sent by requests that don't pick their own
+    #[serde(default)]
+    token: Option<String>,
+    // Inherited by folders and requests; Inherit here means no auth
+    #[serde(default)]
+    auth: RequestAuth,
+    #[serde(default)]
+    signer: Option<RequestSigner>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Environment {
+    name: String,
+    variables: Vec<(String, String)>,
+    #[serde(default)]
+    secret_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AppStorage {
+    collections: Vec<Collection>,
+    environments: Vec<Environment>,
+    #[serde(default)]
+    settings: WorkspaceSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AppCache {
+    current_workspace: usize,
+    workspaces: Vec<Workspace>,
+    selected_sidebar_item: Option<SidebarItem>,
+    request_tab: RequestTab,
+    response_tab: ResponseTab,
+    raw_body_type: RawBodyType,
+    #[serde(default)]
+    history: Vec<HistoryEntry>,
+    #[serde(default)]
+    monitor_results: HashMap<String, Vec<MonitorResult>>,
+    #[serde(default)]
+    layout: LayoutState,
+    #[serde(default)]
+    recent_workspaces: Vec<std::path::PathBuf>,
+    #[serde(default)]
+    run_records: Vec<RunRecord>,
+    // Unsaved edits by request id, so quitting doesn't lose them
+    #[serde(default)]
+    drafts: HashMap<String, HttpRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct LayoutState {
+    mode: PanelLayout,
+    // Share of the central area given to the request pane
+    stacked_split: f32,
+    side_by_side_split: f32,
+    single_pane: Pane,
+    show_mini_sidebar: bool,
+    // A pinned sidebar switches between items instead of closing
+    sidebar_pinned: bool,
+    sidebar_widths: HashMap<SidebarItem, f32>,
+}
+
+impl Default for LayoutState {
+    fn default() -> Self {
+        Self {
+            mode: PanelLayout::Stacked,
+            stacked_split: 0.55,
+            side_by_side_split: 0.5,
+            single_pane: Pane::Request,
+            show_mini_sidebar: true,
+            sidebar_pinned: false,
+            sidebar_widths: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum PanelLayout {
+    Stacked,
+    SideBySide,
+    Single,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Pane {
+    Request,
+    Response,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryEntry {
+    request: HttpRequest,
+    status: u16,
+    time: u128,
+    #[serde(default)]
+    attempt: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct AppSettings {
+    theme: ThemeSetting,
+    language: Language,
+    default_method: String,
+    default_url: String,
+    request_timeout_secs: u64,
+    follow_redirects: bool,
+    max_redirects: usize,
+    body_editor_rows: usize,
+    body_soft_wrap: bool,
+    history_limit: usize,
+    proxy: ProxySettings,
+    user_agent: UserAgentSetting,
+    custom_user_agent: String,
+    max_response_size_mb: u64,
+    // Past the size limit, keep receiving into a temporary file instead of truncating
+    spool_large_responses: bool,
+    runner_requests_per_second: f64,
+    runner_max_concurrency: usize,
+    runner_delay_ms: u64,
+    runner_iterations: usize,
+    offline_mode: bool,
+    // Only bindings changed from their defaults
+    shortcuts: HashMap<ShortcutAction, KeyBinding>,
+    // None keeps egui's own selection color
+    accent_color: Option<[u8; 3]>,
+    method_colors: MethodColors,
+    status_colors: StatusColors,
+    // Prefix status codes with an icon so the class doesn't rely on color alone
+    status_badges: bool,
+    skip_confirmations: HashSet<ConfirmKind>,
+    // Closing the window minimizes it while monitors are scheduled
+    run_in_background: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct MethodColors {
+    get: [u8; 3],
+    post: [u8; 3],
+    put: [u8; 3],
+    patch: [u8; 3],
+    delete: [u8; 3],
+    head: [u8; 3],
+    options: [u8; 3],
+    other: [u8; 3],
+}
+
+// Mid-tone defaults that stay readable on both light and dark backgrounds
+impl Default for MethodColors {
+    fn default() -> Self {
+        Self {
+            get: [46, 160, 67],
+            post: [224, 130, 20],
+            put: [52, 120, 220],
+            patch: [150, 90, 200],
+            delete: [215, 58, 73],
+            head: [20, 150, 150],
+            options: [190, 80, 150],
+            other: [128, 128, 128],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct StatusColors {
+    success: [u8; 3],
+    redirect: [u8; 3],
+    client_error: [u8; 3],
+    server_error: [u8; 3],
+    // Connection failures and other responses without a status code
+    error: [u8; 3],
+}
+
+impl Default for StatusColors {
+    fn default() -> Self {
+        Self {
+            success: [0, 128, 0],
+            redirect: [255, 165, 0],
+            client_error: [255, 0, 0],
+            server_error: [255, 0, 0],
+            error: [255, 0, 0],
+        }
+    }
+}
+
+impl StatusColors {
+    // Okabe-Ito colors, distinguishable with the common forms of color blindness
+    fn colorblind() -> Self {
+        Self {
+            success: [0, 114, 178],
+            redirect: [86, 180, 233],
+            client_error: [230, 159, 0],
+            server_error: [213, 94, 0],
+            error: [204, 121, 167],
+        }
+    }
+
+    fn color(&self, status: u16) -> Color32 {
+        let [r, g, b] = match status {
+            200..=299 => self.success,
+            300..=399 => self.redirect,
+            400..=499 => self.client_error,
+            500..=599 => self.server_error,
+            _ => self.error,
+        };
+        Color32::from_rgb(r, g, b)
+    }
+
+    fn badge(status: u16) -> &'static str {
+        match status {
+            100..=199 => "ℹ",
+            200..=299 => "✔",
+            300..=399 => "↪",
+            400..=499 => "⚠",
+            _ => "✖",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum ShortcutAction {
+    Send,
+    Save,
+    NewRequest,
+    SwitchEnvironment,
+    CloseRequest,
+    ShowShortcuts,
+    CommandPalette,
+    Undo,
+    Redo,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PaletteCommand {
+    Shortcut(ShortcutAction),
+    NewCollection,
+    NewFolder,
+    NewEnvironment,
+    NewWorkspace,
+    SelectEnvironment(Option<usize>),
+    SelectWorkspace(usize),
+    OpenRequest(String),
+    ShowSidebar(SidebarItem),
+    RunCollection,
+    LoadTest,
+    Monitors,
+    CaptureTraffic,
+    SaveWorkspace,
+    LoadWorkspace,
+    ExportCollection,
+    ImportCollection,
+    ExportForSharing,
+    ToggleOfflineMode,
+    WorkspaceSettings,
+    Settings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct KeyBinding {
+    command: bool,
+    shift: bool,
+    alt: bool,
+    key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum UserAgentSetting {
+    App,
+    Browser,
+    Custom,
+    None,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            theme: ThemeSetting::System,
+            language: Language::System,
+            default_method: "GET".to_string(),
+            default_url: "https://httpbin.org/get".to_string(),
+            request_timeout_secs: 30,
+            follow_redirects: true,
+            max_redirects: 10,
+            body_editor_rows: 12,
+            body_soft_wrap: true,
+            history_limit: 100,
+            proxy: ProxySettings::default(),
+            user_agent: UserAgentSetting::App,
+            custom_user_agent: String::new(),
+            max_response_size_mb: 50,
+            spool_large_responses: true,
+            runner_requests_per_second: 0.0,
+            runner_max_concurrency: 1,
+            runner_delay_ms: 0,
+            runner_iterations: 1,
+            offline_mode: false,
+            shortcuts: HashMap::new(),
+            accent_color: None,
+            method_colors: MethodColors::default(),
+            status_colors: StatusColors::default(),
+            status_badges: false,
+            skip_confirmations: HashSet::new(),
+            run_in_background: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+enum Language {
+    // Follows LANG / LC_ALL, falling back to English
+    #[default]
+    System,
+    English,
+    German,
+}
+
+// Looks up translated UI strings; the catalog of the chosen language falls back to English
+struct Localizer {
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+// Shared across threads, so messages built in background tasks follow the chosen language too
+static LOCALIZER: std::sync::LazyLock<std::sync::RwLock<Localizer>> =
+    std::sync::LazyLock::new(|| std::sync::RwLock::new(Localizer::new(Language::System)));
+
+fn set_language(language: Language) {
+    if let Ok(mut localizer) = LOCALIZER.write() {
+        *localizer = Localizer::new(language);
+    }
+}
+
+fn tr(id: &str) -> String {
+    LOCALIZER
+        .read()
+        .map_or_else(|_| id.to_string(), |localizer| localizer.format(id, None))
+}
+
+fn tr_args(id: &str, args: &FluentArgs) -> String {
+    LOCALIZER.read().map_or_else(
+        |_| id.to_string(),
+        |localizer| localizer.format(id, Some(args)),
+    )
+}
+
+// A catalog message whose only argument is the error
+fn tr_error(id: &str, error: impl std::fmt::Display) -> String {
+    let mut args = FluentArgs::new();
+    args.set("error", error.to_string());
+    tr_args(id, &args)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum ThemeSetting {
+    System,
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Workspace {
+    name: String,
+    file_path: Option<std::path::PathBuf>,
+    collections: Vec<Collection>,
+    environments: Vec<Environment>,
+    selected_collection: Option<usize>,
+    selected_folder_path: Vec<usize>, // Path to selected folder within collection
+    selected_request: Option<usize>,
+    selected_environment: Option<usize>,
+    #[serde(default)]
+    settings: WorkspaceSettings,
+    #[serde(default)]
+    active_session: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct WorkspaceSettings {
+    override_proxy: bool,
+    proxy: ProxySettings,
+    skip_tls_verification: bool,
+    ca_certificates: Vec<CaCertificate>,
+    min_tls_version: TlsVersion,
+    max_tls_version: TlsVersion,
+    retry: RetryPolicy,
+    default_headers: Vec<(String, String)>,
+    ip_preference: IpPreference,
+    http_version: HttpVersion,
+    local_address: String,
+    interface: String,
+    monitors: Vec<Monitor>,
+    certificate_pins: Vec<CertificatePin>,
+    client_certificates: Vec<ClientCertificate>,
+    tokens: Vec<NamedToken>,
+    sessions: Vec<Session>,
+    // Used while no session is active
+    cookies: Vec<SessionCookie>,
+    // Off keeps cookies for this run of the app only. Files from before the option always
+    // kept their cookies, so it is on when missing.
+    #[serde(default = "enabled_by_default")]
+    persist_cookies: bool,
+    // Secure and HttpOnly cookies usually carry a login, so keeping them is a second opt-in
+    persist_login_cookies: bool,
+    header_presets: Vec<HeaderPreset>,
+}
+
+// Named set of headers the Headers tab can add to a request
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct HeaderPreset {
+    name: String,
+    headers: Vec<(String, String)>,
+}
+
+impl HeaderPreset {
+    // Adds the preset's headers, replacing the values of ones the request already has and
+    // switching them back on
+    fn apply(&self, headers: &mut Vec<KeyValue>) {
+        for (key, value) in &self.headers {
+            if key.trim().is_empty() {
+                continue;
+            }
+            match headers
+                .iter_mut()
+                .find(|row| row.key.trim().eq_ignore_ascii_case(key.trim()))
+            {
+                Some(row) => {
+                    row.value = value.clone();
+                    row.enabled = true;
+                }
+                None => headers.push(KeyValue::new(key.clone(), value.clone())),
+            }
+        }
+    }
+}
+
+impl WorkspaceSettings {
+    // What gets written to the workspace file and app state
+    fn for_storage(&self) -> Self {
+        let mut settings = self.clone();
+        if !settings.persist_cookies {
+            settings.cookies.clear();
+            for session in &mut settings.sessions {
+                session.cookies.clear();
+            }
+        }
+        if !settings.persist_login_cookies {
+            let keep = |cookie: &SessionCookie| !cookie.secure && !cookie.http_only;
+            settings.cookies.retain(keep);
+            for session in &mut settings.sessions {
+                session.cookies.retain(keep);
+            }
+        }
+        settings
+    }
+}
+
+// An identity to send requests as, with its own cookie jar and token values
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Session {
+    name: String,
+    cookies: Vec<SessionCookie>,
+    // Token Manager name -> value sent while this session is active
+    tokens: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SessionCookie {
+    domain: String,
+    path: String,
+    name: String,
+    #[serde(
+        serialize_with = "SessionCookie::mask_value",
+        deserialize_with = "SessionCookie::unmask_value"
+    )]
+    value: String,
+    // Unix time; None lasts until the jar is cleared
+    expires: Option<i64>,
+    // Set without a Domain attribute, so only sent back to that exact host
+    #[serde(default)]
+    host_only: bool,
+    // Only sent over https
+    #[serde(default)]
+    secure: bool,
+    #[serde(default)]
+    http_only: bool,
+}
+
+impl SessionCookie {
+    // Stored values are base64 so they don't show up in a diff or a search of the file.
+    // This is not encryption.
+    const MASK_PREFIX: &str = "masked:";
+
+    fn mask_value<S: serde::Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_empty() {
+            return serializer.serialize_str(value);
+        }
+        serializer.serialize_str(&format!(
+            "{}{}",
+            Self::MASK_PREFIX,
+            base64::engine::general_purpose::STANDARD.encode(value)
+        ))
+    }
+
+    // Values saved before masking was added are read as they are
+    fn unmask_value<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(value
+            .strip_prefix(Self::MASK_PREFIX)
+            .and_then(|masked| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(masked)
+                    .ok()
+            })
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .unwrap_or(value))
+    }
+
+    // Reads a Set-Cookie header received from url, following RFC 6265 section 5.3.
+    // None when the cookie is malformed or the server may not set it.
+    fn parse(header: &str, url: &reqwest::Url) -> Option<Self> {
+        let host = url.host_str()?.to_lowercase();
+        let path = url.path();
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        if name.trim().is_empty() {
+            return None;
+        }
+        let mut cookie = Self {
+            domain: host.clone(),
+            path: match path.rfind('/') {
+                Some(0) | None => "/".to_string(),
+                Some(end) => path[..end].to_string(),
+            },
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+            expires: None,
+            host_only: true,
+            secure: false,
+            http_only: false,
+        };
+        let mut max_age = None;
+        for attribute in parts {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_lowercase();
+                    // A host may only set cookies for itself or a parent domain, and never
+                    // for a bare top-level name like "com"
+                    let is_ip = host
+                        .trim_matches(['[', ']'])
+                        .parse::<std::net::IpAddr>()
+                        .is_ok();
+                    if domain == host {
+                        cookie.host_only = false;
+                    } else if !is_ip
+                        && domain.contains('.')
+                        && host.ends_with(&format!(".{}", domain))
+                    {
+                        cookie.domain = domain;
+                        cookie.host_only = false;
+                    } else {
+                        return None;
+                    }
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                "expires" => cookie.expires = Self::parse_date(value),
+                _ => {}
+            }
+        }
+        // Max-Age wins over Expires
+        if let Some(seconds) = max_age {
+            cookie.expires = Some(chrono::Local::now().timestamp() + seconds);
+        }
+        // Only a secure origin may set a Secure cookie
+        if cookie.secure && url.scheme() != "https" {
+            return None;
+        }
+        Some(cookie)
+    }
+
+    // The lenient cookie-date algorithm of RFC 6265 section 5.1.1, which also reads the
+    // Netscape "Thu, 01-Jan-1970 00:00:01 GMT" and asctime forms servers still send
+    fn parse_date(value: &str) -> Option<i64> {
+        const MONTHS: [&str; 12] = [
+            "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+        ];
+        let is_delimiter =
+            |c: char| matches!(c, '\t' | ' '..='/' | ';'..='@' | '['..='`' | '{'..='~');
+        // The digits a token starts with, when there are between min and max of them
+        let digits = |token: &str, min: usize, max: usize| {
+            let count = token.chars().take_while(char::is_ascii_digit).count();
+            if (min..=max).contains(&count) {
+                token[..count].parse::<u32>().ok()
+            } else {
+                None
+            }
+        };
+        let mut time = None;
+        let mut day = None;
+        let mut month = None;
+        let mut year = None;
+        for token in value.split(is_delimiter).filter(|token| !token.is_empty()) {
+            if time.is_none() {
+                let mut fields = token.splitn(3, ':');
+                let mut field = || fields.next().and_then(|field| digits(field, 1, 2));
+                if let (Some(hour), Some(minute), Some(second)) = (field(), field(), field()) {
+                    time = Some((hour, minute, second));
+                    continue;
+                }
+            }
+            if day.is_none()
+                && let Some(parsed) = digits(token, 1, 2)
+            {
+                day = Some(parsed);
+                continue;
+            }
+            if month.is_none()
+                && let Some(index) = token.get(..3).and_then(|prefix| {
+                    MONTHS
+                        .iter()
+                        .position(|month| prefix.eq_ignore_ascii_case(month))
+                })
+            {
+                month = Some(index as u32 + 1);
+                continue;
+            }
+            if year.is_none()
+                && let Some(parsed) = digits(token, 2, 4)
+            {
+                year = Some(match parsed {
+                    70..=99 => parsed + 1900,
+                    0..=69 => parsed + 2000,
+                    _ => parsed,
+                });
+            }
+        }
+        let (hour, minute, second) = time?;
+        let (day, month, year) = (day?, month?, year?);
+        if !(1..=31).contains(&day) || year < 1601 || hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        chrono::NaiveDate::from_ymd_opt(year as i32, month, day)?
+            .and_hms_opt(hour, minute, second)
+            .map(|time| time.and_utc().timestamp())
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires
+            .is_some_and(|at| at <= chrono::Local::now().timestamp())
+    }
+
+    // Whether the cookie goes with a request to url (RFC 6265 section 5.4)
+    fn matches(&self, url: &reqwest::Url) -> bool {
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        let domain_matches = host == self.domain
+            || (!self.host_only && host.ends_with(&format!(".{}", self.domain)));
+        let path = url.path();
+        // "/api" covers "/api" and "/api/users" but not "/apiary"
+        let path_matches = path == self.path
+            || (path.starts_with(&self.path)
+                && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/')));
+        domain_matches
+            && path_matches
+            && (!self.secure || url.scheme() == "https")
+            && !self.is_expired()
+    }
+
+    // Replaces a cookie with the same name, domain and path; an expired one just removes it
+    fn store(jar: &mut Vec<SessionCookie>, cookie: SessionCookie) {
+        jar.retain(|existing| {
+            existing.name != cookie.name
+                || existing.domain != cookie.domain
+                || existing.path != cookie.path
+        });
+        if !cookie.is_expired() {
+            jar.push(cookie);
+        }
+    }
+}
+
+// Which stored jar a send's cookies came from: a workspace, and a session in it by name.
+// The workspace name guards against the index pointing elsewhere after a removal.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CookieOwner {
+    workspace: usize,
+    workspace_name: String,
+    session: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct CookieJarState {
+    cookies: Vec<SessionCookie>,
+    // Every cookie the server set, in order; expired ones are deletions
+    changes: Vec<SessionCookie>,
+}
+
+// The cookies a send works with, shared by its redirect hops (or every request of a run) so
+// a cookie set on the way is sent on the next request. The changes are merged back into the
+// owner's jar by keep_cookies.
+#[derive(Debug, Clone, Default)]
+struct CookieJar {
+    state: Arc<std::sync::Mutex<CookieJarState>>,
+    owner: CookieOwner,
+}
+
+impl CookieJar {
+    fn new(owner: CookieOwner, cookies: Vec<SessionCookie>) -> Self {
+        Self {
+            state: Arc::new(std::sync::Mutex::new(CookieJarState {
+                cookies,
+                changes: Vec::new(),
+            })),
+            owner,
+        }
+    }
+
+    // Takes the changes made since the last call
+    fn take_changes(&self) -> Vec<SessionCookie> {
+        self.state
+            .lock()
+            .map(|mut state| std::mem::take(&mut state.changes))
+            .unwrap_or_default()
+    }
+
+    fn header(&self, url: &str) -> Option<String> {
+        let url = reqwest::Url::parse(url).ok()?;
+        let state = self.state.lock().ok()?;
+        let cookies: Vec<String> = state
+            .cookies
+            .iter()
+            .filter(|cookie| !cookie.name.is_empty() && cookie.matches(&url))
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        (!cookies.is_empty()).then(|| cookies.join("; "))
+    }
+
+    fn store(&self, response: &reqwest::Response) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        for header in response.headers().get_all(reqwest::header::SET_COOKIE) {
+            if let Ok(header) = header.to_str()
+                && let Some(cookie) = SessionCookie::parse(header, response.url())
+            {
+                SessionCookie::store(&mut state.cookies, cookie.clone());
+                state.changes.push(cookie);
+            }
+        }
+    }
+}
+
+// A credential kept in the Token Manager; requests and collections refer to it by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct NamedToken {
+    name: String,
+    value: String,
+    // Put in front of the value in the Authorization header, e.g. "Bearer"
+    scheme: String,
+    // Local "YYYY-MM-DD HH:MM"; empty falls back to a JWT's exp claim
+    expires: String,
+}
+
+impl Default for NamedToken {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            value: String::new(),
+            scheme: "Bearer".to_string(),
+            expires: String::new(),
+        }
+    }
+}
+
+impl NamedToken {
+    fn header_value(&self, value: &str) -> String {
+        if self.scheme.trim().is_empty() {
+            value.to_string()
+        } else {
+            format!("{} {}", self.scheme.trim(), value)
+        }
+    }
+
+    fn parsed_expiry(&self) -> Option<i64> {
+        chrono::NaiveDateTime::parse_from_str(self.expires.trim(), "%Y-%m-%d %H:%M")
+            .ok()
+            .and_then(|time| time.and_local_timezone(chrono::Local).earliest())
+            .map(|time| time.timestamp())
+    }
+
+    // Unix time the token stops working, if known
+    fn expires_at(&self) -> Option<i64> {
+        if !self.expires.trim().is_empty() {
+            return self.parsed_expiry();
+        }
+        let payload = self.value.trim().split('.').nth(1)?;
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(payload.trim_end_matches('='))
+            .ok()?;
+        serde_json::from_slice::<serde_json::Value>(&bytes)
+            .ok()?
+            .get("exp")?
+            .as_i64()
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at()
+            .is_some_and(|at| at <= chrono::Local::now().timestamp())
+    }
+}
+
+// Runs saved requests on a schedule while the app is open
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Monitor {
+    id: String,
+    name: String,
+    request_ids: Vec<String>,
+    enabled: bool,
+    schedule: MonitorSchedule,
+    interval_secs: u64,
+    cron: String,
+    expect_success: bool,
+    // 0 = no latency threshold
+    max_latency_ms: u64,
+    notify: bool,
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name: String::new(),
+            request_ids: vec![],
+            enabled: true,
+            schedule: MonitorSchedule::Interval,
+            interval_secs: 300,
+            cron: "0 */5 * * * *".to_string(),
+            expect_success: true,
+            max_latency_ms: 0,
+            notify: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum MonitorSchedule {
+    Interval,
+    Cron,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MonitorResult {
+    timestamp: String,
+    request_name: String,
+    status: u16,
+    time: u128,
+    // Why the check failed, if it did
+    failure: Option<String>,
+}
+
+enum MonitorEvent {
+    Result {
+        monitor_id: String,
+        result: MonitorResult,
+    },
+    Record(RunRecord),
+    Finished(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CaCertificate {
+    name: String,
+    pem: String,
+}
+
+// Identity presented to hosts that ask for a client certificate (mutual TLS)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct ClientCertificate {
+    enabled: bool,
+    // Same patterns as certificate pins: "api.internal" or "*.internal"
+    host: String,
+    name: String,
+    format: CertificateFormat,
+    // The PEM certificate chain, or the base64 of a PKCS#12 archive
+    certificate: String,
+    // PEM PKCS#8 private key; PKCS#12 archives carry their own
+    key: String,
+    passphrase: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum CertificateFormat {
+    #[default]
+    Pem,
+    Pkcs12,
+}
+
+impl ClientCertificate {
+    fn identity(&self) -> Result<reqwest::Identity, String> {
+        match self.format {
+            CertificateFormat::Pem => {
+                reqwest::Identity::from_pkcs8_pem(self.certificate.as_bytes(), self.key.as_bytes())
+                    .map_err(|e| e.to_string())
+            }
+            CertificateFormat::Pkcs12 => {
+                let der = base64::engine::general_purpose::STANDARD
+                    .decode(&self.certificate)
+                    .map_err(|e| e.to_string())?;
+                reqwest::Identity::from_pkcs12_der(&der, &self.passphrase)
+                    .map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+// Expected certificate for a host, checked after the TLS handshake
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct CertificatePin {
+    // "*.example.com" also covers subdomains
+    host: String,
+    // "sha256/<base64>" pins the public key, hex pins the whole certificate
+    fingerprint: String,
+}
+
+impl CertificatePin {
+    fn applies_to(&self, host: &str) -> bool {
+        Self::host_matches(&self.host, host)
+    }
+
+    fn host_matches(pattern: &str, host: &str) -> bool {
+        let pattern = pattern.trim().to_lowercase();
+        let host = host.to_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => host == domain || host.ends_with(&format!(".{}", domain)),
+            None => !pattern.is_empty() && host == pattern,
+        }
+    }
+
+    fn sha256(data: &[u8]) -> Vec<u8> {
+        ring::digest::digest(&ring::digest::SHA256, data)
+            .as_ref()
+            .to_vec()
+    }
+
+    fn certificate_fingerprint(certificate: &[u8]) -> String {
+        Self::sha256(certificate)
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<_>>()
+            .join(":")
+    }
+
+    fn public_key_fingerprint(certificate: &[u8]) -> Option<String> {
+        let key = Self::subject_public_key_info(certificate)?;
+        Some(format!(
+            "sha256/{}",
+            base64::engine::general_purpose::STANDARD.encode(Self::sha256(key))
+        ))
+    }
+
+    // Splits one DER element off the front: (whole element, contents, rest)
+    fn der_element(data: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+        let first = *data.get(1)? as usize;
+        let (len, header) = if first < 0x80 {
+            (first, 2)
+        } else {
+            let count = first & 0x7f;
+            if count == 0 || count > 4 {
+                return None;
+            }
+            let len = data
+                .get(2..2 + count)?
+                .iter()
+                .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+            (len, 2 + count)
+        };
+        let end = header.checked_add(len)?;
+        Some((data.get(..end)?, data.get(header..end)?, data.get(end..)?))
+    }
+
+    // The SubjectPublicKeyInfo a public key pin hashes
+    fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+        let (_, certificate, _) = Self::der_element(certificate)?;
+        let (_, mut fields, _) = Self::der_element(certificate)?;
+        // Skip the optional version, then serial, signature, issuer, validity and subject
+        if fields.first() == Some(&0xa0) {
+            fields = Self::der_element(fields)?.2;
+        }
+        for _ in 0..5 {
+            fields = Self::der_element(fields)?.2;
+        }
+        Some(Self::der_element(fields)?.0)
+    }
+
+    fn matches(&self, certificate: &[u8]) -> bool {
+        let fingerprint = self.fingerprint.trim();
+        match fingerprint.strip_prefix("sha256/") {
+            Some(_) => Self::public_key_fingerprint(certificate).as_deref() == Some(fingerprint),
+            None => {
+                let expected: String = fingerprint
+                    .chars()
+                    .filter(|c| c.is_ascii_hexdigit())
+                    .collect();
+                expected.eq_ignore_ascii_case(
+                    &Self::certificate_fingerprint(certificate).replace(':', ""),
+                )
+            }
+        }
+    }
+
+    // Fails when the response's host is pinned and its certificate matches none of the pins
+    fn check(response: &reqwest::Response, pins: &[CertificatePin]) -> Result<(), String> {
+        let host = response.url().host_str().unwrap_or_default();
+        let pins: Vec<&CertificatePin> = pins.iter().filter(|pin| pin.applies_to(host)).collect();
+        if pins.is_empty() {
+            return Ok(());
+        }
+        let Some(certificate) = response
+            .extensions()
+            .get::<reqwest::tls::TlsInfo>()
+            .and_then(|info| info.peer_certificate())
+        else {
+            return Err(format!("{} is pinned but presented no certificate", host));
+        };
+        if pins.iter().any(|pin| pin.matches(certificate)) {
+            return Ok(());
+        }
+        Err(format!(
+            "Certificate for {} does not match any pin (certificate {}, public key {})",
+            host,
+            Self::certificate_fingerprint(certificate),
+            Self::public_key_fingerprint(certificate).unwrap_or_default()
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct ProxySettings {
+    enabled: bool,
+    url: String,
+    username: String,
+    password: String,
+    no_proxy: String,
+    use_system_proxy: bool,
+    auth: ProxyAuth,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum ProxyAuth {
+    #[default]
+    Basic,
+    Ntlm,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            no_proxy: String::new(),
+            use_system_proxy: true,
+            auth: ProxyAuth::Basic,
+        }
+    }
+}
+
+// Local proxy that answers NTLM challenges from the upstream proxy. NTLM authenticates a
+// connection rather than a request, which reqwest's proxy support can't do, so the client
+// talks to this relay and each connection is authenticated before the bytes are piped.
+struct NtlmProxyRelay {
+    settings: ProxySettings,
+    url: String,
+    // Presented by the client as Basic proxy credentials, so other local programs can't
+    // use the relay to get through the proxy as the user
+    password: String,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for NtlmProxyRelay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl NtlmProxyRelay {
+    const USERNAME: &'static str = "send";
+
+    // Must be called inside the tokio runtime
+    fn start(settings: &ProxySettings) -> Result<Self, String> {
+        let url = settings.url.trim();
+        let url = if url.contains("://") {
+            url.to_string()
+        } else {
+            format!("http://{}", url)
+        };
+        let upstream = reqwest::Url::parse(&url)
+            .ok()
+            .filter(|url| url.scheme() == "http")
+            .and_then(|url| {
+                Some(format!(
+                    "{}:{}",
+                    url.host_str()?,
+                    url.port_or_known_default()?
+                ))
+            })
+            .ok_or_else(|| "NTLM proxy authentication needs an http:// proxy URL".to_string())?;
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0))
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                tokio::net::TcpListener::from_std(listener)
+            })
+            .map_err(|e| format!("Failed to start the NTLM proxy relay: {}", e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to start the NTLM proxy relay: {}", e))?
+            .port();
+        let credentials = NtlmCredentials::new(&settings.username, &settings.password, "", false);
+        let password = Uuid::new_v4().simple().to_string();
+        let expected = Arc::new(RequestAuth::basic_header(Self::USERNAME, &password));
+        let task = tokio::spawn(async move {
+            while let Ok((client, _)) = listener.accept().await {
+                let upstream = upstream.clone();
+                let credentials = credentials.clone();
+                let expected = expected.clone();
+                tokio::spawn(async move {
+                    let _ = Self::relay(client, &upstream, &credentials, &expected).await;
+                });
+            }
+        });
+        Ok(Self {
+            settings: settings.clone(),
+            url: format!("http://127.0.0.1:{}", port),
+            password,
+            task,
+        })
+    }
+
+    // The proxy settings that send the client through this relay
+    fn proxy_settings(&self, proxy: &ProxySettings) -> ProxySettings {
+        ProxySettings {
+            url: self.url.clone(),
+            username: Self::USERNAME.to_string(),
+            password: self.password.clone(),
+            auth: ProxyAuth::Basic,
+            ..proxy.clone()
+        }
+    }
+
+    async fn relay(
+        mut client: tokio::net::TcpStream,
+        upstream: &str,
+        credentials: &NtlmCredentials,
+        expected: &str,
+    ) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut client_buffer = Vec::new();
+        let head = Self::read_head(&mut client, &mut client_buffer).await?;
+        if !Self::header(&head, "Proxy-Authorization").any(|value| value == expected) {
+            client
+                .write_all(
+                    b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                      Proxy-Authenticate: Basic realm=\"Send\"\r\n\
+                      Content-Length: 0\r\nConnection: close\r\n\r\n",
+                )
+                .await?;
+            return Ok(());
+        }
+        let mut server = tokio::net::TcpStream::connect(upstream).await?;
+        let connect = head
+            .split(' ')
+            .next()
+            .is_some_and(|method| method.eq_ignore_ascii_case("CONNECT"));
+
+        // Other methods authenticate with a HEAD of the same URL, so the body is sent only once
+        let negotiate = credentials.header(&credentials.negotiate_message());
+        let probe = if connect {
+            Self::rewrite_head(&head, None, &[], Some(&negotiate))
+        } else {
+            Self::rewrite_head(
+                &head,
+                Some("HEAD"),
+                &["Content-Length", "Transfer-Encoding"],
+                Some(&negotiate),
+            )
+        };
+        server.write_all(probe.as_bytes()).await?;
+        let mut server_buffer = Vec::new();
+        let response = Self::read_head(&mut server, &mut server_buffer).await?;
+        let message = (response.split(' ').nth(1) == Some("407"))
+            .then(|| {
+                Self::header(&response, "Proxy-Authenticate")
+                    .find_map(NtlmCredentials::challenge_token)
+            })
+            .flatten()
+            .and_then(|challenge| credentials.authenticate_message(&challenge));
+        match message {
+            Some(message) => {
+                // A 407 to HEAD has no body; one to CONNECT is read so the connection can go on
+                if connect {
+                    let length = Self::header(&response, "Content-Length")
+                        .find_map(|value| value.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    Self::read_at_least(&mut server, &mut server_buffer, length).await?;
+                    server_buffer.drain(..length);
+                }
+                let authenticated =
+                    Self::rewrite_head(&head, None, &[], Some(&credentials.header(&message)));
+                server.write_all(authenticated.as_bytes()).await?;
+            }
+            // The proxy didn't ask for NTLM, so its answer to CONNECT is the client's answer
+            None if connect => client.write_all(response.as_bytes()).await?,
+            None => {
+                let head = Self::rewrite_head(&head, None, &[], None);
+                server.write_all(head.as_bytes()).await?
+            }
+        }
+        client.write_all(&server_buffer).await?;
+        server.write_all(&client_buffer).await?;
+        tokio::io::copy_bidirectional(&mut client, &mut server).await?;
+        Ok(())
+    }
+
+    // Reads through the blank line after the headers, leaving anything past it in `buffer`
+    async fn read_head(
+        stream: &mut tokio::net::TcpStream,
+        buffer: &mut Vec<u8>,
+    ) -> std::io::Result<String> {
+        loop {
+            if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                let head = String::from_utf8_lossy(&buffer[..end + 4]).to_string();
+                buffer.drain(..end + 4);
+                return Ok(head);
+            }
+            if buffer.len() > 64 * 1024 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Header section too large",
+                ));
+            }
+            Self::read_at_least(stream, buffer, buffer.len() + 1).await?;
+        }
+    }
+
+    async fn read_at_least(
+        stream: &mut tokio::net::TcpStream,
+        buffer: &mut Vec<u8>,
+        length: usize,
+    ) -> std::io::Result<()> {
+        use tokio::io::AsyncReadExt;
+
+        let mut chunk = [0; 8192];
+        while buffer.len() < length {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(std::io::ErrorKind::UnexpectedEof.into());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        Ok(())
+    }
+
+    fn header<'a>(head: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> {
+        head.lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .filter(move |(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    }
+
+    // The header section with another method, without `drop`, and with the relay's own
+    // Proxy-Authorization replaced by `authorization`
+    fn rewrite_head(
+        head: &str,
+        method: Option<&str>,
+        drop: &[&str],
+        authorization: Option<&str>,
+    ) -> String {
+        let mut lines = head.lines();
+        let request_line = lines.next().unwrap_or_default();
+        let mut result = match method {
+            Some(method) => {
+                let target = request_line.split_once(' ').map_or("", |(_, rest)| rest);
+                format!("{} {}\r\n", method, target)
+            }
+            None => format!("{}\r\n", request_line),
+        };
+        for line in lines.filter(|line| !line.is_empty()) {
+            let name = line.split_once(':').map_or(line, |(name, _)| name).trim();
+            if !name.eq_ignore_ascii_case("Proxy-Authorization")
+                && !drop.iter().any(|drop| drop.eq_ignore_ascii_case(name))
+            {
+                result.push_str(line);
+                result.push_str("\r\n");
+            }
+        }
+        if let Some(authorization) = authorization {
+            result.push_str(&format!("Proxy-Authorization: {}\r\n", authorization));
+        }
+        result.push_str("\r\n");
+        result
+    }
+}
+
+// Proxy configuration picked up from the environment or the operating system
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SystemProxy {
+    http: Option<String>,
+    https: Option<String>,
+    no_proxy: String,
+    source: &'static str,
+}
+
+impl SystemProxy {
+    // Environment variables win, as they do for curl and most CLI tools
+    fn detect() -> Option<Self> {
+        Self::from_env().or_else(Self::from_os)
+    }
+
+    fn from_env() -> Option<Self> {
+        let var = |names: &[&str]| {
+            names
+                .iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .map(|value| value.trim().to_string())
+                .find(|value| !value.is_empty())
+        };
+        let all = var(&["ALL_PROXY", "all_proxy"]);
+        Self {
+            http: var(&["HTTP_PROXY", "http_proxy"]).or_else(|| all.clone()),
+            https: var(&["HTTPS_PROXY", "https_proxy"]).or(all),
+            no_proxy: var(&["NO_PROXY", "no_proxy"]).unwrap_or_default(),
+            source: "environment",
+        }
+        .found()
+    }
+
+    fn found(self) -> Option<Self> {
+        (self.http.is_some() || self.https.is_some()).then_some(self)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+    fn command_output(program: &str, args: &[&str]) -> Option<String> {
+        let output = std::process::Command::new(program)
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    // GNOME and the desktops sharing its settings schema
+    #[cfg(target_os = "linux")]
+    fn from_os() -> Option<Self> {
+        let get = |schema: &str, key: &str| {
+            Self::command_output("gsettings", &["get", schema, key])
+                .map(|value| value.trim_matches('\'').to_string())
+        };
+        if get("org.gnome.system.proxy", "mode")? != "manual" {
+            return None;
+        }
+        let server = |kind: &str, scheme: &str| {
+            let schema = format!("org.gnome.system.proxy.{}", kind);
+            let host = get(&schema, "host").filter(|host| !host.is_empty())?;
+            let port = get(&schema, "port").filter(|port| port != "0")?;
+            Some(format!("{}://{}:{}", scheme, host, port))
+        };
+        let socks = server("socks", "socks5");
+        // A string list such as ['localhost', '127.0.0.0/8'], or @as [] when empty
+        let ignore_hosts = get("org.gnome.system.proxy", "ignore-hosts").unwrap_or_default();
+        Self {
+            http: server("http", "http").or_else(|| socks.clone()),
+            https: server("https", "http").or(socks),
+            no_proxy: ignore_hosts
+                .trim_start_matches("@as ")
+                .trim_matches(['[', ']'])
+                .split(',')
+                .map(|host| host.trim().trim_matches('\''))
+                .filter(|host| !host.is_empty())
+                .collect::<Vec<_>>()
+                .join(","),
+            source: "GNOME settings",
+        }
+        .found()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn from_os() -> Option<Self> {
+        let key = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings";
+        // Lines look like "    ProxyServer    REG_SZ    host:8080"
+        let value = |name: &str| {
+            let output = Self::command_output("reg", &["query", key, "/v", name])?;
+            output.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                (parts.next()? == name).then(|| parts.skip(1).collect::<Vec<_>>().join(" "))
+            })
+        };
+        if value("ProxyEnable")? != "0x1" {
+            return None;
+        }
+        let mut proxy = Self {
+            source: "Windows settings",
+            ..Default::default()
+        };
+        // Either one server for every scheme or "http=host:port;https=host:port"
+        for entry in value("ProxyServer")?.split(';') {
+            match entry.split_once('=') {
+                Some(("http", server)) => proxy.http = Some(format!("http://{}", server)),
+                Some(("https", server)) => proxy.https = Some(format!("http://{}", server)),
+                Some(("socks", server)) => {
+                    let socks = format!("socks5://{}", server);
+                    proxy.http.get_or_insert_with(|| socks.clone());
+                    proxy.https.get_or_insert(socks);
+                }
+                Some(_) => {}
+                None if !entry.is_empty() => {
+                    proxy.http = Some(format!("http://{}", entry));
+                    proxy.https = proxy.http.clone();
+                }
+                None => {}
+            }
+        }
+        // <local> (any host without a dot) has no NO_PROXY equivalent
+        proxy.no_proxy = value("ProxyOverride")
+            .unwrap_or_default()
+            .split(';')
+            .filter(|host| !host.is_empty() && *host != "<local>")
+            .collect::<Vec<_>>()
+            .join(",");
+        proxy.found()
+    }
+
+    #[cfg(target_os = "macos")]
+    fn from_os() -> Option<Self> {
+        let output = Self::command_output("scutil", &["--proxy"])?;
+        // Per-interface overrides follow __SCOPED__; only the global settings matter here
+        let global = output.split("__SCOPED__").next().unwrap_or_default();
+        let entries: Vec<(&str, &str)> = global
+            .lines()
+            .filter_map(|line| line.split_once(" : "))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+        let field = |name: &str| {
+            entries
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+        let server = |prefix: &str, scheme: &str| {
+            if field(&format!("{}Enable", prefix))? != "1" {
+                return None;
+            }
+            Some(format!(
+                "{}://{}:{}",
+                scheme,
+                field(&format!("{}Proxy", prefix))?,
+                field(&format!("{}Port", prefix))?
+            ))
+        };
+        let socks = server("SOCKS", "socks5");
+        Self {
+            http: server("HTTP", "http").or_else(|| socks.clone()),
+            https: server("HTTPS", "http").or(socks),
+            // ExceptionsList entries are the only keys that are array indexes
+            no_proxy: entries
+                .iter()
+                .filter(|(key, _)| key.parse::<usize>().is_ok())
+                .map(|(_, host)| *host)
+                .collect::<Vec<_>>()
+                .join(","),
+            source: "macOS settings",
+        }
+        .found()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    fn from_os() -> Option<Self> {
+        None
+    }
+
+    fn url(&self) -> &str {
+        self.https
+            .as_deref()
+            .or(self.http.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+struct SendApp {
+    // Workspaces
+    workspaces: Vec<Workspace>,
+    current_workspace: usize,
+    // Current request
+    current_request: HttpRequest,
+    // Response
+    current_response: Option<HttpResponse>,
+    is_loading: bool,
+    // UI State
+    selected_sidebar_item: Option<SidebarItem>,
+    request_tab: RequestTab,
+    raw_body_type: RawBodyType,
+    response_tab: ResponseTab,
+    show_encoded_body: bool,
+    // The method combo box is swapped for a text field while typing a custom method
+    editing_method: bool,
+    // Headers shown as `Key: Value` lines instead of the table while bulk editing
+    headers_bulk_text: Option<String>,
+    // Last body checked by the JSON editor and its syntax error, if any
+    json_body_check: Option<(String, Option<JsonSyntaxError>)>,
+    // Settings and history
+    settings: AppSettings,
+    history: Vec<HistoryEntry>,
+    pending_request: Option<HttpRequest>,
+    // Runtime for async operations
+    runtime: Runtime,
+    // Pooled clients of each workspace, by workspace index, most recently used last
+    http_clients: HashMap<usize, Vec<CachedClients>>,
+    system_proxy: Option<SystemProxy>,
+    last_resolved_request: Option<ResolvedRequest>,
+    // The clients last_resolved_request was sent with, so fetching the rest of its response
+    // uses its proxy and TLS settings whatever request is open now
+    last_request_clients: Option<HttpClients>,
+    download: Option<DownloadState>,
+    runner: Option<RunnerState>,
+    capture: Option<CaptureState>,
+    captured: Vec<CapturedExchange>,
+    load_test: Option<LoadTestState>,
+    monitor_results: HashMap<String, Vec<MonitorResult>>,
+    monitor_next_run: HashMap<String, Instant>,
+    monitors_running: HashSet<String>,
+    monitor_tasks: HashMap<String, tokio::task::AbortHandle>,
+    monitor_sender: mpsc::Sender<MonitorEvent>,
+    monitor_receiver: mpsc::Receiver<MonitorEvent>,
+    deep_link_receiver: Option<mpsc::Receiver<String>>,
+    #[cfg(feature = "tray")]
+    tray_receiver: Option<mpsc::Receiver<TrayEvent>>,
+    // Dropping the icon removes it; on Linux it lives on the GTK thread instead
+    #[cfg(all(feature = "tray", not(target_os = "linux")))]
+    tray_icon: Option<tray_icon::TrayIcon>,
+    import_receiver: Option<mpsc::Receiver<Result<Collection, String>>>,
+    response_receiver: Option<mpsc::Receiver<Result<HttpResponse, RequestError>>>,
+    request_task: Option<tokio::task::AbortHandle>,
+    retry_wait_receiver: Option<mpsc::Receiver<RetryWait>>,
+    // Bytes received and Content-Length while a large body streams to disk
+    response_progress: Option<(u64, Option<u64>)>,
+    response_progress_receiver: Option<mpsc::Receiver<(u64, Option<u64>)>>,
+    upload_progress: Option<Arc<UploadProgress>>,
+    retry_wait: Option<RetryWait>,
+    request_started: Option<Instant>,
+    request_queue_dialog: bool,
+    request_error: Option<RequestError>,
+    // Header assertions checked against the current response
+    assertion_results: Vec<(String, Result<(), String>)>,
+    oauth_tokens: OAuthTokenCache,
+    // Variable assignments made by the last response's capture rules
+    capture_results: Vec<(String, Result<String, String>)>,
+    // Text selected in the response body, for the Tools panel
+    response_selection: String,
+    tools_input: String,
+    tools_output: Result<String, String>,
+    // Headers being edited for a one-off retry
+    retry_headers: Option<Vec<KeyValue>>,
+    // Collection or folder whose auth is being edited, with the edited copy
+    auth_dialog: Option<(TreeNode, RequestAuth)>,
+    // Collection index and the signer being edited
+    signer_dialog: Option<(usize, RequestSigner)>,
+    // Dialogs
+    new_collection_dialog: bool,
+    new_collection_name: String,
+    new_request_dialog: bool,
+    new_request_name: String,
+    new_workspace_dialog: bool,
+    new_workspace_name: String,
+    new_environment_dialog: bool,
+    new_environment_name: String,
+    new_folder_dialog: bool,
+    new_folder_name: String,
+    share_export_dialog: bool,
+    share_export_scope: ShareExportScope,
+    share_export_strip_values: bool,
+    settings_dialog: bool,
+    workspace_settings_dialog: bool,
+    workspace_settings_error: Option<String>,
+    runner_dialog: bool,
+    capture_dialog: bool,
+    capture_port: u16,
+    capture_host_filter: String,
+    capture_error: Option<String>,
+    load_test_dialog: bool,
+    load_test_folder: bool,
+    load_test_concurrency: usize,
+    load_test_limit: LoadTestLimit,
+    load_test_duration_secs: u64,
+    load_test_request_count: usize,
+    load_test_error: Option<String>,
+    monitors_dialog: bool,
+    deep_link_message: Option<String>,
+    shortcuts_dialog: bool,
+    environment_switcher: bool,
+    recording_shortcut: Option<ShortcutAction>,
+    command_palette: bool,
+    command_palette_query: String,
+    command_palette_selected: usize,
+    undo_stack: Vec<UndoSnapshot>,
+    redo_stack: Vec<UndoSnapshot>,
+    undo_baseline: Option<UndoSnapshot>,
+    // Bumped by every edit to a workspace's collections, so undo needn't compare them
+    collections_generation: u64,
+    last_input: Instant,
+    layout: LayoutState,
+    layout_dirty: bool,
+    // History entries are written with the cache a little later, so bursts of sends share a write
+    history_unsaved_since: Option<Instant>,
+    // Toasts can be raised from &self helpers and background tasks alike
+    toast_sender: mpsc::Sender<Toast>,
+    toast_receiver: mpsc::Receiver<Toast>,
+    toasts: Vec<Toast>,
+    tree_focused: bool,
+    tree_cursor: Option<TreeNode>,
+    tree_rename: Option<(TreeNode, String)>,
+    // Request ids picked with Ctrl+click to send together
+    tree_selection: Vec<String>,
+    last_auto_save: Option<chrono::DateTime<chrono::Local>>,
+    // Unsaved edits of requests other than the current one, by request id
+    drafts: HashMap<String, HttpRequest>,
+    confirmation: Option<Confirmation>,
+    show_welcome: bool,
+    // Most recent first
+    recent_workspaces: Vec<std::path::PathBuf>,
+    curl_import: Option<String>,
+    curl_import_error: Option<String>,
+    // Final URL and raw request text shown by the Preview button
+    request_preview: Option<Result<(String, String), String>>,
+    monitors_paused: bool,
+    // Oldest first
+    run_records: Vec<RunRecord>,
+    metrics_dialog: bool,
+    token_manager_dialog: bool,
+    sessions_dialog: bool,
+    cookie_manager_dialog: bool,
+    // Domain typed into the cookie manager's add row
+    new_cookie_domain: String,
+    // Name typed into the Headers tab's save-as-preset field
+    new_header_preset_name: String,
+    metrics_range: MetricsRange,
+    // None shows every collection
+    metrics_collection: Option<String>,
+    // Set by File > Quit so the close isn't turned into a minimize
+    quitting: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ShareExportScope {
+    Workspace,
+    Collection,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum ResponseTab {
+    Body,
+    Headers,
+    Cookies,
+    Redirects,
+    Tests,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum SidebarItem {
+    Collections,
+    Environment,
+    History,
+    Tools,
+}
+
+// Values request fields can insert, either literally or as a {{$variable}} resolved at send time
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Generator {
+    Uuid,
+    Timestamp,
+    IsoTimestamp,
+    RandomInt,
+    RandomString,
+}
+
+impl Generator {
+    const ALL: [Generator; 5] = [
+        Generator::Uuid,
+        Generator::Timestamp,
+        Generator::IsoTimestamp,
+        Generator::RandomInt,
+        Generator::RandomString,
+    ];
+
+    fn label(&self) -> String {
+        tr(match self {
+            Generator::Uuid => "generator-uuid",
+            Generator::Timestamp => "generator-timestamp",
+            Generator::IsoTimestamp => "generator-iso-timestamp",
+            Generator::RandomInt => "generator-random-int",
+            Generator::RandomString => "generator-random-string",
+        })
+    }
+
+    fn variable(&self) -> &'static str {
+        match self {
+            Generator::Uuid => "$uuid",
+            Generator::Timestamp => "$timestamp",
+            Generator::IsoTimestamp => "$isoTimestamp",
+            Generator::RandomInt => "$randomInt",
+            Generator::RandomString => "$randomString",
+        }
+    }
+
+    // Also accepts the names Postman uses for the same values
+    fn from_variable(name: &str) -> Option<Self> {
+        match name {
+            "$guid" | "$randomUUID" => Some(Generator::Uuid),
+            name => Self::ALL
+                .into_iter()
+                .find(|generator| generator.variable() == name),
+        }
+    }
+
+    fn generate(&self) -> String {
+        match self {
+            Generator::Uuid => Uuid::new_v4().to_string(),
+            Generator::Timestamp => chrono::Local::now().timestamp().to_string(),
+            Generator::IsoTimestamp => {
+                chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+            }
+            Generator::RandomInt => (Uuid::new_v4().as_u128() % 1001).to_string(),
+            Generator::RandomString => {
+                const CHARSET: &[u8] =
+                    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+                (0..16)
+                    .map(|_| {
+                        CHARSET[(Uuid::new_v4().as_u128() % CHARSET.len() as u128) as usize] as char
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    // Fills in every {{$variable}} with a fresh value; unknown names are left alone
+    fn resolve(input: &str) -> String {
+        let mut result = String::new();
+        let mut rest = input;
+        while let Some(start) = rest.find("{{$") {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            match after.find("}}").and_then(|end| {
+                Self::from_variable(after[..end].trim()).map(|generator| (end, generator))
+            }) {
+                Some((end, generator)) => {
+                    result.push_str(&generator.generate());
+                    rest = &after[end + 2..];
+                }
+                None => {
+                    result.push_str("{{");
+                    rest = after;
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+// Shells a request can be exported to as a standalone script
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScriptShell {
+    Bash,
+    PowerShell,
+}
+
+impl ScriptShell {
+    const ALL: [ScriptShell; 2] = [ScriptShell::Bash, ScriptShell::PowerShell];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ScriptShell::Bash => "Bash (.sh)",
+            ScriptShell::PowerShell => "PowerShell (.ps1)",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ScriptShell::Bash => "sh",
+            ScriptShell::PowerShell => "ps1",
+        }
+    }
+
+    // Splits a template into (text, is_variable) parts; dynamic {{$variables}} stay literal
+    fn template_parts(template: &str) -> Vec<(String, bool)> {
+        let mut parts = vec![];
+        let mut rest = template;
+        while let Some(start) = rest.find("{{")
+            && let Some(end) = rest[start..].find("}}")
+        {
+            if start > 0 {
+                parts.push((rest[..start].to_string(), false));
+            }
+            let name = rest[start + 2..start + end].trim();
+            if name.is_empty() || name.starts_with('$') {
+                parts.push((rest[start..start + end + 2].to_string(), false));
+            } else {
+                parts.push((name.to_string(), true));
+            }
+            rest = &rest[start + end + 2..];
+        }
+        if !rest.is_empty() {
+            parts.push((rest.to_string(), false));
+        }
+        parts
+    }
+
+    // Percent-encodes the literal text of a query parameter, leaving {{variables}} in place
+    fn encode_template(template: &str) -> String {
+        Self::template_parts(&Generator::resolve(template))
+            .into_iter()
+            .map(|(text, is_variable)| {
+                if is_variable {
+                    format!("{{{{{}}}}}", text)
+                } else {
+                    urlencoding::encode(&text).into_owned()
+                }
+            })
+            .collect()
+    }
+
+    // Environment variable names may contain characters shells don't accept
+    fn variable_name(name: &str) -> String {
+        let mut name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            name.insert(0, '_');
+        }
+        name
+    }
+
+    fn quote(&self, text: &str) -> String {
+        match self {
+            ScriptShell::Bash => format!("'{}'", text.replace('\'', "'\\''")),
+            ScriptShell::PowerShell => format!("'{}'", text.replace('\'', "''")),
+        }
+    }
+
+    // A single shell argument for the template, reading {{variables}} from script variables
+    fn word(&self, template: &str) -> String {
+        let parts = Self::template_parts(template);
+        let terms: Vec<String> = parts
+            .iter()
+            .map(|(text, is_variable)| match (self, is_variable) {
+                (ScriptShell::Bash, true) => format!("\"${{{}}}\"", Self::variable_name(text)),
+                (ScriptShell::PowerShell, true) => format!("${}", Self::variable_name(text)),
+                (_, false) => self.quote(text),
+            })
+            .collect();
+        match (self, terms.len()) {
+            (_, 0) => self.quote(""),
+            (_, 1) => terms[0].clone(),
+            (ScriptShell::Bash, _) => terms.concat(),
+            (ScriptShell::PowerShell, _) => format!("({})", terms.join(" + ")),
+        }
+    }
+
+    // Uses the value from the calling environment when it is set
+    fn variable_default(&self, name: &str, value: &str) -> String {
+        let name = Self::variable_name(name);
+        match self {
+            ScriptShell::Bash => format!("{}=${{{}:-{}}}", name, name, self.quote(value)),
+            ScriptShell::PowerShell => format!(
+                "${} = if ($env:{}) {{ $env:{} }} else {{ {} }}",
+                name,
+                name,
+                name,
+                self.quote(value)
+            ),
+        }
+    }
+}
+
+// Text conversions offered in the Tools panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tool {
+    Base64Encode,
+    Base64Decode,
+    UrlEncode,
+    UrlDecode,
+    JwtDecode,
+    Md5,
+    Sha256,
+}
+
+impl Tool {
+    const ALL: [Tool; 7] = [
+        Tool::Base64Encode,
+        Tool::Base64Decode,
+        Tool::UrlEncode,
+        Tool::UrlDecode,
+        Tool::JwtDecode,
+        Tool::Md5,
+        Tool::Sha256,
+    ];
+
+    fn label(&self) -> String {
+        tr(match self {
+            Tool::Base64Encode => "tool-base64-encode",
+            Tool::Base64Decode => "tool-base64-decode",
+            Tool::UrlEncode => "tool-url-encode",
+            Tool::UrlDecode => "tool-url-decode",
+            Tool::JwtDecode => "tool-jwt-decode",
+            Tool::Md5 => "tool-md5",
+            Tool::Sha256 => "tool-sha256",
+        })
+    }
+
+    fn run(&self, input: &str) -> Result<String, String> {
+        match self {
+            Tool::Base64Encode => {
+                Ok(base64::engine::general_purpose::STANDARD.encode(input.as_bytes()))
+            }
+            Tool::Base64Decode => {
+                let bytes = Self::decode_base64(input)?;
+                String::from_utf8(bytes).map_err(|e| {
+                    let mut args = FluentArgs::new();
+                    args.set("count", e.as_bytes().len());
+                    tr_args("tool-not-utf8", &args)
+                })
+            }
+            Tool::UrlEncode => Ok(urlencoding::encode(input).into_owned()),
+            Tool::UrlDecode => urlencoding::decode(&input.replace('+', " "))
+                .map(|decoded| decoded.into_owned())
+                .map_err(|e| e.to_string()),
+            Tool::JwtDecode => Self::decode_jwt(input),
+            Tool::Md5 => Ok(Self::hex(&Md5::digest(input.as_bytes()))),
+            Tool::Sha256 => Ok(Self::hex(
+                ring::digest::digest(&ring::digest::SHA256, input.as_bytes()).as_ref(),
+            )),
+        }
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    // Accepts both alphabets, with or without padding
+    fn decode_base64(input: &str) -> Result<Vec<u8>, String> {
+        let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        let config = base64::engine::GeneralPurposeConfig::new()
+            .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent);
+        let alphabet = if input.contains(['-', '_']) {
+            &base64::alphabet::URL_SAFE
+        } else {
+            &base64::alphabet::STANDARD
+        };
+        base64::engine::GeneralPurpose::new(alphabet, config)
+            .decode(input)
+            .map_err(|e| tr_error("tool-invalid-base64", e))
+    }
+
+    fn decode_jwt(input: &str) -> Result<String, String> {
+        let parts: Vec<&str> = input.trim().split('.').collect();
+        if parts.len() != 3 {
+            return Err(tr("tool-jwt-parts"));
+        }
+        let section = |part: &str, name: &str| -> Result<serde_json::Value, String> {
+            let bytes = Self::decode_base64(part)?;
+            serde_json::from_slice(&bytes).map_err(|e| {
+                let mut args = FluentArgs::new();
+                args.set("part", tr(name));
+                args.set("error", e.to_string());
+                tr_args("tool-jwt-not-json", &args)
+            })
+        };
+        let header = section(parts[0], "jwt-header")?;
+        let claims = section(parts[1], "jwt-claims")?;
+        let mut lines = vec![
+            format!("{}:", tr("jwt-header")),
+            serde_json::to_string_pretty(&header).unwrap_or_default(),
+            String::new(),
+            format!("{}:", tr("jwt-claims")),
+            serde_json::to_string_pretty(&claims).unwrap_or_default(),
+        ];
+        let now = chrono::Local::now().timestamp();
+        for (claim, label) in [
+            ("iat", "jwt-issued"),
+            ("nbf", "jwt-not-before"),
+            ("exp", "jwt-expires"),
+        ] {
+            let Some(time) = claims.get(claim).and_then(|value| value.as_i64()) else {
+                continue;
+            };
+            let Some(date) = chrono::DateTime::from_timestamp(time, 0) else {
+                continue;
+            };
+            let date = date
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S");
+            let note = match claim {
+                "exp" if time <= now => format!(" {}", tr("jwt-expired")),
+                "exp" => {
+                    let mut args = FluentArgs::new();
+                    args.set("duration", Self::duration_label(time - now));
+                    format!(" {}", tr_args("jwt-expires-in", &args))
+                }
+                _ => String::new(),
+            };
+            if lines.len() == 5 {
+                lines.push(String::new());
+            }
+            lines.push(format!("{}: {}{}", tr(label), date, note));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn duration_label(seconds: i64) -> String {
+        match seconds {
+            ..60 => format!("{}s", seconds),
+            60..3600 => format!("{}m", seconds / 60),
+            3600..86400 => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+            _ => format!("{}d {}h", seconds / 86400, seconds % 86400 / 3600),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum RequestTab {
+    Params,
+    Headers,
+    Auth,
+    Body,
+    Tests,
+    Settings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+enum RawBodyType {
+    Text,
+    JavaScript,
+    JSON,
+    HTML,
+    XML,
+}
+
+impl Default for SendApp {
+    fn default() -> Self {
+        let mut app = Self::with_settings(Self::load_settings().unwrap_or_default());
+
+        // Restore UI state from cache if available
+        if let Some(cache) = Self::load_cache() {
+            if !cache.workspaces.is_empty() {
+                app.workspaces = cache.workspaces;
+            }
+            if cache.current_workspace < app.workspaces.len() {
+                app.current_workspace = cache.current_workspace;
+            }
+            app.selected_sidebar_item = cache.selected_sidebar_item;
+            app.request_tab = cache.request_tab;
+            app.raw_body_type = cache.raw_body_type;
+            app.response_tab = cache.response_tab;
+            app.layout = cache.layout;
+            app.history = cache.history;
+            app.monitor_results = cache.monitor_results;
+            app.recent_workspaces = cache.recent_workspaces;
+            app.run_records = cache.run_records;
+            // Drop drafts of requests deleted since
+            app.drafts = cache
+                .drafts
+                .into_iter()
+                .filter(|(id, _)| {
+                    app.workspaces
+                        .iter()
+                        .flat_map(|workspace| &workspace.collections)
+                        .any(|collection| {
+                            Self::find_request_by_id(&collection.root_folder, id).is_some()
+                        })
+                })
+                .collect();
+            // Returning users only see the welcome screen while there is nothing to work with
+            app.show_welcome = !app
+                .workspaces
+                .iter()
+                .flat_map(|workspace| &workspace.collections)
+                .any(|collection| Self::folder_has_requests(&collection.root_folder));
+        }
+
+        app
+    }
+}
+
+impl SendApp {
+    // A fresh app with the given preferences and nothing restored from the app state
+    fn with_settings(settings: AppSettings) -> Self {
+        let default_workspace = Workspace {
+            name: "Default Workspace".to_string(),
+            file_path: None,
+            collections: vec![Collection {
+                id: Uuid::new_v4().to_string(),
+                name: "Default Collection".to_string(),
+                root_folder: Folder {
+                    id: Uuid::new_v4().to_string(),
+                    name: "Root".to_string(),
+                    requests: vec![],
+                    folders: vec![],
+                    auth: RequestAuth::Inherit,
+                },
+                token: None,
+                auth: RequestAuth::None,
+                signer: None,
+            }],
+            environments: vec![Environment {
+                name: "Default".to_string(),
+                variables: vec![],
+                secret_keys: vec![],
+            }],
+            selected_collection: Some(0),
+            selected_folder_path: vec![],
+            selected_request: None,
+            selected_environment: Some(0),
+            settings: WorkspaceSettings::default(),
+            active_session: None,
+        };
+
+        set_language(settings.language);
+        let (monitor_sender, monitor_receiver) = mpsc::channel();
+        let (toast_sender, toast_receiver) = mpsc::channel();
+
+        Self {
+            workspaces: vec![default_workspace],
+            current_workspace: 0,
+            current_request: Self::new_default_request(&settings),
+            current_response: None,
+            is_loading: false,
+            selected_sidebar_item: None,
+            request_tab: RequestTab::Params,
+            raw_body_type: RawBodyType::JSON,
+            response_tab: ResponseTab::Body,
+            show_encoded_body: false,
+            editing_method: false,
+            headers_bulk_text: None,
+            json_body_check: None,
+            settings,
+            history: vec![],
+            pending_request: None,
+            runtime: Runtime::new().unwrap(),
+            http_clients: HashMap::new(),
+            system_proxy: SystemProxy::detect(),
+            last_resolved_request: None,
+            last_request_clients: None,
+            download: None,
+            runner: None,
+            capture: None,
+            captured: vec![],
+            load_test: None,
+            monitor_results: HashMap::new(),
+            monitor_next_run: HashMap::new(),
+            monitors_running: HashSet::new(),
+            monitor_tasks: HashMap::new(),
+            monitor_sender,
+            monitor_receiver,
+            deep_link_receiver: None,
+            #[cfg(feature = "tray")]
+            tray_receiver: None,
+            #[cfg(all(feature = "tray", not(target_os = "linux")))]
+            tray_icon: None,
+            import_receiver: None,
+            response_receiver: None,
+            request_task: None,
+            retry_wait_receiver: None,
+            response_progress: None,
+            response_progress_receiver: None,
+            upload_progress: None,
+            retry_wait: None,
+            request_started: None,
+            request_queue_dialog: false,
+            request_error: None,
+            assertion_results: vec![],
+            capture_results: vec![],
+            oauth_tokens: OAuthTokenCache::default(),
+            response_selection: String::new(),
+            tools_input: String::new(),
+            tools_output: Ok(String::new()),
+            retry_headers: None,
+            auth_dialog: None,
+            signer_dialog: None,
+            new_collection_dialog: false,
+            new_collection_name: String::new(),
+            new_request_dialog: false,
+            new_request_name: String::new(),
+            new_workspace_dialog: false,
+            new_workspace_name: String::new(),
+            new_environment_dialog: false,
+            new_environment_name: String::new(),
+            new_folder_dialog: false,
+            new_folder_name: String::new(),
+            share_export_dialog: false,
+            share_export_scope: ShareExportScope::Workspace,
+            share_export_strip_values: false,
+            settings_dialog: false,
+            workspace_settings_dialog: false,
+            workspace_settings_error: None,
+            runner_dialog: false,
+            capture_dialog: false,
+            capture_port: 8899,
+            capture_host_filter: String::new(),
+            capture_error: None,
+            load_test_dialog: false,
+            load_test_folder: false,
+            load_test_concurrency: 10,
+            load_test_limit: LoadTestLimit::Duration,
+            load_test_duration_secs: 30,
+            load_test_request_count: 100,
+            load_test_error: None,
+            monitors_dialog: false,
+            deep_link_message: None,
+            shortcuts_dialog: false,
+            environment_switcher: false,
+            recording_shortcut: None,
+            command_palette: false,
+            command_palette_query: String::new(),
+            command_palette_selected: 0,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            undo_baseline: None,
+            collections_generation: 0,
+            last_input: Instant::now(),
+            layout: LayoutState::default(),
+            layout_dirty: false,
+            history_unsaved_since: None,
+            toast_sender,
+            toast_receiver,
+            toasts: vec![],
+            tree_focused: false,
+            tree_selection: vec![],
+            tree_cursor: None,
+            tree_rename: None,
+            last_auto_save: None,
+            drafts: HashMap::new(),
+            confirmation: None,
+            show_welcome: true,
+            recent_workspaces: vec![],
+            curl_import: None,
+            curl_import_error: None,
+            request_preview: None,
+            monitors_paused: false,
+            run_records: vec![],
+            metrics_dialog: false,
+            token_manager_dialog: false,
+            sessions_dialog: false,
+            cookie_manager_dialog: false,
+            new_cookie_domain: String::new(),
+            new_header_preset_name: String::new(),
+            metrics_range: MetricsRange::Day,
+            metrics_collection: None,
+            quitting: false,
+        }
+    }
+}
+
+impl eframe::App for SendApp {
+    fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+        self.save_cache();
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Check for response
+        if let Some(receiver) = &self.response_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok(response) => {
+                    for (attempt, record) in response.attempts.iter().enumerate() {
+                        self.record_history(record.status, record.time, attempt as u32 + 1);
+                    }
+                    self.record_history(
+                        response.status,
+                        response.timing.total,
+                        response.attempts.len() as u32 + 1,
+                    );
+                    if let Some(jar) = self
+                        .last_resolved_request
+                        .as_ref()
+                        .and_then(|resolved| resolved.cookie_jar.clone())
+                        && self.keep_cookies(&jar)
+                    {
+                        self.auto_save_workspace();
+                    }
+                    self.assertion_results = self
+                        .pending_request
+                        .as_ref()
+                        .map(|request| {
+                            HeaderAssertion::check_all(&self.resolved_assertions(request), |name| {
+                                response
+                                    .headers
+                                    .iter()
+                                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                                    .map(|(_, value)| value.clone())
+                            })
+                        })
+                        .unwrap_or_default();
+                    self.apply_capture_rules(&response);
+                    self.current_response = Some(response);
+                    self.is_loading = false;
+                    self.layout.single_pane = Pane::Response;
+                }
+                Err(error) => {
+                    self.assertion_results.clear();
+                    self.capture_results.clear();
+                    for (attempt, record) in error.retried.iter().enumerate() {
+                        self.record_history(record.status, record.time, attempt as u32 + 1);
+                    }
+                    self.record_history(0, 0, error.attempts);
+                    self.notify(ToastKind::Error, error.to_string());
+                    self.request_error = Some(error);
+                    self.is_loading = false;
+                    self.layout.single_pane = Pane::Response;
+                }
+            }
+            self.pending_request = None;
+            self.response_receiver = None;
+            self.request_task = None;
+            self.request_started = None;
+            self.retry_wait = None;
+            self.retry_wait_receiver = None;
+            self.response_progress = None;
+            self.response_progress_receiver = None;
+            self.upload_progress = None;
+        }
+        if let Some(receiver) = &self.retry_wait_receiver
+            && let Some(wait) = receiver.try_iter().last()
+        {
+            self.retry_wait = Some(wait);
+        }
+        if let Some(receiver) = &self.response_progress_receiver
+            && let Some(progress) = receiver.try_iter().last()
+        {
+            self.response_progress = Some(progress);
+        }
+        if self.retry_wait.is_some() || self.response_progress.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+        if self.upload_progress.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        // Check for download progress
+        let mut download_declined = false;
+        let mut download_jar = None;
+        if let Some(download) = &mut self.download
+            && download.result.is_none()
+        {
+            loop {
+                let event = match download.receiver.try_recv() {
+                    Ok(event) => event,
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    // The task ended without a result: the request failed, was cancelled, or
+                    // Send and Download never got a body to save
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        if !download.paused && download.result.is_none() {
+                            if download.path.as_os_str().is_empty() {
+                                download_declined = true;
+                            } else {
+                                download.result = Some(Err(tr("download-interrupted")));
+                            }
+                        }
+                        break;
+                    }
+                };
+                match event {
+                    DownloadEvent::Progress { downloaded, total } => {
+                        download.downloaded = downloaded;
+                        download.total = total;
+                    }
+                    DownloadEvent::Paused => download.paused = true,
+                    DownloadEvent::Finished(result) => download.result = Some(result),
+                    DownloadEvent::ChooseFile { suggested, reply } => {
+                        let path = rfd::FileDialog::new()
+                            .set_title(tr("save-response-body-title"))
+                            .set_file_name(&suggested)
+                            .save_file();
+                        match &path {
+                            Some(path) => download.path = path.clone(),
+                            None => download_declined = true,
+                        }
+                        let _ = reply.send(path);
+                    }
+                }
+            }
+            if download.paused || download.result.is_some() {
+                download_jar = download.request.cookie_jar.clone();
+            } else {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+        }
+        if download_declined {
+            self.download = None;
+        }
+        // Cookies the server set while the rest of the response came in
+        if let Some(jar) = download_jar
+            && self.keep_cookies(&jar)
+        {
+            self.auto_save_workspace();
+        }
+
+        // Check for runner progress
+        let mut runner_finished = false;
+        if let Some(runner) = &mut self.runner
+            && !runner.finished
+        {
+            while let Ok(event) = runner.receiver.try_recv() {
+                match event {
+                    RunnerEvent::Started { index, task } => {
+                        if let Some(entry) = runner.results.get_mut(index) {
+                            entry.task = Some(task);
+                        }
+                    }
+                    RunnerEvent::Waiting { index, wait } => {
+                        if let Some(entry) = runner.results.get_mut(index) {
+                            entry.retry_wait = Some(wait);
+                        }
+                    }
+                    RunnerEvent::Completed {
+                        index,
+                        result,
+                        time,
+                        failures,
+                    } => {
+                        if let Some(entry) = runner.results.get_mut(index)
+                            && entry.outcome.is_none()
+                        {
+                            let status = *result.as_ref().unwrap_or(&0);
+                            entry.outcome = Some(result);
+                            entry.time = time;
+                            entry.failures = failures;
+                            self.run_records.push(RunRecord {
+                                timestamp: chrono::Local::now().timestamp(),
+                                source: RunSource::Runner,
+                                collection: runner.collection.clone(),
+                                endpoint: Self::endpoint_label(&entry.method, &entry.url),
+                                status,
+                                time,
+                                passed: entry.passed(),
+                            });
+                        }
+                    }
+                    RunnerEvent::Finished => {
+                        runner.finished = true;
+                        runner_finished = true;
+                    }
+                }
+            }
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+        if runner_finished {
+            self.trim_run_records();
+            self.save_cache();
+            if let Some(jar) = self.runner.as_ref().map(|runner| runner.cookie_jar.clone())
+                && self.keep_cookies(&jar)
+            {
+                self.auto_save_workspace();
+            }
+        }
+
+        // Check for load test progress
+        if let Some(load_test) = &mut self.load_test
+            && load_test.finished_at.is_none()
+        {
+            while let Ok(event) = load_test.receiver.try_recv() {
+                match event {
+                    LoadTestEvent::Completed {
+                        elapsed_ms,
+                        latency,
+                        failed,
+                    } => {
+                        load_test.latencies.push(latency);
+                        if failed {
+                            load_test.errors += 1;
+                        }
+                        let second = (elapsed_ms / 1000) as usize;
+                        if load_test.per_second.len() <= second {
+                            load_test.per_second.resize(second + 1, 0);
+                        }
+                        load_test.per_second[second] += 1;
+                    }
+                    LoadTestEvent::Finished => load_test.finished_at = Some(Instant::now()),
+                }
+            }
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        if self.recording_shortcut.is_none() {
+            self.handle_shortcuts(ctx);
+        }
+
+        // Links forwarded by other instances
+        let links: Vec<String> = self
+            .deep_link_receiver
+            .as_ref()
+            .map(|receiver| receiver.try_iter().collect())
+            .unwrap_or_default();
+        for link in links {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            self.handle_deep_link(&link);
+        }
+        #[cfg(feature = "tray")]
+        self.handle_tray_events(ctx);
+
+        // Check for collections imported from a link
+        if let Some(receiver) = &self.import_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok(collection) => {
+                    let workspace = self.current_workspace_mut();
+                    workspace.collections.push(collection);
+                    workspace.selected_collection = Some(workspace.collections.len() - 1);
+                    workspace.selected_folder_path = vec![];
+                    workspace.selected_request = None;
+                    self.selected_sidebar_item = Some(SidebarItem::Collections);
+                    self.collections_generation += 1;
+                    self.auto_save_workspace();
+                    self.save_cache();
+                    self.notify_imported(self.current_workspace().collections.last());
+                }
+                Err(e) => self.notify(ToastKind::Error, e),
+            }
+            self.import_receiver = None;
+        }
+
+        // Run due monitors and collect their results
+        self.poll_monitors(ctx);
+        self.handle_close_request(ctx);
+
+        // The proxy forwards to the network, which offline mode blocks
+        if self.settings.offline_mode && self.capture.is_some() {
+            self.stop_capture();
+        }
+        // Check for captured traffic
+        if let Some(capture) = &self.capture {
+            let exchanges: Vec<CapturedExchange> = capture.receiver.try_iter().collect();
+            for exchange in exchanges {
+                self.record_captured_exchange(exchange);
+            }
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+
+        // Top panel
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                ui.menu_button(tr("file"), |ui| {
+                    if ui.button(tr("new-workspace")).clicked() {
+                        self.new_workspace_dialog = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("new-collection")).clicked() {
+                        self.new_collection_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("new-folder")).clicked() {
+                        self.new_folder_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("new-request")).clicked() {
+                        self.open_new_request_dialog();
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("new-environment")).clicked() {
+                        self.new_environment_dialog = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("save-workspace")).clicked() {
+                        self.save_to_file();
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("load-workspace")).clicked() {
+                        self.load_from_file();
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("export-collection")).clicked() {
+                        self.export_collection();
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("import-collection")).clicked() {
+                        self.import_collection();
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("import-curl-menu")).clicked() {
+                        self.curl_import = Some(String::new());
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("export-for-sharing-menu")).clicked() {
+                        self.share_export_dialog = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("run-collection")).clicked() {
+                        self.runner_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("monitors-menu")).clicked() {
+                        self.monitors_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("run-metrics-menu")).clicked() {
+                        self.metrics_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("token-manager-menu")).clicked() {
+                        self.token_manager_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("sessions-menu")).clicked() {
+                        self.sessions_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("cookie-manager-menu")).clicked() {
+                        self.cookie_manager_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("load-test-menu")).clicked() {
+                        self.load_test_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("capture-traffic-menu")).clicked() {
+                        self.capture_dialog = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("workspace-settings")).clicked() {
+                        self.workspace_settings_dialog = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("settings-menu")).clicked() {
+                        self.settings_dialog = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("quit")).clicked() {
+                        self.quitting = true;
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                        ui.close_menu();
+                    }
+                });
+                ui.menu_button(tr("edit"), |ui| {
+                    if ui
+                        .add_enabled(
+                            !self.undo_stack.is_empty() || self.has_pending_edit(),
+                            egui::Button::new(tr("undo")),
+                        )
+                        .clicked()
+                    {
+                        self.undo();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(!self.redo_stack.is_empty(), egui::Button::new(tr("redo")))
+                        .clicked()
+                    {
+                        self.redo();
+                        ui.close_menu();
+                    }
+                });
+                ui.menu_button(tr("help"), |ui| {
+                    if ui.button(tr("keyboard-shortcuts")).clicked() {
+                        self.shortcuts_dialog = true;
+                        ui.close_menu();
+                    }
+                });
+                ui.menu_button(tr("view"), |ui| {
+                    if ui.button(tr("welcome-screen")).clicked() {
+                        self.show_welcome = true;
+                        ui.close_menu();
+                    }
+                    ui.menu_button(tr("layout"), |ui| {
+                        for (mode, label) in [
+                            (PanelLayout::Stacked, tr("layout-stacked")),
+                            (PanelLayout::SideBySide, tr("layout-side-by-side")),
+                            (PanelLayout::Single, tr("layout-single")),
+                        ] {
+                            if ui.radio_value(&mut self.layout.mode, mode, label).clicked() {
+                                self.save_cache();
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    ui.separator();
+                    if ui
+                        .checkbox(&mut self.layout.show_mini_sidebar, tr("mini-sidebar"))
+                        .changed()
+                    {
+                        self.save_cache();
+                    }
+                    if ui
+                        .checkbox(&mut self.layout.sidebar_pinned, tr("pin-sidebar-open"))
+                        .changed()
+                    {
+                        if self.layout.sidebar_pinned && self.selected_sidebar_item.is_none() {
+                            self.selected_sidebar_item = Some(SidebarItem::Collections);
+                        }
+                        self.save_cache();
+                    }
+                    ui.separator();
+                    if ui.button(tr("collections")).clicked() {
+                        self.toggle_sidebar(SidebarItem::Collections);
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("environment")).clicked() {
+                        self.toggle_sidebar(SidebarItem::Environment);
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("history")).clicked() {
+                        self.toggle_sidebar(SidebarItem::History);
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("tools")).clicked() {
+                        self.toggle_sidebar(SidebarItem::Tools);
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("request-queue")).clicked() {
+                        self.request_queue_dialog = true;
+                        ui.close_menu();
+                    }
+                });
+
+                ui.separator();
+
+                // Workspace tabs
+                ui.horizontal(|ui| {
+                    ui.label(tr("workspaces"));
+                    let can_delete = self.workspaces.len() > 1;
+                    let mut delete_workspace = None;
+                    for (idx, workspace) in self.workspaces.iter().enumerate() {
+                        let selected = idx == self.current_workspace;
+                        let response = ui.selectable_label(selected, &workspace.name);
+                        if response.clicked() {
+                            self.current_workspace = idx;
+                            self.save_cache();
+                        }
+                        response.context_menu(|ui| {
+                            if ui
+                                .add_enabled(can_delete, egui::Button::new(tr("delete")))
+                                .clicked()
+                            {
+                                delete_workspace = Some((idx, workspace.name.clone()));
+                                ui.close_menu();
+                            }
+                        });
+                    }
+                    if let Some((idx, name)) = delete_workspace {
+                        let mut args = FluentArgs::new();
+                        args.set("name", name);
+                        self.confirm(
+                            ConfirmKind::DeleteWorkspace,
+                            tr_args("confirm-delete-workspace", &args),
+                            PendingAction::DeleteWorkspace(idx),
+                        );
+                    }
+                });
+            });
+        });
+
+        // Status bar, added before the side panels so it spans the full width
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            self.draw_status_bar(ui);
+        });
+
+        // Mini sidebar
+        egui::SidePanel::left("mini_sidebar")
+            .exact_width(50.0)
+            .resizable(false)
+            .show_animated(ctx, self.layout.show_mini_sidebar, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+
+                    // Collections button
+                    let collections_selected =
+                        self.selected_sidebar_item == Some(SidebarItem::Collections);
+                    let collections_button = egui::Button::new("📁")
+                        .min_size(egui::Vec2::new(40.0, 40.0))
+                        .fill(if collections_selected {
+                            egui::Color32::from_gray(80)
+                        } else {
+                            egui::Color32::TRANSPARENT
+                        });
+
+                    if ui.add(collections_button).clicked() {
+                        self.toggle_sidebar(SidebarItem::Collections);
+                    }
+
+                    ui.add_space(5.0);
+
+                    // Environment button
+                    let environment_selected =
+                        self.selected_sidebar_item == Some(SidebarItem::Environment);
+                    let environment_button = egui::Button::new("🌍")
+                        .min_size(egui::Vec2::new(40.0, 40.0))
+                        .fill(if environment_selected {
+                            egui::Color32::from_gray(80)
+                        } else {
+                            egui::Color32::TRANSPARENT
+                        });
+
+                    if ui.add(environment_button).clicked() {
+                        self.toggle_sidebar(SidebarItem::Environment);
+                    }
+
+                    ui.add_space(5.0);
+
+                    // History button
+                    let history_selected = self.selected_sidebar_item == Some(SidebarItem::History);
+                    let history_button = egui::Button::new("🕘")
+                        .min_size(egui::Vec2::new(40.0, 40.0))
+                        .fill(if history_selected {
+                            egui::Color32::from_gray(80)
+                        } else {
+                            egui::Color32::TRANSPARENT
+                        });
+
+                    if ui.add(history_button).clicked() {
+                        self.toggle_sidebar(SidebarItem::History);
+                    }
+
+                    ui.add_space(5.0);
+
+                    // Tools button
+                    let tools_selected = self.selected_sidebar_item == Some(SidebarItem::Tools);
+                    let tools_button = egui::Button::new("🧰")
+                        .min_size(egui::Vec2::new(40.0, 40.0))
+                        .fill(if tools_selected {
+                            egui::Color32::from_gray(80)
+                        } else {
+                            egui::Color32::TRANSPARENT
+                        });
+
+                    if ui.add(tools_button).clicked() {
+                        self.toggle_sidebar(SidebarItem::Tools);
+                    }
+                });
+
+                ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+                    ui.add_space(5.0);
+                    if ui
+                        .button("«")
+                        .on_hover_text(tr("hide-the-mini-sidebar-view-menu"))
+                        .clicked()
+                    {
+                        self.layout.show_mini_sidebar = false;
+                        self.save_cache();
+                    }
+                });
+            });
+
+        // Expandable sidebar panel
+        if let Some(selected_item) = self.selected_sidebar_item.clone() {
+            let default_width = self
+                .layout
+                .sidebar_widths
+                .get(&selected_item)
+                .copied()
+                .unwrap_or(250.0);
+            // Separate panel ids so egui keeps a width per item
+            let sidebar =
+                egui::SidePanel::left(egui::Id::new(("expandable_sidebar", selected_item.clone())))
+                    .min_width(250.0)
+                    .max_width(400.0)
+                    .default_width(default_width)
+                    .show(ctx, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.heading(tr(match selected_item {
+                                SidebarItem::Collections => "collections",
+                                SidebarItem::Environment => "environment",
+                                SidebarItem::History => "history",
+                                SidebarItem::Tools => "tools",
+                            }));
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if !self.layout.sidebar_pinned && ui.small_button("✖").clicked()
+                                    {
+                                        self.selected_sidebar_item = None;
+                                        self.save_cache();
+                                    }
+                                    let pin = RichText::new("📌");
+                                    let pin = if self.layout.sidebar_pinned {
+                                        pin.strong()
+                                    } else {
+                                        pin.weak()
+                                    };
+                                    if ui
+                                        .selectable_label(self.layout.sidebar_pinned, pin)
+                                        .on_hover_text(tr("keep-the-sidebar-open"))
+                                        .clicked()
+                                    {
+                                        self.layout.sidebar_pinned = !self.layout.sidebar_pinned;
+                                        self.save_cache();
+                                    }
+                                },
+                            );
+                        });
+                        ui.separator();
+                        match selected_item {
+                            SidebarItem::Collections => self.draw_collections_panel(ui),
+                            SidebarItem::Environment => self.draw_environment_panel(ui),
+                            SidebarItem::History => self.draw_history_panel(ui),
+                            SidebarItem::Tools => self.draw_tools_panel(ui),
+                        }
+                    });
+            let width = sidebar.response.rect.width();
+            if self
+                .layout
+                .sidebar_widths
+                .get(&selected_item)
+                .is_none_or(|stored| (stored - width).abs() > 0.5)
+            {
+                self.layout.sidebar_widths.insert(selected_item, width);
+                self.layout_dirty = true;
+            }
+        }
+
+        // Central panel
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if self.show_welcome {
+                self.draw_welcome(ui);
+            } else {
+                self.draw_panes(ui);
+            }
+        });
+        // Persist the splitter position once a drag ends
+        if self.layout_dirty && !ctx.input(|input| input.pointer.any_down()) {
+            self.layout_dirty = false;
+            self.save_cache();
+        }
+        if let Some(since) = self.history_unsaved_since {
+            let delay = Duration::from_secs(5);
+            if since.elapsed() >= delay {
+                self.history_unsaved_since = None;
+                self.save_cache();
+            } else {
+                ctx.request_repaint_after(delay.saturating_sub(since.elapsed()));
+            }
+        }
+
+        // Dialogs
+        self.draw_dialogs(ctx);
+        self.draw_toasts(ctx);
+
+        self.track_undo(ctx);
+    }
+}
+
+impl TlsVersion {
+    // The native-tls backend rejects 1.3 as a min or max, so it isn't offered. Settings saved
+    // with it still load; see min_version and max_version.
+    const OPTIONS: [(TlsVersion, &'static str); 4] = [
+        (TlsVersion::Any, "Any"),
+        (TlsVersion::Tls1_0, "TLS 1.0"),
+        (TlsVersion::Tls1_1, "TLS 1.1"),
+        (TlsVersion::Tls1_2, "TLS 1.2"),
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            TlsVersion::Any => "Any",
+            TlsVersion::Tls1_0 => "TLS 1.0",
+            TlsVersion::Tls1_1 => "TLS 1.1",
+            TlsVersion::Tls1_2 => "TLS 1.2",
+            TlsVersion::Tls1_3 => "TLS 1.3",
+        }
+    }
+
+    // A 1.3 minimum is the strictest native-tls can require: 1.2
+    fn min_version(&self) -> Option<reqwest::tls::Version> {
+        match self {
+            TlsVersion::Any => None,
+            TlsVersion::Tls1_0 => Some(reqwest::tls::Version::TLS_1_0),
+            TlsVersion::Tls1_1 => Some(reqwest::tls::Version::TLS_1_1),
+            TlsVersion::Tls1_2 | TlsVersion::Tls1_3 => Some(reqwest::tls::Version::TLS_1_2),
+        }
+    }
+
+    // 1.3 is the newest version anyway, so a 1.3 maximum is no maximum
+    fn max_version(&self) -> Option<reqwest::tls::Version> {
+        match self {
+            TlsVersion::Tls1_3 => None,
+            version => version.min_version(),
+        }
+    }
+}
+
+const DEEP_LINK_PORT: u16 = 47321;
+
+// Chosen from the tray icon's menu; Failed reports a tray that couldn't be created
+#[cfg(feature = "tray")]
+#[derive(Debug)]
+enum TrayEvent {
+    Open,
+    PauseMonitors,
+    ResumeMonitors,
+    Quit,
+    Failed(String),
+}
+
+#[cfg(feature = "tray")]
+impl TrayEvent {
+    fn from_menu_id(id: &str) -> Option<Self> {
+        match id {
+            "open" => Some(TrayEvent::Open),
+            "pause-monitors" => Some(TrayEvent::PauseMonitors),
+            "resume-monitors" => Some(TrayEvent::ResumeMonitors),
+            "quit" => Some(TrayEvent::Quit),
+            _ => None,
+        }
+    }
+}
+
+// Offered in the method picker; anything else can be typed in
+const HTTP_METHODS: [&str; 7] = ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"];
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36";
+
+impl ShortcutAction {
+    // Redo comes before Undo so Ctrl+Shift+Z isn't taken by Ctrl+Z
+    const ALL: [ShortcutAction; 9] = [
+        ShortcutAction::Redo,
+        ShortcutAction::Undo,
+        ShortcutAction::Send,
+        ShortcutAction::Save,
+        ShortcutAction::NewRequest,
+        ShortcutAction::SwitchEnvironment,
+        ShortcutAction::CloseRequest,
+        ShortcutAction::ShowShortcuts,
+        ShortcutAction::CommandPalette,
+    ];
+
+    fn label(&self) -> String {
+        tr(match self {
+            ShortcutAction::Send => "shortcut-send",
+            ShortcutAction::Save => "shortcut-save",
+            ShortcutAction::NewRequest => "shortcut-new-request",
+            ShortcutAction::SwitchEnvironment => "shortcut-switch-environment",
+            ShortcutAction::CloseRequest => "shortcut-close-request",
+            ShortcutAction::ShowShortcuts => "shortcut-show-shortcuts",
+            ShortcutAction::CommandPalette => "shortcut-command-palette",
+            ShortcutAction::Undo => "undo",
+            ShortcutAction::Redo => "redo",
+        })
+    }
+
+    fn default_binding(&self) -> KeyBinding {
+        let key = match self {
+            ShortcutAction::Send => egui::Key::Enter,
+            ShortcutAction::Save => egui::Key::S,
+            ShortcutAction::NewRequest => egui::Key::N,
+            ShortcutAction::SwitchEnvironment => egui::Key::E,
+            ShortcutAction::CloseRequest => egui::Key::W,
+            ShortcutAction::ShowShortcuts => egui::Key::Slash,
+            ShortcutAction::CommandPalette => egui::Key::P,
+            ShortcutAction::Undo | ShortcutAction::Redo => egui::Key::Z,
+        };
+        KeyBinding {
+            command: true,
+            shift: matches!(self, ShortcutAction::CommandPalette | ShortcutAction::Redo),
+            alt: false,
+            key: key.name().to_string(),
+        }
+    }
+}
+
+impl KeyBinding {
+    fn to_shortcut(&self) -> Option<egui::KeyboardShortcut> {
+        let key = egui::Key::from_name(&self.key)?;
+        let mut modifiers = egui::Modifiers::NONE;
+        if self.command {
+            modifiers = modifiers | egui::Modifiers::COMMAND;
+        }
+        if self.shift {
+            modifiers = modifiers | egui::Modifiers::SHIFT;
+        }
+        if self.alt {
+            modifiers = modifiers | egui::Modifiers::ALT;
+        }
+        Some(egui::KeyboardShortcut::new(modifiers, key))
+    }
+
+    fn label(&self) -> String {
+        let mut label = String::new();
+        if self.command {
+            label.push_str(if cfg!(target_os = "macos") {
+                "Cmd+"
+            } else {
+                "Ctrl+"
+            });
+        }
+        if self.shift {
+            label.push_str("Shift+");
+        }
+        if self.alt {
+            label.push_str("Alt+");
+        }
+        label.push_str(&self.key);
+        label
+    }
+}
+
+impl RequestErrorKind {
+    fn title(&self) -> String {
+        tr(match self {
+            RequestErrorKind::Dns => "error-dns",
+            RequestErrorKind::ConnectionRefused => "error-connection-refused",
+            RequestErrorKind::ConnectTimeout => "error-connect-timeout",
+            RequestErrorKind::Connect => "error-connect",
+            RequestErrorKind::Timeout => "error-timeout",
+            RequestErrorKind::Tls => "error-tls",
+            RequestErrorKind::CertificatePin => "error-certificate-pin",
+            RequestErrorKind::TooManyRedirects => "error-too-many-redirects",
+            RequestErrorKind::HttpVersion => "error-http-version",
+            RequestErrorKind::BodyDecode => "error-body-decode",
+            RequestErrorKind::InvalidRequest => "error-invalid-request",
+            RequestErrorKind::Client => "error-client",
+            RequestErrorKind::Offline => "error-offline",
+            RequestErrorKind::Other => "error-other",
+        })
+    }
+
+    // Catalog ids of the hints shown under the error
+    fn suggestions(&self) -> &'static [&'static str] {
+        match self {
+            RequestErrorKind::Dns => &["error-dns-hint-1", "error-dns-hint-2", "error-dns-hint-3"],
+            RequestErrorKind::ConnectionRefused => &[
+                "error-connection-refused-hint-1",
+                "error-connection-refused-hint-2",
+            ],
+            RequestErrorKind::ConnectTimeout | RequestErrorKind::Connect => &[
+                "error-connect-timeout-hint-1",
+                "error-connect-timeout-hint-2",
+                "error-connect-timeout-hint-3",
+            ],
+            RequestErrorKind::Timeout => &["error-timeout-hint-1", "error-timeout-hint-2"],
+            RequestErrorKind::Tls => &["error-tls-hint-1", "error-tls-hint-2", "error-tls-hint-3"],
+            RequestErrorKind::CertificatePin => &[
+                "error-certificate-pin-hint-1",
+                "error-certificate-pin-hint-2",
+            ],
+            RequestErrorKind::TooManyRedirects => &[
+                "error-too-many-redirects-hint-1",
+                "error-too-many-redirects-hint-2",
+            ],
+            RequestErrorKind::HttpVersion => {
+                &["error-http-version-hint-1", "error-http-version-hint-2"]
+            }
+            RequestErrorKind::BodyDecode => {
+                &["error-body-decode-hint-1", "error-body-decode-hint-2"]
+            }
+            RequestErrorKind::InvalidRequest => &[
+                "error-invalid-request-hint-1",
+                "error-invalid-request-hint-2",
+            ],
+            RequestErrorKind::Client => &["error-client-hint-1"],
+            RequestErrorKind::Offline => &["error-offline-hint-1"],
+            RequestErrorKind::Other => &[],
+        }
+    }
+}
+
+impl RequestError {
+    fn new(kind: RequestErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            causes: vec![message.into()],
+            attempts: 1,
+            retried: vec![],
+        }
+    }
+
+    fn from_reqwest(error: &reqwest::Error, attempts: u32) -> Self {
+        let mut causes = vec![error.to_string()];
+        let mut source = std::error::Error::source(error);
+        while let Some(cause) = source {
+            let message = cause.to_string();
+            if causes.last() != Some(&message) {
+                causes.push(message);
+            }
+            source = cause.source();
+        }
+        let chain = causes.join("\n").to_lowercase();
+        let is_tls = ["certificate", "tls", "ssl", "handshake"]
+            .iter()
+            .any(|needle| chain.contains(needle));
+        let kind = if error.is_redirect() {
+            RequestErrorKind::TooManyRedirects
+        } else if error.is_timeout() && error.is_connect() {
+            RequestErrorKind::ConnectTimeout
+        } else if error.is_timeout() {
+            RequestErrorKind::Timeout
+        } else if error.is_builder() {
+            RequestErrorKind::InvalidRequest
+        } else if error.is_decode() || error.is_body() {
+            RequestErrorKind::BodyDecode
+        } else if chain.contains("unsupportedversion") {
+            RequestErrorKind::HttpVersion
+        } else if chain.contains("dns error") || chain.contains("failed to lookup address") {
+            RequestErrorKind::Dns
+        } else if is_tls {
+            RequestErrorKind::Tls
+        } else if chain.contains("connection refused") {
+            RequestErrorKind::ConnectionRefused
+        } else if error.is_connect() {
+            RequestErrorKind::Connect
+        } else {
+            RequestErrorKind::Other
+        };
+        Self {
+            kind,
+            causes,
+            attempts,
+            retried: vec![],
+        }
+    }
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind.title())?;
+        if let Some(cause) = self.causes.last() {
+            write!(f, ": {}", cause)?;
+        }
+        Ok(())
+    }
+}
+
+impl Language {
+    const ALL: [Language; 3] = [Language::System, Language::English, Language::German];
+
+    // Native names, so the list stays readable whatever language is active
+    fn name(&self) -> String {
+        match self {
+            Language::System => tr("language-system"),
+            Language::English => "English".to_string(),
+            Language::German => "Deutsch".to_string(),
+        }
+    }
+
+    fn resolve(&self) -> Language {
+        match self {
+            Language::System => {
+                let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+                    .iter()
+                    .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+                    .unwrap_or_default();
+                if locale.starts_with("de") {
+                    Language::German
+                } else {
+                    Language::English
+                }
+            }
+            language => *language,
+        }
+    }
+
+    fn catalog(&self) -> (&'static str, &'static str) {
+        match self.resolve() {
+            Language::German => ("de", include_str!("../locales/de/main.ftl")),
+            _ => ("en-US", include_str!("../locales/en-US/main.ftl")),
+        }
+    }
+}
+
+impl Localizer {
+    fn new(language: Language) -> Self {
+        let mut bundles = vec![Self::bundle(language.catalog())];
+        if language.resolve() != Language::English {
+            bundles.push(Self::bundle(Language::English.catalog()));
+        }
+        Self { bundles }
+    }
+
+    fn bundle((code, source): (&str, &str)) -> FluentBundle<FluentResource> {
+        let language_id: LanguageIdentifier = code.parse().unwrap_or_default();
+        let mut bundle = FluentBundle::new_concurrent(vec![language_id]);
+        // egui draws the Unicode isolation marks fluent wraps arguments in
+        bundle.set_use_isolating(false);
+        let resource =
+            FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, _)| resource);
+        let _ = bundle.add_resource(resource);
+        bundle
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in &self.bundles {
+            if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+                let mut errors = vec![];
+                return bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned();
+            }
+        }
+        id.to_string()
+    }
+}
+
+impl HttpVersion {
+    const OPTIONS: [(HttpVersion, &'static str); 4] = [
+        (HttpVersion::Auto, "Auto"),
+        (HttpVersion::Http1, "HTTP/1.1"),
+        (HttpVersion::Http2, "HTTP/2 (ALPN)"),
+        (HttpVersion::Http2PriorKnowledge, "HTTP/2 (prior knowledge)"),
+    ];
+
+    fn label(&self) -> &'static str {
+        Self::OPTIONS
+            .iter()
+            .find(|(option, _)| option == self)
+            .map_or("Auto", |(_, label)| label)
+    }
+
+    fn negotiated(version: reqwest::Version) -> String {
+        match version {
+            reqwest::Version::HTTP_09 => "HTTP/0.9".to_string(),
+            reqwest::Version::HTTP_10 => "HTTP/1.0".to_string(),
+            reqwest::Version::HTTP_11 => "HTTP/1.1".to_string(),
+            reqwest::Version::HTTP_2 => "HTTP/2".to_string(),
+            reqwest::Version::HTTP_3 => "HTTP/3".to_string(),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+impl AcceptEncoding {
+    const OPTIONS: [(AcceptEncoding, &'static str); 6] = [
+        (AcceptEncoding::Unset, ""),
+        (AcceptEncoding::GzipDeflate, "gzip, deflate"),
+        (AcceptEncoding::Gzip, "gzip"),
+        (AcceptEncoding::Deflate, "deflate"),
+        (AcceptEncoding::Brotli, "br"),
+        (AcceptEncoding::Identity, "identity"),
+    ];
+
+    fn header_value(&self) -> Option<&'static str> {
+        match self {
+            AcceptEncoding::Unset => None,
+            other => Self::OPTIONS
+                .iter()
+                .find(|(option, _)| option == other)
+                .map(|(_, value)| *value),
+        }
+    }
+
+    fn label(&self) -> String {
+        self.header_value()
+            .map_or_else(|| tr("accept-encoding-not-sent"), str::to_string)
+    }
+
+    // None when the coding isn't one we can undo
+    fn decode(content_encoding: &str, bytes: &[u8], limit: usize) -> Option<Vec<u8>> {
+        use std::io::Read;
+        let limit = if limit == 0 { u64::MAX } else { limit as u64 };
+        let mut decoded = Vec::new();
+        match content_encoding.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => flate2::read::MultiGzDecoder::new(bytes)
+                .take(limit)
+                .read_to_end(&mut decoded)
+                .ok()?,
+            // Meant to be zlib-wrapped, but some servers send raw deflate
+            "deflate" => match flate2::read::ZlibDecoder::new(bytes)
+                .take(limit)
+                .read_to_end(&mut decoded)
+            {
+                Ok(size) => size,
+                Err(_) => {
+                    decoded.clear();
+                    flate2::read::DeflateDecoder::new(bytes)
+                        .take(limit)
+                        .read_to_end(&mut decoded)
+                        .ok()?
+                }
+            },
+            "br" => brotli_decompressor::Decompressor::new(bytes, 4096)
+                .take(limit)
+                .read_to_end(&mut decoded)
+                .ok()?,
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
+
+impl ExpectContinue {
+    const OPTIONS: [(ExpectContinue, &'static str); 3] = [
+        (ExpectContinue::Headers, "expect-continue-headers"),
+        (ExpectContinue::Send, "expect-continue-send"),
+        (ExpectContinue::Never, "expect-continue-never"),
+    ];
+
+    fn label(&self) -> String {
+        tr(Self::OPTIONS
+            .iter()
+            .find(|(option, _)| option == self)
+            .map_or("expect-continue-headers", |(_, label)| label))
+    }
+}
+
+impl IpPreference {
+    const OPTIONS: [(IpPreference, &'static str); 5] = [
+        (IpPreference::Auto, "ip-preference-auto"),
+        (IpPreference::PreferIpv4, "ip-preference-prefer-ipv4"),
+        (IpPreference::PreferIpv6, "ip-preference-prefer-ipv6"),
+        (IpPreference::Ipv4Only, "ip-preference-ipv4-only"),
+        (IpPreference::Ipv6Only, "ip-preference-ipv6-only"),
+    ];
+
+    fn label(&self) -> String {
+        tr(Self::OPTIONS
+            .iter()
+            .find(|(option, _)| option == self)
+            .map_or("ip-preference-auto", |(_, label)| label))
+    }
+}
+
+impl RetryPolicy {
+    fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        match retry_after {
+            Some(retry_after) => retry_after.min(Duration::from_secs(self.max_retry_after_secs)),
+            None => Duration::from_millis(self.backoff_ms.saturating_mul(1 << attempt.min(16))),
+        }
+    }
+
+    // How long to wait before retrying this attempt, or None to keep its result
+    fn retry_delay(
+        &self,
+        attempt: u32,
+        send_result: &Result<reqwest::Response, reqwest::Error>,
+    ) -> Option<Duration> {
+        let can_retry = attempt < self.max_retries;
+        match send_result {
+            Ok(response) => {
+                let status = response.status();
+                let retryable = (status.as_u16() == 429 && self.retry_on_429)
+                    || (status.is_server_error() && self.retry_on_5xx);
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(SendApp::parse_retry_after)
+                    .filter(|_| self.honor_retry_after);
+                (retryable && can_retry).then(|| self.delay_for(attempt, retry_after))
+            }
+            Err(e) => {
+                let retryable =
+                    (e.is_connect() || e.is_timeout()) && self.retry_on_connection_error;
+                (retryable && can_retry).then(|| self.delay_for(attempt, None))
+            }
+        }
+    }
+
+    fn wait(
+        &self,
+        attempt: u32,
+        delay: Duration,
+        send_result: &Result<reqwest::Response, reqwest::Error>,
+    ) -> RetryWait {
+        RetryWait {
+            until: Instant::now() + delay,
+            status: send_result
+                .as_ref()
+                .map_or(0, |response| response.status().as_u16()),
+            attempt: attempt + 1,
+            max_retries: self.max_retries,
+        }
+    }
+}
+
+impl SavedExample {
+    fn to_response(&self) -> HttpResponse {
+        HttpResponse {
+            status: self.status,
+            status_text: self.status_text.clone(),
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+            timing: ResponseTiming::default(),
+            body_size: self.body.len(),
+            headers_size: self
+                .headers
+                .iter()
+                .map(|(key, value)| key.len() + value.len() + 4)
+                .sum(),
+            tls_verification_disabled: false,
+            attempts: vec![],
+            remote_addr: None,
+            truncated_bytes: None,
+            from_example: true,
+            set_cookies: vec![],
+            redirects: RedirectChain::default(),
+            version: String::new(),
+            connection_reused: None,
+            content_encoding: None,
+            encoded_body: None,
+            spooled: None,
+            reformatted_body: None,
+        }
+    }
+}
+
+impl RawBodyType {
+    fn get_content_type(&self) -> &'static str {
+        match self {
+            RawBodyType::Text => "text/plain",
+            RawBodyType::JavaScript => "application/javascript",
+            RawBodyType::JSON => "application/json",
+            RawBodyType::HTML => "text/html",
+            RawBodyType::XML => "application/xml",
+        }
+    }
+}
+
+impl SendApp {
+    fn get_cache_dir() -> std::path::PathBuf {
+        let mut cache_dir = dirs::cache_dir().unwrap_or_else(|| std::env::current_dir().unwrap());
+        cache_dir.push("send");
+        cache_dir
+    }
+
+    fn get_cache_file_path() -> std::path::PathBuf {
+        let mut cache_path = Self::get_cache_dir();
+        cache_path.push("send.json");
+        cache_path
+    }
+
+    fn get_config_dir() -> std::path::PathBuf {
+        let mut config_dir = dirs::config_dir().unwrap_or_else(|| std::env::current_dir().unwrap());
+        config_dir.push("send");
+        config_dir
+    }
+
+    fn get_settings_file_path() -> std::path::PathBuf {
+        let mut settings_path = Self::get_config_dir();
+        settings_path.push("settings.json");
+        settings_path
+    }
+
+    fn notify(&self, kind: ToastKind, message: impl Into<String>) {
+        let _ = self.toast_sender.send(Toast {
+            kind,
+            message: message.into(),
+            shown_at: Instant::now(),
+        });
+    }
+
+    fn notify_imported(&self, collection: Option<&Collection>) {
+        if let Some(collection) = collection {
+            let mut requests = vec![];
+            Self::collect_folder_requests(&collection.root_folder, &mut requests);
+            let mut args = FluentArgs::new();
+            args.set("name", collection.name.clone());
+            args.set("count", requests.len());
+            self.notify(ToastKind::Success, tr_args("collection-imported", &args));
+        }
+    }
+
+    // Writes a file, creating its directory; failures become an error toast. `what` is the
+    // catalog id naming the kind of file.
+    fn write_file(&self, path: &std::path::Path, contents: &str, what: &str) -> bool {
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(path, contents));
+        if let Err(e) = &result {
+            let mut args = FluentArgs::new();
+            args.set("what", tr(what));
+            args.set("path", path.display().to_string());
+            args.set("error", e.to_string());
+            self.notify(ToastKind::Error, tr_args("save-file-failed", &args));
+        }
+        result.is_ok()
+    }
+
+    fn confirm(&mut self, kind: ConfirmKind, message: String, action: PendingAction) {
+        if self.settings.skip_confirmations.contains(&kind) {
+            self.run_pending_action(action);
+        } else {
+            self.confirmation = Some(Confirmation {
+                kind,
+                message,
+                action,
+                dont_ask_again: false,
+            });
+        }
+    }
+
+    // Writes an export, asking before replacing a file that already exists
+    fn export_file(
+        &mut self,
+        path: std::path::PathBuf,
+        contents: String,
+        what: &'static str,
+        success: String,
+    ) {
+        let action = PendingAction::WriteFile {
+            path: path.clone(),
+            contents,
+            what,
+            success,
+        };
+        if path.exists() {
+            let mut args = FluentArgs::new();
+            args.set("path", path.display().to_string());
+            self.confirm(
+                ConfirmKind::OverwriteFile,
+                tr_args("confirm-overwrite-file", &args),
+                action,
+            );
+        } else {
+            self.run_pending_action(action);
+        }
+    }
+
+    fn run_pending_action(&mut self, action: PendingAction) {
+        match action {
+            PendingAction::DeleteTreeNode(node) => self.delete_tree_node(&node),
+            PendingAction::DeleteEnvironment(idx) => {
+                let workspace = self.current_workspace_mut();
+                if idx >= workspace.environments.len() {
+                    return;
+                }
+                workspace.environments.remove(idx);
+                workspace.selected_environment = match workspace.selected_environment {
+                    Some(selected) if selected == idx => None,
+                    Some(selected) if selected > idx => Some(selected - 1),
+                    selected => selected,
+                };
+                self.auto_save_workspace();
+                self.save_cache();
+            }
+            PendingAction::ClearVariables(idx) => {
+                if let Some(env) = self.current_workspace_mut().environments.get_mut(idx) {
+                    env.variables.clear();
+                    env.secret_keys.clear();
+                    self.auto_save_workspace();
+                    self.save_cache();
+                }
+            }
+            PendingAction::DeleteWorkspace(idx) => {
+                if idx >= self.workspaces.len() || self.workspaces.len() == 1 {
+                    return;
+                }
+                self.workspaces.remove(idx);
+                self.http_clients.clear();
+                if self.current_workspace == idx {
+                    self.current_workspace = idx.min(self.workspaces.len() - 1);
+                    self.current_request = Self::new_default_request(&self.settings);
+                } else if self.current_workspace > idx {
+                    self.current_workspace -= 1;
+                }
+                // Snapshots refer to workspaces by index
+                self.undo_stack.clear();
+                self.redo_stack.clear();
+                self.undo_baseline = None;
+                self.save_cache();
+            }
+            PendingAction::ImportFromUrl(url) => self.import_from_url(url),
+            PendingAction::WriteFile {
+                path,
+                contents,
+                what,
+                success,
+            } => {
+                if self.write_file(&path, &contents, what) {
+                    self.notify(ToastKind::Success, success);
+                }
+            }
+        }
+    }
+
+    fn draw_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(confirmation) = &mut self.confirmation else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr("confirm"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(&confirmation.message);
+                if confirmation.kind != ConfirmKind::ImportFromUrl {
+                    ui.checkbox(&mut confirmation.dont_ask_again, tr("dont-ask-again"));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let label = match confirmation.kind {
+                        ConfirmKind::OverwriteFile => tr("overwrite"),
+                        ConfirmKind::ImportFromUrl => tr("import"),
+                        _ => tr("delete"),
+                    };
+                    if ui
+                        .button(RichText::new(label).color(Color32::from_rgb(215, 58, 73)))
+                        .clicked()
+                    {
+                        confirmed = true;
+                    }
+                    if ui.button(tr("cancel")).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape))
+                    {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if confirmed && let Some(confirmation) = self.confirmation.take() {
+            if confirmation.dont_ask_again {
+                self.settings.skip_confirmations.insert(confirmation.kind);
+                self.save_settings();
+            }
+            self.run_pending_action(confirmation.action);
+        } else if cancelled {
+            self.confirmation = None;
+        }
+    }
+
+    fn draw_toasts(&mut self, ctx: &egui::Context) {
+        while let Ok(toast) = self.toast_receiver.try_recv() {
+            // Repeated failures (e.g. an unwritable cache) refresh one toast instead of stacking
+            if let Some(existing) = self
+                .toasts
+                .iter_mut()
+                .find(|existing| existing.kind == toast.kind && existing.message == toast.message)
+            {
+                existing.shown_at = toast.shown_at;
+            } else {
+                self.toasts.push(toast);
+            }
+        }
+        self.toasts.retain(|toast| {
+            let lifetime = match toast.kind {
+                ToastKind::Error => Duration::from_secs(8),
+                _ => Duration::from_secs(4),
+            };
+            toast.shown_at.elapsed() < lifetime
+        });
+        if self.toasts.is_empty() {
+            return;
+        }
+        ctx.request_repaint_after(Duration::from_millis(250));
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::Vec2::new(-12.0, -12.0))
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .show(ctx, |ui| {
+                ui.with_layout(egui::Layout::bottom_up(egui::Align::Max), |ui| {
+                    for (idx, toast) in self.toasts.iter().enumerate().rev() {
+                        let (icon, color) = match toast.kind {
+                            ToastKind::Info => ("ℹ", ui.visuals().text_color()),
+                            ToastKind::Success => ("✔", Color32::from_rgb(46, 160, 67)),
+                            ToastKind::Error => ("⚠", ui.visuals().error_fg_color),
+                        };
+                        let response = egui::Frame::popup(ui.style())
+                            .show(ui, |ui| {
+                                ui.set_max_width(360.0);
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(icon).color(color));
+                                    ui.add(egui::Label::new(&toast.message).wrap());
+                                });
+                            })
+                            .response
+                            .interact(egui::Sense::click())
+                            .on_hover_text(tr("click-to-dismiss"));
+                        if response.clicked() {
+                            dismissed = Some(idx);
+                        }
+                        ui.add_space(4.0);
+                    }
+                });
+            });
+        if let Some(idx) = dismissed {
+            self.toasts.remove(idx);
+        }
+    }
+
+    fn save_settings(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(&self.settings) {
+            self.write_file(&Self::get_settings_file_path(), &json, "file-kind-settings");
+        }
+    }
+
+    fn load_settings() -> Option<AppSettings> {
+        let settings_path = Self::get_settings_file_path();
+        if let Ok(content) = std::fs::read_to_string(settings_path)
+            && let Ok(settings) = serde_json::from_str::<AppSettings>(&content)
+        {
+            return Some(settings);
+        }
+        None
+    }
+
+    fn apply_theme(&self, ctx: &egui::Context) {
+        ctx.set_theme(match self.settings.theme {
+            ThemeSetting::System => egui::ThemePreference::System,
+            ThemeSetting::Light => egui::ThemePreference::Light,
+            ThemeSetting::Dark => egui::ThemePreference::Dark,
+        });
+        for theme in [egui::Theme::Dark, egui::Theme::Light] {
+            let mut visuals = theme.default_visuals();
+            if let Some([r, g, b]) = self.settings.accent_color {
+                let accent = Color32::from_rgb(r, g, b);
+                visuals.selection.bg_fill = accent;
+                visuals.hyperlink_color = accent;
+                visuals.widgets.hovered.bg_stroke.color = accent;
+                visuals.widgets.active.bg_fill = accent;
+            }
+            ctx.set_visuals_of(theme, visuals);
+        }
+    }
+
+    fn method_color(&self, method: &str) -> Color32 {
+        let colors = &self.settings.method_colors;
+        let [r, g, b] = match method {
+            "GET" => colors.get,
+            "POST" => colors.post,
+            "PUT" => colors.put,
+            "PATCH" => colors.patch,
+            "DELETE" => colors.delete,
+            "HEAD" => colors.head,
+            "OPTIONS" => colors.options,
+            _ => colors.other,
+        };
+        Color32::from_rgb(r, g, b)
+    }
+
+    // Colored status label, with an icon badge when enabled
+    fn status_text(&self, status: u16, text: impl Into<String>) -> RichText {
+        let text = text.into();
+        let text = if self.settings.status_badges {
+            format!("{} {}", StatusColors::badge(status), text)
+        } else {
+            text
+        };
+        RichText::new(text).color(self.settings.status_colors.color(status))
+    }
+
+    // Short method label for the collections tree
+    fn method_badge(method: &str) -> &str {
+        match method {
+            "DELETE" => "DEL",
+            "OPTIONS" => "OPT",
+            _ => method,
+        }
+    }
+
+    // "GET /users/:id" from the last two path segments, with ids and variables as placeholders
+    fn suggested_request_name(method: &str, url: &str) -> String {
+        let url = url.trim().split(['?', '#']).next().unwrap_or_default();
+        let path = if let Some((_, rest)) = url.split_once("://") {
+            rest.split_once('/').map_or("", |(_, path)| path)
+        } else if url.starts_with("{{") {
+            url.split_once('/').map_or("", |(_, path)| path)
+        } else {
+            url
+        };
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                let is_id = segment.chars().all(|c| c.is_ascii_digit())
+                    || (segment.len() >= 16
+                        && segment.chars().all(|c| c.is_ascii_hexdigit() || c == '-'));
+                if is_id {
+                    ":id".to_string()
+                } else if let Some(name) = segment
+                    .strip_prefix("{{")
+                    .and_then(|segment| segment.strip_suffix("}}"))
+                {
+                    format!(":{}", name.trim())
+                } else {
+                    urlencoding::decode(segment)
+                        .map(|segment| segment.into_owned())
+                        .unwrap_or_else(|_| segment.to_string())
+                }
+            })
+            .collect();
+        let tail = &segments[segments.len().saturating_sub(2)..];
+        format!("{} /{}", method, tail.join("/"))
+    }
+
+    fn open_new_request_dialog(&mut self) {
+        if self.new_request_name.trim().is_empty() {
+            self.new_request_name = Self::suggested_request_name(
+                &self.current_request.method,
+                &self.current_request.url,
+            );
+        }
+        self.new_request_dialog = true;
+    }
+
+    fn is_default_request_name(name: &str) -> bool {
+        name.trim().is_empty() || name == "New Request"
+    }
+
+    fn name_requests_from_url(folder: &mut Folder) -> usize {
+        let mut renamed = 0;
+        for request in &mut folder.requests {
+            if Self::is_default_request_name(&request.name) && !request.url.trim().is_empty() {
+                request.name = Self::suggested_request_name(&request.method, &request.url);
+                renamed += 1;
+            }
+        }
+        for subfolder in &mut folder.folders {
+            renamed += Self::name_requests_from_url(subfolder);
+        }
+        renamed
+    }
+
+    fn new_default_request(settings: &AppSettings) -> HttpRequest {
+        HttpRequest {
+            id: Uuid::new_v4().to_string(),
+            name: "New Request".to_string(),
+            method: settings.default_method.clone(),
+            url: settings.default_url.clone(),
+            headers: vec![],
+            body: String::new(),
+            body_type: BodyType::None,
+            form_data: vec![],
+            url_encoded_data: vec![],
+            query_params: HttpRequest::query_pairs(&settings.default_url),
+            query_in_url: true,
+            path_variables: vec![],
+            settings: RequestSettings::default(),
+            example: None,
+            body_schema: None,
+            token: None,
+            assertions: vec![],
+            captures: vec![],
+            auth: RequestAuth::Inherit,
+            binary_file: String::new(),
+            body_file: None,
+        }
+    }
+
+    fn record_history(&mut self, status: u16, time: u128, attempt: u32) {
+        if let Some(request) = &self.pending_request {
+            self.history.insert(
+                0,
+                HistoryEntry {
+                    request: request.clone(),
+                    status,
+                    time,
+                    attempt,
+                },
+            );
+            self.history.truncate(self.settings.history_limit);
+            self.history_unsaved_since.get_or_insert_with(Instant::now);
+        }
+    }
+
+    fn save_cache(&self) {
+        let cache = AppCache {
+            current_workspace: self.current_workspace,
+            workspaces: self
+                .workspaces
+                .iter()
+                .map(|workspace| Workspace {
+                    settings: workspace.settings.for_storage(),
+                    ..workspace.clone()
+                })
+                .collect(),
+            selected_sidebar_item: self.selected_sidebar_item.clone(),
+            request_tab: self.request_tab.clone(),
+            response_tab: self.response_tab.clone(),
+            layout: self.layout.clone(),
+            raw_body_type: self.raw_body_type.clone(),
+            history: self.history.clone(),
+            monitor_results: self.monitor_results.clone(),
+            recent_workspaces: self.recent_workspaces.clone(),
+            run_records: self.run_records.clone(),
+            drafts: {
+                let mut drafts = self.drafts.clone();
+                if self.has_unsaved_changes() {
+                    drafts.insert(
+                        self.current_request.id.clone(),
+                        self.current_request.clone(),
+                    );
+                }
+                drafts
+            },
+        };
+
+        if let Ok(json) = serde_json::to_string_pretty(&cache) {
+            self.write_file(&Self::get_cache_file_path(), &json, "file-kind-app-state");
+        }
+    }
+
+    fn load_cache() -> Option<AppCache> {
+        let cache_path = Self::get_cache_file_path();
+        if let Ok(content) = std::fs::read_to_string(cache_path)
+            && let Ok(cache) = serde_json::from_str::<AppCache>(&content)
+        {
+            return Some(cache);
+        }
+        None
+    }
+
+    // A stacked bar of the request phases, with each phase on its own row on hover
+    fn draw_timing_waterfall(ui: &mut Ui, timing: &ResponseTiming) {
+        let phases = timing.phases();
+        let total = phases.iter().map(|(_, ms, _)| *ms).sum::<u128>().max(1) as f32;
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(120.0, 10.0), egui::Sense::hover());
+        let mut left = rect.left();
+        for (_, ms, color) in &phases {
+            let width = rect.width() * *ms as f32 / total;
+            ui.painter().rect_filled(
+                egui::Rect::from_min_size(
+                    egui::pos2(left, rect.top()),
+                    egui::vec2(width, rect.height()),
+                ),
+                0.0,
+                *color,
+            );
+            left += width;
+        }
+        response.on_hover_ui(|ui| {
+            egui::Grid::new("timing_waterfall").show(ui, |ui| {
+                let mut start = 0.0;
+                for (label, ms, color) in &phases {
+                    ui.label(label);
+                    let (rect, _) =
+                        ui.allocate_exact_size(egui::vec2(200.0, 10.0), egui::Sense::hover());
+                    let left = rect.left() + rect.width() * start / total;
+                    let width = (rect.width() * *ms as f32 / total).max(1.0);
+                    ui.painter().rect_filled(
+                        egui::Rect::from_min_size(
+                            egui::pos2(left, rect.top()),
+                            egui::vec2(width, rect.height()),
+                        ),
+                        0.0,
+                        *color,
+                    );
+                    start += *ms as f32;
+                    ui.label(format!("{} ms", ms));
+                    ui.end_row();
+                }
+            });
+            if timing.dns.is_none() && timing.connect.is_none() {
+                ui.colored_label(Color32::GRAY, tr("timing-no-connect"));
+            } else if timing.secure && timing.connect.is_some() {
+                ui.colored_label(Color32::GRAY, tr("timing-connect-tls-hint"));
+            }
+        });
+    }
+
+    // Offset, hex and printable columns, 16 bytes a line
+    fn hex_dump(bytes: &[u8]) -> String {
+        bytes
+            .chunks(16)
+            .enumerate()
+            .map(|(line, chunk)| {
+                let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+                let text: String = chunk
+                    .iter()
+                    .map(|&byte| {
+                        if byte.is_ascii_graphic() || byte == b' ' {
+                            byte as char
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                format!("{:08x}  {:<47}  {}", line * 16, hex.join(" "), text)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn format_size(size: usize) -> String {
+        if size < 1024 {
+            format!("{} B", size)
+        } else if size < 1024 * 1024 {
+            format!("{:.1} KB", size as f64 / 1024.0)
+        } else if size < 1024 * 1024 * 1024 {
+            format!("{:.1} MB", size as f64 / (1024.0 * 1024.0))
+        } else {
+            format!("{:.1} GB", size as f64 / (1024.0 * 1024.0 * 1024.0))
+        }
+    }
+
+    fn current_workspace(&self) -> &Workspace {
+        &self.workspaces[self.current_workspace]
+    }
+
+    fn current_workspace_mut(&mut self) -> &mut Workspace {
+        &mut self.workspaces[self.current_workspace]
+    }
+
+    fn get_folder_by_path_mut<'a>(
+        collection: &'a mut Collection,
+        folder_path: &[usize],
+    ) -> Option<&'a mut Folder> {
+        let mut current_folder = &mut collection.root_folder;
+
+        for &folder_idx in folder_path {
+            if folder_idx < current_folder.folders.len() {
+                current_folder = &mut current_folder.folders[folder_idx];
+            } else {
+                return None;
+            }
+        }
+        Some(current_folder)
+    }
+
+    fn get_folder_by_path<'a>(
+        collection: &'a Collection,
+        folder_path: &[usize],
+    ) -> Option<&'a Folder> {
+        let mut current_folder = &collection.root_folder;
+
+        for &folder_idx in folder_path {
+            if folder_idx < current_folder.folders.len() {
+                current_folder = &current_folder.folders[folder_idx];
+            } else {
+                return None;
+            }
+        }
+        Some(current_folder)
+    }
+
+    // The stored version of the request selected in the tree
+    fn saved_request(&self) -> Option<&HttpRequest> {
+        let workspace = self.current_workspace();
+        let collection = workspace.collections.get(workspace.selected_collection?)?;
+        Self::get_folder_by_path(collection, &workspace.selected_folder_path)?
+            .requests
+            .get(workspace.selected_request?)
+    }
+
+    fn saved_request_mut(&mut self) -> Option<&mut HttpRequest> {
+        self.collections_generation += 1;
+        let workspace = self.current_workspace_mut();
+        let collection = workspace
+            .collections
+            .get_mut(workspace.selected_collection?)?;
+        Self::get_folder_by_path_mut(collection, &workspace.selected_folder_path)?
+            .requests
+            .get_mut(workspace.selected_request?)
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        self.saved_request()
+            .is_some_and(|saved| *saved != self.current_request)
+    }
+
+    // Keep unsaved edits of the request being left, so they come back when it is reopened
+    fn stash_draft(&mut self) {
+        if self.has_unsaved_changes() {
+            self.drafts.insert(
+                self.current_request.id.clone(),
+                self.current_request.clone(),
+            );
+        } else {
+            self.drafts.remove(&self.current_request.id);
+        }
+    }
+
+    fn revert_current_request(&mut self) {
+        if let Some(saved) = self.saved_request().cloned() {
+            self.current_request = saved;
+            self.drafts.remove(&self.current_request.id);
+        }
+    }
+
+    fn save_current_request(&mut self) {
+        if Self::is_default_request_name(&self.current_request.name)
+            && !self.current_request.url.trim().is_empty()
+        {
+            self.current_request.name = Self::suggested_request_name(
+                &self.current_request.method,
+                &self.current_request.url,
+            );
+        }
+        let current_request = self.current_request.clone();
+        let current_workspace_idx = self.current_workspace;
+        let collection_idx = self.workspaces[current_workspace_idx].selected_collection;
+        let request_idx = self.workspaces[current_workspace_idx].selected_request;
+        let folder_path = self.workspaces[current_workspace_idx]
+            .selected_folder_path
+            .clone();
+
+        if let (Some(collection_idx), Some(request_idx)) = (collection_idx, request_idx)
+            && collection_idx < self.workspaces[current_workspace_idx].collections.len()
+            && let Some(folder) = Self::get_folder_by_path_mut(
+                &mut self.workspaces[current_workspace_idx].collections[collection_idx],
+                &folder_path,
+            )
+            && request_idx < folder.requests.len()
+        {
+            folder.requests[request_idx] = current_request;
+            self.collections_generation += 1;
+            self.auto_save_workspace();
+        }
+    }
+
+    fn set_content_type_header(&mut self, content_type: &str) {
+        // Find existing Content-Type header (case-insensitive)
+        let content_type_index = self
+            .current_request
+            .headers
+            .iter()
+            .position(|row| row.key.to_lowercase() == "content-type");
+
+        if let Some(index) = content_type_index {
+            // Update existing Content-Type header, switching it back on
+            let row = &mut self.current_request.headers[index];
+            row.value = content_type.to_string();
+            row.enabled = true;
+        } else {
+            // Add new Content-Type header
+            self.current_request
+                .headers
+                .push(KeyValue::new("Content-Type", content_type));
+        }
+    }
+
+    fn remove_content_type_header(&mut self) {
+        // Remove Content-Type header when body type is None
+        self.current_request
+            .headers
+            .retain(|row| row.key.to_lowercase() != "content-type");
+    }
+
+    fn auto_save_workspace(&mut self) {
+        let workspace = self.current_workspace();
+        if let Some(path) = &workspace.file_path {
+            let data = AppStorage {
+                collections: workspace.collections.clone(),
+                environments: workspace.environments.clone(),
+                settings: workspace.settings.for_storage(),
+            };
+            if let Ok(json) = serde_json::to_string_pretty(&data)
+                && self.write_file(path, &json, "file-kind-workspace")
+            {
+                self.last_auto_save = Some(chrono::Local::now());
+            }
+        }
+    }
+
+    fn effective_proxy_settings<'a>(&'a self, request: &'a HttpRequest) -> &'a ProxySettings {
+        let workspace = self.current_workspace();
+        if let Some(proxy) = &request.settings.proxy {
+            proxy
+        } else if workspace.settings.override_proxy {
+            &workspace.settings.proxy
+        } else {
+            &self.settings.proxy
+        }
+    }
+
+    fn effective_proxy(&self, request: &HttpRequest) -> Option<ProxySettings> {
+        let proxy = self.effective_proxy_settings(request);
+        if proxy.enabled && !proxy.url.trim().is_empty() {
+            Some(proxy.clone())
+        } else {
+            None
+        }
+    }
+
+    // The detected system proxy when it is in use, with the bypass hosts added to its exceptions
+    fn effective_system_proxy(&self, request: &HttpRequest) -> Option<SystemProxy> {
+        let settings = self.effective_proxy_settings(request);
+        if !settings.use_system_proxy || self.effective_proxy(request).is_some() {
+            return None;
+        }
+        let mut proxy = self.system_proxy.clone()?;
+        proxy.no_proxy = [proxy.no_proxy.trim(), settings.no_proxy.trim()]
+            .into_iter()
+            .filter(|hosts| !hosts.is_empty())
+            .collect::<Vec<_>>()
+            .join(",");
+        Some(proxy)
+    }
+
+    fn build_proxy(settings: &ProxySettings) -> reqwest::Result<reqwest::Proxy> {
+        let mut proxy = reqwest::Proxy::all(settings.url.trim())?;
+        if !settings.username.is_empty() && settings.auth == ProxyAuth::Basic {
+            proxy = proxy.basic_auth(&settings.username, &settings.password);
+        }
+        if !settings.no_proxy.trim().is_empty() {
+            proxy = proxy.no_proxy(reqwest::NoProxy::from_string(&settings.no_proxy));
+        }
+        Ok(proxy)
+    }
+
+    fn effective_skip_tls_verification(&self, request: &HttpRequest) -> bool {
+        request
+            .settings
+            .skip_tls_verification
+            .unwrap_or(self.current_workspace().settings.skip_tls_verification)
+    }
+
+    fn default_user_agent(&self) -> String {
+        match self.settings.user_agent {
+            UserAgentSetting::App => format!("Send/{}", env!("CARGO_PKG_VERSION")),
+            UserAgentSetting::Browser => BROWSER_USER_AGENT.to_string(),
+            UserAgentSetting::Custom => self.settings.custom_user_agent.clone(),
+            UserAgentSetting::None => String::new(),
+        }
+    }
+
+    fn effective_user_agent(&self, request: &HttpRequest) -> String {
+        match &request.settings.user_agent {
+            Some(user_agent) => self.resolve_value(user_agent),
+            None => self.default_user_agent(),
+        }
+    }
+
+    fn effective_retry_policy(&self, request: &HttpRequest) -> RetryPolicy {
+        request
+            .settings
+            .retry
+            .clone()
+            .unwrap_or(self.current_workspace().settings.retry.clone())
+    }
+
+    fn effective_tls_versions(&self, request: &HttpRequest) -> (TlsVersion, TlsVersion) {
+        let workspace = &self.current_workspace().settings;
+        let request = &request.settings;
+        (
+            request
+                .min_tls_version
+                .clone()
+                .unwrap_or(workspace.min_tls_version.clone()),
+            request
+                .max_tls_version
+                .clone()
+                .unwrap_or(workspace.max_tls_version.clone()),
+        )
+    }
+
+    fn resolve_value(&self, input: &str) -> String {
+        let mut result = input.to_string();
+        let workspace = self.current_workspace();
+        if let Some(env_idx) = workspace.selected_environment
+            && env_idx < workspace.environments.len()
+        {
+            let env = &workspace.environments[env_idx];
+            for (key, value) in &env.variables {
+                let placeholder = format!("{{{{{}}}}}", key);
+                result = result.replace(&placeholder, value);
+            }
+        }
+        Generator::resolve(&result)
+    }
+
+    // Variables inside a query key or value are percent-encoded, as the Params table used to do;
+    // one standing for whole pairs, like ?{{query}}, is left as is
+    fn resolve_url(&self, url: &str) -> String {
+        let Some(query) = HttpRequest::query_range(url) else {
+            return self.resolve_value(url);
+        };
+        let resolve_text = |text: &str| {
+            let mut resolved = String::new();
+            let mut rest = text;
+            while let Some(start) = rest.find("{{")
+                && let Some(end) = rest[start..].find("}}")
+            {
+                resolved.push_str(&rest[..start]);
+                let variable = &rest[start..start + end + 2];
+                let value = self.resolve_value(variable);
+                if value == variable {
+                    resolved.push_str(variable);
+                } else {
+                    resolved.push_str(&urlencoding::encode(&value));
+                }
+                rest = &rest[start + end + 2..];
+            }
+            resolved.push_str(rest);
+            resolved
+        };
+        let pairs: Vec<String> = url[query.clone()]
+            .split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) => format!("{}={}", resolve_text(key), resolve_text(value)),
+                None => self.resolve_value(pair),
+            })
+            .collect();
+        format!(
+            "{}{}{}",
+            self.resolve_value(&url[..query.start]),
+            pairs.join("&"),
+            self.resolve_value(&url[query.end..])
+        )
+    }
+
+    // Right-click menu for request fields that inserts a generated value or its dynamic variable
+    fn generator_menu(response: &egui::Response, text: &mut String) {
+        response.context_menu(|ui| {
+            let mut insert = None;
+            ui.menu_button(tr("insert-value"), |ui| {
+                for generator in Generator::ALL {
+                    if ui.button(generator.label()).clicked() {
+                        insert = Some(generator.generate());
+                    }
+                }
+            });
+            ui.menu_button(tr("insert-dynamic-variable"), |ui| {
+                for generator in Generator::ALL {
+                    let variable = format!("{{{{{}}}}}", generator.variable());
+                    if ui
+                        .button(&variable)
+                        .on_hover_text(generator.label())
+                        .clicked()
+                    {
+                        insert = Some(variable);
+                    }
+                }
+            });
+            if let Some(value) = insert {
+                Self::insert_at_cursor(ui.ctx(), response.id, text, &value);
+                ui.close_menu();
+            }
+        });
+    }
+
+    // Replaces the field's selection, or appends when it has never been focused
+    fn insert_at_cursor(ctx: &egui::Context, id: egui::Id, text: &mut String, value: &str) {
+        let state = TextEdit::load_state(ctx, id);
+        let range = state
+            .as_ref()
+            .and_then(|state| state.cursor.char_range())
+            .map(|range| {
+                let [start, end] = range.sorted();
+                start.index..end.index
+            })
+            .unwrap_or_else(|| {
+                let end = text.chars().count();
+                end..end
+            });
+        let byte = |index: usize| {
+            text.char_indices()
+                .nth(index)
+                .map_or(text.len(), |(byte, _)| byte)
+        };
+        let (start, end) = (byte(range.start), byte(range.end));
+        text.replace_range(start..end, value);
+        if let Some(mut state) = state {
+            state
+                .cursor
+                .set_char_range(Some(egui::text::CCursorRange::one(
+                    egui::text::CCursor::new(range.start + value.chars().count()),
+                )));
+            state.store(ctx, id);
+        }
+    }
+
+    fn save_to_file(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("save-workspace-title"))
+            .add_filter("JSON", &["json"])
+            .save_file()
+        {
+            let workspace = self.current_workspace();
+            let data = AppStorage {
+                collections: workspace.collections.clone(),
+                environments: workspace.environments.clone(),
+                settings: workspace.settings.for_storage(),
+            };
+            let json = serde_json::to_string_pretty(&data).unwrap();
+            if self.write_file(&path, &json, "file-kind-workspace") {
+                let mut args = FluentArgs::new();
+                args.set("path", path.display().to_string());
+                self.notify(ToastKind::Success, tr_args("workspace-saved-to", &args));
+                self.remember_workspace_file(&path);
+                self.current_workspace_mut().file_path = Some(path);
+                self.save_cache();
+            }
+        }
+    }
+
+    fn load_from_file(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("load-workspace-title"))
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        {
+            self.open_workspace_file(path);
+        }
+    }
+
+    fn remember_workspace_file(&mut self, path: &std::path::Path) {
+        self.recent_workspaces.retain(|recent| recent != path);
+        self.recent_workspaces.insert(0, path.to_path_buf());
+        self.recent_workspaces.truncate(8);
+    }
+
+    fn open_workspace_file(&mut self, path: std::path::PathBuf) {
+        // Switch to the workspace instead of loading the same file twice
+        if let Some(idx) = self
+            .workspaces
+            .iter()
+            .position(|workspace| workspace.file_path.as_ref() == Some(&path))
+        {
+            self.current_workspace = idx;
+            self.show_welcome = false;
+            self.remember_workspace_file(&path);
+            self.save_cache();
+            return;
+        }
+        let storage = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<AppStorage>(&content).map_err(|e| e.to_string())
+            });
+        match storage {
+            Err(e) => {
+                let mut args = FluentArgs::new();
+                args.set("path", path.display().to_string());
+                args.set("error", e);
+                self.notify(ToastKind::Error, tr_args("load-file-failed", &args));
+            }
+            Ok(storage) => {
+                let workspace_name = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map_or_else(|| tr("loaded-workspace"), str::to_string);
+
+                let selected_collection = if !storage.collections.is_empty() {
+                    Some(0)
+                } else {
+                    None
+                };
+                let selected_environment = if !storage.environments.is_empty() {
+                    Some(0)
+                } else {
+                    None
+                };
+
+                let new_workspace = Workspace {
+                    name: workspace_name,
+                    file_path: Some(path.clone()),
+                    collections: storage.collections,
+                    environments: storage.environments,
+                    selected_collection,
+                    selected_folder_path: vec![],
+                    selected_request: None,
+                    selected_environment,
+                    settings: storage.settings,
+                    active_session: None,
+                };
+
+                self.remember_workspace_file(&path);
+                self.workspaces.push(new_workspace);
+                self.current_workspace = self.workspaces.len() - 1;
+                self.show_welcome = false;
+                self.save_cache();
+                self.notify(ToastKind::Success, tr("workspace-loaded"));
+            }
+        }
+    }
+
+    fn export_collection(&mut self) {
+        let workspace = self.current_workspace();
+        if let Some(idx) = workspace.selected_collection
+            && let Some(collection) = workspace.collections.get(idx)
+        {
+            let mut args = FluentArgs::new();
+            args.set("name", collection.name.clone());
+            let Some(path) = rfd::FileDialog::new()
+                .set_title(tr_args("export-named-title", &args))
+                .add_filter("JSON", &["json"])
+                .save_file()
+            else {
+                return;
+            };
+            let json = serde_json::to_string_pretty(collection).unwrap();
+            let success = tr_args("exported-named", &args);
+            self.export_file(path, json, "file-kind-collection", success);
+        }
+    }
+
+    fn export_script(&mut self, node: &TreeNode, shell: ScriptShell) {
+        let workspace = self.current_workspace();
+        let (title, requests) = match node {
+            TreeNode::Collection(collection_idx) => {
+                let collection = &workspace.collections[*collection_idx];
+                let mut requests = vec![];
+                Self::collect_folder_requests(&collection.root_folder, &mut requests);
+                (collection.name.clone(), requests)
+            }
+            TreeNode::Folder(collection_idx, path) => {
+                let Some(folder) =
+                    Self::get_folder_by_path(&workspace.collections[*collection_idx], path)
+                else {
+                    return;
+                };
+                let mut requests = vec![];
+                Self::collect_folder_requests(folder, &mut requests);
+                (folder.name.clone(), requests)
+            }
+            TreeNode::Request(collection_idx, path, request_idx) => {
+                let Some(request) =
+                    Self::get_folder_by_path(&workspace.collections[*collection_idx], path)
+                        .and_then(|folder| folder.requests.get(*request_idx))
+                else {
+                    return;
+                };
+                (request.name.clone(), vec![request.clone()])
+            }
+        };
+        let mut args = FluentArgs::new();
+        args.set("name", title.clone());
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title(tr_args("export-named-title", &args))
+            .add_filter(shell.label(), &[shell.extension()])
+            .set_file_name(format!("{}.{}", title, shell.extension()))
+            .save_file()
+        {
+            let script = self.request_script(&title, &requests, shell);
+            let success = tr_args("exported-named-as-script", &args);
+            self.export_file(path, script, "file-kind-script", success);
+        }
+    }
+
+    // A script that sends the requests with curl or Invoke-WebRequest. Variables become script
+    // variables defaulting to the active environment, so they can be overridden from the shell.
+    fn request_script(&self, title: &str, requests: &[HttpRequest], shell: ScriptShell) -> String {
+        let mut templates = vec![];
+        let mut commands = vec![];
+        // Token values are left for the caller to supply, like secret variables
+        let mut token_variables = vec![];
+        for request in requests {
+            let auth = self.effective_auth(request);
+            let mut url = request
+                .url_with_path_variables(ScriptShell::encode_template)
+                .trim()
+                .to_string();
+            let params: Vec<String> = KeyValue::enabled(&request.query_params)
+                .filter(|_| !request.query_in_url)
+                .filter(|(key, _)| !key.trim().is_empty())
+                .map(|(key, value)| {
+                    format!(
+                        "{}={}",
+                        ScriptShell::encode_template(key),
+                        ScriptShell::encode_template(value)
+                    )
+                })
+                .collect();
+            if !params.is_empty() {
+                let separator = if url.contains('?') { "&" } else { "?" };
+                url = format!("{}{}{}", url, separator, params.join("&"));
+            }
+            if let Some((key, value)) = auth.api_key(ApiKeyPlacement::Query) {
+                let separator = if url.contains('?') { "&" } else { "?" };
+                url = format!(
+                    "{}{}{}={}",
+                    url,
+                    separator,
+                    ScriptShell::encode_template(key),
+                    ScriptShell::encode_template(value)
+                );
+            }
+            let mut headers: Vec<(String, String)> = self
+                .inherited_default_headers(request)
+                .cloned()
+                .chain(
+                    request
+                        .headers
+                        .iter()
+                        .filter(|row| row.enabled)
+                        .map(KeyValue::pair),
+                )
+                .filter(|(key, _)| !key.trim().is_empty())
+                .collect();
+            if let Some((key, value)) = auth.api_key(ApiKeyPlacement::Header)
+                && !headers
+                    .iter()
+                    .any(|(name, _)| name.trim().eq_ignore_ascii_case(key))
+            {
+                headers.push((key.to_string(), value.to_string()));
+            }
+            let has_authorization = headers
+                .iter()
+                .any(|(key, _)| key.trim().eq_ignore_ascii_case("authorization"));
+            if let RequestAuth::Bearer { token } = &auth
+                && !has_authorization
+            {
+                headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+            }
+            if matches!(auth, RequestAuth::OAuth2 { .. }) && !has_authorization {
+                if !token_variables.iter().any(|name| name == "access_token") {
+                    token_variables.push("access_token".to_string());
+                }
+                headers.push((
+                    "Authorization".to_string(),
+                    "Bearer {{access_token}}".to_string(),
+                ));
+            }
+            // Basic credentials stay readable; curl and PowerShell encode them at run time
+            let credentials = match &auth {
+                RequestAuth::Basic { username, password } if !has_authorization => {
+                    Some(format!("{}:{}", username, password))
+                }
+                _ => None,
+            };
+            // (user, password, curl flag) for curl's and PowerShell's own NTLM support
+            let windows_credentials = match &auth {
+                RequestAuth::Ntlm {
+                    username,
+                    password,
+                    domain,
+                    negotiate,
+                } if !has_authorization => {
+                    let user = if domain.trim().is_empty() || username.contains('\\') {
+                        username.clone()
+                    } else {
+                        format!("{}\\{}", domain.trim(), username)
+                    };
+                    let flag = if *negotiate { "--negotiate" } else { "--ntlm" };
+                    Some((user, password.clone(), flag))
+                }
+                _ => None,
+            };
+            if let Some(token) = self.effective_token(request)
+                && !auth.sets_authorization()
+                && !headers
+                    .iter()
+                    .any(|(key, _)| key.trim().eq_ignore_ascii_case("authorization"))
+            {
+                let value = if ScriptShell::template_parts(&token.value)
+                    .iter()
+                    .any(|(_, is_variable)| *is_variable)
+                {
+                    token.value.clone()
+                } else {
+                    if !token_variables.contains(&token.name) {
+                        token_variables.push(token.name.clone());
+                    }
+                    format!("{{{{{}}}}}", token.name)
+                };
+                headers.push(("Authorization".to_string(), token.header_value(&value)));
+            }
+            let fields: Vec<(String, String, bool)> = match request.body_type {
+                BodyType::FormData => request
+                    .form_data
+                    .iter()
+                    .filter(|entry| entry.is_enabled())
+                    .filter_map(|entry| match entry {
+                        FormDataEntry::Text { key, value, .. } if !key.trim().is_empty() => {
+                            Some((key.clone(), value.clone(), false))
+                        }
+                        FormDataEntry::File { key, file_path, .. } if !key.trim().is_empty() => {
+                            Some((key.clone(), file_path.clone(), true))
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+                BodyType::UrlEncoded => KeyValue::enabled(&request.url_encoded_data)
+                    .filter(|(key, _)| !key.trim().is_empty())
+                    .map(|(key, value)| (key.clone(), value.clone(), false))
+                    .collect(),
+                _ => vec![],
+            };
+            let binary_file = match request.body_type {
+                BodyType::Binary => Some(request.binary_file.clone()),
+                BodyType::Raw | BodyType::Json => request.body_file.clone(),
+                _ => None,
+            }
+            .filter(|path| !path.trim().is_empty());
+            let body = matches!(request.body_type, BodyType::Raw | BodyType::Json)
+                .then(|| Generator::resolve(&request.body))
+                .filter(|body| !body.is_empty() && binary_file.is_none());
+
+            templates.push(url.clone());
+            templates.extend(
+                headers
+                    .iter()
+                    .flat_map(|(key, value)| [key.clone(), value.clone()]),
+            );
+            templates.extend(
+                fields
+                    .iter()
+                    .flat_map(|(key, value, _)| [key.clone(), value.clone()]),
+            );
+            templates.extend(body.clone());
+            templates.extend(credentials.clone());
+            templates.extend(
+                windows_credentials
+                    .iter()
+                    .flat_map(|(user, password, _)| [user.clone(), password.clone()]),
+            );
+
+            let url = Generator::resolve(&url);
+            let mut lines = vec![format!("# {} {}", request.method, request.name)];
+            match shell {
+                ScriptShell::Bash => {
+                    let method = if request.method == "HEAD" {
+                        "--head".to_string()
+                    } else {
+                        format!("-X {}", shell.quote(&request.method))
+                    };
+                    let mut args = vec![format!("curl -sS {} {}", method, shell.word(&url))];
+                    for (key, value) in &headers {
+                        let header = format!("{}: {}", key, Generator::resolve(value));
+                        args.push(format!("-H {}", shell.word(&header)));
+                    }
+                    if let Some(credentials) = &credentials {
+                        args.push(format!(
+                            "-u {}",
+                            shell.word(&Generator::resolve(credentials))
+                        ));
+                    }
+                    if let Some((user, password, flag)) = &windows_credentials {
+                        let credentials = format!("{}:{}", user, password);
+                        args.push(format!(
+                            "{} -u {}",
+                            flag,
+                            shell.word(&Generator::resolve(&credentials))
+                        ));
+                    }
+                    for (key, value, is_file) in &fields {
+                        let field = if *is_file {
+                            format!("{}=@{}", key, value)
+                        } else {
+                            format!("{}={}", key, Generator::resolve(value))
+                        };
+                        let flag = match request.body_type {
+                            BodyType::FormData => "-F",
+                            _ => "--data-urlencode",
+                        };
+                        args.push(format!("{} {}", flag, shell.word(&field)));
+                    }
+                    if let Some(body) = &body {
+                        args.push(format!("--data-raw {}", shell.word(body)));
+                    }
+                    if let Some(path) = &binary_file {
+                        args.push(format!(
+                            "--data-binary {}",
+                            shell.word(&format!("@{}", path))
+                        ));
+                    }
+                    lines.push(args.join(" \\\n  "));
+                    lines.push("echo".to_string());
+                }
+                ScriptShell::PowerShell => {
+                    let table = |entries: Vec<String>| format!("@{{ {} }}", entries.join("; "));
+                    let mut args = vec![format!(
+                        "Invoke-WebRequest -Method {} -Uri {}",
+                        shell.quote(&request.method),
+                        shell.word(&url)
+                    )];
+                    let mut entries: Vec<String> = headers
+                        .iter()
+                        .map(|(key, value)| {
+                            format!(
+                                "{} = {}",
+                                shell.word(key),
+                                shell.word(&Generator::resolve(value))
+                            )
+                        })
+                        .collect();
+                    if let Some(credentials) = &credentials {
+                        entries.push(format!(
+                            "'Authorization' = 'Basic ' + [Convert]::ToBase64String([Text.Encoding]::UTF8.GetBytes({}))",
+                            shell.word(&Generator::resolve(credentials))
+                        ));
+                    }
+                    if !entries.is_empty() {
+                        args.push(format!("-Headers {} -SkipHeaderValidation", table(entries)));
+                    }
+                    if let Some((user, password, _)) = &windows_credentials {
+                        args.push(format!(
+                            "-Credential ([pscredential]::new({}, (ConvertTo-SecureString {} -AsPlainText -Force)))",
+                            shell.word(&Generator::resolve(user)),
+                            shell.word(&Generator::resolve(password))
+                        ));
+                    }
+                    if !fields.is_empty() {
+                        let entries = fields
+                            .iter()
+                            .map(|(key, value, is_file)| {
+                                let value = if *is_file {
+                                    format!("(Get-Item -LiteralPath {})", shell.word(value))
+                                } else {
+                                    shell.word(&Generator::resolve(value))
+                                };
+                                format!("{} = {}", shell.word(key), value)
+                            })
+                            .collect();
+                        let flag = match request.body_type {
+                            BodyType::FormData => "-Form",
+                            _ => "-Body",
+                        };
+                        args.push(format!("{} {}", flag, table(entries)));
+                    }
+                    if let Some(body) = &body {
+                        args.push(format!("-Body {}", shell.word(body)));
+                    }
+                    if let Some(path) = &binary_file {
+                        args.push(format!("-InFile {}", shell.word(path)));
+                    }
+                    args.push("Select-Object -ExpandProperty Content".to_string());
+                    let (pipe, rest) = args.split_last().unwrap();
+                    lines.push(format!("{} |\n  {}", rest.join(" `\n  "), pipe));
+                }
+            }
+            commands.push(lines.join("\n"));
+        }
+
+        let workspace = self.current_workspace();
+        let environment = workspace
+            .selected_environment
+            .and_then(|idx| workspace.environments.get(idx));
+        let mut variables: Vec<String> = vec![];
+        for template in &templates {
+            for (name, is_variable) in ScriptShell::template_parts(template) {
+                if is_variable && !variables.contains(&name) {
+                    variables.push(name);
+                }
+            }
+        }
+        let defaults: Vec<String> = variables
+            .iter()
+            .map(|name| {
+                let value = environment
+                    .filter(|environment| !environment.secret_keys.contains(name))
+                    .and_then(|environment| {
+                        environment
+                            .variables
+                            .iter()
+                            .find(|(key, _)| key == name)
+                            .map(|(_, value)| value.as_str())
+                    })
+                    .filter(|_| !token_variables.contains(name))
+                    .unwrap_or_default();
+                shell.variable_default(name, value)
+            })
+            .collect();
+
+        let mut script = match shell {
+            ScriptShell::Bash => "#!/usr/bin/env bash\nset -euo pipefail\n".to_string(),
+            ScriptShell::PowerShell => "$ErrorActionPreference = 'Stop'\n".to_string(),
+        };
+        script.push_str(&format!("# {} - exported from Send\n", title));
+        if !defaults.is_empty() {
+            script.push_str("\n# Override any of these from the environment\n");
+            script.push_str(&defaults.join("\n"));
+            script.push('\n');
+        }
+        for command in commands {
+            script.push('\n');
+            script.push_str(&command);
+            script.push('\n');
+        }
+        script
+    }
+
+    fn is_sensitive_header(name: &str) -> bool {
+        let name = name.to_lowercase();
+        name == "authorization"
+            || name == "proxy-authorization"
+            || name == "cookie"
+            || name.contains("api-key")
+            || name.contains("apikey")
+            || name.contains("token")
+            || name.contains("secret")
+    }
+
+    fn scrub_folder_for_sharing(folder: &mut Folder) {
+        folder.auth.scrub_for_sharing();
+        for request in &mut folder.requests {
+            if let Some(proxy) = &mut request.settings.proxy {
+                proxy.password.clear();
+            }
+            request.auth.scrub_for_sharing();
+            for row in &mut request.headers {
+                if Self::is_sensitive_header(&row.key) {
+                    row.value.clear();
+                }
+            }
+            for entry in &mut request.form_data {
+                if let FormDataEntry::File { file_path, .. } = entry {
+                    file_path.clear();
+                }
+            }
+            request.binary_file.clear();
+            if let Some(path) = &mut request.body_file {
+                path.clear();
+            }
+        }
+        for subfolder in &mut folder.folders {
+            Self::scrub_folder_for_sharing(subfolder);
+        }
+    }
+
+    fn scrub_collection_for_sharing(collection: &mut Collection) {
+        collection.auth.scrub_for_sharing();
+        if let Some(signer) = &mut collection.signer {
+            signer.secret.clear();
+        }
+        Self::scrub_folder_for_sharing(&mut collection.root_folder);
+    }
+
+    fn scrub_environment_for_sharing(environment: &mut Environment, strip_values: bool) {
+        for (key, value) in &mut environment.variables {
+            if strip_values || environment.secret_keys.contains(key) {
+                value.clear();
+            }
+        }
+    }
+
+    fn export_for_sharing(&mut self) {
+        let workspace = self.current_workspace();
+        let json = match self.share_export_scope {
+            ShareExportScope::Workspace => {
+                let mut data = AppStorage {
+                    collections: workspace.collections.clone(),
+                    environments: workspace.environments.clone(),
+                    settings: workspace.settings.clone(),
+                };
+                data.settings.proxy.password.clear();
+                for certificate in &mut data.settings.client_certificates {
+                    certificate.key.clear();
+                    certificate.passphrase.clear();
+                    if certificate.format == CertificateFormat::Pkcs12 {
+                        certificate.certificate.clear();
+                    }
+                }
+                for token in &mut data.settings.tokens {
+                    token.value.clear();
+                }
+                for session in &mut data.settings.sessions {
+                    session.cookies.clear();
+                    session.tokens.clear();
+                }
+                data.settings.cookies.clear();
+                for (key, value) in data.settings.default_headers.iter_mut().chain(
+                    data.settings
+                        .header_presets
+                        .iter_mut()
+                        .flat_map(|p| &mut p.headers),
+                ) {
+                    if Self::is_sensitive_header(key) {
+                        value.clear();
+                    }
+                }
+                for collection in &mut data.collections {
+                    Self::scrub_collection_for_sharing(collection);
+                }
+                for environment in &mut data.environments {
+                    Self::scrub_environment_for_sharing(
+                        environment,
+                        self.share_export_strip_values,
+                    );
+                }
+                serde_json::to_string_pretty(&data)
+            }
+            ShareExportScope::Collection => {
+                let Some(mut collection) = workspace
+                    .selected_collection
+                    .and_then(|idx| workspace.collections.get(idx))
+                    .cloned()
+                else {
+                    return;
+                };
+                Self::scrub_collection_for_sharing(&mut collection);
+                serde_json::to_string_pretty(&collection)
+            }
+        };
+
+        if let Ok(json) = json
+            && let Some(path) = rfd::FileDialog::new()
+                .set_title(tr("export-for-sharing"))
+                .add_filter("JSON", &["json"])
+                .save_file()
+        {
+            let mut args = FluentArgs::new();
+            args.set("path", path.display().to_string());
+            let success = tr_args("exported-to", &args);
+            self.export_file(path, json, "file-kind-export", success);
+        }
+    }
+
+    fn import_collection(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("import-collection-title"))
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        {
+            self.import_collection_file(&path);
+        }
+    }
+
+    fn import_collection_file(&mut self, path: &std::path::Path) {
+        let collection = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| Self::parse_collection(&content));
+        match collection {
+            Ok(collection) => {
+                let workspace = self.current_workspace_mut();
+                workspace.collections.push(collection);
+                workspace.selected_collection = Some(workspace.collections.len() - 1);
+                workspace.selected_folder_path = vec![];
+                workspace.selected_request = None;
+                self.selected_sidebar_item = Some(SidebarItem::Collections);
+                self.show_welcome = false;
+                self.collections_generation += 1;
+                self.auto_save_workspace();
+                self.save_cache();
+                self.notify_imported(self.current_workspace().collections.last());
+            }
+            Err(e) => {
+                let mut args = FluentArgs::new();
+                args.set("path", path.display().to_string());
+                args.set("error", e);
+                self.notify(ToastKind::Error, tr_args("import-file-failed", &args));
+            }
+        }
+    }
+
+    // Accepts Send collections, Postman v2 collections and OpenAPI/Swagger JSON documents
+    fn parse_collection(content: &str) -> Result<Collection, String> {
+        let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        if value.get("info").is_some() && value.get("item").is_some() {
+            Self::collection_from_postman(&value)
+        } else if value.get("openapi").is_some() || value.get("swagger").is_some() {
+            Self::collection_from_openapi(&value)
+        } else {
+            serde_json::from_value(value).map_err(|e| e.to_string())
+        }
+    }
+
+    fn imported_request(name: String, method: String, url: String) -> HttpRequest {
+        let mut request = HttpRequest {
+            id: Uuid::new_v4().to_string(),
+            name,
+            method,
+            url,
+            headers: vec![],
+            body: String::new(),
+            body_type: BodyType::None,
+            form_data: vec![],
+            url_encoded_data: vec![],
+            query_params: vec![],
+            query_in_url: true,
+            path_variables: vec![],
+            settings: RequestSettings::default(),
+            example: None,
+            body_schema: None,
+            token: None,
+            assertions: vec![],
+            captures: vec![],
+            auth: RequestAuth::Inherit,
+            binary_file: String::new(),
+            body_file: None,
+        };
+        request.sync_query_params();
+        request.sync_path_variables();
+        request
+    }
+
+    fn empty_folder(name: &str) -> Folder {
+        Folder {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            requests: vec![],
+            folders: vec![],
+            auth: RequestAuth::Inherit,
+        }
+    }
+
+    fn collection_from_postman(value: &serde_json::Value) -> Result<Collection, String> {
+        let items = value["item"]
+            .as_array()
+            .ok_or("Not a Postman collection: 'item' is not a list")?;
+        let mut root_folder = Self::empty_folder("Root");
+        Self::postman_items(items, &mut root_folder);
+        Ok(Collection {
+            id: Uuid::new_v4().to_string(),
+            name: value["info"]["name"]
+                .as_str()
+                .unwrap_or("Postman Collection")
+                .to_string(),
+            root_folder,
+            token: None,
+            auth: Self::auth_from_postman(&value["auth"]),
+            signer: None,
+        })
+    }
+
+    fn postman_items(items: &[serde_json::Value], folder: &mut Folder) {
+        for item in items {
+            let name = item["name"].as_str().unwrap_or("Untitled");
+            if let Some(children) = item["item"].as_array() {
+                let mut child = Self::empty_folder(name);
+                child.auth = Self::auth_from_postman(&item["auth"]);
+                Self::postman_items(children, &mut child);
+                folder.folders.push(child);
+            } else if !item["request"].is_null() {
+                folder
+                    .requests
+                    .push(Self::request_from_postman(name, &item["request"]));
+            }
+        }
+    }
+
+    fn request_from_postman(name: &str, request: &serde_json::Value) -> HttpRequest {
+        // A request may be given as just its URL
+        let url = request
+            .as_str()
+            .or_else(|| request["url"].as_str())
+            .or_else(|| request["url"]["raw"].as_str())
+            .unwrap_or_default();
+        let method = request["method"].as_str().unwrap_or("GET").to_uppercase();
+        let mut result = Self::imported_request(name.to_string(), method, url.to_string());
+        // Entries switched off in Postman come in switched off
+        let rows = |list: &serde_json::Value| -> Vec<KeyValue> {
+            list.as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| {
+                    Some(KeyValue {
+                        key: entry["key"].as_str()?.to_string(),
+                        value: entry["value"].as_str().unwrap_or_default().to_string(),
+                        enabled: !entry["disabled"].as_bool().unwrap_or(false),
+                    })
+                })
+                .collect()
+        };
+        result.headers = rows(&request["header"]);
+        for KeyValue {
+            key: name, value, ..
+        } in rows(&request["url"]["variable"])
+        {
+            if let Some((_, existing)) = result
+                .path_variables
+                .iter_mut()
+                .find(|(existing, _)| *existing == name)
+            {
+                *existing = value;
+            }
+        }
+
+        let body = &request["body"];
+        match body["mode"].as_str() {
+            Some("raw") => {
+                result.body = body["raw"].as_str().unwrap_or_default().to_string();
+                let is_json = body["options"]["raw"]["language"].as_str() == Some("json")
+                    || KeyValue::enabled(&result.headers).any(|(key, value)| {
+                        key.eq_ignore_ascii_case("content-type") && value.contains("json")
+                    });
+                result.body_type = if is_json {
+                    BodyType::Json
+                } else {
+                    BodyType::Raw
+                };
+            }
+            Some("urlencoded") => {
+                result.url_encoded_data = rows(&body["urlencoded"]);
+                result.body_type = BodyType::UrlEncoded;
+            }
+            Some("formdata") => {
+                result.form_data = body["formdata"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| {
+                        let key = entry["key"].as_str()?.to_string();
+                        let mut form_entry = if entry["type"].as_str() == Some("file") {
+                            FormDataEntry::file(
+                                key,
+                                entry["src"].as_str().unwrap_or_default().to_string(),
+                            )
+                        } else {
+                            FormDataEntry::text(
+                                key,
+                                entry["value"].as_str().unwrap_or_default().to_string(),
+                            )
+                        };
+                        if let Some(part_type) = entry["contentType"].as_str() {
+                            *form_entry.part_options_mut().0 = part_type.to_string();
+                        }
+                        *form_entry.enabled_mut() = !entry["disabled"].as_bool().unwrap_or(false);
+                        Some(form_entry)
+                    })
+                    .collect();
+                result.body_type = BodyType::FormData;
+            }
+            Some("graphql") => {
+                let graphql = serde_json::json!({
+                    "query": body["graphql"]["query"],
+                    "variables": body["graphql"]["variables"],
+                });
+                result.body = serde_json::to_string_pretty(&graphql).unwrap_or_default();
+                result.body_type = BodyType::Json;
+            }
+            _ => {}
+        }
+
+        result.auth = Self::auth_from_postman(&request["auth"]);
+        result
+    }
+
+    // Postman auth on a collection, folder or request; missing means inherit
+    fn auth_from_postman(auth: &serde_json::Value) -> RequestAuth {
+        let field = |kind: &str, name: &str| {
+            auth[kind]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|entry| entry["key"].as_str() == Some(name))
+                .and_then(|entry| entry["value"].as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        match auth["type"].as_str() {
+            Some("noauth") => RequestAuth::None,
+            Some("basic") => RequestAuth::Basic {
+                username: field("basic", "username"),
+                password: field("basic", "password"),
+            },
+            Some("bearer") => RequestAuth::Bearer {
+                token: field("bearer", "token"),
+            },
+            Some("apikey") => RequestAuth::ApiKey {
+                key: field("apikey", "key"),
+                value: field("apikey", "value"),
+                placement: if field("apikey", "in") == "query" {
+                    ApiKeyPlacement::Query
+                } else {
+                    ApiKeyPlacement::Header
+                },
+            },
+            _ => RequestAuth::Inherit,
+        }
+    }
+
+    fn collection_from_openapi(value: &serde_json::Value) -> Result<Collection, String> {
+        let paths = value["paths"]
+            .as_object()
+            .ok_or("The OpenAPI document has no paths")?;
+        // OpenAPI 3 lists servers; Swagger 2 splits the base URL into parts
+        let base_url = if let Some(url) = value["servers"][0]["url"].as_str() {
+            url.trim_end_matches('/').to_string()
+        } else if let Some(host) = value["host"].as_str() {
+            format!(
+                "{}://{}{}",
+                value["schemes"][0].as_str().unwrap_or("https"),
+                host,
+                value["basePath"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .trim_end_matches('/')
+            )
+        } else {
+            String::new()
+        };
+
+        let mut root_folder = Self::empty_folder("Root");
+        for (path, operations) in paths {
+            let Some(operations) = operations.as_object() else {
+                continue;
+            };
+            for (method, operation) in operations {
+                let method = method.to_uppercase();
+                if !["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"]
+                    .contains(&method.as_str())
+                {
+                    continue;
+                }
+                let name = operation["summary"]
+                    .as_str()
+                    .or_else(|| operation["operationId"].as_str())
+                    .map_or_else(|| format!("{} {}", method, path), str::to_string);
+                // Path parameters become {{variables}}
+                let url = format!("{}{}", base_url, path.replace('{', "{{").replace('}', "}}"));
+                let mut request = Self::imported_request(name, method, url);
+
+                let parameters = operations
+                    .get("parameters")
+                    .and_then(|parameters| parameters.as_array())
+                    .into_iter()
+                    .chain(operation["parameters"].as_array())
+                    .flatten();
+                for parameter in parameters {
+                    let Some(name) = parameter["name"].as_str() else {
+                        continue;
+                    };
+                    let example = match &parameter["example"] {
+                        serde_json::Value::String(example) => example.clone(),
+                        serde_json::Value::Null => String::new(),
+                        example => example.to_string(),
+                    };
+                    match parameter["in"].as_str() {
+                        Some("query") => request.query_params.push(KeyValue::new(name, example)),
+                        Some("header") => request.headers.push(KeyValue::new(name, example)),
+                        Some("body") => {
+                            request.body = "{}".to_string();
+                            request.body_type = BodyType::Json;
+                            request.body_schema =
+                                Self::resolve_schema(&parameter["schema"], value, 0);
+                        }
+                        _ => {}
+                    }
+                }
+                request.sync_url_query();
+
+                let json = &operation["requestBody"]["content"]["application/json"];
+                if !json.is_null() {
+                    let example = if json["example"].is_null() {
+                        json["examples"]
+                            .as_object()
+                            .and_then(|examples| examples.values().next())
+                            .map(|example| example["value"].clone())
+                    } else {
+                        Some(json["example"].clone())
+                    };
+                    request.body = example
+                        .and_then(|example| serde_json::to_string_pretty(&example).ok())
+                        .unwrap_or_else(|| "{}".to_string());
+                    request.body_type = BodyType::Json;
+                    request.body_schema = Self::resolve_schema(&json["schema"], value, 0);
+                }
+
+                // Operations are grouped into a folder per tag
+                match operation["tags"][0].as_str() {
+                    Some(tag) => {
+                        let idx = match root_folder.folders.iter().position(|f| f.name == tag) {
+                            Some(idx) => idx,
+                            None => {
+                                root_folder.folders.push(Self::empty_folder(tag));
+                                root_folder.folders.len() - 1
+                            }
+                        };
+                        root_folder.folders[idx].requests.push(request);
+                    }
+                    None => root_folder.requests.push(request),
+                }
+            }
+        }
+
+        Ok(Collection {
+            id: Uuid::new_v4().to_string(),
+            name: value["info"]["title"]
+                .as_str()
+                .unwrap_or("OpenAPI")
+                .to_string(),
+            root_folder,
+            token: None,
+            auth: RequestAuth::None,
+            signer: None,
+        })
+    }
+
+    // Inlines local $refs and merges allOf, so a schema can be walked without its document
+    fn resolve_schema(
+        schema: &serde_json::Value,
+        document: &serde_json::Value,
+        depth: usize,
+    ) -> Option<serde_json::Value> {
+        use serde_json::Value;
+        // Recursive schemas are cut off rather than expanded forever
+        if depth > 8 {
+            return Some(Value::Object(Default::default()));
+        }
+        if let Some(reference) = schema["$ref"].as_str() {
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| document.pointer(pointer))?;
+            return Self::resolve_schema(target, document, depth + 1);
+        }
+        match schema {
+            Value::Null => None,
+            Value::Array(items) => Some(Value::Array(
+                items
+                    .iter()
+                    .filter_map(|item| Self::resolve_schema(item, document, depth))
+                    .collect(),
+            )),
+            Value::Object(map) => {
+                let mut resolved: serde_json::Map<String, Value> = map
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        Some((key.clone(), Self::resolve_schema(value, document, depth)?))
+                    })
+                    .collect();
+                if let Some(Value::Array(parts)) = resolved.remove("allOf") {
+                    for part in parts {
+                        if let Some(properties) = part["properties"].as_object() {
+                            let merged = resolved
+                                .entry("properties")
+                                .or_insert_with(|| Value::Object(Default::default()));
+                            if let Some(merged) = merged.as_object_mut() {
+                                merged.extend(properties.clone());
+                            }
+                        }
+                        if let Some(required) = part["required"].as_array() {
+                            let merged = resolved
+                                .entry("required")
+                                .or_insert_with(|| Value::Array(vec![]));
+                            if let Some(merged) = merged.as_array_mut() {
+                                merged.extend(required.iter().cloned());
+                            }
+                        }
+                    }
+                    resolved
+                        .entry("type")
+                        .or_insert_with(|| Value::String("object".to_string()));
+                }
+                Some(Value::Object(resolved))
+            }
+            other => Some(other.clone()),
+        }
+    }
+
+    // Splits a shell command line into words, honoring quotes, escapes and line continuations
+    fn shell_words(input: &str) -> Result<Vec<String>, String> {
+        let mut words = vec![];
+        let mut word = String::new();
+        let mut in_word = false;
+        let mut chars = input.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\'' => {
+                    in_word = true;
+                    loop {
+                        match chars.next() {
+                            Some('\'') => break,
+                            Some(c) => word.push(c),
+                            None => return Err("Unterminated single quote".to_string()),
+                        }
+                    }
+                }
+                '"' => {
+                    in_word = true;
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some('\\') => match chars.next() {
+                                Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                                Some('\n') => {}
+                                Some(c) => {
+                                    word.push('\\');
+                                    word.push(c);
+                                }
+                                None => return Err("Unterminated double quote".to_string()),
+                            },
+                            Some(c) => word.push(c),
+                            None => return Err("Unterminated double quote".to_string()),
+                        }
+                    }
+                }
+                '\\' => match chars.next() {
+                    // Line continuation
+                    Some('\n') | Some('\r') => {}
+                    Some(c) => {
+                        in_word = true;
+                        word.push(c);
+                    }
+                    None => {}
+                },
+                c if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                c => {
+                    in_word = true;
+                    word.push(c);
+                }
+            }
+        }
+        if in_word {
+            words.push(word);
+        }
+        Ok(words)
+    }
+
+    fn parse_curl_command(command: &str, settings: &AppSettings) -> Result<HttpRequest, String> {
+        // Short options that take a value, which curl also accepts attached (-XPOST)
+        const SHORT_WITH_VALUE: &str = "XHdFuAebomxwTr";
+        const IGNORED_WITH_VALUE: &[&str] = &[
+            "-o",
+            "--output",
+            "-m",
+            "--max-time",
+            "--connect-timeout",
+            "-w",
+            "--write-out",
+            "--retry",
+            "-T",
+            "--upload-file",
+            "-r",
+            "--range",
+            "--cacert",
+            "--cert",
+            "--key",
+        ];
+
+        let mut words = Self::shell_words(command)?.into_iter();
+        if words.next().as_deref() != Some("curl") {
+            return Err("Expected a command starting with 'curl'".to_string());
+        }
+        let mut request = Self::new_default_request(settings);
+        request.url.clear();
+        let mut method = None;
+        let mut data: Vec<String> = vec![];
+        let mut get = false;
+
+        while let Some(word) = words.next() {
+            let (flag, inline) = if word.starts_with("--") {
+                match word.split_once('=') {
+                    Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                    None => (word.clone(), None),
+                }
+            } else if word.len() > 2
+                && word.starts_with('-')
+                && SHORT_WITH_VALUE.contains(&word[1..2])
+            {
+                (word[..2].to_string(), Some(word[2..].to_string()))
+            } else {
+                (word.clone(), None)
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| words.next())
+                    .ok_or_else(|| format!("{} needs a value", flag))
+            };
+            match flag.as_str() {
+                "-X" | "--request" => method = Some(value()?.to_uppercase()),
+                "-H" | "--header" => {
+                    let header = value()?;
+                    if let Some((key, value)) = header.split_once(':') {
+                        request
+                            .headers
+                            .push(KeyValue::new(key.trim(), value.trim()));
+                    }
+                }
+                // Only --data-binary sends a file exactly as it is
+                "--data-binary" => {
+                    let value = value()?;
+                    match value.strip_prefix('@') {
+                        Some(file_path) => request.binary_file = file_path.to_string(),
+                        None => data.push(value),
+                    }
+                }
+                "-d" | "--data" | "--data-raw" | "--data-ascii" => data.push(value()?),
+                "--json" => {
+                    data.push(value()?);
+                    request
+                        .headers
+                        .push(KeyValue::new("Content-Type", "application/json"));
+                }
+                "--data-urlencode" => {
+                    let pair = value()?;
+                    let (key, value) = pair.split_once('=').unwrap_or(("", pair.as_str()));
+                    request.url_encoded_data.push(KeyValue::new(key, value));
+                }
+                "-F" | "--form" => {
+                    let field = value()?;
+                    let (key, value) = field.split_once('=').unwrap_or((field.as_str(), ""));
+                    // curl takes the part's type after the value: name=@file;type=image/png
+                    let (value, part_type) = match value.rsplit_once(";type=") {
+                        Some((value, part_type)) => (value, part_type),
+                        None => (value, ""),
+                    };
+                    let mut entry = match value.strip_prefix('@') {
+                        Some(file_path) => {
+                            FormDataEntry::file(key.to_string(), file_path.to_string())
+                        }
+                        None => FormDataEntry::text(key.to_string(), value.to_string()),
+                    };
+                    *entry.part_options_mut().0 = part_type.to_string();
+                    request.form_data.push(entry);
+                }
+                "-u" | "--user" => {
+                    let credentials = value()?;
+                    let (username, password) =
+                        credentials.split_once(':').unwrap_or((&credentials, ""));
+                    request.auth = RequestAuth::Basic {
+                        username: username.to_string(),
+                        password: password.to_string(),
+                    };
+                }
+                "-A" | "--user-agent" => {
+                    request.headers.push(KeyValue::new("User-Agent", value()?))
+                }
+                "-e" | "--referer" => request.headers.push(KeyValue::new("Referer", value()?)),
+                "-b" | "--cookie" => request.headers.push(KeyValue::new("Cookie", value()?)),
+                "--url" => request.url = value()?,
+                "-G" | "--get" => get = true,
+                "-I" | "--head" => method = Some("HEAD".to_string()),
+                "-k" | "--insecure" => request.settings.skip_tls_verification = Some(true),
+                "-x" | "--proxy" => {
+                    let url = value()?;
+                    request.settings.proxy = Some(ProxySettings {
+                        enabled: !url.is_empty(),
+                        url,
+                        use_system_proxy: false,
+                        ..Default::default()
+                    });
+                }
+                "--noproxy" => {
+                    let hosts = value()?;
+                    request.settings.proxy.get_or_insert_default().no_proxy = hosts;
+                }
+                flag if IGNORED_WITH_VALUE.contains(&flag) => {
+                    value()?;
+                }
+                // Other switches (-s, -L, --compressed, ...) don't change the request
+                flag if flag.starts_with('-') && flag.len() > 1 => {}
+                _ if request.url.is_empty() => request.url = word,
+                _ => return Err(format!("Unexpected argument '{}'", word)),
+            }
+        }
+
+        if request.url.is_empty() {
+            return Err("No URL found in the curl command".to_string());
+        }
+        let has_body = !data.is_empty()
+            || !request.binary_file.is_empty()
+            || !request.form_data.is_empty()
+            || !request.url_encoded_data.is_empty();
+        if get && !data.is_empty() {
+            let separator = if request.url.contains('?') { '&' } else { '?' };
+            request.url = format!("{}{}{}", request.url, separator, data.join("&"));
+        } else if !data.is_empty() {
+            request.body = data.join("&");
+            let content_type = KeyValue::enabled(&request.headers)
+                .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+                .map(|(_, value)| value.to_lowercase());
+            let trimmed = request.body.trim_start();
+            request.body_type = if content_type.as_deref().is_some_and(|t| t.contains("json"))
+                || trimmed.starts_with('{')
+                || trimmed.starts_with('[')
+            {
+                BodyType::Json
+            } else if content_type.is_none_or(|t| t.contains("x-www-form-urlencoded"))
+                && request.body.split('&').all(|pair| pair.contains('='))
+            {
+                request.url_encoded_data = request
+                    .body
+                    .split('&')
+                    .filter_map(|pair| pair.split_once('='))
+                    .map(|(key, value)| {
+                        let decode = |s: &str| {
+                            urlencoding::decode(&s.replace('+', " "))
+                                .map_or_else(|_| s.to_string(), |s| s.into_owned())
+                        };
+                        KeyValue::new(decode(key), decode(value))
+                    })
+                    .collect();
+                request.body.clear();
+                BodyType::UrlEncoded
+            } else {
+                BodyType::Raw
+            };
+        } else if !request.binary_file.is_empty() {
+            request.body_type = BodyType::Binary;
+        } else if !request.form_data.is_empty() {
+            request.body_type = BodyType::FormData;
+        } else if !request.url_encoded_data.is_empty() {
+            request.body_type = BodyType::UrlEncoded;
+        }
+        request.method =
+            method.unwrap_or_else(|| if has_body && !get { "POST" } else { "GET" }.to_string());
+        let without_scheme = request
+            .url
+            .split_once("://")
+            .map_or(request.url.as_str(), |(_, rest)| rest);
+        request.name = format!(
+            "{} {}",
+            request.method,
+            Self::split_captured_url(without_scheme).1
+        );
+        request.sync_query_params();
+        request.sync_path_variables();
+        Ok(request)
+    }
+
+    fn draw_curl_import_dialog(&mut self, ctx: &egui::Context) {
+        let Some(command) = &mut self.curl_import else {
+            return;
+        };
+        let mut open = true;
+        let mut import = false;
+        let mut cancel = false;
+        egui::Window::new(tr("import-curl"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(tr("paste-a-curl-command"));
+                ui.add(
+                    TextEdit::multiline(command)
+                        .code_editor()
+                        .desired_rows(6)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("curl -X POST https://example.com -H 'Content-Type: application/json' -d '{}'"),
+                );
+                if let Some(error) = &self.curl_import_error {
+                    ui.colored_label(Color32::from_rgb(215, 58, 73), error);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("import")).clicked() {
+                        import = true;
+                    }
+                    if ui.button(tr("cancel")).clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if import && let Some(command) = &self.curl_import {
+            match Self::parse_curl_command(command, &self.settings) {
+                Ok(request) => {
+                    self.stash_draft();
+                    self.current_request = request;
+                    self.current_response = None;
+                    self.request_error = None;
+                    self.curl_import = None;
+                    self.curl_import_error = None;
+                    self.show_welcome = false;
+                }
+                Err(e) => self.curl_import_error = Some(e),
+            }
+        } else if cancel || !open {
+            self.curl_import = None;
+            self.curl_import_error = None;
+        }
+    }
+
+    fn folder_has_requests(folder: &Folder) -> bool {
+        !folder.requests.is_empty() || folder.folders.iter().any(Self::folder_has_requests)
+    }
+
+    fn add_sample_collection(&mut self) {
+        let request = |name: &str, method: &str, url: &str| {
+            Self::imported_request(name.to_string(), method.to_string(), url.to_string())
+        };
+        let get = request(
+            "Get with query parameters",
+            "GET",
+            "https://httpbin.org/get?page=1&search=send",
+        );
+        let mut post = request("Post JSON", "POST", "https://httpbin.org/post");
+        post.headers = vec![KeyValue::new("Content-Type", "application/json")];
+        post.body = "{\n  \"name\": \"Send\",\n  \"sample\": true\n}".to_string();
+        post.body_type = BodyType::Json;
+        let mut form = request("Submit a form", "PUT", "https://httpbin.org/put");
+        form.url_encoded_data = vec![KeyValue::new("greeting", "hello")];
+        form.body_type = BodyType::UrlEncoded;
+        let mut basic = request(
+            "Basic auth",
+            "GET",
+            "https://httpbin.org/basic-auth/user/pass",
+        );
+        basic.auth = RequestAuth::Basic {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        };
+
+        let mut responses = Self::empty_folder("Responses");
+        responses.requests = vec![
+            request("Not found", "GET", "https://httpbin.org/status/404"),
+            request("Slow response", "GET", "https://httpbin.org/delay/2"),
+            request("Redirect", "GET", "https://httpbin.org/redirect/2"),
+            request("Delete", "DELETE", "https://httpbin.org/delete"),
+        ];
+        let mut root_folder = Self::empty_folder("Root");
+        root_folder.requests = vec![get, post, form, basic];
+        root_folder.folders = vec![responses];
+
+        let workspace = self.current_workspace_mut();
+        workspace.collections.push(Collection {
+            id: Uuid::new_v4().to_string(),
+            name: "Sample Requests".to_string(),
+            root_folder,
+            token: None,
+            auth: RequestAuth::None,
+            signer: None,
+        });
+        workspace.selected_collection = Some(workspace.collections.len() - 1);
+        workspace.selected_folder_path = vec![];
+        workspace.selected_request = None;
+        self.selected_sidebar_item = Some(SidebarItem::Collections);
+        self.show_welcome = false;
+        self.collections_generation += 1;
+        self.auto_save_workspace();
+        self.save_cache();
+    }
+
+    fn draw_welcome(&mut self, ui: &mut Ui) {
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(40.0);
+                ui.label(RichText::new(tr("welcome-title")).size(28.0).strong());
+                ui.label(tr("welcome-subtitle"));
+                ui.add_space(24.0);
+
+                let button = |text: String| egui::Button::new(text).min_size([260.0, 28.0].into());
+
+                ui.heading(tr("welcome-start"));
+                if ui.add(button(tr("new-request"))).clicked() {
+                    self.current_request = Self::new_default_request(&self.settings);
+                    self.show_welcome = false;
+                }
+                if ui.add(button(tr("open-sample-collection"))).clicked() {
+                    self.add_sample_collection();
+                }
+                ui.add_space(16.0);
+
+                ui.heading(tr("welcome-import"));
+                if ui
+                    .add(button(tr("import-from-postman")))
+                    .on_hover_text(tr("import-from-postman-hint"))
+                    .clicked()
+                {
+                    self.import_collection();
+                }
+                if ui
+                    .add(button(tr("import-from-openapi")))
+                    .on_hover_text(tr("import-from-openapi-hint"))
+                    .clicked()
+                {
+                    self.import_collection();
+                }
+                if ui.add(button(tr("import-from-curl"))).clicked() {
+                    self.curl_import = Some(String::new());
+                }
+                ui.add_space(16.0);
+
+                ui.heading(tr("recent-workspaces"));
+                let mut open = None;
+                for path in &self.recent_workspaces {
+                    let name = path.file_stem().map_or_else(
+                        || path.display().to_string(),
+                        |name| name.to_string_lossy().to_string(),
+                    );
+                    if ui
+                        .add_enabled(path.exists(), button(name))
+                        .on_hover_text(path.display().to_string())
+                        .on_disabled_hover_text(tr("file-not-found"))
+                        .clicked()
+                    {
+                        open = Some(path.clone());
+                    }
+                }
+                if self.recent_workspaces.is_empty() {
+                    ui.colored_label(Color32::GRAY, tr("no-recent-workspaces"));
+                }
+                if ui.add(button(tr("load-workspace"))).clicked() {
+                    self.load_from_file();
+                }
+                if let Some(path) = open {
+                    self.open_workspace_file(path);
+                }
+            });
+        });
+    }
+
+    fn draw_collections_panel(&mut self, ui: &mut Ui) {
+        // Typing in any text field takes keyboard focus away from the tree
+        if ui.memory(|memory| memory.focused().is_some()) && self.tree_rename.is_none() {
+            self.tree_focused = false;
+        }
+        let nodes = Self::visible_tree_nodes(self.current_workspace());
+        let moved = if self.tree_focused && self.tree_rename.is_none() {
+            self.handle_tree_keys(ui, &nodes)
+        } else {
+            false
+        };
+        // Deletes and renames above may have changed the tree
+        let nodes = Self::visible_tree_nodes(self.current_workspace());
+
+        if !self.tree_selection.is_empty() {
+            ui.horizontal(|ui| {
+                let mut args = FluentArgs::new();
+                args.set("count", self.tree_selection.len());
+                ui.label(tr_args("selected-requests", &args));
+                if ui.button(tr("send-in-parallel")).clicked() {
+                    self.send_selected_requests();
+                }
+                if ui.button(tr("clear")).clicked() {
+                    self.tree_selection.clear();
+                }
+            });
+            ui.separator();
+        }
+
+        let mut activated = None;
+        let mut delete_node = None;
+        let mut collection_token = None;
+        let mut edit_auth = None;
+        let mut edit_signer = None;
+        let mut name_from_url = None;
+        let mut export_script = None;
+        let mut toggle_selected = None;
+        let mut send_selected = false;
+        let mut rename_done = None;
+        let mut tree_rename = self.tree_rename.take();
+        let tree = ScrollArea::vertical().show(ui, |ui| {
+            let workspace = self.current_workspace();
+            for (node, depth) in &nodes {
+                let row = ui.horizontal(|ui| {
+                    ui.add_space(*depth as f32 * 16.0);
+                    if let Some((renaming, name)) = &mut tree_rename
+                        && renaming == node
+                    {
+                        let response = ui.add(TextEdit::singleline(name).desired_width(180.0));
+                        if !response.has_focus() && !response.lost_focus() {
+                            response.request_focus();
+                        }
+                        if response.lost_focus() {
+                            rename_done =
+                                Some(!ui.input(|input| input.key_pressed(egui::Key::Escape)));
+                        }
+                        return;
+                    }
+                    let response = match node {
+                        TreeNode::Collection(collection_idx) => {
+                            let collection = &workspace.collections[*collection_idx];
+                            let response = ui.selectable_label(
+                                workspace.selected_collection == Some(*collection_idx),
+                                &collection.name,
+                            );
+                            if let Some(token) = &collection.token {
+                                ui.label("🔑").on_hover_text(token);
+                            }
+                            response.context_menu(|ui| {
+                                Self::export_script_menu(ui, node, &mut export_script);
+                                if ui.button(tr("name-requests-from-url")).clicked() {
+                                    name_from_url = Some(*collection_idx);
+                                    ui.close_menu();
+                                }
+                                if ui.button(tr("auth-menu")).clicked() {
+                                    let auth = match &collection.auth {
+                                        RequestAuth::Inherit => RequestAuth::None,
+                                        auth => auth.clone(),
+                                    };
+                                    edit_auth = Some((node.clone(), auth));
+                                    ui.close_menu();
+                                }
+                                if ui.button(tr("signer-menu")).clicked() {
+                                    edit_signer = Some((
+                                        *collection_idx,
+                                        collection.signer.clone().unwrap_or_default(),
+                                    ));
+                                    ui.close_menu();
+                                }
+                                ui.menu_button(tr("token-label"), |ui| {
+                                    if ui
+                                        .radio(collection.token.is_none(), tr("no-token"))
+                                        .clicked()
+                                    {
+                                        collection_token = Some((*collection_idx, None));
+                                        ui.close_menu();
+                                    }
+                                    for token in &workspace.settings.tokens {
+                                        if ui
+                                            .radio(
+                                                collection.token.as_ref() == Some(&token.name),
+                                                &token.name,
+                                            )
+                                            .clicked()
+                                        {
+                                            collection_token =
+                                                Some((*collection_idx, Some(token.name.clone())));
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                            });
+                            response
+                        }
+                        TreeNode::Folder(collection_idx, path) => {
+                            ui.label("📁");
+                            let folder = Self::get_folder_by_path(
+                                &workspace.collections[*collection_idx],
+                                path,
+                            );
+                            let name = folder
+                                .map(|folder| folder.name.as_str())
+                                .unwrap_or_default();
+                            let response =
+                                ui.selectable_label(workspace.selected_folder_path == *path, name);
+                            response.context_menu(|ui| {
+                                Self::export_script_menu(ui, node, &mut export_script);
+                                if let Some(folder) = folder
+                                    && ui.button(tr("auth-menu")).clicked()
+                                {
+                                    edit_auth = Some((node.clone(), folder.auth.clone()));
+                                    ui.close_menu();
+                                }
+                            });
+                            response
+                        }
+                        TreeNode::Request(collection_idx, path, request_idx) => {
+                            let Some(request) = Self::get_folder_by_path(
+                                &workspace.collections[*collection_idx],
+                                path,
+                            )
+                            .and_then(|folder| folder.requests.get(*request_idx)) else {
+                                return;
+                            };
+                            ui.label(
+                                RichText::new(Self::method_badge(&request.method))
+                                    .monospace()
+                                    .color(self.method_color(&request.method)),
+                            )
+                            .on_hover_text(&request.method);
+                            let response = ui.selectable_label(
+                                workspace.selected_request == Some(*request_idx)
+                                    || self.tree_selection.contains(&request.id),
+                                &request.name,
+                            );
+                            if response.clicked() && ui.input(|input| input.modifiers.command) {
+                                toggle_selected = Some(request.id.clone());
+                            }
+                            let dirty = if request.id == self.current_request.id {
+                                *request != self.current_request
+                            } else {
+                                self.drafts.contains_key(&request.id)
+                            };
+                            if dirty {
+                                ui.label(RichText::new("●").color(Color32::from_rgb(255, 165, 0)))
+                                    .on_hover_text(tr("unsaved-changes"));
+                            }
+                            response.context_menu(|ui| {
+                                if !self.tree_selection.is_empty() {
+                                    let mut args = FluentArgs::new();
+                                    args.set("count", self.tree_selection.len());
+                                    if ui
+                                        .button(tr_args("send-selected-in-parallel", &args))
+                                        .clicked()
+                                    {
+                                        send_selected = true;
+                                        ui.close_menu();
+                                    }
+                                    ui.separator();
+                                }
+                                Self::export_script_menu(ui, node, &mut export_script);
+                                if ui.button(tr("copy-link")).clicked() {
+                                    ui.ctx()
+                                        .copy_text(format!("send://request?id={}", request.id));
+                                    ui.close_menu();
+                                }
+                                if ui.button(tr("delete")).clicked() {
+                                    delete_node = Some(node.clone());
+                                    ui.close_menu();
+                                }
+                            });
+                            response
+                        }
+                    };
+                    if response.clicked() && toggle_selected.is_none() {
+                        activated = Some(node.clone());
+                    }
+                });
+                if self.tree_focused && self.tree_cursor.as_ref() == Some(node) {
+                    ui.painter().rect_stroke(
+                        row.response.rect.expand(1.0),
+                        2.0,
+                        ui.visuals().selection.stroke,
+                    );
+                    if moved {
+                        row.response.scroll_to_me(None);
+                    }
+                }
+            }
+        });
+        self.tree_rename = tree_rename;
+
+        if let Some(id) = toggle_selected {
+            // The open request joins the selection so Ctrl+click builds on it
+            if self.tree_selection.is_empty()
+                && self.current_workspace().selected_request.is_some()
+                && self.current_request.id != id
+            {
+                self.tree_selection.push(self.current_request.id.clone());
+            }
+            match self
+                .tree_selection
+                .iter()
+                .position(|selected| *selected == id)
+            {
+                Some(idx) => {
+                    self.tree_selection.remove(idx);
+                }
+                None => self.tree_selection.push(id),
+            }
+        }
+        if send_selected {
+            self.send_selected_requests();
+        }
+        if let Some(node) = activated {
+            if matches!(node, TreeNode::Request(..)) {
+                self.tree_selection.clear();
+            }
+            self.tree_focused = true;
+            self.tree_cursor = Some(node.clone());
+            self.activate_tree_node(&node);
+        }
+        if let Some(node) = delete_node {
+            self.request_delete_tree_node(node);
+        }
+        if edit_auth.is_some() {
+            self.auth_dialog = edit_auth;
+        }
+        if edit_signer.is_some() {
+            self.signer_dialog = edit_signer;
+        }
+        if let Some((collection_idx, token)) = collection_token {
+            self.current_workspace_mut().collections[collection_idx].token = token;
+            self.collections_generation += 1;
+            self.auto_save_workspace();
+        }
+        if let Some((node, shell)) = export_script {
+            self.export_script(&node, shell);
+        }
+        if let Some(collection_idx) = name_from_url {
+            let renamed = Self::name_requests_from_url(
+                &mut self.current_workspace_mut().collections[collection_idx].root_folder,
+            );
+            // Keep the open request in step so saving it doesn't undo the rename
+            if Self::is_default_request_name(&self.current_request.name)
+                && let Some(request) = Self::find_request_by_id(
+                    &self.current_workspace().collections[collection_idx].root_folder,
+                    &self.current_request.id,
+                )
+            {
+                self.current_request.name = request.name.clone();
+            }
+            let mut args = FluentArgs::new();
+            args.set("count", renamed);
+            self.notify(ToastKind::Success, tr_args("requests-renamed", &args));
+            self.collections_generation += 1;
+            self.auto_save_workspace();
+        }
+        if let Some(commit) = rename_done {
+            if let Some((node, name)) = self.tree_rename.take()
+                && commit
+            {
+                self.rename_tree_node(&node, name);
+            }
+            self.tree_focused = true;
+        }
+        // Clicking anywhere outside the tree hands the keyboard back
+        let clicked_outside = ui.input(|input| {
+            input.pointer.any_pressed()
+                && input
+                    .pointer
+                    .interact_pos()
+                    .is_some_and(|pos| !tree.inner_rect.contains(pos))
+        });
+        if clicked_outside {
+            self.tree_focused = false;
+        }
+    }
+
+    fn export_script_menu(
+        ui: &mut egui::Ui,
+        node: &TreeNode,
+        export: &mut Option<(TreeNode, ScriptShell)>,
+    ) {
+        ui.menu_button(tr("export-as-script"), |ui| {
+            for shell in ScriptShell::ALL {
+                if ui.button(shell.label()).clicked() {
+                    *export = Some((node.clone(), shell));
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+
+    // The rows the tree currently shows: only the selected collection is expanded,
+    // and only the folders along the selected path
+    fn visible_tree_nodes(workspace: &Workspace) -> Vec<(TreeNode, usize)> {
+        let mut nodes = vec![];
+        for (collection_idx, collection) in workspace.collections.iter().enumerate() {
+            nodes.push((TreeNode::Collection(collection_idx), 0));
+            if workspace.selected_collection == Some(collection_idx) {
+                Self::push_folder_nodes(
+                    collection_idx,
+                    &collection.root_folder,
+                    vec![],
+                    &workspace.selected_folder_path,
+                    1,
+                    &mut nodes,
+                );
+            }
+        }
+        nodes
+    }
+
+    fn push_folder_nodes(
+        collection_idx: usize,
+        folder: &Folder,
+        path: Vec<usize>,
+        selected_folder_path: &[usize],
+        depth: usize,
+        nodes: &mut Vec<(TreeNode, usize)>,
+    ) {
+        for (folder_idx, subfolder) in folder.folders.iter().enumerate() {
+            let mut subfolder_path = path.clone();
+            subfolder_path.push(folder_idx);
+            nodes.push((
+                TreeNode::Folder(collection_idx, subfolder_path.clone()),
+                depth,
+            ));
+            if selected_folder_path.starts_with(&subfolder_path) {
+                Self::push_folder_nodes(
+                    collection_idx,
+                    subfolder,
+                    subfolder_path,
+                    selected_folder_path,
+                    depth + 1,
+                    nodes,
+                );
+            }
+        }
+        if selected_folder_path == path {
+            for request_idx in 0..folder.requests.len() {
+                nodes.push((
+                    TreeNode::Request(collection_idx, path.clone(), request_idx),
+                    depth,
+                ));
+            }
+        }
+    }
+
+    fn activate_tree_node(&mut self, node: &TreeNode) {
+        self.stash_draft();
+        let workspace = self.current_workspace_mut();
+        match node {
+            TreeNode::Collection(collection_idx) => {
+                workspace.selected_collection = Some(*collection_idx);
+                workspace.selected_folder_path = vec![];
+                workspace.selected_request = None;
+            }
+            TreeNode::Folder(collection_idx, path) => {
+                workspace.selected_collection = Some(*collection_idx);
+                workspace.selected_folder_path = path.clone();
+                workspace.selected_request = None;
+            }
+            TreeNode::Request(collection_idx, path, request_idx) => {
+                let Some(request) =
+                    Self::get_folder_by_path(&workspace.collections[*collection_idx], path)
+                        .and_then(|folder| folder.requests.get(*request_idx))
+                        .cloned()
+                else {
+                    return;
+                };
+                workspace.selected_collection = Some(*collection_idx);
+                workspace.selected_folder_path = path.clone();
+                workspace.selected_request = Some(*request_idx);
+                self.current_request = self.drafts.remove(&request.id).unwrap_or(request);
+            }
+        }
+    }
+
+    // Returns true when the cursor moved, so the tree can scroll to it
+    fn handle_tree_keys(&mut self, ui: &Ui, nodes: &[(TreeNode, usize)]) -> bool {
+        if nodes.is_empty() {
+            return false;
+        }
+        let [up, down, left, right, enter, rename, delete] = ui.input_mut(|input| {
+            [
+                egui::Key::ArrowUp,
+                egui::Key::ArrowDown,
+                egui::Key::ArrowLeft,
+                egui::Key::ArrowRight,
+                egui::Key::Enter,
+                egui::Key::F2,
+                egui::Key::Delete,
+            ]
+            .map(|key| input.consume_key(egui::Modifiers::NONE, key))
+        });
+        let position = self
+            .tree_cursor
+            .as_ref()
+            .and_then(|cursor| nodes.iter().position(|(node, _)| node == cursor));
+        let Some(position) = position else {
+            // Start from the current selection, or the top of the tree
+            let workspace = self.current_workspace();
+            let selected = nodes
+                .iter()
+                .rposition(|(node, _)| match node {
+                    TreeNode::Collection(idx) => workspace.selected_collection == Some(*idx),
+                    TreeNode::Folder(_, path) => workspace.selected_folder_path == *path,
+                    TreeNode::Request(_, _, idx) => workspace.selected_request == Some(*idx),
+                })
+                .unwrap_or(0);
+            self.tree_cursor = Some(nodes[selected].0.clone());
+            return up || down;
+        };
+        let node = nodes[position].0.clone();
+
+        if up && position > 0 {
+            self.tree_cursor = Some(nodes[position - 1].0.clone());
+            return true;
+        }
+        if down && position + 1 < nodes.len() {
+            self.tree_cursor = Some(nodes[position + 1].0.clone());
+            return true;
+        }
+        if right || enter {
+            self.activate_tree_node(&node);
+        }
+        if left {
+            let parent = match &node {
+                TreeNode::Collection(_) => None,
+                TreeNode::Folder(collection_idx, path) if path.len() > 1 => Some(TreeNode::Folder(
+                    *collection_idx,
+                    path[..path.len() - 1].to_vec(),
+                )),
+                TreeNode::Folder(collection_idx, _) => Some(TreeNode::Collection(*collection_idx)),
+                TreeNode::Request(collection_idx, path, _) if path.is_empty() => {
+                    Some(TreeNode::Collection(*collection_idx))
+                }
+                TreeNode::Request(collection_idx, path, _) => {
+                    Some(TreeNode::Folder(*collection_idx, path.clone()))
+                }
+            };
+            match parent {
+                // Collapsing a folder selects its parent
+                Some(parent) => {
+                    if matches!(node, TreeNode::Folder(..)) {
+                        self.activate_tree_node(&parent);
+                    }
+                    self.tree_cursor = Some(parent);
+                }
+                None => {
+                    let workspace = self.current_workspace_mut();
+                    workspace.selected_collection = None;
+                    workspace.selected_folder_path = vec![];
+                    workspace.selected_request = None;
+                }
+            }
+            return true;
+        }
+        if rename {
+            let workspace = self.current_workspace();
+            let name = match &node {
+                TreeNode::Collection(idx) => {
+                    workspace.collections.get(*idx).map(|c| c.name.clone())
+                }
+                TreeNode::Folder(collection_idx, path) => workspace
+                    .collections
+                    .get(*collection_idx)
+                    .and_then(|collection| Self::get_folder_by_path(collection, path))
+                    .map(|folder| folder.name.clone()),
+                TreeNode::Request(collection_idx, path, request_idx) => workspace
+                    .collections
+                    .get(*collection_idx)
+                    .and_then(|collection| Self::get_folder_by_path(collection, path))
+                    .and_then(|folder| folder.requests.get(*request_idx))
+                    .map(|request| request.name.clone()),
+            };
+            if let Some(name) = name {
+                self.tree_rename = Some((node.clone(), name));
+            }
+        }
+        if delete {
+            self.request_delete_tree_node(node.clone());
+            // Keep the cursor at the same row
+            let nodes = Self::visible_tree_nodes(self.current_workspace());
+            self.tree_cursor = nodes
+                .get(position.min(nodes.len().saturating_sub(1)))
+                .map(|(node, _)| node.clone());
+        }
+        false
+    }
+
+    fn rename_tree_node(&mut self, node: &TreeNode, name: String) {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let current_request_id = self.current_request.id.clone();
+        let workspace = self.current_workspace_mut();
+        match node {
+            TreeNode::Collection(idx) => {
+                if let Some(collection) = workspace.collections.get_mut(*idx) {
+                    collection.name = name;
+                }
+            }
+            TreeNode::Folder(collection_idx, path) => {
+                if let Some(folder) = workspace
+                    .collections
+                    .get_mut(*collection_idx)
+                    .and_then(|collection| Self::get_folder_by_path_mut(collection, path))
+                {
+                    folder.name = name;
+                }
+            }
+            TreeNode::Request(collection_idx, path, request_idx) => {
+                let Some(request) = workspace
+                    .collections
+                    .get_mut(*collection_idx)
+                    .and_then(|collection| Self::get_folder_by_path_mut(collection, path))
+                    .and_then(|folder| folder.requests.get_mut(*request_idx))
+                else {
+                    return;
+                };
+                request.name = name.clone();
+                if request.id == current_request_id {
+                    self.current_request.name = name;
+                }
+            }
+        }
+        self.collections_generation += 1;
+        self.auto_save_workspace();
+        self.save_cache();
+    }
+
+    // Collections are confirmed first; folders and requests go straight away
+    fn request_delete_tree_node(&mut self, node: TreeNode) {
+        if let TreeNode::Collection(idx) = node
+            && let Some(collection) = self.current_workspace().collections.get(idx)
+        {
+            let mut args = FluentArgs::new();
+            args.set("name", collection.name.clone());
+            self.confirm(
+                ConfirmKind::DeleteCollection,
+                tr_args("confirm-delete-collection", &args),
+                PendingAction::DeleteTreeNode(node),
+            );
+        } else {
+            self.delete_tree_node(&node);
+        }
+    }
+
+    fn delete_tree_node(&mut self, node: &TreeNode) {
+        let workspace = self.current_workspace_mut();
+        match node {
+            TreeNode::Collection(idx) => {
+                if *idx >= workspace.collections.len() {
+                    return;
+                }
+                workspace.collections.remove(*idx);
+                match workspace.selected_collection {
+                    Some(selected) if selected == *idx => {
+                        workspace.selected_collection = None;
+                        workspace.selected_folder_path = vec![];
+                        workspace.selected_request = None;
+                    }
+                    Some(selected) if selected > *idx => {
+                        workspace.selected_collection = Some(selected - 1);
+                    }
+                    _ => {}
+                }
+            }
+            TreeNode::Folder(collection_idx, path) => {
+                let Some((&folder_idx, parent_path)) = path.split_last() else {
+                    return;
+                };
+                let Some(parent) = workspace
+                    .collections
+                    .get_mut(*collection_idx)
+                    .and_then(|collection| Self::get_folder_by_path_mut(collection, parent_path))
+                else {
+                    return;
+                };
+                if folder_idx >= parent.folders.len() {
+                    return;
+                }
+                parent.folders.remove(folder_idx);
+                let selected_path = &mut workspace.selected_folder_path;
+                if selected_path.starts_with(path) {
+                    *selected_path = parent_path.to_vec();
+                    workspace.selected_request = None;
+                } else if selected_path.len() > parent_path.len()
+                    && selected_path.starts_with(parent_path)
+                    && selected_path[parent_path.len()] > folder_idx
+                {
+                    selected_path[parent_path.len()] -= 1;
+                }
+            }
+            TreeNode::Request(_, _, request_idx) => {
+                // Requests are only listed for the selected folder
+                self.delete_request(*request_idx);
+                return;
+            }
+        }
+        self.collections_generation += 1;
+        self.auto_save_workspace();
+        self.save_cache();
+    }
+
+    fn delete_request(&mut self, request_idx: usize) {
+        let workspace = self.current_workspace_mut();
+        let folder_path = workspace.selected_folder_path.clone();
+        let Some(collection) = workspace
+            .selected_collection
+            .and_then(|idx| workspace.collections.get_mut(idx))
+        else {
+            return;
+        };
+        let Some(folder) = Self::get_folder_by_path_mut(collection, &folder_path) else {
+            return;
+        };
+        if request_idx >= folder.requests.len() {
+            return;
+        }
+        folder.requests.remove(request_idx);
+        workspace.selected_request = match workspace.selected_request {
+            Some(idx) if idx == request_idx => None,
+            Some(idx) if idx > request_idx => Some(idx - 1),
+            selected => selected,
+        };
+        self.collections_generation += 1;
+        self.auto_save_workspace();
+        self.save_cache();
+    }
+
+    // Cookies the current request sends, from its own and the workspace's Cookie headers
+    fn current_cookie_count(&self) -> usize {
+        KeyValue::enabled(&self.current_request.headers)
+            .chain(
+                self.current_workspace()
+                    .settings
+                    .default_headers
+                    .iter()
+                    .map(|(key, value)| (key, value)),
+            )
+            .filter(|(key, _)| key.eq_ignore_ascii_case("cookie"))
+            .flat_map(|(_, value)| value.split(';'))
+            .filter(|cookie| !cookie.trim().is_empty())
+            .count()
+    }
+
+    fn in_flight_count(&self) -> usize {
+        let runner = self
+            .runner
+            .as_ref()
+            .filter(|runner| !runner.finished)
+            .map_or(0, |runner| {
+                runner
+                    .results
+                    .iter()
+                    .filter(|result| result.outcome.is_none())
+                    .count()
+                    .min(runner.max_concurrency)
+            });
+        let load_test = self
+            .load_test
+            .as_ref()
+            .filter(|load_test| load_test.finished_at.is_none())
+            .map_or(0, |_| self.load_test_concurrency);
+        self.is_loading as usize + self.monitors_running.len() + runner + load_test
+    }
+
+    fn cancel_request(&mut self) {
+        if let Some(task) = self.request_task.take() {
+            task.abort();
+        }
+        if self.is_loading {
+            self.is_loading = false;
+            self.response_receiver = None;
+            self.pending_request = None;
+            self.request_started = None;
+            self.retry_wait = None;
+            self.retry_wait_receiver = None;
+            self.response_progress = None;
+            self.response_progress_receiver = None;
+            self.upload_progress = None;
+            self.notify(ToastKind::Info, tr("request-cancelled"));
+        }
+    }
+
+    fn cancel_runner_item(&mut self, index: usize) {
+        if let Some(runner) = &mut self.runner
+            && let Some(entry) = runner.results.get_mut(index)
+            && entry.outcome.is_none()
+        {
+            if let Some(flag) = runner.skip.get(index) {
+                flag.store(true, Ordering::Relaxed);
+            }
+            if let Some(task) = entry.task.take() {
+                task.abort();
+            }
+            entry.outcome = Some(Err("Cancelled".to_string()));
+        }
+    }
+
+    fn cancel_monitor_run(&mut self, monitor_id: &str) {
+        if let Some(task) = self.monitor_tasks.remove(monitor_id) {
+            task.abort();
+        }
+        self.monitors_running.remove(monitor_id);
+    }
+
+    fn cancel_all_requests(&mut self) {
+        self.cancel_request();
+        if let Some(runner) = &self.runner
+            && !runner.finished
+        {
+            runner.cancel.store(true, Ordering::Relaxed);
+            for index in 0..runner.results.len() {
+                self.cancel_runner_item(index);
+            }
+        }
+        let monitor_ids: Vec<String> = self.monitors_running.iter().cloned().collect();
+        for monitor_id in monitor_ids {
+            self.cancel_monitor_run(&monitor_id);
+        }
+        if let Some(load_test) = &self.load_test {
+            load_test.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // Everything currently in flight or waiting to be sent, each with its own cancel button
+    fn draw_request_queue_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.request_queue_dialog;
+        let mut cancel_request = false;
+        let mut cancel_runner_item = None;
+        let mut cancel_monitor = None;
+        let mut stop_load_test = false;
+        let mut cancel_all = false;
+
+        egui::Window::new(tr("request-queue"))
+            .open(&mut open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let mut empty = true;
+                ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("request_queue_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            if self.is_loading
+                                && let Some(request) = &self.pending_request
+                            {
+                                empty = false;
+                                ui.label(RichText::new(&request.method).strong());
+                                ui.label(&request.name);
+                                ui.label(
+                                    self.request_started.map_or_else(String::new, |started| {
+                                        format!("{} ms", started.elapsed().as_millis())
+                                    }),
+                                );
+                                if ui.small_button(tr("cancel")).clicked() {
+                                    cancel_request = true;
+                                }
+                                ui.end_row();
+                            }
+                            if let Some(runner) = self.runner.as_ref().filter(|r| !r.finished) {
+                                for (index, entry) in runner.results.iter().enumerate() {
+                                    if entry.outcome.is_some() {
+                                        continue;
+                                    }
+                                    empty = false;
+                                    ui.label(RichText::new(&entry.method).strong());
+                                    ui.label(format!("{} › {}", runner.target, entry.name));
+                                    ui.label(if entry.task.is_some() {
+                                        tr("queue-in-flight")
+                                    } else {
+                                        tr("queue-pending")
+                                    });
+                                    if ui.small_button(tr("cancel")).clicked() {
+                                        cancel_runner_item = Some(index);
+                                    }
+                                    ui.end_row();
+                                }
+                            }
+                            let monitors = self
+                                .workspaces
+                                .iter()
+                                .flat_map(|workspace| &workspace.settings.monitors)
+                                .filter(|monitor| self.monitors_running.contains(&monitor.id));
+                            for monitor in monitors {
+                                empty = false;
+                                ui.label(RichText::new(tr("monitor")).strong());
+                                ui.label(&monitor.name);
+                                ui.label(tr("queue-in-flight"));
+                                if ui.small_button(tr("cancel")).clicked() {
+                                    cancel_monitor = Some(monitor.id.clone());
+                                }
+                                ui.end_row();
+                            }
+                            if let Some(load_test) = self
+                                .load_test
+                                .as_ref()
+                                .filter(|load_test| load_test.finished_at.is_none())
+                            {
+                                empty = false;
+                                ui.label(RichText::new(tr("load-test")).strong());
+                                ui.label(&load_test.target);
+                                ui.label(format!("{} s", load_test.started.elapsed().as_secs()));
+                                if ui.small_button(tr("stop")).clicked() {
+                                    stop_load_test = true;
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                if empty {
+                    ui.colored_label(Color32::GRAY, tr("queue-empty"));
+                } else {
+                    ctx.request_repaint_after(Duration::from_millis(250));
+                }
+                ui.separator();
+                if ui
+                    .add_enabled(!empty, egui::Button::new(tr("cancel-all")))
+                    .clicked()
+                {
+                    cancel_all = true;
+                }
+            });
+
+        if cancel_request {
+            self.cancel_request();
+        }
+        if let Some(index) = cancel_runner_item {
+            self.cancel_runner_item(index);
+        }
+        if let Some(monitor_id) = cancel_monitor {
+            self.cancel_monitor_run(&monitor_id);
+        }
+        if stop_load_test && let Some(load_test) = &self.load_test {
+            load_test.cancel.store(true, Ordering::Relaxed);
+        }
+        if cancel_all {
+            self.cancel_all_requests();
+        }
+        self.request_queue_dialog = open;
+    }
+
+    fn draw_status_bar(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let workspace = self.current_workspace();
+            ui.label(format!("🗂 {}", workspace.name));
+            ui.separator();
+            let environment = workspace
+                .selected_environment
+                .and_then(|idx| workspace.environments.get(idx))
+                .map_or_else(
+                    || tr("no-environment"),
+                    |environment| environment.name.clone(),
+                );
+            if ui
+                .add(egui::Label::new(format!("🌍 {}", environment)).sense(egui::Sense::click()))
+                .on_hover_text(tr("switch-environment"))
+                .clicked()
+            {
+                self.environment_switcher = true;
+            }
+            ui.separator();
+
+            let mut args = FluentArgs::new();
+            if let Some(proxy) = self.effective_proxy(&self.current_request) {
+                args.set("url", proxy.url);
+                ui.label(tr_args("status-proxy", &args));
+            } else if let Some(system_proxy) = self.effective_system_proxy(&self.current_request) {
+                args.set("url", system_proxy.url().to_string());
+                ui.label(tr_args("status-proxy-system", &args));
+            } else {
+                ui.label(tr("status-proxy-off"));
+            }
+            ui.separator();
+
+            let mut args = FluentArgs::new();
+            args.set("count", self.current_cookie_count());
+            ui.label(tr_args("status-cookies", &args))
+                .on_hover_text(tr("status-cookies-hint"));
+
+            if self.monitors_paused {
+                ui.separator();
+                if ui
+                    .add(egui::Label::new(tr("monitors-paused")).sense(egui::Sense::click()))
+                    .on_hover_text(tr("resume-monitors"))
+                    .clicked()
+                {
+                    self.monitors_paused = false;
+                }
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let in_flight = self.in_flight_count();
+                if in_flight > 0 {
+                    let mut args = FluentArgs::new();
+                    args.set("count", in_flight);
+                    ui.spinner();
+                    if ui
+                        .add(
+                            egui::Label::new(tr_args("status-in-flight", &args))
+                                .sense(egui::Sense::click()),
+                        )
+                        .on_hover_text(tr("request-queue"))
+                        .clicked()
+                    {
+                        self.request_queue_dialog = true;
+                    }
+                    ui.separator();
+                }
+                if self.current_workspace().file_path.is_none() {
+                    ui.label(tr("status-not-saved"))
+                        .on_hover_text(tr("status-not-saved-hint"));
+                } else if let Some(saved) = self.last_auto_save {
+                    let mut args = FluentArgs::new();
+                    args.set("time", saved.format("%H:%M:%S").to_string());
+                    ui.label(tr_args("status-saved", &args));
+                } else {
+                    ui.label(tr("status-auto-save-on"));
+                }
+            });
+        });
+    }
+
+    fn toggle_sidebar(&mut self, item: SidebarItem) {
+        if self.selected_sidebar_item.as_ref() == Some(&item) && !self.layout.sidebar_pinned {
+            self.selected_sidebar_item = None;
+        } else {
+            self.selected_sidebar_item = Some(item);
+        }
+        self.save_cache();
+    }
+
+    fn draw_panes(&mut self, ui: &mut Ui) {
+        let available = ui.available_size();
+        match self.layout.mode {
+            PanelLayout::Stacked => {
+                let request_pane = egui::TopBottomPanel::top("request_pane")
+                    .resizable(true)
+                    .frame(egui::Frame::none())
+                    .min_height(150.0)
+                    .max_height((available.y - 150.0).max(150.0))
+                    .default_height(available.y * self.layout.stacked_split)
+                    .show_inside(ui, |ui| {
+                        self.draw_request_panel(ui);
+                    });
+                let split = request_pane.response.rect.height() / available.y;
+                if (split - self.layout.stacked_split).abs() > 0.001 {
+                    self.layout.stacked_split = split;
+                    self.layout_dirty = true;
+                }
+            }
+            PanelLayout::SideBySide => {
+                let request_pane = egui::SidePanel::left("request_pane_side")
+                    .resizable(true)
+                    .frame(egui::Frame::none().inner_margin(egui::Margin {
+                        right: 8.0,
+                        ..Default::default()
+                    }))
+                    .min_width(300.0)
+                    .max_width((available.x - 250.0).max(300.0))
+                    .default_width(available.x * self.layout.side_by_side_split)
+                    .show_inside(ui, |ui| {
+                        self.draw_request_panel(ui);
+                    });
+                let split = request_pane.response.rect.width() / available.x;
+                if (split - self.layout.side_by_side_split).abs() > 0.001 {
+                    self.layout.side_by_side_split = split;
+                    self.layout_dirty = true;
+                }
+            }
+            PanelLayout::Single => {
+                ui.horizontal(|ui| {
+                    let mut changed = ui
+                        .selectable_value(
+                            &mut self.layout.single_pane,
+                            Pane::Request,
+                            tr("request"),
+                        )
+                        .changed();
+                    changed |= ui
+                        .selectable_value(
+                            &mut self.layout.single_pane,
+                            Pane::Response,
+                            tr("response"),
+                        )
+                        .changed();
+                    if changed {
+                        self.save_cache();
+                    }
+                });
+                ui.separator();
+                match self.layout.single_pane {
+                    Pane::Request => self.draw_request_panel(ui),
+                    Pane::Response => self.draw_response_panel(ui),
+                }
+                return;
+            }
+        }
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none())
+            .show_inside(ui, |ui| {
+                self.draw_response_panel(ui);
+            });
+    }
+
+    fn draw_tools_panel(&mut self, ui: &mut Ui) {
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.label(tr("tools-input"));
+            ui.add(
+                TextEdit::multiline(&mut self.tools_input)
+                    .desired_rows(6)
+                    .desired_width(f32::INFINITY)
+                    .code_editor(),
+            );
+            ui.horizontal_wrapped(|ui| {
+                if ui
+                    .add_enabled(
+                        !self.response_selection.is_empty(),
+                        egui::Button::new(tr("use-response-selection")),
+                    )
+                    .on_disabled_hover_text(tr("select-text-in-the-response-body"))
+                    .clicked()
+                {
+                    self.tools_input = self.response_selection.clone();
+                }
+                if let Some(response) = &self.current_response
+                    && ui.button(tr("use-whole-response")).clicked()
+                {
+                    self.tools_input = response.body.clone();
+                }
+            });
+            ui.separator();
+
+            ui.horizontal_wrapped(|ui| {
+                for tool in Tool::ALL {
+                    if ui.button(tool.label()).clicked() {
+                        self.tools_output = tool.run(&self.tools_input);
+                    }
+                }
+            });
+            ui.separator();
+
+            match &self.tools_output {
+                Ok(output) => {
+                    ui.label(tr("tools-output"));
+                    ui.add(
+                        TextEdit::multiline(&mut output.as_str())
+                            .desired_rows(8)
+                            .desired_width(f32::INFINITY)
+                            .code_editor(),
+                    );
+                    let output = output.clone();
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("copy")).clicked() {
+                            ui.ctx().copy_text(output.clone());
+                        }
+                        if ui.button(tr("use-as-input")).clicked() {
+                            self.tools_input = output;
+                        }
+                    });
+                }
+                Err(e) => {
+                    ui.colored_label(Color32::from_rgb(255, 0, 0), e);
+                }
+            }
+        });
+    }
+
+    fn draw_history_panel(&mut self, ui: &mut Ui) {
+        let mut selected_entry = None;
+
+        if self.history.is_empty() {
+            ui.label(tr("no-requests-sent-yet"));
+        } else if ui.button(tr("clear-history")).clicked() {
+            self.history.clear();
+            self.save_cache();
+            return;
+        }
+
+        ScrollArea::vertical().show(ui, |ui| {
+            for (idx, entry) in self.history.iter().enumerate() {
+                let status_text = self.status_text(
+                    entry.status,
+                    if entry.status == 0 {
+                        "ERR".to_string()
+                    } else {
+                        entry.status.to_string()
+                    },
+                );
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(&entry.request.method)
+                            .strong()
+                            .color(self.method_color(&entry.request.method)),
+                    );
+                    if ui
+                        .selectable_label(false, &entry.request.url)
+                        .on_hover_text(format!("{} ms", entry.time))
+                        .clicked()
+                    {
+                        selected_entry = Some(idx);
+                    }
+                    ui.label(status_text);
+                    if entry.attempt > 1 {
+                        let mut args = FluentArgs::new();
+                        args.set("attempt", entry.attempt);
+                        ui.label(format!("↻{}", entry.attempt))
+                            .on_hover_text(tr_args("attempt-number", &args));
+                    }
+                });
+            }
+        });
+
+        if let Some(idx) = selected_entry {
+            self.stash_draft();
+            // Load a detached copy so edits don't overwrite a saved request
+            self.current_request = self.history[idx].request.clone();
+            self.current_request.id = Uuid::new_v4().to_string();
+            self.current_workspace_mut().selected_request = None;
+        }
+    }
+
+    fn draw_environment_panel(&mut self, ui: &mut Ui) {
+        let current_workspace_idx = self.current_workspace;
+        let mut env_changed = false;
+        let mut pending = None;
+
+        // Environment selector and management
+        let workspace = &mut self.workspaces[current_workspace_idx];
+        ui.horizontal(|ui| {
+            let selected_text = if let Some(env_idx) = workspace.selected_environment {
+                if env_idx < workspace.environments.len() {
+                    workspace.environments[env_idx].name.clone()
+                } else {
+                    tr("no-environment")
+                }
+            } else {
+                tr("no-environment")
+            };
+
+            egui::ComboBox::from_label(tr("active-environment"))
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut workspace.selected_environment,
+                        None,
+                        tr("no-environment"),
+                    );
+                    for (idx, env) in workspace.environments.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut workspace.selected_environment,
+                            Some(idx),
+                            &env.name,
+                        );
+                    }
+                });
+
+            if ui.button(tr("new-environment")).clicked() {
+                self.new_environment_dialog = true;
+            }
+            if let Some(env_idx) = workspace.selected_environment
+                && let Some(env) = workspace.environments.get(env_idx)
+            {
+                let mut args = FluentArgs::new();
+                args.set("name", env.name.clone());
+                if ui
+                    .button("🗑")
+                    .on_hover_text(tr("delete-environment"))
+                    .clicked()
+                {
+                    pending = Some((
+                        ConfirmKind::DeleteEnvironment,
+                        tr_args("confirm-delete-environment", &args),
+                        PendingAction::DeleteEnvironment(env_idx),
+                    ));
+                }
+                if ui
+                    .add_enabled(
+                        !env.variables.is_empty(),
+                        egui::Button::new(tr("clear-variables")),
+                    )
+                    .clicked()
+                {
+                    args.set("count", env.variables.len());
+                    pending = Some((
+                        ConfirmKind::ClearVariables,
+                        tr_args("confirm-clear-variables", &args),
+                        PendingAction::ClearVariables(env_idx),
+                    ));
+                }
+            }
+        });
+        if let Some((kind, message, action)) = pending.take() {
+            self.confirm(kind, message, action);
+            return;
+        }
+        let workspace = &mut self.workspaces[current_workspace_idx];
+        ui.separator();
+        // Variables
+        if let Some(env_idx) = workspace.selected_environment
+            && env_idx < workspace.environments.len()
+        {
+            ui.label(tr("variables"));
+            ScrollArea::vertical().show(ui, |ui| {
+                let workspace = &mut self.workspaces[current_workspace_idx];
+                let env = &mut workspace.environments[env_idx];
+                let mut to_remove = Vec::new();
+
+                // Table header
+                ui.horizontal(|ui| {
+                    ui.label(tr("key"));
+                    ui.add_space(150.0);
+                    ui.label(tr("value"));
+                });
+                ui.separator();
+
+                // Pre-calculate duplicate information to avoid borrow checker issues
+                let mut duplicate_keys = Vec::new();
+                for (i, (key, _)) in env.variables.iter().enumerate() {
+                    let is_duplicate = !key.trim().is_empty()
+                        && env
+                            .variables
+                            .iter()
+                            .enumerate()
+                            .filter(|(idx, (k, _))| *idx != i && k.trim() == key.trim())
+                            .count()
+                            > 0;
+                    duplicate_keys.push(is_duplicate);
+                }
+
+                let mut secret_toggled = Vec::new();
+                let mut secret_renamed = Vec::new();
+                for (i, (key, value)) in env.variables.iter_mut().enumerate() {
+                    let is_secret = env.secret_keys.contains(key);
+                    let previous_key = key.clone();
+                    ui.horizontal(|ui| {
+                        let is_duplicate = duplicate_keys.get(i).copied().unwrap_or(false);
+
+                        let key_color = if is_duplicate && !key.trim().is_empty() {
+                            Color32::from_rgb(255, 100, 100) // Red for duplicates
+                        } else if key.trim().is_empty() {
+                            Color32::from_rgb(150, 150, 150) // Gray for empty
+                        } else {
+                            Color32::WHITE // Normal
+                        };
+
+                        let mut key_edit = TextEdit::singleline(key)
+                            .hint_text(tr("variable-name"))
+                            .desired_width(150.0);
+
+                        if is_duplicate {
+                            key_edit = key_edit.text_color(key_color);
+                        }
+
+                        let key_response = ui.add(key_edit);
+                        let value_response = ui.add(
+                            TextEdit::singleline(value)
+                                .hint_text(tr("variable-value"))
+                                .password(is_secret)
+                                .desired_width(200.0),
+                        );
+
+                        if is_duplicate && !key.trim().is_empty() {
+                            ui.colored_label(Color32::from_rgb(255, 100, 100), "⚠");
+                        }
+
+                        if key_response.changed() || value_response.changed() {
+                            env_changed = true;
+                        }
+                        if key_response.changed() && is_secret {
+                            secret_renamed.push((previous_key, key.clone()));
+                        }
+
+                        if ui
+                            .selectable_label(is_secret, if is_secret { "🔒" } else { "🔓" })
+                            .on_hover_text(tr("secret-variables-are-masked-and-blanked"))
+                            .clicked()
+                        {
+                            secret_toggled.push(key.clone());
+                        }
+
+                        if ui.button("🗑").clicked() {
+                            to_remove.push(i);
+                        }
+                    });
+                }
+
+                // Toggle secret flags
+                for key in secret_toggled {
+                    if let Some(pos) = env.secret_keys.iter().position(|k| *k == key) {
+                        env.secret_keys.remove(pos);
+                    } else {
+                        env.secret_keys.push(key);
+                    }
+                    env_changed = true;
+                }
+
+                // A secret stays secret under its new name
+                for (old, new) in secret_renamed {
+                    if !env.secret_keys.contains(&new) {
+                        env.secret_keys.push(new);
+                    }
+                    if !env.variables.iter().any(|(key, _)| *key == old) {
+                        env.secret_keys.retain(|key| *key != old);
+                    }
+                }
+
+                // Remove variables
+                if !to_remove.is_empty() {
+                    for &i in to_remove.iter().rev() {
+                        env.variables.remove(i);
+                    }
+                    // So a later variable with a deleted secret's name isn't secret too
+                    env.secret_keys
+                        .retain(|name| env.variables.iter().any(|(key, _)| key == name));
+                    env_changed = true;
+                }
+
+                // Add new variable button
+                if ui.button(tr("add-variable")).clicked() {
+                    env.variables.push(("".to_string(), "".to_string()));
+                    env_changed = true;
+                }
+            });
+        }
+
+        if env_changed {
+            self.auto_save_workspace();
+        }
+    }
+
+    fn draw_request_panel(&mut self, ui: &mut Ui) {
+        // Opened from an older file; the stored copy moves too so it doesn't look edited
+        if !self.current_request.query_in_url {
+            let unedited = self
+                .saved_request()
+                .is_some_and(|saved| *saved == self.current_request);
+            self.current_request.move_query_params_into_url();
+            let migrated = self.current_request.clone();
+            if unedited && let Some(saved) = self.saved_request_mut() {
+                *saved = migrated;
+            }
+        }
+        // Migrate old JSON body type to Raw with JSON sub-type for consistency
+        if self.current_request.body_type == BodyType::Json {
+            self.current_request.body_type = BodyType::Raw;
+            self.raw_body_type = RawBodyType::JSON;
+        }
+
+        let mut save_clicked = false;
+        let mut revert_clicked = false;
+        ui.horizontal(|ui| {
+            ui.heading(tr("request"));
+            if self.has_unsaved_changes() {
+                ui.label(RichText::new("●").color(Color32::from_rgb(255, 165, 0)))
+                    .on_hover_text(tr("unsaved-changes"));
+                if ui
+                    .button(tr("save"))
+                    .on_hover_text(self.key_binding(ShortcutAction::Save).label())
+                    .clicked()
+                {
+                    save_clicked = true;
+                }
+                if ui.button(tr("revert-changes")).clicked() {
+                    revert_clicked = true;
+                }
+            }
+        });
+        if save_clicked {
+            self.save_current_request();
+        }
+        if revert_clicked {
+            self.revert_current_request();
+        }
+        ui.separator();
+        // Method and URL
+        ui.horizontal(|ui| {
+            if self.editing_method {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.current_request.method)
+                        .hint_text(tr("custom-method-hint"))
+                        .desired_width(80.0),
+                );
+                if response.lost_focus() {
+                    self.editing_method = false;
+                    let method = self.current_request.method.trim().to_uppercase();
+                    if Method::from_bytes(method.as_bytes()).is_ok() {
+                        self.current_request.method = method;
+                    } else {
+                        let mut args = FluentArgs::new();
+                        args.set("method", self.current_request.method.clone());
+                        self.notify(ToastKind::Error, tr_args("invalid-method", &args));
+                        self.current_request.method = "GET".to_string();
+                    }
+                } else if !response.has_focus() {
+                    response.request_focus();
+                }
+            } else {
+                egui::ComboBox::from_id_salt("method")
+                    .selected_text(&self.current_request.method)
+                    .width(80.0)
+                    .show_ui(ui, |ui| {
+                        for method in HTTP_METHODS {
+                            ui.selectable_value(
+                                &mut self.current_request.method,
+                                method.to_string(),
+                                method,
+                            );
+                        }
+                        ui.separator();
+                        if ui.selectable_label(false, tr("custom-method")).clicked() {
+                            self.editing_method = true;
+                        }
+                    });
+            }
+            let url_response = ui.add(
+                TextEdit::singleline(&mut self.current_request.url)
+                    .hint_text(tr("enter-url-supports-variable"))
+                    .desired_width(ui.available_width() - 150.0),
+            );
+            Self::generator_menu(&url_response, &mut self.current_request.url);
+            if url_response.changed() || url_response.context_menu_opened() {
+                self.current_request.sync_query_params();
+                self.current_request.sync_path_variables();
+            }
+            if ui
+                .button("👁")
+                .on_hover_text(tr("preview-request"))
+                .clicked()
+            {
+                let request = self.current_request.clone();
+                self.request_preview = Some(self.raw_request_preview(&request));
+            }
+            if self.is_loading {
+                if ui.button("⏸").on_hover_text(tr("cancel")).clicked() {
+                    self.cancel_request();
+                }
+            } else {
+                let send_button = ui.button(tr("send"));
+                if send_button.clicked() {
+                    self.send_request();
+                }
+                send_button.context_menu(|ui| {
+                    if ui.button(tr("send-on-new-connection")).clicked() {
+                        let mut request = self.current_request.clone();
+                        request.settings.fresh_connection = true;
+                        self.send_request_as(request, false);
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("send-and-download")).clicked() {
+                        self.send_and_download();
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("send-repeatedly")).clicked() {
+                        self.send_request_repeatedly();
+                        ui.close_menu();
+                    }
+                });
+            }
+            self.draw_request_size(ui);
+        });
+
+        // Show the exact URL when normalization changes more than a trailing slash
+        let typed_url = self.resolve_url(
+            &self
+                .current_request
+                .url_with_path_variables(|value| urlencoding::encode(value).into_owned()),
+        );
+        let typed_url = typed_url.trim();
+        let normalized_url = Self::normalize_url(typed_url);
+        if normalized_url.trim_end_matches('/') != typed_url.trim_end_matches('/') {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(tr("normalized-url")).weak());
+                ui.add(egui::Label::new(RichText::new(&normalized_url).monospace()).truncate())
+                    .on_hover_text(&normalized_url);
+            });
+        }
+
+        let token_names: Vec<String> = self
+            .current_workspace()
+            .settings
+            .tokens
+            .iter()
+            .map(|token| token.name.clone())
+            .collect();
+        let collection_token = self
+            .current_workspace()
+            .collections
+            .iter()
+            .find(|collection| {
+                Self::find_request_by_id(&collection.root_folder, &self.current_request.id)
+                    .is_some()
+            })
+            .and_then(|collection| collection.token.clone());
+        let token_expired = self
+            .effective_token(&self.current_request)
+            .is_some_and(|token| token.is_expired());
+
+        // Environment indicator
+        ui.horizontal(|ui| {
+            ui.label(tr("environment-label"));
+            let workspace = self.current_workspace();
+            if let Some(env_idx) = workspace.selected_environment {
+                if env_idx < workspace.environments.len() {
+                    ui.colored_label(
+                        Color32::from_rgb(0, 128, 255),
+                        &workspace.environments[env_idx].name,
+                    );
+                } else {
+                    ui.colored_label(Color32::GRAY, tr("no-environment"));
+                }
+            } else {
+                ui.colored_label(Color32::GRAY, tr("no-environment"));
+            }
+
+            ui.separator();
+            ui.label(tr("token-label"));
+            let inherit_label = match &collection_token {
+                Some(name) => format!("{} ({})", tr("token-from-collection"), name),
+                None => tr("no-token"),
+            };
+            let mut open_manager = false;
+            egui::ComboBox::from_id_salt("request_token")
+                .selected_text(
+                    self.current_request
+                        .token
+                        .clone()
+                        .unwrap_or_else(|| inherit_label.clone()),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.current_request.token, None, inherit_label);
+                    for name in &token_names {
+                        ui.selectable_value(
+                            &mut self.current_request.token,
+                            Some(name.clone()),
+                            name,
+                        );
+                    }
+                    ui.separator();
+                    if ui.button(tr("token-manager-menu")).clicked() {
+                        open_manager = true;
+                    }
+                });
+            if open_manager {
+                self.token_manager_dialog = true;
+            }
+            if token_expired {
+                ui.colored_label(Color32::from_rgb(255, 0, 0), tr("token-expired"))
+                    .on_hover_text(tr("token-expired-hint"));
+            }
+
+            ui.separator();
+            ui.label(tr("session-label"));
+            let mut open_sessions = false;
+            let workspace = &mut self.workspaces[self.current_workspace];
+            let previous_session = workspace.active_session;
+            egui::ComboBox::from_id_salt("active_session")
+                .selected_text(
+                    workspace
+                        .active_session
+                        .and_then(|idx| workspace.settings.sessions.get(idx))
+                        .map_or_else(|| tr("no-session"), |session| session.name.clone()),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut workspace.active_session, None, tr("no-session"));
+                    for (idx, session) in workspace.settings.sessions.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut workspace.active_session,
+                            Some(idx),
+                            &session.name,
+                        );
+                    }
+                    ui.separator();
+                    if ui.button(tr("sessions-menu")).clicked() {
+                        open_sessions = true;
+                    }
+                });
+            if open_sessions {
+                self.sessions_dialog = true;
+            }
+            if self.current_workspace().active_session != previous_session {
+                self.save_cache();
+            }
+
+            if self.settings.offline_mode {
+                ui.separator();
+                ui.colored_label(Color32::from_rgb(255, 165, 0), tr("offline-mode-badge"))
+                    .on_hover_text(tr("requests-are-not-sent-responses-come"));
+            }
+
+            if let Some(proxy) = self.effective_proxy(&self.current_request) {
+                ui.separator();
+                ui.colored_label(
+                    Color32::from_rgb(255, 165, 0),
+                    format!("🔀 Proxy: {}", proxy.url),
+                )
+                .on_hover_text(if proxy.no_proxy.trim().is_empty() {
+                    tr("proxy-routes-all")
+                } else {
+                    let mut args = FluentArgs::new();
+                    args.set("hosts", proxy.no_proxy.clone());
+                    tr_args("proxy-bypassed-for", &args)
+                });
+            } else if let Some(system_proxy) = self.effective_system_proxy(&self.current_request) {
+                ui.separator();
+                let mut args = FluentArgs::new();
+                args.set("source", system_proxy.source);
+                let mut hover = tr_args("system-proxy-detected", &args);
+                if !system_proxy.no_proxy.is_empty() {
+                    args.set("hosts", system_proxy.no_proxy.clone());
+                    hover = format!("{}\n{}", hover, tr_args("proxy-bypassed-for", &args));
+                }
+                args.set("url", system_proxy.url());
+                ui.colored_label(
+                    Color32::from_rgb(255, 165, 0),
+                    tr_args("system-proxy-badge", &args),
+                )
+                .on_hover_text(hover);
+            }
+
+            let host = reqwest::Url::parse(&self.resolve_value(&self.current_request.url))
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string));
+            let pins: Vec<String> = self
+                .current_workspace()
+                .settings
+                .certificate_pins
+                .iter()
+                .filter(|pin| host.as_deref().is_some_and(|host| pin.applies_to(host)))
+                .map(|pin| pin.fingerprint.clone())
+                .collect();
+            if !pins.is_empty() {
+                ui.separator();
+                ui.label(tr("certificate-pinned"))
+                    .on_hover_text(pins.join("\n"));
+            }
+
+            if self.effective_skip_tls_verification(&self.current_request) {
+                ui.separator();
+                ui.colored_label(
+                    Color32::from_rgb(255, 0, 0),
+                    tr("tls-verification-disabled"),
+                )
+                .on_hover_text(tr("invalid-and-self-signed-certificates-will"));
+            }
+        });
+        ui.separator();
+
+        // Request tabs (Postman style)
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_value(&mut self.request_tab, RequestTab::Params, tr("params"))
+                .changed()
+            {
+                self.save_cache();
+            }
+            if ui
+                .selectable_value(&mut self.request_tab, RequestTab::Headers, tr("headers"))
+                .changed()
+            {
+                self.save_cache();
+            }
+            if ui
+                .selectable_value(&mut self.request_tab, RequestTab::Auth, tr("auth"))
+                .changed()
+            {
+                self.save_cache();
+            }
+            if ui
+                .selectable_value(&mut self.request_tab, RequestTab::Body, tr("body"))
+                .changed()
+            {
+                self.save_cache();
+            }
+            if ui
+                .selectable_value(&mut self.request_tab, RequestTab::Tests, tr("tests"))
+                .changed()
+            {
+                self.save_cache();
+            }
+            if ui
+                .selectable_value(&mut self.request_tab, RequestTab::Settings, tr("settings"))
+                .changed()
+            {
+                self.save_cache();
+            }
+        });
+        ui.separator();
+
+        // Tab content
+        match self.request_tab {
+            RequestTab::Params => {
+                self.draw_query_params_panel(ui);
+            }
+            RequestTab::Headers => {
+                self.draw_headers_panel(ui);
+            }
+            RequestTab::Auth => {
+                self.draw_auth_panel(ui);
+            }
+            RequestTab::Body => {
+                self.draw_body_panel(ui);
+            }
+            RequestTab::Tests => {
+                self.draw_tests_panel(ui);
+            }
+            RequestTab::Settings => {
+                self.draw_request_settings_panel(ui);
+            }
+        }
+    }
+
+    // Selector for a per-request override of a workspace setting
+    fn draw_override<T: Clone + PartialEq, S: AsRef<str>>(
+        ui: &mut Ui,
+        id: &str,
+        value: &mut Option<T>,
+        inherited: &T,
+        options: &[(T, S)],
+    ) -> bool {
+        let label = |v: &T| {
+            options
+                .iter()
+                .find(|(option, _)| option == v)
+                .map(|(_, label)| label.as_ref().to_string())
+                .unwrap_or_default()
+        };
+        let mut args = FluentArgs::new();
+        args.set("value", label(inherited));
+        let default_text = tr_args("workspace-default-value", &args);
+        let selected_text = match value {
+            None => default_text.clone(),
+            Some(v) => label(v),
+        };
+        let mut changed = false;
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                changed |= ui.selectable_value(value, None, default_text).changed();
+                for (option, label) in options {
+                    changed |= ui
+                        .selectable_value(value, Some(option.clone()), label.as_ref())
+                        .changed();
+                }
+            });
+        changed
+    }
+
+    fn draw_bool_override(
+        ui: &mut Ui,
+        id: &str,
+        value: &mut Option<bool>,
+        inherited: bool,
+    ) -> bool {
+        Self::draw_override(
+            ui,
+            id,
+            value,
+            &inherited,
+            &[(true, tr("on")), (false, tr("off"))],
+        )
+    }
+
+    fn draw_request_settings_panel(&mut self, ui: &mut Ui) {
+        let workspace_settings = self.current_workspace().settings.clone();
+        let default_user_agent = self.default_user_agent();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("request_settings_grid")
+                .num_columns(2)
+                .spacing([20.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("User-Agent");
+                    ui.horizontal(|ui| {
+                        let mut override_user_agent =
+                            self.current_request.settings.user_agent.is_some();
+                        if ui
+                            .checkbox(&mut override_user_agent, tr("override"))
+                            .changed()
+                        {
+                            self.current_request.settings.user_agent =
+                                override_user_agent.then(|| default_user_agent.clone());
+                        }
+                        match &mut self.current_request.settings.user_agent {
+                            Some(user_agent) => {
+                                ui.add(
+                                    TextEdit::singleline(user_agent)
+                                        .hint_text(tr("user-agent-supports-variable"))
+                                        .desired_width(300.0),
+                                );
+                            }
+                            None => {
+                                ui.colored_label(
+                                    Color32::GRAY,
+                                    if default_user_agent.is_empty() {
+                                        "(none)"
+                                    } else {
+                                        default_user_agent.as_str()
+                                    },
+                                );
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("ip-version"));
+                    Self::draw_override(
+                        ui,
+                        "ip_preference",
+                        &mut self.current_request.settings.ip_preference,
+                        &workspace_settings.ip_preference,
+                        &IpPreference::OPTIONS.map(|(option, label)| (option, tr(label))),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("http-version"));
+                    Self::draw_override(
+                        ui,
+                        "http_version",
+                        &mut self.current_request.settings.http_version,
+                        &workspace_settings.http_version,
+                        &HttpVersion::OPTIONS,
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("accept-encoding"));
+                    egui::ComboBox::from_id_salt("accept_encoding")
+                        .selected_text(self.current_request.settings.accept_encoding.label())
+                        .show_ui(ui, |ui| {
+                            for (option, _) in AcceptEncoding::OPTIONS {
+                                ui.selectable_value(
+                                    &mut self.current_request.settings.accept_encoding,
+                                    option,
+                                    option.label(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("accept-encoding-hint"));
+                    ui.end_row();
+
+                    ui.label(tr("request-body"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(
+                            &mut self.current_request.settings.chunked,
+                            tr("chunked-transfer"),
+                        )
+                        .on_hover_text(tr("chunked-transfer-hint"));
+                        egui::ComboBox::from_id_salt("expect_continue")
+                            .selected_text(self.current_request.settings.expect_continue.label())
+                            .show_ui(ui, |ui| {
+                                for (option, label) in ExpectContinue::OPTIONS {
+                                    ui.selectable_value(
+                                        &mut self.current_request.settings.expect_continue,
+                                        option,
+                                        tr(label),
+                                    );
+                                }
+                            })
+                            .response
+                            .on_hover_text(tr("expect-continue-hint"));
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("cookies"));
+                    ui.checkbox(
+                        &mut self.current_request.settings.ignore_cookie_jar,
+                        tr("ignore-cookie-jar"),
+                    )
+                    .on_hover_text(tr("ignore-cookie-jar-hint"));
+                    ui.end_row();
+
+                    ui.label(tr("connection"));
+                    ui.checkbox(
+                        &mut self.current_request.settings.fresh_connection,
+                        tr("always-new-connection"),
+                    )
+                    .on_hover_text(tr("always-new-connection-hint"));
+                    ui.end_row();
+
+                    ui.label(tr("minimum-tls-version"));
+                    Self::draw_override(
+                        ui,
+                        "min_tls_version",
+                        &mut self.current_request.settings.min_tls_version,
+                        &workspace_settings.min_tls_version,
+                        &TlsVersion::OPTIONS,
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("maximum-tls-version"));
+                    Self::draw_override(
+                        ui,
+                        "max_tls_version",
+                        &mut self.current_request.settings.max_tls_version,
+                        &workspace_settings.max_tls_version,
+                        &TlsVersion::OPTIONS,
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("follow-redirects"));
+                    ui.horizontal(|ui| {
+                        Self::draw_bool_override(
+                            ui,
+                            "follow_redirects",
+                            &mut self.current_request.settings.follow_redirects,
+                            self.settings.follow_redirects,
+                        );
+                        let mut override_max =
+                            self.current_request.settings.max_redirects.is_some();
+                        if ui
+                            .checkbox(&mut override_max, tr("max-redirects"))
+                            .changed()
+                        {
+                            self.current_request.settings.max_redirects =
+                                override_max.then_some(self.settings.max_redirects);
+                        }
+                        if let Some(max_redirects) =
+                            &mut self.current_request.settings.max_redirects
+                        {
+                            ui.add(egui::DragValue::new(max_redirects).range(0..=50));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("disable-tls-verification"));
+                    ui.horizontal(|ui| {
+                        Self::draw_bool_override(
+                            ui,
+                            "skip_tls_verification",
+                            &mut self.current_request.settings.skip_tls_verification,
+                            workspace_settings.skip_tls_verification,
+                        );
+                        ui.colored_label(
+                            Color32::from_rgb(255, 165, 0),
+                            tr("accepts-invalid-certificates"),
+                        );
+                    });
+                    ui.end_row();
+                });
+
+            ui.separator();
+            let mut override_retry = self.current_request.settings.retry.is_some();
+            if ui
+                .checkbox(&mut override_retry, tr("override-workspace-retry-policy"))
+                .changed()
+            {
+                self.current_request.settings.retry =
+                    override_retry.then(|| workspace_settings.retry.clone());
+            }
+            if let Some(retry) = &mut self.current_request.settings.retry {
+                Self::draw_retry_policy(ui, retry);
+            }
+
+            ui.separator();
+            let mut override_proxy = self.current_request.settings.proxy.is_some();
+            if ui
+                .checkbox(&mut override_proxy, tr("override-workspace-proxy"))
+                .changed()
+            {
+                self.current_request.settings.proxy = override_proxy.then(|| {
+                    if workspace_settings.override_proxy {
+                        workspace_settings.proxy.clone()
+                    } else {
+                        self.settings.proxy.clone()
+                    }
+                });
+            }
+            if let Some(proxy) = &mut self.current_request.settings.proxy {
+                ui.push_id("request_proxy", |ui| {
+                    Self::draw_proxy_settings(ui, proxy, &mut self.system_proxy)
+                });
+            }
+        });
+    }
+
+    fn draw_auth_panel(&mut self, ui: &mut Ui) {
+        let effective = self.effective_auth(&self.current_request);
+        let cached_token = self.client_credentials(&effective).map(|oauth| {
+            let key = oauth.cache_key();
+            oauth
+                .cache
+                .try_lock()
+                .ok()
+                .and_then(|cache| cache.get(&key).cloned())
+                .map(|token| (key, token))
+        });
+        Self::draw_auth_type(ui, "request_auth", &mut self.current_request.auth, true);
+        ui.separator();
+        if self.current_request.auth == RequestAuth::Inherit {
+            let mut args = FluentArgs::new();
+            args.set("auth", effective.label());
+            ui.colored_label(Color32::GRAY, tr_args("auth-inherited", &args));
+        }
+        Self::draw_auth_fields(ui, &mut self.current_request.auth);
+
+        if effective.sets_authorization()
+            && KeyValue::enabled(&self.current_request.headers)
+                .any(|(key, _)| key.trim().eq_ignore_ascii_case("authorization"))
+        {
+            ui.colored_label(
+                Color32::from_rgb(255, 165, 0),
+                tr("auth-overridden-by-header"),
+            );
+        }
+        if let Some(cached_token) = cached_token {
+            ui.horizontal(|ui| match cached_token {
+                Some((key, token)) => {
+                    let status = match token.expires_at {
+                        Some(expires_at) if token.is_fresh() => {
+                            let mut args = FluentArgs::new();
+                            args.set(
+                                "seconds",
+                                expires_at
+                                    .saturating_duration_since(Instant::now())
+                                    .as_secs(),
+                            );
+                            tr_args("oauth-token-expires-in", &args)
+                        }
+                        Some(_) => tr("oauth-token-expired"),
+                        None => tr("oauth-token-cached"),
+                    };
+                    ui.label(status);
+                    if ui.button(tr("oauth-clear-token")).clicked()
+                        && let Ok(mut cache) = self.oauth_tokens.try_lock()
+                    {
+                        cache.remove(&key);
+                    }
+                }
+                None => {
+                    ui.colored_label(Color32::GRAY, tr("oauth-token-not-fetched"));
+                }
+            });
+        }
+    }
+
+    fn draw_auth_type(ui: &mut Ui, id: &str, auth: &mut RequestAuth, allow_inherit: bool) {
+        ui.horizontal(|ui| {
+            ui.label(tr("auth-type"));
+            egui::ComboBox::from_id_salt(id)
+                .selected_text(auth.label())
+                .show_ui(ui, |ui| {
+                    for kind in RequestAuth::kinds() {
+                        if kind == RequestAuth::Inherit && !allow_inherit {
+                            continue;
+                        }
+                        let selected =
+                            std::mem::discriminant(&kind) == std::mem::discriminant(auth);
+                        if ui.selectable_label(selected, kind.label()).clicked() && !selected {
+                            *auth = kind;
+                        }
+                    }
+                });
+        });
+    }
+
+    // The settings of one auth type, shared by the Auth tab and the collection/folder dialog
+    fn draw_auth_fields(ui: &mut Ui, auth: &mut RequestAuth) {
+        match auth {
+            RequestAuth::Inherit => {}
+            RequestAuth::None => {
+                ui.colored_label(Color32::GRAY, tr("no-auth-hint"));
+            }
+            RequestAuth::Basic { username, password } => {
+                egui::Grid::new("basic_auth_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("username"));
+                        ui.add(
+                            TextEdit::singleline(username)
+                                .hint_text(tr("header-value-supports-variable"))
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("password"));
+                        ui.add(
+                            TextEdit::singleline(password)
+                                .password(true)
+                                .hint_text(tr("header-value-supports-variable"))
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                    });
+                ui.colored_label(Color32::GRAY, tr("basic-auth-hint"));
+            }
+            RequestAuth::Bearer { token } => {
+                egui::Grid::new("bearer_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("bearer-token"));
+                        ui.add(
+                            TextEdit::singleline(token)
+                                .password(true)
+                                .hint_text(tr("header-value-supports-variable"))
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                    });
+            }
+            RequestAuth::ApiKey {
+                key,
+                value,
+                placement,
+            } => {
+                egui::Grid::new("api_key_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("api-key-name"));
+                        ui.add(
+                            TextEdit::singleline(key)
+                                .hint_text("X-API-Key")
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("value"));
+                        ui.add(
+                            TextEdit::singleline(value)
+                                .password(true)
+                                .hint_text(tr("header-value-supports-variable"))
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("api-key-add-to"));
+                        ui.horizontal(|ui| {
+                            ui.radio_value(placement, ApiKeyPlacement::Header, tr("header"));
+                            ui.radio_value(placement, ApiKeyPlacement::Query, tr("query-string"));
+                        });
+                        ui.end_row();
+                    });
+                ui.colored_label(Color32::GRAY, tr("api-key-hint"));
+            }
+            RequestAuth::OAuth2 {
+                token_url,
+                client_id,
+                client_secret,
+                scopes,
+            } => {
+                egui::Grid::new("oauth2_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("token-url"));
+                        ui.add(
+                            TextEdit::singleline(token_url)
+                                .hint_text("https://auth.example.com/oauth/token")
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("client-id"));
+                        ui.add(TextEdit::singleline(client_id).desired_width(300.0));
+                        ui.end_row();
+                        ui.label(tr("client-secret"));
+                        ui.add(
+                            TextEdit::singleline(client_secret)
+                                .password(true)
+                                .hint_text(tr("header-value-supports-variable"))
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("scopes"));
+                        ui.add(
+                            TextEdit::singleline(scopes)
+                                .hint_text("read write")
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                    });
+                ui.colored_label(Color32::GRAY, tr("oauth-hint"));
+            }
+            RequestAuth::Ntlm {
+                username,
+                password,
+                domain,
+                negotiate,
+            } => {
+                egui::Grid::new("ntlm_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("username"));
+                        ui.add(
+                            TextEdit::singleline(username)
+                                .hint_text("DOMAIN\\user")
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("password"));
+                        ui.add(
+                            TextEdit::singleline(password)
+                                .password(true)
+                                .hint_text(tr("header-value-supports-variable"))
+                                .desired_width(300.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("ntlm-domain"));
+                        ui.add(TextEdit::singleline(domain).desired_width(300.0));
+                        ui.end_row();
+                        ui.label(tr("ntlm-scheme"));
+                        ui.horizontal(|ui| {
+                            ui.radio_value(negotiate, false, "NTLM");
+                            ui.radio_value(negotiate, true, tr("ntlm-over-negotiate"));
+                        });
+                        ui.end_row();
+                    });
+                ui.colored_label(Color32::GRAY, tr("ntlm-hint"));
+            }
+        }
+    }
+
+    fn draw_headers_panel(&mut self, ui: &mut Ui) {
+        let inherited_headers: Vec<(String, String)> = self
+            .inherited_default_headers(&self.current_request)
+            .cloned()
+            .collect();
+        let token_header = self
+            .effective_token(&self.current_request)
+            .filter(|_| {
+                !self
+                    .effective_auth(&self.current_request)
+                    .sets_authorization()
+            })
+            .filter(|_| {
+                !KeyValue::enabled(&self.current_request.headers)
+                    .chain(inherited_headers.iter().map(|(key, value)| (key, value)))
+                    .any(|(key, _)| key.trim().eq_ignore_ascii_case("authorization"))
+            })
+            .map(|token| (token.name.clone(), token.header_value("••••••")));
+
+        // Headers changed outside the text, e.g. by opening another request
+        if let Some(text) = &mut self.headers_bulk_text
+            && Self::parse_header_block(text) != self.current_request.headers
+        {
+            *text = Self::header_block(&self.current_request.headers);
+        }
+
+        ScrollArea::vertical().show(ui, |ui| {
+            let mut to_remove = Vec::new();
+
+            // Table header
+            ui.horizontal(|ui| {
+                ui.label(tr("header-name"));
+                ui.add_space(150.0);
+                ui.label(tr("header-value"));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let mut bulk = self.headers_bulk_text.is_some();
+                    if ui
+                        .toggle_value(&mut bulk, tr("bulk-edit"))
+                        .on_hover_text(tr("bulk-edit-headers-hint"))
+                        .changed()
+                    {
+                        self.headers_bulk_text =
+                            bulk.then(|| Self::header_block(&self.current_request.headers));
+                    }
+                    self.draw_header_presets_menu(ui);
+                });
+            });
+            ui.separator();
+
+            // Workspace default headers (read-only until overridden)
+            for (key, value) in &inherited_headers {
+                ui.horizontal(|ui| {
+                    ui.add_enabled(
+                        false,
+                        TextEdit::singleline(&mut key.as_str()).desired_width(200.0),
+                    );
+                    ui.add_enabled(
+                        false,
+                        TextEdit::singleline(&mut value.as_str()).desired_width(300.0),
+                    );
+                    if ui
+                        .button(tr("override"))
+                        .on_hover_text(tr("workspace-default-header-copy-it-to"))
+                        .clicked()
+                    {
+                        self.current_request
+                            .headers
+                            .push(KeyValue::new(key.clone(), value.clone()));
+                    }
+                });
+            }
+
+            // Authorization sent from the Token Manager
+            if let Some((name, value)) = &token_header {
+                let mut args = FluentArgs::new();
+                args.set("name", name.clone());
+                ui.horizontal(|ui| {
+                    ui.add_enabled(
+                        false,
+                        TextEdit::singleline(&mut "Authorization").desired_width(200.0),
+                    );
+                    ui.add_enabled(
+                        false,
+                        TextEdit::singleline(&mut value.as_str()).desired_width(300.0),
+                    );
+                    ui.label("🔑")
+                        .on_hover_text(tr_args("token-header-hint", &args));
+                });
+            }
+
+            if let Some(text) = &mut self.headers_bulk_text {
+                if ui
+                    .add(
+                        TextEdit::multiline(text)
+                            .code_editor()
+                            .hint_text("Accept: application/json\nX-Request-Id: {{$uuid}}")
+                            .desired_rows(10)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .changed()
+                {
+                    self.current_request.headers = Self::parse_header_block(text);
+                }
+                return;
+            }
+
+            for (
+                i,
+                KeyValue {
+                    key,
+                    value,
+                    enabled,
+                },
+            ) in self.current_request.headers.iter_mut().enumerate()
+            {
+                ui.horizontal(|ui| {
+                    ui.checkbox(enabled, "").on_hover_text(tr("send-this-row"));
+                    ui.add(
+                        TextEdit::singleline(key)
+                            .hint_text(tr("header-name-hint"))
+                            .desired_width(200.0),
+                    );
+                    let value_response = ui.add(
+                        TextEdit::singleline(value)
+                            .hint_text(tr("header-value-supports-variable"))
+                            .desired_width(300.0),
+                    );
+                    Self::generator_menu(&value_response, value);
+                    if ui.button("🗑").clicked() {
+                        to_remove.push(i);
+                    }
+                });
+            }
+
+            // Remove headers
+            if !to_remove.is_empty() {
+                for &i in to_remove.iter().rev() {
+                    self.current_request.headers.remove(i);
+                }
+            }
+
+            // Add new header button
+            if ui.button(tr("add-header")).clicked() {
+                self.current_request.headers.push(KeyValue::new("", ""));
+            }
+        });
+    }
+
+    fn draw_header_presets_menu(&mut self, ui: &mut Ui) {
+        let presets = self.current_workspace().settings.header_presets.clone();
+        let mut save = false;
+        ui.menu_button(tr("header-presets"), |ui| {
+            if presets.is_empty() {
+                ui.label(RichText::new(tr("no-header-presets")).weak());
+            }
+            for preset in &presets {
+                let hover: Vec<String> = preset
+                    .headers
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect();
+                if ui
+                    .button(&preset.name)
+                    .on_hover_text(hover.join("\n"))
+                    .clicked()
+                {
+                    preset.apply(&mut self.current_request.headers);
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.new_header_preset_name)
+                        .hint_text(tr("preset-name"))
+                        .desired_width(150.0),
+                );
+                let can_save = !self.new_header_preset_name.trim().is_empty()
+                    && KeyValue::enabled(&self.current_request.headers)
+                        .any(|(key, _)| !key.trim().is_empty());
+                if ui
+                    .add_enabled(can_save, egui::Button::new(tr("save-headers-as-preset")))
+                    .clicked()
+                {
+                    save = true;
+                    ui.close_menu();
+                }
+            });
+            if ui.button(tr("manage-header-presets")).clicked() {
+                self.workspace_settings_dialog = true;
+                ui.close_menu();
+            }
+        });
+
+        if save {
+            let name = std::mem::take(&mut self.new_header_preset_name)
+                .trim()
+                .to_string();
+            let headers: Vec<(String, String)> = KeyValue::enabled(&self.current_request.headers)
+                .filter(|(key, _)| !key.trim().is_empty())
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            let presets = &mut self.current_workspace_mut().settings.header_presets;
+            match presets.iter_mut().find(|preset| preset.name == name) {
+                Some(preset) => preset.headers = headers,
+                None => presets.push(HeaderPreset {
+                    name: name.clone(),
+                    headers,
+                }),
+            }
+            self.auto_save_workspace();
+            self.save_cache();
+            let mut args = FluentArgs::new();
+            args.set("name", name);
+            self.notify(ToastKind::Success, tr_args("header-preset-saved", &args));
+        }
+    }
+
+    fn header_block(headers: &[KeyValue]) -> String {
+        headers
+            .iter()
+            .filter(|row| !row.key.is_empty() || !row.value.is_empty())
+            .map(|row| {
+                let prefix = if row.enabled { "" } else { "// " };
+                format!("{}{}: {}\n", prefix, row.key, row.value)
+            })
+            .collect()
+    }
+
+    // `Key: Value` lines as copied from browser devtools. HTTP/2 pseudo-headers like
+    // :authority are skipped, and a line without a colon is a header with no value.
+    // A line starting with // is a switched-off header.
+    fn parse_header_block(text: &str) -> Vec<KeyValue> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with(':'))
+            .map(|line| {
+                let disabled = line.strip_prefix("//").map(str::trim_start);
+                let (key, value) = disabled
+                    .unwrap_or(line)
+                    .split_once(':')
+                    .unwrap_or((disabled.unwrap_or(line), ""));
+                KeyValue {
+                    key: key.trim().to_string(),
+                    value: value.trim().to_string(),
+                    enabled: disabled.is_none(),
+                }
+            })
+            .collect()
+    }
+
+    fn draw_tests_panel(&mut self, ui: &mut Ui) {
+        ui.label(tr("header-assertions-hint"));
+        ui.separator();
+        let mut to_remove = None;
+        ScrollArea::vertical().show(ui, |ui| {
+            for (i, assertion) in self.current_request.assertions.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut assertion.enabled, "");
+                    ui.add(
+                        TextEdit::singleline(&mut assertion.header)
+                            .hint_text("X-RateLimit-Remaining")
+                            .desired_width(200.0),
+                    );
+                    egui::ComboBox::from_id_salt(("assertion_check", i))
+                        .width(80.0)
+                        .selected_text(assertion.check.label())
+                        .show_ui(ui, |ui| {
+                            for check in HeaderCheck::ALL {
+                                ui.selectable_value(&mut assertion.check, check, check.label());
+                            }
+                        });
+                    if assertion.check != HeaderCheck::Exists {
+                        ui.add(
+                            TextEdit::singleline(&mut assertion.expected)
+                                .hint_text(if assertion.check == HeaderCheck::Matches {
+                                    "^\\d+$"
+                                } else {
+                                    ""
+                                })
+                                .desired_width(200.0),
+                        );
+                    }
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(i);
+                    }
+                });
+            }
+            if ui.button(tr("add-header-assertion")).clicked() {
+                self.current_request
+                    .assertions
+                    .push(HeaderAssertion::default());
+            }
+        });
+        if let Some(i) = to_remove {
+            self.current_request.assertions.remove(i);
+        }
+
+        ui.separator();
+        ui.label(tr("capture-rules-hint"));
+        let mut to_remove = None;
+        for (i, rule) in self.current_request.captures.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut rule.enabled, "");
+                ui.add(
+                    TextEdit::singleline(&mut rule.variable)
+                        .hint_text(tr("variable-name"))
+                        .desired_width(120.0),
+                );
+                ui.label("←");
+                egui::ComboBox::from_id_salt(("capture_source", i))
+                    .width(80.0)
+                    .selected_text(match rule.source {
+                        CaptureSource::Body => tr("body"),
+                        CaptureSource::Header => tr("header"),
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut rule.source, CaptureSource::Body, tr("body"));
+                        ui.selectable_value(&mut rule.source, CaptureSource::Header, tr("header"));
+                    });
+                if rule.source == CaptureSource::Header {
+                    ui.add(
+                        TextEdit::singleline(&mut rule.header)
+                            .hint_text("Location")
+                            .desired_width(120.0),
+                    );
+                }
+                ui.add(
+                    TextEdit::singleline(&mut rule.pattern)
+                        .hint_text("name=\"csrf\" value=\"(.+?)\"")
+                        .desired_width(240.0),
+                );
+                if ui.button("🗑").clicked() {
+                    to_remove = Some(i);
+                }
+            });
+        }
+        if ui.button(tr("add-capture-rule")).clicked() {
+            self.current_request.captures.push(CaptureRule::default());
+        }
+        if let Some(i) = to_remove {
+            self.current_request.captures.remove(i);
+        }
+    }
+
+    fn draw_body_panel(&mut self, ui: &mut Ui) {
+        // Body type tabs (Postman style)
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_value(&mut self.current_request.body_type, BodyType::None, "none")
+                .changed()
+            {
+                self.remove_content_type_header();
+            }
+            if ui
+                .selectable_value(
+                    &mut self.current_request.body_type,
+                    BodyType::FormData,
+                    "form-data",
+                )
+                .changed()
+            {
+                // Form data uses multipart/form-data, but this is set automatically by reqwest
+                self.remove_content_type_header();
+            }
+            if ui
+                .selectable_value(
+                    &mut self.current_request.body_type,
+                    BodyType::UrlEncoded,
+                    "x-www-form-urlencoded",
+                )
+                .changed()
+            {
+                self.set_content_type_header("application/x-www-form-urlencoded");
+            }
+            if ui
+                .selectable_value(&mut self.current_request.body_type, BodyType::Raw, "raw")
+                .changed()
+            {
+                // Set Content-Type based on current raw body type
+                let content_type = self.raw_body_type.get_content_type();
+                self.set_content_type_header(content_type);
+            }
+            if ui
+                .selectable_value(
+                    &mut self.current_request.body_type,
+                    BodyType::Binary,
+                    "binary",
+                )
+                .changed()
+            {
+                self.set_content_type_header("application/octet-stream");
+            }
+        });
+
+        // Raw sub-tabs (shown when Raw is selected)
+        if self.current_request.body_type == BodyType::Raw {
+            ui.horizontal(|ui| {
+                ui.label("  "); // Indent for sub-tabs
+
+                let mut raw_type_changed = false;
+
+                if ui
+                    .selectable_value(&mut self.raw_body_type, RawBodyType::Text, tr("text"))
+                    .changed()
+                {
+                    raw_type_changed = true;
+                }
+                if ui
+                    .selectable_value(
+                        &mut self.raw_body_type,
+                        RawBodyType::JavaScript,
+                        "JavaScript",
+                    )
+                    .changed()
+                {
+                    raw_type_changed = true;
+                }
+                if ui
+                    .selectable_value(&mut self.raw_body_type, RawBodyType::JSON, "JSON")
+                    .changed()
+                {
+                    raw_type_changed = true;
+                }
+                if ui
+                    .selectable_value(&mut self.raw_body_type, RawBodyType::HTML, "HTML")
+                    .changed()
+                {
+                    raw_type_changed = true;
+                }
+                if ui
+                    .selectable_value(&mut self.raw_body_type, RawBodyType::XML, "XML")
+                    .changed()
+                {
+                    raw_type_changed = true;
+                }
+
+                if raw_type_changed {
+                    // Update Content-Type header when raw body type changes
+                    let content_type = self.raw_body_type.get_content_type();
+                    self.set_content_type_header(content_type);
+                    self.save_cache();
+                }
+
+                ui.separator();
+                let mut from_file = self.current_request.body_file.is_some();
+                if ui
+                    .checkbox(&mut from_file, tr("body-from-file"))
+                    .on_hover_text(tr("body-from-file-hint"))
+                    .changed()
+                {
+                    self.current_request.body_file = from_file.then(String::new);
+                }
+            });
+        }
+
+        ui.separator();
+
+        // Body content based on type
+        match self.current_request.body_type {
+            BodyType::None => {
+                ui.label(tr("this-request-does-not-have-a"));
+            }
+            BodyType::FormData => {
+                self.draw_form_data_panel(ui);
+            }
+            BodyType::UrlEncoded => {
+                self.draw_url_encoded_panel(ui);
+            }
+            BodyType::Binary => {
+                self.draw_binary_body_panel(ui);
+            }
+            BodyType::Raw if self.current_request.body_file.is_some() => {
+                let resolved_path = self.resolve_value(
+                    self.current_request
+                        .body_file
+                        .as_deref()
+                        .unwrap_or_default(),
+                );
+                if let Some(path) = &mut self.current_request.body_file {
+                    Self::draw_body_file_picker(ui, path, &resolved_path);
+                }
+            }
+            BodyType::Raw => {
+                let hint = tr(match self.raw_body_type {
+                    RawBodyType::Text => "enter-plain-text",
+                    RawBodyType::JavaScript => "enter-javascript-code",
+                    RawBodyType::JSON => "enter-json-data",
+                    RawBodyType::HTML => "enter-html-content",
+                    RawBodyType::XML => "enter-xml-content",
+                });
+
+                if self.raw_body_type == RawBodyType::JSON {
+                    self.draw_json_body_editor(ui, &hint);
+                    return;
+                }
+
+                let mut code = self.current_request.body.clone();
+                let soft_wrap = self.settings.body_soft_wrap;
+                let output = Self::draw_code_editor(
+                    ui,
+                    ui.make_persistent_id("raw_body_editor"),
+                    &mut code,
+                    &hint,
+                    self.settings.body_editor_rows,
+                    &mut self.settings.body_soft_wrap,
+                );
+                if self.settings.body_soft_wrap != soft_wrap {
+                    self.save_settings();
+                }
+                Self::generator_menu(&output.response, &mut code);
+
+                if code != self.current_request.body {
+                    self.current_request.body = code;
+                }
+            }
+            BodyType::Json => {
+                // This should not be reached anymore, but keeping for backwards compatibility
+                ui.label(RichText::new("JSON").color(Color32::from_rgb(0, 150, 255)));
+                self.draw_json_body_editor(ui, &tr("enter-json-data"));
+            }
+        }
+    }
+
+    fn attach_body_schema(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("attach-json-schema-title"))
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        let schema = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<serde_json::Value>(&content).map_err(|e| e.to_string())
+            });
+        match schema {
+            Ok(schema) => {
+                self.current_request.body_schema = Self::resolve_schema(&schema, &schema, 0);
+            }
+            Err(e) => {
+                let mut args = FluentArgs::new();
+                args.set("path", path.display().to_string());
+                args.set("error", e);
+                self.notify(ToastKind::Error, tr_args("read-schema-failed", &args));
+            }
+        }
+    }
+
+    fn json_cursor_context(text: &str, cursor: usize) -> JsonCursor {
+        enum Frame {
+            Object {
+                key: Option<String>,
+                expecting_key: bool,
+                present: Vec<String>,
+            },
+            Array,
+        }
+        fn path_of(frames: &[Frame]) -> Option<Vec<JsonStep>> {
+            frames
+                .iter()
+                .map(|frame| match frame {
+                    Frame::Object { key, .. } => key.clone().map(JsonStep::Key),
+                    Frame::Array => Some(JsonStep::Item),
+                })
+                .collect()
+        }
+
+        let chars: Vec<char> = text.chars().collect();
+        let cursor = cursor.min(chars.len());
+        let mut stack: Vec<Frame> = vec![];
+        let mut i = 0;
+        while i < cursor {
+            match chars[i] {
+                '"' => {
+                    let start = i;
+                    let mut value = String::new();
+                    let mut closed = false;
+                    i += 1;
+                    while i < cursor {
+                        match chars[i] {
+                            '\\' => {
+                                i += 1;
+                                if let Some(&c) = chars.get(i) {
+                                    value.push(c);
+                                }
+                            }
+                            '"' => {
+                                closed = true;
+                                break;
+                            }
+                            c => value.push(c),
+                        }
+                        i += 1;
+                    }
+                    if !closed {
+                        // The cursor is inside this string
+                        return match stack.last() {
+                            Some(Frame::Object {
+                                key: None,
+                                expecting_key: true,
+                                present,
+                            }) => path_of(&stack[..stack.len() - 1]).map_or(
+                                JsonCursor::Other,
+                                |path| JsonCursor::Key {
+                                    path,
+                                    prefix: value,
+                                    start,
+                                    present: present.clone(),
+                                },
+                            ),
+                            _ => path_of(&stack).map_or(JsonCursor::Other, JsonCursor::Value),
+                        };
+                    }
+                    if let Some(Frame::Object {
+                        key,
+                        expecting_key: true,
+                        present,
+                    }) = stack.last_mut()
+                    {
+                        present.push(value.clone());
+                        *key = Some(value);
+                    }
+                }
+                ':' => {
+                    if let Some(Frame::Object { expecting_key, .. }) = stack.last_mut() {
+                        *expecting_key = false;
+                    }
+                }
+                ',' => {
+                    if let Some(Frame::Object {
+                        key, expecting_key, ..
+                    }) = stack.last_mut()
+                    {
+                        *key = None;
+                        *expecting_key = true;
+                    }
+                }
+                '{' => stack.push(Frame::Object {
+                    key: None,
+                    expecting_key: true,
+                    present: vec![],
+                }),
+                '[' => stack.push(Frame::Array),
+                '}' | ']' => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        match stack.last() {
+            Some(Frame::Object {
+                key: None,
+                expecting_key: true,
+                present,
+            }) => {
+                // A name typed without its opening quote yet
+                let mut start = cursor;
+                while start > 0 && (chars[start - 1].is_alphanumeric() || chars[start - 1] == '_') {
+                    start -= 1;
+                }
+                path_of(&stack[..stack.len() - 1]).map_or(JsonCursor::Other, |path| {
+                    JsonCursor::Key {
+                        path,
+                        prefix: chars[start..cursor].iter().collect(),
+                        start,
+                        present: present.clone(),
+                    }
+                })
+            }
+            Some(Frame::Object {
+                expecting_key: false,
+                ..
+            })
+            | Some(Frame::Array) => path_of(&stack).map_or(JsonCursor::Other, JsonCursor::Value),
+            _ => JsonCursor::Other,
+        }
+    }
+
+    fn schema_at<'a>(
+        schema: &'a serde_json::Value,
+        path: &[JsonStep],
+    ) -> Option<&'a serde_json::Value> {
+        path.iter().try_fold(schema, |schema, step| match step {
+            JsonStep::Key(key) => schema["properties"].get(key),
+            JsonStep::Item => schema.get("items"),
+        })
+    }
+
+    fn schema_type_label(schema: &serde_json::Value) -> String {
+        if let Some(options) = schema["enum"].as_array() {
+            let options: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+            return options.join(" | ");
+        }
+        let label = match &schema["type"] {
+            serde_json::Value::String(kind) => kind.clone(),
+            serde_json::Value::Array(kinds) => kinds
+                .iter()
+                .filter_map(|kind| kind.as_str())
+                .collect::<Vec<_>>()
+                .join(" | "),
+            _ if schema.get("properties").is_some() => "object".to_string(),
+            _ => "any".to_string(),
+        };
+        if label == "array"
+            && let Some(items) = schema.get("items")
+        {
+            return format!("array of {}", Self::schema_type_label(items));
+        }
+        match schema["format"].as_str() {
+            Some(format) => format!("{} ({})", label, format),
+            None => label,
+        }
+    }
+
+    // Property names the schema allows at the cursor, required ones first
+    fn json_key_suggestions(
+        schema: &serde_json::Value,
+        path: &[JsonStep],
+        prefix: &str,
+        present: &[String],
+    ) -> Vec<(String, String)> {
+        let Some(object) = Self::schema_at(schema, path) else {
+            return vec![];
+        };
+        let Some(properties) = object["properties"].as_object() else {
+            return vec![];
+        };
+        let required = |name: &str| {
+            object["required"]
+                .as_array()
+                .is_some_and(|required| required.iter().any(|r| r.as_str() == Some(name)))
+        };
+        let prefix = prefix.to_lowercase();
+        let mut suggestions: Vec<(String, String)> = properties
+            .iter()
+            .filter(|(name, _)| !present.contains(name) && name.to_lowercase().starts_with(&prefix))
+            .map(|(name, property)| (name.clone(), Self::schema_type_label(property)))
+            .collect();
+        suggestions.sort_by_key(|(name, _)| !required(name));
+        suggestions
+    }
+
+    // Replaces a partly typed property name with `name` and returns the new cursor position
+    fn apply_json_completion(code: &mut String, start: usize, cursor: usize, name: &str) -> usize {
+        let chars: Vec<char> = code.chars().collect();
+        let cursor = cursor.min(chars.len());
+        let mut end = cursor;
+        // Swallow the closing quote of a name that was already quoted
+        if chars.get(start) == Some(&'"') && chars.get(end) == Some(&'"') {
+            end += 1;
+        }
+        let insert = format!("\"{}\": ", name);
+        *code = chars[..start]
+            .iter()
+            .copied()
+            .chain(insert.chars())
+            .chain(chars[end..].iter().copied())
+            .collect();
+        start + insert.chars().count()
+    }
+
+    fn json_kind(value: &serde_json::Value) -> &'static str {
+        match value {
+            serde_json::Value::Null => "null",
+            serde_json::Value::Bool(_) => "boolean",
+            serde_json::Value::Number(_) => "number",
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Array(_) => "array",
+            serde_json::Value::Object(_) => "object",
+        }
+    }
+
+    fn validate_json(
+        value: &serde_json::Value,
+        schema: &serde_json::Value,
+        path: &str,
+        issues: &mut Vec<String>,
+    ) {
+        let kinds: Vec<&str> = match &schema["type"] {
+            serde_json::Value::String(kind) => vec![kind.as_str()],
+            serde_json::Value::Array(kinds) => {
+                kinds.iter().filter_map(|kind| kind.as_str()).collect()
+            }
+            _ => vec![],
+        };
+        let matches = |kind: &str| match kind {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        let nullable = value.is_null() && schema["nullable"] == true;
+        if !kinds.is_empty() && !nullable && !kinds.iter().any(|kind| matches(kind)) {
+            let mut args = FluentArgs::new();
+            args.set("path", path.to_string());
+            args.set("expected", kinds.join(" | "));
+            args.set("found", Self::json_kind(value));
+            issues.push(tr_args("schema-wrong-type", &args));
+            return;
+        }
+        if let Some(options) = schema["enum"].as_array()
+            && !options.contains(value)
+        {
+            let mut args = FluentArgs::new();
+            args.set("path", path.to_string());
+            args.set("options", Self::schema_type_label(schema));
+            issues.push(tr_args("schema-not-in-enum", &args));
+        }
+
+        match value {
+            serde_json::Value::Object(map) => {
+                let properties = schema["properties"].as_object();
+                for name in schema["required"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|name| name.as_str())
+                {
+                    if !map.contains_key(name) {
+                        let mut args = FluentArgs::new();
+                        args.set("path", path.to_string());
+                        args.set("name", name.to_string());
+                        issues.push(tr_args("schema-missing-field", &args));
+                    }
+                }
+                let closed = !schema["additionalProperties"].is_object()
+                    && schema["additionalProperties"] != true;
+                for (key, child) in map {
+                    let child_path = format!("{}.{}", path, key);
+                    match properties.and_then(|properties| properties.get(key)) {
+                        Some(child_schema) => {
+                            Self::validate_json(child, child_schema, &child_path, issues)
+                        }
+                        None if properties.is_some() && closed => {
+                            let mut args = FluentArgs::new();
+                            args.set("path", child_path);
+                            issues.push(tr_args("schema-unknown-field", &args));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (idx, item) in items.iter().enumerate() {
+                        let item_path = format!("{}[{}]", path, idx);
+                        Self::validate_json(item, item_schema, &item_path, issues);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // A multiline editor with line numbers, bracket matching, optional soft wrap and a find
+    // bar (Ctrl+F)
+    fn draw_code_editor(
+        ui: &mut Ui,
+        id: egui::Id,
+        code: &mut String,
+        hint: &str,
+        rows: usize,
+        soft_wrap: &mut bool,
+    ) -> egui::text_edit::TextEditOutput {
+        let find_id = id.with("find");
+        let mut find: CodeFind = ui.data(|data| data.get_temp(find_id)).unwrap_or_default();
+        if ui.memory(|memory| memory.has_focus(id) || memory.has_focus(find_id))
+            && ui.input_mut(|input| input.consume_key(egui::Modifiers::COMMAND, egui::Key::F))
+        {
+            find.open = true;
+            ui.memory_mut(|memory| memory.request_focus(find_id));
+        }
+
+        ui.horizontal(|ui| {
+            ui.toggle_value(soft_wrap, tr("soft-wrap"));
+            if ui
+                .toggle_value(&mut find.open, "🔍")
+                .on_hover_text(tr("find-in-body"))
+                .clicked()
+                && find.open
+            {
+                ui.memory_mut(|memory| memory.request_focus(find_id));
+            }
+            if find.open {
+                let previous_query = find.query.clone();
+                let response = ui.add(
+                    TextEdit::singleline(&mut find.query)
+                        .id(find_id)
+                        .hint_text(tr("find"))
+                        .desired_width(200.0),
+                );
+                let count = CodeFind::matches(code, &find.query).len();
+                let mut step = None;
+                if response.lost_focus() {
+                    if ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                        step = Some(!ui.input(|input| input.modifiers.shift));
+                        ui.memory_mut(|memory| memory.request_focus(find_id));
+                    } else if ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+                        find.open = false;
+                        ui.memory_mut(|memory| memory.request_focus(id));
+                    }
+                }
+                if find.query != previous_query {
+                    find.current = 0;
+                    find.reveal = true;
+                }
+                if count == 0 {
+                    if !find.query.is_empty() {
+                        ui.colored_label(Color32::GRAY, tr("no-matches"));
+                    }
+                } else {
+                    find.current = find.current.min(count - 1);
+                    ui.label(format!("{}/{}", find.current + 1, count));
+                }
+                if ui.add_enabled(count > 0, egui::Button::new("⏶")).clicked() {
+                    step = Some(false);
+                }
+                if ui.add_enabled(count > 0, egui::Button::new("⏷")).clicked() {
+                    step = Some(true);
+                }
+                if let Some(forward) = step
+                    && count > 0
+                {
+                    find.current = if forward {
+                        (find.current + 1) % count
+                    } else {
+                        (find.current + count - 1) % count
+                    };
+                    find.reveal = true;
+                }
+            }
+        });
+
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let text_color = ui
+            .visuals()
+            .override_text_color
+            .unwrap_or_else(|| ui.visuals().widgets.inactive.text_color());
+        let cursor = TextEdit::load_state(ui.ctx(), id)
+            .and_then(|state| state.cursor.char_range())
+            .map(|range| range.primary.index);
+        let wrap = *soft_wrap;
+        let query = if find.open {
+            find.query.clone()
+        } else {
+            String::new()
+        };
+        let current = find.current;
+        let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+            let mut marks: Vec<(std::ops::Range<usize>, Color32)> = CodeFind::matches(text, &query)
+                .into_iter()
+                .enumerate()
+                .map(|(index, range)| {
+                    let color = if index == current {
+                        Color32::from_rgba_unmultiplied(255, 165, 0, 140)
+                    } else {
+                        Color32::from_rgba_unmultiplied(255, 210, 0, 60)
+                    };
+                    (range, color)
+                })
+                .collect();
+            if let Some((bracket, partner)) =
+                cursor.and_then(|cursor| CodeFind::bracket_pair(text, cursor))
+            {
+                match partner {
+                    Some(partner) => {
+                        let color = Color32::from_rgba_unmultiplied(0, 150, 255, 90);
+                        marks.push((bracket..bracket + 1, color));
+                        marks.push((partner..partner + 1, color));
+                    }
+                    None => marks.push((
+                        bracket..bracket + 1,
+                        Color32::from_rgba_unmultiplied(215, 58, 73, 140),
+                    )),
+                }
+            }
+            marks.sort_by_key(|(range, _)| range.start);
+
+            let mut job = egui::text::LayoutJob::default();
+            job.wrap.max_width = if wrap { wrap_width } else { f32::INFINITY };
+            let plain = egui::TextFormat::simple(font_id.clone(), text_color);
+            let mut at = 0;
+            // Overlapping marks keep the first; a bracket inside a match isn't marked
+            for (range, background) in marks {
+                if range.start < at || range.end > text.len() {
+                    continue;
+                }
+                job.append(&text[at..range.start], 0.0, plain.clone());
+                job.append(
+                    &text[range.clone()],
+                    0.0,
+                    egui::TextFormat {
+                        background,
+                        ..plain.clone()
+                    },
+                );
+                at = range.end;
+            }
+            job.append(&text[at..], 0.0, plain);
+            ui.fonts(|fonts| fonts.layout_job(job))
+        };
+
+        let digits = (code.matches('\n').count() + 1).to_string().len();
+        let gutter_width =
+            ui.fonts(|fonts| fonts.glyph_width(&font_id, '0')) * digits as f32 + 12.0;
+        let (gutter, output) = ui
+            .horizontal_top(|ui| {
+                let (gutter, _) =
+                    ui.allocate_exact_size(egui::vec2(gutter_width, 0.0), egui::Sense::hover());
+                let editor = TextEdit::multiline(code)
+                    .id(id)
+                    .code_editor()
+                    .desired_rows(rows)
+                    .hint_text(hint)
+                    .layouter(&mut layouter);
+                let output = if wrap {
+                    editor.desired_width(ui.available_width()).show(ui)
+                } else {
+                    let width = ui.available_width();
+                    ScrollArea::horizontal()
+                        .id_salt(id.with("scroll"))
+                        .show(ui, |ui| editor.desired_width(width).show(ui))
+                        .inner
+                };
+                (gutter, output)
+            })
+            .inner;
+
+        // One number per line, level with the first row it wraps onto
+        let number_color = ui.visuals().weak_text_color();
+        let mut line = 0;
+        for (index, row) in output.galley.rows.iter().enumerate() {
+            if index == 0 || output.galley.rows[index - 1].ends_with_newline {
+                line += 1;
+                ui.painter().text(
+                    egui::pos2(gutter.right() - 6.0, output.galley_pos.y + row.rect.min.y),
+                    egui::Align2::RIGHT_TOP,
+                    line.to_string(),
+                    font_id.clone(),
+                    number_color,
+                );
+            }
+        }
+
+        if std::mem::take(&mut find.reveal)
+            && let Some(range) = CodeFind::matches(code, &find.query).get(find.current)
+        {
+            let start = code[..range.start].chars().count();
+            let end = start + code[range.clone()].chars().count();
+            let mut state = output.state.clone();
+            state
+                .cursor
+                .set_char_range(Some(egui::text::CCursorRange::two(
+                    egui::text::CCursor::new(start),
+                    egui::text::CCursor::new(end),
+                )));
+            state.store(ui.ctx(), id);
+            let rect = output
+                .galley
+                .pos_from_ccursor(egui::text::CCursor::new(start))
+                .translate(output.galley_pos.to_vec2());
+            ui.scroll_to_rect(rect.expand(20.0), Some(egui::Align::Center));
+        }
+        ui.data_mut(|data| data.insert_temp(find_id, find));
+        output
+    }
+
+    fn draw_json_body_editor(&mut self, ui: &mut Ui, hint: &str) {
+        let schema = self.current_request.body_schema.clone();
+        let mut layout = None;
+        ui.horizontal(|ui| {
+            match &schema {
+                Some(schema) => {
+                    let title = schema["title"]
+                        .as_str()
+                        .map_or_else(|| Self::schema_type_label(schema), str::to_string);
+                    ui.label(RichText::new(format!("📐 {}", title)).weak())
+                        .on_hover_text(tr("body-schema-hint"));
+                    if ui.small_button(tr("remove-schema")).clicked() {
+                        self.current_request.body_schema = None;
+                    }
+                }
+                None => {
+                    if ui.small_button(tr("attach-json-schema")).clicked() {
+                        self.attach_body_schema();
+                    }
+                }
+            }
+            ui.separator();
+            if ui.small_button(tr("format-json")).clicked() {
+                layout = Some(JsonLayout::Pretty);
+            }
+            if ui.small_button(tr("minify-json")).clicked() {
+                layout = Some(JsonLayout::Minified);
+            }
+        });
+        if let Some(layout) = layout
+            && !self.current_request.body.trim().is_empty()
+        {
+            match layout.apply(&self.current_request.body) {
+                Ok(body) => self.current_request.body = body,
+                Err(e) => {
+                    let mut args = FluentArgs::new();
+                    args.set("error", e.to_string());
+                    self.notify(ToastKind::Error, tr_args("cannot-format-json", &args));
+                }
+            }
+        }
+
+        let id = ui.make_persistent_id("json_body_editor");
+        let mut code = self.current_request.body.clone();
+        let cursor_at = |ctx: &egui::Context| {
+            TextEdit::load_state(ctx, id)
+                .and_then(|state| state.cursor.char_range())
+                .map(|range| range.primary.index)
+        };
+        let move_cursor = |ctx: &egui::Context, index: usize| {
+            if let Some(mut state) = TextEdit::load_state(ctx, id) {
+                state
+                    .cursor
+                    .set_char_range(Some(egui::text::CCursorRange::one(
+                        egui::text::CCursor::new(index),
+                    )));
+                state.store(ctx, id);
+            }
+        };
+        let suggestions_at =
+            |code: &str, cursor: usize| match (&schema, Self::json_cursor_context(code, cursor)) {
+                (
+                    Some(schema),
+                    JsonCursor::Key {
+                        path,
+                        prefix,
+                        start,
+                        present,
+                    },
+                ) => Some((
+                    start,
+                    Self::json_key_suggestions(schema, &path, &prefix, &present),
+                )),
+                _ => None,
+            };
+
+        // Tab takes the first suggestion, before the editor would insert a tab
+        if ui.memory(|memory| memory.has_focus(id))
+            && let Some(cursor) = cursor_at(ui.ctx())
+            && let Some((start, suggestions)) = suggestions_at(&code, cursor)
+            && let Some((name, _)) = suggestions.first()
+            && ui.input_mut(|input| input.consume_key(egui::Modifiers::NONE, egui::Key::Tab))
+        {
+            let cursor = Self::apply_json_completion(&mut code, start, cursor, name);
+            move_cursor(ui.ctx(), cursor);
+        }
+
+        let soft_wrap = self.settings.body_soft_wrap;
+        let output = Self::draw_code_editor(
+            ui,
+            id,
+            &mut code,
+            hint,
+            self.settings.body_editor_rows,
+            &mut self.settings.body_soft_wrap,
+        );
+        if self.settings.body_soft_wrap != soft_wrap {
+            self.save_settings();
+        }
+        Self::generator_menu(&output.response, &mut code);
+
+        // Only parsed again when the text changes
+        if self
+            .json_body_check
+            .as_ref()
+            .is_none_or(|(checked, _)| *checked != code)
+        {
+            let error = JsonSyntaxError::check(&code);
+            self.json_body_check = Some((code.clone(), error));
+        }
+        let syntax_error = self
+            .json_body_check
+            .as_ref()
+            .and_then(|(_, error)| error.clone());
+        if let Some(error) = &syntax_error {
+            // A bar in the editor's left margin on the failing row, and a line under the spot
+            let red = Color32::from_rgb(215, 58, 73);
+            let position = output
+                .galley
+                .pos_from_ccursor(egui::text::CCursor::new(error.char_index(&code)))
+                .translate(output.galley_pos.to_vec2());
+            let painter = ui.painter();
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(output.response.rect.left() + 1.0, position.top()),
+                    egui::pos2(output.response.rect.left() + 3.0, position.bottom()),
+                ),
+                0.0,
+                red,
+            );
+            painter.line_segment(
+                [
+                    egui::pos2(position.left() - 1.0, position.bottom()),
+                    egui::pos2(position.left() + 7.0, position.bottom()),
+                ],
+                egui::Stroke::new(2.0, red),
+            );
+            let mut args = FluentArgs::new();
+            args.set("line", error.line);
+            args.set("column", error.column);
+            args.set("message", error.message.clone());
+            ui.colored_label(red, tr_args("json-syntax-error", &args));
+        }
+
+        // Suggestions float under the name being typed; they stay up while the pointer is on them
+        let popup_id = id.with("completions");
+        let over_popup = ui
+            .ctx()
+            .memory(|memory| memory.area_rect(popup_id))
+            .zip(ui.ctx().pointer_hover_pos())
+            .is_some_and(|(rect, pointer)| rect.contains(pointer));
+        let cursor = cursor_at(ui.ctx());
+        if (output.response.has_focus() || over_popup)
+            && let Some(cursor) = cursor
+            && let Some((start, suggestions)) = suggestions_at(&code, cursor)
+            && !suggestions.is_empty()
+        {
+            let anchor = output
+                .galley
+                .pos_from_ccursor(egui::text::CCursor::new(start));
+            let mut chosen = None;
+            egui::Area::new(popup_id)
+                .order(egui::Order::Foreground)
+                .fixed_pos(output.galley_pos + anchor.left_bottom().to_vec2())
+                .show(ui.ctx(), |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        for (name, kind) in suggestions.iter().take(12) {
+                            let label = RichText::new(format!("{}  {}", name, kind)).monospace();
+                            if ui.selectable_label(false, label).clicked() {
+                                chosen = Some(name.clone());
+                            }
+                        }
+                        ui.label(RichText::new(tr("tab-accepts-first")).weak().small());
+                    });
+                });
+            if let Some(name) = chosen {
+                let cursor = Self::apply_json_completion(&mut code, start, cursor, &name);
+                move_cursor(ui.ctx(), cursor);
+                ui.memory_mut(|memory| memory.request_focus(id));
+            }
+        }
+
+        if let Some(schema) = &schema {
+            // Type hint for the value being edited
+            if let Some(cursor) = cursor
+                && let JsonCursor::Value(path) = Self::json_cursor_context(&code, cursor)
+                && let Some(JsonStep::Key(name)) = path.last()
+                && let Some(property) = Self::schema_at(schema, &path)
+            {
+                let mut hint = format!("{}: {}", name, Self::schema_type_label(property));
+                if let Some(description) = property["description"].as_str() {
+                    hint = format!("{} — {}", hint, description);
+                }
+                ui.label(RichText::new(hint).weak());
+            }
+
+            // Check the body once variables are filled in
+            let resolved = self.resolve_value(&code);
+            // Syntax errors are already marked above
+            if !resolved.trim().is_empty()
+                && !resolved.contains("{{")
+                && let Ok(value) = serde_json::from_str::<serde_json::Value>(&resolved)
+            {
+                let mut issues = vec![];
+                Self::validate_json(&value, schema, "$", &mut issues);
+                for issue in issues.iter().take(20) {
+                    ui.colored_label(Color32::from_rgb(255, 165, 0), format!("⚠ {}", issue));
+                }
+            }
+        }
+
+        if code != self.current_request.body {
+            self.current_request.body = code;
+        }
+    }
+
+    // The file a body is streamed from; nothing is read until the request is sent
+    fn draw_body_file_picker(ui: &mut Ui, path: &mut String, resolved_path: &str) {
+        ui.horizontal(|ui| {
+            ui.label(tr("file"));
+            // Typed paths may use {{variables}}
+            ui.add(
+                TextEdit::singleline(path)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text(tr("no-file-selected"))
+                    .desired_width(400.0),
+            );
+            if ui.button(tr("browse")).clicked()
+                && let Some(picked) = rfd::FileDialog::new()
+                    .set_title(tr("select-file-title"))
+                    .pick_file()
+            {
+                *path = picked.to_string_lossy().to_string();
+            }
+            if !path.is_empty() && ui.button("🗑").clicked() {
+                path.clear();
+            }
+        });
+        if !path.is_empty() {
+            match std::fs::metadata(resolved_path) {
+                Ok(metadata) => {
+                    ui.label(Self::format_size(metadata.len() as usize));
+                }
+                Err(_) => {
+                    ui.colored_label(Color32::from_rgb(255, 0, 0), tr("binary-file-missing"));
+                }
+            }
+        }
+    }
+
+    fn draw_binary_body_panel(&mut self, ui: &mut Ui) {
+        let resolved_path = self.resolve_value(&self.current_request.binary_file);
+        Self::draw_body_file_picker(ui, &mut self.current_request.binary_file, &resolved_path);
+
+        // Content-Type lives in the headers like every other body type
+        let mut content_type = self
+            .current_request
+            .headers
+            .iter()
+            .find(|row| row.key.trim().eq_ignore_ascii_case("content-type"))
+            .map(|row| row.value.clone())
+            .unwrap_or_default();
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Content-Type");
+            changed |= ui
+                .add(
+                    TextEdit::singleline(&mut content_type)
+                        .hint_text("application/octet-stream")
+                        .desired_width(250.0),
+                )
+                .changed();
+            ui.menu_button("⏷", |ui| {
+                for option in [
+                    "application/octet-stream",
+                    "application/pdf",
+                    "application/zip",
+                    "image/png",
+                    "image/jpeg",
+                    "text/csv",
+                    "video/mp4",
+                ] {
+                    if ui.button(option).clicked() {
+                        content_type = option.to_string();
+                        changed = true;
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
+        if changed {
+            if content_type.trim().is_empty() {
+                self.remove_content_type_header();
+            } else {
+                self.set_content_type_header(&content_type);
+            }
+        }
+    }
+
+    fn draw_form_data_panel(&mut self, ui: &mut Ui) {
+        ScrollArea::vertical().show(ui, |ui| {
+            let mut to_remove = Vec::new();
+
+            for (i, entry) in self.current_request.form_data.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.checkbox(entry.enabled_mut(), "")
+                        .on_hover_text(tr("send-this-row"));
+                    match entry {
+                        FormDataEntry::Text { key, value, .. } => {
+                            ui.label(tr("text"));
+                            ui.add(
+                                TextEdit::singleline(key)
+                                    .hint_text(tr("key"))
+                                    .desired_width(150.0),
+                            );
+                            let value_response = ui.add(
+                                TextEdit::singleline(value)
+                                    .hint_text(tr("value"))
+                                    .desired_width(200.0),
+                            );
+                            Self::generator_menu(&value_response, value);
+                        }
+                        FormDataEntry::File {
+                            key,
+                            file_path,
+                            file_name,
+                            ..
+                        } => {
+                            ui.label(tr("file"));
+                            ui.add(
+                                TextEdit::singleline(key)
+                                    .hint_text(tr("key"))
+                                    .desired_width(150.0),
+                            );
+                            if ui
+                                .add(
+                                    TextEdit::singleline(file_path)
+                                        .hint_text(tr("no-file-selected"))
+                                        .desired_width(200.0),
+                                )
+                                .on_hover_text(file_name.as_str())
+                                .changed()
+                            {
+                                *file_name = std::path::Path::new(file_path.as_str())
+                                    .file_name()
+                                    .unwrap_or_default()
+                                    .to_string_lossy()
+                                    .to_string();
+                            }
+                            if ui.button(tr("browse")).clicked()
+                                && let Some(path) = rfd::FileDialog::new()
+                                    .set_title(tr("select-file-title"))
+                                    .pick_file()
+                            {
+                                *file_path = path.to_string_lossy().to_string();
+                                *file_name = path
+                                    .file_name()
+                                    .unwrap_or_default()
+                                    .to_string_lossy()
+                                    .to_string();
+                            }
+                        }
+                    }
+
+                    // Type toggle button
+                    let current_is_text = matches!(entry, FormDataEntry::Text { .. });
+                    let toggle_text = if current_is_text {
+                        "→File"
+                    } else {
+                        "→Text"
+                    };
+                    if ui.button(toggle_text).clicked() {
+                        if current_is_text {
+                            if let FormDataEntry::Text {
+                                key,
+                                content_type,
+                                headers,
+                                enabled,
+                                ..
+                            } = entry
+                            {
+                                *entry = FormDataEntry::File {
+                                    key: key.clone(),
+                                    file_path: String::new(),
+                                    file_name: String::new(),
+                                    content_type: std::mem::take(content_type),
+                                    headers: std::mem::take(headers),
+                                    enabled: *enabled,
+                                };
+                            }
+                        } else {
+                            if let FormDataEntry::File {
+                                key,
+                                content_type,
+                                headers,
+                                enabled,
+                                ..
+                            } = entry
+                            {
+                                *entry = FormDataEntry::Text {
+                                    key: key.clone(),
+                                    value: String::new(),
+                                    content_type: std::mem::take(content_type),
+                                    headers: std::mem::take(headers),
+                                    enabled: *enabled,
+                                };
+                            }
+                        }
+                    }
+
+                    if ui.button("🗑").clicked() {
+                        to_remove.push(i);
+                    }
+                });
+
+                let (content_type, headers) = entry.part_options_mut();
+                let mut args = FluentArgs::new();
+                args.set(
+                    "count",
+                    headers.len() + usize::from(!content_type.trim().is_empty()),
+                );
+                ui.indent(("form_part", i), |ui| {
+                    egui::CollapsingHeader::new(tr_args("part-headers", &args))
+                        .id_salt(("form_part_headers", i))
+                        .show(ui, |ui| {
+                            let mut remove_header = None;
+                            egui::Grid::new(("form_part_grid", i)).show(ui, |ui| {
+                                ui.label("Content-Type");
+                                ui.add(
+                                    TextEdit::singleline(content_type)
+                                        .hint_text(tr("part-content-type-hint"))
+                                        .desired_width(200.0),
+                                );
+                                ui.end_row();
+                                for (header_idx, (key, value)) in headers.iter_mut().enumerate() {
+                                    ui.add(
+                                        TextEdit::singleline(key)
+                                            .hint_text(tr("key"))
+                                            .desired_width(150.0),
+                                    );
+                                    ui.horizontal(|ui| {
+                                        ui.add(
+                                            TextEdit::singleline(value)
+                                                .hint_text(tr("value"))
+                                                .desired_width(200.0),
+                                        );
+                                        if ui.button("🗑").clicked() {
+                                            remove_header = Some(header_idx);
+                                        }
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                            if let Some(header_idx) = remove_header {
+                                headers.remove(header_idx);
+                            }
+                            if ui.button(tr("add-header")).clicked() {
+                                headers.push((String::new(), String::new()));
+                            }
+                        });
+                });
+            }
+
+            // Remove entries
+            if !to_remove.is_empty() {
+                for &i in to_remove.iter().rev() {
+                    self.current_request.form_data.remove(i);
+                }
+            }
+
+            // Add new entry button
+            ui.horizontal(|ui| {
+                if ui.button(tr("add-text-field")).clicked() {
+                    self.current_request
+                        .form_data
+                        .push(FormDataEntry::text(String::new(), String::new()));
+                }
+                if ui.button(tr("add-file")).clicked() {
+                    self.current_request
+                        .form_data
+                        .push(FormDataEntry::file(String::new(), String::new()));
+                }
+            });
+        });
+    }
+
+    fn draw_url_encoded_panel(&mut self, ui: &mut Ui) {
+        ScrollArea::vertical().show(ui, |ui| {
+            let mut to_remove = Vec::new();
+
+            for (
+                i,
+                KeyValue {
+                    key,
+                    value,
+                    enabled,
+                },
+            ) in self.current_request.url_encoded_data.iter_mut().enumerate()
+            {
+                ui.horizontal(|ui| {
+                    ui.checkbox(enabled, "").on_hover_text(tr("send-this-row"));
+                    ui.add(
+                        TextEdit::singleline(key)
+                            .hint_text(tr("key"))
+                            .desired_width(200.0),
+                    );
+                    let value_response = ui.add(
+                        TextEdit::singleline(value)
+                            .hint_text(tr("value"))
+                            .desired_width(250.0),
+                    );
+                    Self::generator_menu(&value_response, value);
+
+                    if ui.button("🗑").clicked() {
+                        to_remove.push(i);
+                    }
+                });
+            }
+
+            // Remove entries
+            if !to_remove.is_empty() {
+                for &i in to_remove.iter().rev() {
+                    self.current_request.url_encoded_data.remove(i);
+                }
+            }
+
+            // Add new entry button
+            if ui.button(tr("add-parameter")).clicked() {
+                self.current_request
+                    .url_encoded_data
+                    .push(KeyValue::new("", ""));
+            }
+        });
+    }
+
+    fn draw_query_params_panel(&mut self, ui: &mut Ui) {
+        ScrollArea::vertical().show(ui, |ui| {
+            let mut to_remove = Vec::new();
+
+            // Table header
+            ui.horizontal(|ui| {
+                ui.label(tr("parameter-name"));
+                ui.add_space(150.0);
+                ui.label(tr("parameter-value"));
+            });
+            ui.separator();
+
+            let mut changed = false;
+            for (
+                i,
+                KeyValue {
+                    key,
+                    value,
+                    enabled,
+                },
+            ) in self.current_request.query_params.iter_mut().enumerate()
+            {
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .checkbox(enabled, "")
+                        .on_hover_text(tr("send-this-row"))
+                        .changed();
+                    changed |= ui
+                        .add(
+                            TextEdit::singleline(key)
+                                .hint_text(tr("parameter-name-hint"))
+                                .desired_width(200.0),
+                        )
+                        .changed();
+                    let value_response = ui.add(
+                        TextEdit::singleline(value)
+                            .hint_text(tr("parameter-value-supports-variable"))
+                            .desired_width(300.0),
+                    );
+                    Self::generator_menu(&value_response, value);
+                    changed |= value_response.changed() || value_response.context_menu_opened();
+
+                    if ui.button("🗑").clicked() {
+                        to_remove.push(i);
+                    }
+                });
+            }
+
+            // Remove entries
+            if !to_remove.is_empty() {
+                for &i in to_remove.iter().rev() {
+                    self.current_request.query_params.remove(i);
+                }
+                changed = true;
+            }
+            // The URL shows the table as its query string
+            if changed {
+                self.current_request.sync_url_query();
+            }
+
+            // Add new entry button
+            if ui.button(tr("add-query-parameter")).clicked() {
+                self.current_request
+                    .query_params
+                    .push(KeyValue::new("", ""));
+            }
+
+            // Names come from the URL, so only the values are editable here
+            if !self.current_request.path_variables.is_empty() {
+                ui.add_space(12.0);
+                ui.label(RichText::new(tr("path-variables")).strong())
+                    .on_hover_text(tr("path-variables-hint"));
+                ui.separator();
+                for (name, value) in &mut self.current_request.path_variables {
+                    ui.horizontal(|ui| {
+                        ui.add_sized(
+                            [200.0, ui.spacing().interact_size.y],
+                            egui::Label::new(RichText::new(name.as_str()).monospace()).truncate(),
+                        );
+                        let value_response = ui.add(
+                            TextEdit::singleline(value)
+                                .hint_text(tr("parameter-value-supports-variable"))
+                                .desired_width(300.0),
+                        );
+                        Self::generator_menu(&value_response, value);
+                    });
+                }
+            }
+        });
+    }
+
+    fn draw_retry_buttons(ui: &mut Ui, retry: &mut bool, edit_headers: &mut bool) {
+        if ui.button(tr("retry-icon")).clicked() {
+            *retry = true;
+        }
+        if ui.button(tr("retry-with-modified-headers-menu")).clicked() {
+            *edit_headers = true;
+        }
+    }
+
+    fn draw_request_error(
+        ui: &mut Ui,
+        error: &RequestError,
+        retry: &mut bool,
+        edit_headers: &mut bool,
+    ) {
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.label(
+                RichText::new(format!("⚠ {}", error.kind.title()))
+                    .heading()
+                    .color(ui.visuals().error_fg_color),
+            );
+            if error.attempts > 1 {
+                let mut args = FluentArgs::new();
+                args.set("attempts", error.attempts);
+                ui.label(tr_args("error-gave-up", &args));
+            }
+            if error.kind != RequestErrorKind::Offline {
+                ui.horizontal(|ui| Self::draw_retry_buttons(ui, retry, edit_headers));
+            }
+            ui.add_space(8.0);
+
+            ui.label(RichText::new(tr("error-cause")).strong());
+            for (depth, cause) in error.causes.iter().enumerate() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.add_space(depth as f32 * 12.0);
+                    if depth > 0 {
+                        ui.label("↳");
+                    }
+                    ui.label(RichText::new(cause).monospace());
+                });
+            }
+
+            let suggestions = error.kind.suggestions();
+            if !suggestions.is_empty() {
+                ui.add_space(8.0);
+                ui.label(RichText::new(tr("error-things-to-try")).strong());
+                for suggestion in suggestions {
+                    ui.label(format!("• {}", tr(suggestion)));
+                }
+            }
+
+            ui.add_space(8.0);
+            if ui.button(tr("copy-error")).clicked() {
+                ui.ctx().copy_text(error.causes.join("\n"));
+            }
+        });
+    }
+
+    fn draw_response_panel(&mut self, ui: &mut Ui) {
+        if self.is_loading
+            && let Some(download) = &self.download
+            && download.result.is_none()
+            && !download.path.as_os_str().is_empty()
+        {
+            ui.horizontal(|ui| {
+                ui.heading(tr("response"));
+                ui.spinner();
+            });
+            ui.separator();
+            Self::draw_download_status(ui, download, &mut false, &mut false);
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.heading(tr("response"));
+            if self.is_loading {
+                ui.spinner();
+                if let Some(wait) = &self.retry_wait {
+                    ui.colored_label(Color32::from_rgb(255, 165, 0), wait.label());
+                }
+                if let Some(upload) = &self.upload_progress {
+                    let sent = upload.sent.load(Ordering::Relaxed);
+                    let total = upload.total.load(Ordering::Relaxed);
+                    if total > 0 && sent < total {
+                        ui.add(
+                            egui::ProgressBar::new(sent as f32 / total as f32)
+                                .desired_width(200.0)
+                                .text(format!(
+                                    "↑ {} / {}",
+                                    Self::format_size(sent as usize),
+                                    Self::format_size(total as usize)
+                                )),
+                        );
+                    }
+                }
+                if let Some((received, total)) = self.response_progress {
+                    let received_text = Self::format_size(received as usize);
+                    match total.filter(|total| *total > 0) {
+                        Some(total) => {
+                            ui.add(
+                                egui::ProgressBar::new(received as f32 / total as f32)
+                                    .desired_width(200.0)
+                                    .text(format!(
+                                        "{} / {}",
+                                        received_text,
+                                        Self::format_size(total as usize)
+                                    )),
+                            );
+                        }
+                        None => {
+                            ui.label(received_text);
+                        }
+                    }
+                }
+            }
+        });
+        ui.separator();
+
+        // Response tabs first to avoid borrowing issues
+        let mut response_tab_changed = false;
+        if self.current_response.is_some() {
+            ui.horizontal(|ui| {
+                if ui
+                    .selectable_value(&mut self.response_tab, ResponseTab::Body, tr("body"))
+                    .changed()
+                {
+                    response_tab_changed = true;
+                }
+                if ui
+                    .selectable_value(&mut self.response_tab, ResponseTab::Headers, tr("headers"))
+                    .changed()
+                {
+                    response_tab_changed = true;
+                }
+                if ui
+                    .selectable_value(&mut self.response_tab, ResponseTab::Cookies, tr("cookies"))
+                    .changed()
+                {
+                    response_tab_changed = true;
+                }
+                let redirects = self
+                    .current_response
+                    .as_ref()
+                    .map_or(0, |response| response.redirects.hops.len());
+                if redirects > 0
+                    && ui
+                        .selectable_value(
+                            &mut self.response_tab,
+                            ResponseTab::Redirects,
+                            format!("{} ({})", tr("redirects"), redirects),
+                        )
+                        .changed()
+                {
+                    response_tab_changed = true;
+                }
+                let tests_label = if self.assertion_results.is_empty() {
+                    tr("tests")
+                } else {
+                    let passed = self
+                        .assertion_results
+                        .iter()
+                        .filter(|(_, result)| result.is_ok())
+                        .count();
+                    format!(
+                        "{} ({}/{})",
+                        tr("tests"),
+                        passed,
+                        self.assertion_results.len()
+                    )
+                };
+                if ui
+                    .selectable_value(&mut self.response_tab, ResponseTab::Tests, tests_label)
+                    .changed()
+                {
+                    response_tab_changed = true;
+                }
+            });
+            ui.separator();
+        }
+
+        let mut save_example_clicked = false;
+        let mut retry_clicked = false;
+        let mut edit_headers_clicked = false;
+        if let Some(response) = &self.current_response {
+            // Status and time
+            ui.horizontal(|ui| {
+                ui.label(self.status_text(response.status, {
+                    let mut args = FluentArgs::new();
+                    args.set("status", response.status);
+                    args.set("text", response.status_text.clone());
+                    tr_args("response-status", &args)
+                }));
+                let mut args = FluentArgs::new();
+                args.set("ms", response.timing.total.to_string());
+                ui.label(tr_args("response-time-ms", &args));
+                Self::draw_timing_waterfall(ui, &response.timing);
+                if let Some(reused) = response.connection_reused {
+                    let (text, hint) = if reused {
+                        ("connection-reused", "connection-reused-hint")
+                    } else {
+                        ("connection-new", "connection-new-hint")
+                    };
+                    ui.colored_label(Color32::GRAY, tr(text))
+                        .on_hover_text(tr(hint));
+                }
+                if !response.version.is_empty() {
+                    ui.label(&response.version)
+                        .on_hover_text(tr("negotiated-http-version"));
+                }
+                if let Some(remote_addr) = &response.remote_addr {
+                    ui.label(format!("IP: {}", remote_addr));
+                }
+                let mut args = FluentArgs::new();
+                args.set(
+                    "size",
+                    Self::format_size(response.body_size + response.headers_size),
+                );
+                ui.label(tr_args("response-size", &args));
+                args.set("size", Self::format_size(response.body_size));
+                ui.label(tr_args("response-body-size", &args));
+                if let Some(encoding) = &response.content_encoding {
+                    match &response.encoded_body {
+                        Some(encoded) => {
+                            ui.label(format!(
+                                "{}: {}",
+                                encoding,
+                                Self::format_size(encoded.len())
+                            ))
+                            .on_hover_text(tr("content-encoding-decoded-hint"));
+                        }
+                        None => {
+                            ui.colored_label(
+                                Color32::from_rgb(255, 165, 0),
+                                format!("{} ⚠", encoding),
+                            )
+                            .on_hover_text(tr("content-encoding-not-decoded-hint"));
+                        }
+                    }
+                }
+                args.set("size", Self::format_size(response.headers_size));
+                ui.label(tr_args("response-headers-size", &args));
+                if !response.attempts.is_empty() {
+                    let details = response
+                        .attempts
+                        .iter()
+                        .enumerate()
+                        .map(|(i, record)| match &record.error {
+                            Some(error) => format!("#{}: {} ({} ms)", i + 1, error, record.time),
+                            None => format!("#{}: {} ({} ms)", i + 1, record.status, record.time),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    args.set("count", response.attempts.len());
+                    ui.label(tr_args("response-retries", &args))
+                        .on_hover_text(details);
+                }
+                if response.tls_verification_disabled {
+                    ui.colored_label(Color32::from_rgb(255, 0, 0), tr("tls-not-verified"))
+                        .on_hover_text(tr("this-response-was-received-with-certificate"));
+                }
+                if response.from_example {
+                    ui.colored_label(Color32::from_rgb(255, 165, 0), tr("saved-example-badge"));
+                } else if response.status != 0 && ui.button(tr("save-as-example")).clicked() {
+                    save_example_clicked = true;
+                }
+                if response.status >= 500 && !response.from_example {
+                    Self::draw_retry_buttons(ui, &mut retry_clicked, &mut edit_headers_clicked);
+                }
+            });
+            ui.separator();
+
+            let mut download_clicked = false;
+            let mut pause_clicked = false;
+            let mut resume_clicked = false;
+            if response.truncated_bytes.is_some() {
+                ui.horizontal(|ui| {
+                    ui.colored_label(Color32::from_rgb(255, 165, 0), {
+                        let mut args = FluentArgs::new();
+                        args.set("size", Self::format_size(response.body_size));
+                        tr_args("response-truncated", &args)
+                    });
+                    // Fetching the rest sends the request again, which only GET and HEAD
+                    // can take without repeating side effects
+                    let repeatable = self
+                        .last_resolved_request
+                        .as_ref()
+                        .is_some_and(|request| Self::is_repeatable_method(&request.method));
+                    if ui
+                        .add_enabled(
+                            repeatable,
+                            egui::Button::new(tr("download-remainder-to-file")),
+                        )
+                        .on_disabled_hover_text(tr("download-remainder-get-only"))
+                        .clicked()
+                    {
+                        download_clicked = true;
+                    }
+                });
+            }
+            let mut save_spooled_clicked = false;
+            if let Some(spooled) = &response.spooled {
+                ui.horizontal(|ui| {
+                    let mut args = FluentArgs::new();
+                    args.set("size", Self::format_size(spooled.size as usize));
+                    ui.colored_label(
+                        Color32::from_rgb(255, 165, 0),
+                        tr_args("response-spooled", &args),
+                    )
+                    .on_hover_text(spooled.path.display().to_string());
+                    if ui.button(tr("save-body-as")).clicked() {
+                        save_spooled_clicked = true;
+                    }
+                });
+            }
+            if let Some(download) = &self.download {
+                Self::draw_download_status(ui, download, &mut pause_clicked, &mut resume_clicked);
+            }
+            if response.truncated_bytes.is_some()
+                || response.spooled.is_some()
+                || self.download.is_some()
+            {
+                ui.separator();
+            }
+
+            // Response content
+            let mut selection = None;
+            let mut show_encoded_body = self.show_encoded_body;
+            let mut json_layout = None;
+            let mut remove_cookie = None;
+            let mut clear_cookies = false;
+            let mut open_cookie_manager = false;
+            ScrollArea::vertical().show(ui, |ui| match self.response_tab {
+                ResponseTab::Body => {
+                    if let Some(encoded) = &response.encoded_body {
+                        ui.checkbox(&mut show_encoded_body, tr("show-compressed-bytes"));
+                        if show_encoded_body {
+                            ui.add(
+                                TextEdit::multiline(&mut Self::hex_dump(encoded).as_str())
+                                    .font(egui::TextStyle::Monospace)
+                                    .desired_rows(15)
+                                    .desired_width(ui.available_width()),
+                            );
+                            return;
+                        }
+                    }
+                    // Only whole bodies can be reformatted, not the preview of a large one
+                    let is_json = response.headers.iter().any(|(key, value)| {
+                        key.eq_ignore_ascii_case("content-type") && value.contains("json")
+                    });
+                    if is_json && response.spooled.is_none() && response.truncated_bytes.is_none() {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .selectable_label(
+                                    response.reformatted_body.is_none(),
+                                    tr("as-received"),
+                                )
+                                .clicked()
+                            {
+                                json_layout = Some(JsonLayout::AsReceived);
+                            }
+                            if ui.small_button(tr("format-json")).clicked() {
+                                json_layout = Some(JsonLayout::Pretty);
+                            }
+                            if ui.small_button(tr("minify-json")).clicked() {
+                                json_layout = Some(JsonLayout::Minified);
+                            }
+                        });
+                    }
+                    let body = response.reformatted_body.as_ref().unwrap_or(&response.body);
+                    // Read-only, but selectable so the Tools panel can pick up a selection
+                    let output = TextEdit::multiline(&mut body.as_str())
+                        .desired_rows(15)
+                        .desired_width(ui.available_width())
+                        .show(ui);
+                    if let Some(range) = output.cursor_range {
+                        selection = Some(range.slice_str(body).to_string());
+                    }
+                }
+                ResponseTab::Headers => {
+                    for (key, value) in &response.headers {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(key).strong());
+                            ui.label(value);
+                        });
+                    }
+                }
+                ResponseTab::Cookies => {
+                    ui.label(RichText::new(tr("cookies-received")).strong());
+                    if response.set_cookies.is_empty() {
+                        ui.colored_label(Color32::GRAY, tr("no-cookies-received"));
+                    }
+                    for header in &response.set_cookies {
+                        ui.label(RichText::new(header).monospace());
+                    }
+                    ui.add_space(8.0);
+                    let mut args = FluentArgs::new();
+                    args.set(
+                        "jar",
+                        self.active_session()
+                            .map_or_else(|| tr("workspace"), |session| session.name.clone()),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(tr_args("cookie-jar-of", &args)).strong());
+                        if ui
+                            .add_enabled(
+                                !self.cookie_jar().is_empty(),
+                                egui::Button::new(tr("clear-cookies")),
+                            )
+                            .clicked()
+                        {
+                            clear_cookies = true;
+                        }
+                        if ui.button(tr("cookie-manager-menu")).clicked() {
+                            open_cookie_manager = true;
+                        }
+                    });
+                    if self.cookie_jar().is_empty() {
+                        ui.colored_label(Color32::GRAY, tr("cookie-jar-empty"));
+                    }
+                    egui::Grid::new("cookie_jar").striped(true).show(ui, |ui| {
+                        for (idx, cookie) in self.cookie_jar().iter().enumerate() {
+                            ui.label(format!("{}{}", cookie.domain, cookie.path));
+                            ui.label(RichText::new(&cookie.name).strong());
+                            ui.label(&cookie.value);
+                            if ui.button("🗑").clicked() {
+                                remove_cookie = Some(idx);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                }
+                ResponseTab::Redirects => {
+                    for (idx, hop) in response.redirects.hops.iter().enumerate() {
+                        egui::CollapsingHeader::new(self.status_text(
+                            hop.status,
+                            format!("{}. {} {} {}", idx + 1, hop.status, hop.method, hop.url),
+                        ))
+                        .id_salt(("redirect_hop", idx))
+                        .show(ui, |ui| {
+                            for (key, value) in &hop.headers {
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(key).strong());
+                                    ui.label(value);
+                                });
+                            }
+                        });
+                    }
+                    ui.label(self.status_text(
+                        response.status,
+                        format!(
+                            "{}. {} {}",
+                            response.redirects.hops.len() + 1,
+                            response.status,
+                            response.status_text
+                        ),
+                    ));
+                    if response.redirects.limit_reached {
+                        ui.colored_label(
+                            Color32::from_rgb(255, 165, 0),
+                            tr("redirect-limit-reached"),
+                        );
+                    }
+                }
+                ResponseTab::Tests => {
+                    if self.assertion_results.is_empty() && self.capture_results.is_empty() {
+                        ui.colored_label(Color32::GRAY, tr("no-assertions"));
+                    }
+                    for (description, result) in &self.capture_results {
+                        match result {
+                            Ok(value) => {
+                                ui.label(format!("↳ {} = {}", description, value));
+                            }
+                            Err(e) => {
+                                ui.colored_label(
+                                    Color32::from_rgb(255, 0, 0),
+                                    format!("✖ {} — {}", description, e),
+                                );
+                            }
+                        }
+                    }
+                    for (description, result) in &self.assertion_results {
+                        match result {
+                            Ok(()) => {
+                                ui.colored_label(
+                                    Color32::from_rgb(0, 128, 0),
+                                    format!("✔ {}", description),
+                                );
+                            }
+                            Err(e) => {
+                                ui.colored_label(
+                                    Color32::from_rgb(255, 0, 0),
+                                    format!("✖ {} — {}", description, e),
+                                );
+                            }
+                        }
+                    }
+                }
+            });
+            self.show_encoded_body = show_encoded_body;
+            if let Some(layout) = json_layout
+                && let Some(response) = &mut self.current_response
+            {
+                match layout {
+                    JsonLayout::AsReceived => response.reformatted_body = None,
+                    _ => match layout.apply(&response.body) {
+                        Ok(body) => response.reformatted_body = Some(body),
+                        Err(e) => {
+                            let mut args = FluentArgs::new();
+                            args.set("error", e.to_string());
+                            self.notify(ToastKind::Error, tr_args("cannot-format-json", &args));
+                        }
+                    },
+                }
+            }
+            if let Some(idx) = remove_cookie {
+                self.cookie_jar_mut().remove(idx);
+                self.auto_save_workspace();
+            }
+            if clear_cookies {
+                self.cookie_jar_mut().clear();
+                self.auto_save_workspace();
+            }
+            if open_cookie_manager {
+                self.cookie_manager_dialog = true;
+            }
+
+            if let Some(selection) = selection {
+                self.response_selection = selection;
+            }
+            if download_clicked {
+                self.download_remainder();
+            }
+            if save_spooled_clicked {
+                self.save_spooled_body();
+            }
+            if pause_clicked && let Some(download) = &self.download {
+                download.pause.store(true, Ordering::Relaxed);
+            }
+            if resume_clicked {
+                self.resume_download();
+            }
+            if save_example_clicked && let Some(response) = &self.current_response {
+                let example = SavedExample {
+                    status: response.status,
+                    status_text: response.status_text.clone(),
+                    headers: response.headers.clone(),
+                    body: response.body.clone(),
+                };
+                // Saving an example leaves other unsaved edits alone
+                self.current_request.example = Some(example.clone());
+                if let Some(saved) = self.saved_request_mut() {
+                    saved.example = Some(example);
+                    self.auto_save_workspace();
+                }
+            }
+        } else if let Some(error) = &self.request_error {
+            Self::draw_request_error(ui, error, &mut retry_clicked, &mut edit_headers_clicked);
+        } else {
+            ui.centered_and_justified(|ui| {
+                ui.label(tr("no-response-yet-send-a-request"));
+            });
+        }
+
+        if retry_clicked && !self.is_loading {
+            self.send_request();
+        }
+        if edit_headers_clicked {
+            self.retry_headers = Some(self.current_request.headers.clone());
+        }
+
+        if response_tab_changed {
+            self.save_cache();
+        }
+    }
+
+    fn draw_proxy_settings(
+        ui: &mut Ui,
+        proxy: &mut ProxySettings,
+        system_proxy: &mut Option<SystemProxy>,
+    ) -> bool {
+        let mut changed = false;
+        ui.add_enabled_ui(!proxy.enabled, |ui| {
+            changed |= ui
+                .checkbox(&mut proxy.use_system_proxy, tr("use-system-proxy-settings"))
+                .changed();
+            if proxy.use_system_proxy {
+                ui.horizontal(|ui| {
+                    match system_proxy {
+                        Some(detected) => {
+                            let mut args = FluentArgs::new();
+                            args.set("url", detected.url().to_string());
+                            args.set("source", detected.source);
+                            ui.label(tr_args("system-proxy-found", &args));
+                        }
+                        None => {
+                            ui.label(tr("no-system-proxy-detected"));
+                        }
+                    }
+                    if ui.small_button(tr("detect-again")).clicked() {
+                        *system_proxy = SystemProxy::detect();
+                    }
+                });
+                if let Some(detected) = system_proxy
+                    && !detected.no_proxy.is_empty()
+                {
+                    ui.colored_label(
+                        Color32::GRAY,
+                        format!("{}: {}", tr("system-bypass-hosts"), detected.no_proxy),
+                    );
+                }
+            }
+        });
+        changed |= ui
+            .checkbox(&mut proxy.enabled, tr("use-manual-proxy"))
+            .changed();
+        ui.add_enabled_ui(proxy.enabled, |ui| {
+            egui::Grid::new("proxy_grid")
+                .num_columns(2)
+                .spacing([20.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label(tr("proxy-url"));
+                    changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut proxy.url)
+                                .hint_text("http://host:port or socks5://host:port"),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label(tr("username"));
+                    changed |= ui.text_edit_singleline(&mut proxy.username).changed();
+                    ui.end_row();
+
+                    ui.label(tr("password"));
+                    changed |= ui
+                        .add(TextEdit::singleline(&mut proxy.password).password(true))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label(tr("proxy-auth"));
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .radio_value(&mut proxy.auth, ProxyAuth::Basic, "Basic")
+                            .changed();
+                        changed |= ui
+                            .radio_value(&mut proxy.auth, ProxyAuth::Ntlm, "NTLM")
+                            .changed();
+                        if proxy.auth == ProxyAuth::Ntlm {
+                            ui.colored_label(Color32::GRAY, tr("proxy-ntlm-hint"));
+                        }
+                    });
+                    ui.end_row();
+                });
+        });
+        // Applies to the manual proxy and on top of the system's own exceptions
+        ui.add_enabled_ui(proxy.enabled || proxy.use_system_proxy, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("bypass-hosts"));
+                changed |= ui
+                    .add(
+                        TextEdit::singleline(&mut proxy.no_proxy)
+                            .hint_text("localhost, *.internal, 10.0.0.0/8"),
+                    )
+                    .changed();
+            });
+        });
+        changed
+    }
+
+    fn import_ca_certificate(&mut self) {
+        let Some(paths) = rfd::FileDialog::new()
+            .set_title(tr("import-ca-certificates-title"))
+            .add_filter(tr("pem-certificate-filter"), &["pem", "crt", "cer"])
+            .pick_files()
+        else {
+            return;
+        };
+
+        let mut certificates = Vec::new();
+        for path in paths {
+            let name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let mut args = FluentArgs::new();
+            args.set("name", name.clone());
+            let pem = match std::fs::read_to_string(&path) {
+                Ok(pem) => pem,
+                Err(e) => {
+                    args.set("error", e.to_string());
+                    self.workspace_settings_error = Some(tr_args("read-file-failed", &args));
+                    return;
+                }
+            };
+            match reqwest::Certificate::from_pem_bundle(pem.as_bytes()) {
+                Ok(bundle) if !bundle.is_empty() => {}
+                Ok(_) => {
+                    self.workspace_settings_error = Some(tr_args("no-pem-certificates", &args));
+                    return;
+                }
+                Err(e) => {
+                    args.set("error", e.to_string());
+                    self.workspace_settings_error = Some(tr_args("invalid-pem-certificate", &args));
+                    return;
+                }
+            }
+            certificates.push(CaCertificate { name, pem });
+        }
+
+        self.workspace_settings_error = None;
+        self.current_workspace_mut()
+            .settings
+            .ca_certificates
+            .extend(certificates);
+        self.auto_save_workspace();
+        self.save_cache();
+    }
+
+    // PEM asks for the certificate and then the key, unless the first file holds both
+    fn import_client_certificate(&mut self, format: CertificateFormat) {
+        let dialog = rfd::FileDialog::new().set_title(tr("import-client-certificate-title"));
+        let dialog = match format {
+            CertificateFormat::Pem => {
+                dialog.add_filter(tr("pem-certificate-filter"), &["pem", "crt", "cer"])
+            }
+            CertificateFormat::Pkcs12 => {
+                dialog.add_filter(tr("pkcs12-archive-filter"), &["p12", "pfx"])
+            }
+        };
+        let Some(path) = dialog.pick_file() else {
+            return;
+        };
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let read_error = |e: std::io::Error| {
+            let mut args = FluentArgs::new();
+            args.set("name", name.clone());
+            args.set("error", e.to_string());
+            tr_args("read-file-failed", &args)
+        };
+
+        let mut certificate = ClientCertificate {
+            enabled: true,
+            name: name.clone(),
+            format,
+            ..Default::default()
+        };
+        let result =
+            match format {
+                CertificateFormat::Pem => std::fs::read_to_string(&path)
+                    .map_err(read_error)
+                    .and_then(|pem| {
+                        certificate.key = if pem.contains("PRIVATE KEY-----") {
+                            pem.clone()
+                        } else {
+                            let Some(key_path) = rfd::FileDialog::new()
+                                .set_title(tr("select-private-key-title"))
+                                .add_filter(tr("pem-private-key-filter"), &["pem", "key"])
+                                .pick_file()
+                            else {
+                                return Ok(false);
+                            };
+                            std::fs::read_to_string(&key_path)
+                                .map_err(|e| tr_error("read-key-failed", e))?
+                        };
+                        certificate.certificate = pem;
+                        // Catches a PKCS#1 or encrypted key before the first request fails
+                        certificate.identity().map(|_| true)
+                    }),
+                // Checked once a passphrase can be entered; a wrong one fails the next request
+                CertificateFormat::Pkcs12 => std::fs::read(&path).map_err(read_error).map(|der| {
+                    certificate.certificate = base64::engine::general_purpose::STANDARD.encode(der);
+                    true
+                }),
+            };
+        match result {
+            Ok(true) => {
+                self.workspace_settings_error = None;
+                self.current_workspace_mut()
+                    .settings
+                    .client_certificates
+                    .push(certificate);
+                self.auto_save_workspace();
+                self.save_cache();
+            }
+            Ok(false) => {}
+            Err(e) => {
+                let mut args = FluentArgs::new();
+                args.set("name", name);
+                args.set("error", e);
+                self.workspace_settings_error = Some(tr_args("invalid-client-certificate", &args));
+            }
+        }
+    }
+
+    fn draw_retry_policy(ui: &mut Ui, policy: &mut RetryPolicy) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(tr("retries"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut policy.max_retries).range(0..=10))
+                .changed();
+            ui.label(tr("backoff-ms"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut policy.backoff_ms).range(0..=60000))
+                .on_hover_text(tr("doubled-after-each-attempt"))
+                .changed();
+        });
+        ui.add_enabled_ui(policy.max_retries > 0, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("retry-on"));
+                changed |= ui
+                    .checkbox(
+                        &mut policy.retry_on_connection_error,
+                        tr("connection-errors"),
+                    )
+                    .changed();
+                changed |= ui.checkbox(&mut policy.retry_on_429, "429").changed();
+                changed |= ui.checkbox(&mut policy.retry_on_5xx, "5xx").changed();
+            });
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .checkbox(
+                        &mut policy.honor_retry_after,
+                        tr("honor-retry-after-header"),
+                    )
+                    .changed();
+                ui.add_enabled_ui(policy.honor_retry_after, |ui| {
+                    ui.label(tr("max-wait-secs"));
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut policy.max_retry_after_secs).range(1..=3600))
+                        .changed();
+                });
+            });
+        });
+        changed
+    }
+
+    fn draw_workspace_settings_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.workspace_settings_dialog;
+        let mut settings_changed = false;
+        let mut import_ca = false;
+        let mut import_identity = None;
+        let workspace = &mut self.workspaces[self.current_workspace];
+        let settings_error = &self.workspace_settings_error;
+
+        let mut args = FluentArgs::new();
+        args.set("name", workspace.name.clone());
+        egui::Window::new(tr_args("workspace-settings-title", &args))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading(tr("proxy"));
+                settings_changed |= ui
+                    .checkbox(
+                        &mut workspace.settings.override_proxy,
+                        tr("override-application-proxy"),
+                    )
+                    .changed();
+                ui.add_enabled_ui(workspace.settings.override_proxy, |ui| {
+                    settings_changed |= Self::draw_proxy_settings(
+                        ui,
+                        &mut workspace.settings.proxy,
+                        &mut self.system_proxy,
+                    );
+                });
+
+                ui.separator();
+                ui.heading(tr("network"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("ip-version-label"));
+                    egui::ComboBox::from_id_salt("workspace_ip_preference")
+                        .selected_text(workspace.settings.ip_preference.label())
+                        .show_ui(ui, |ui| {
+                            for (option, label) in IpPreference::OPTIONS {
+                                settings_changed |= ui
+                                    .selectable_value(
+                                        &mut workspace.settings.ip_preference,
+                                        option,
+                                        tr(label),
+                                    )
+                                    .changed();
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("http-version"));
+                    egui::ComboBox::from_id_salt("workspace_http_version")
+                        .selected_text(workspace.settings.http_version.label())
+                        .show_ui(ui, |ui| {
+                            for (option, label) in HttpVersion::OPTIONS {
+                                settings_changed |= ui
+                                    .selectable_value(
+                                        &mut workspace.settings.http_version,
+                                        option,
+                                        label,
+                                    )
+                                    .changed();
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("source-address"));
+                    settings_changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut workspace.settings.local_address)
+                                .hint_text(tr("source-address-hint"))
+                                .desired_width(200.0),
+                        )
+                        .changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("network-interface"));
+                    settings_changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut workspace.settings.interface)
+                                .hint_text(tr("network-interface-hint"))
+                                .desired_width(200.0),
+                        )
+                        .on_hover_text(tr("supported-on-linux-and-macos"))
+                        .changed();
+                });
+
+                ui.separator();
+                ui.heading(tr("cookies"));
+                settings_changed |= ui
+                    .checkbox(
+                        &mut workspace.settings.persist_cookies,
+                        tr("persist-cookies"),
+                    )
+                    .on_hover_text(tr("persist-cookies-hint"))
+                    .changed();
+                if workspace.settings.persist_cookies {
+                    ui.indent("persist-login-cookies", |ui| {
+                        settings_changed |= ui
+                            .checkbox(
+                                &mut workspace.settings.persist_login_cookies,
+                                tr("persist-login-cookies"),
+                            )
+                            .changed();
+                        if workspace.settings.persist_login_cookies {
+                            ui.colored_label(
+                                Color32::from_rgb(255, 0, 0),
+                                tr("persist-login-cookies-warning"),
+                            );
+                        } else {
+                            ui.weak(tr("persist-login-cookies-off"));
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.heading("TLS");
+                settings_changed |= ui
+                    .checkbox(
+                        &mut workspace.settings.skip_tls_verification,
+                        tr("disable-tls-certificate-verification"),
+                    )
+                    .changed();
+                if workspace.settings.skip_tls_verification {
+                    ui.colored_label(
+                        Color32::from_rgb(255, 0, 0),
+                        format!("⚠ {}", tr("invalid-and-self-signed-certificates-will")),
+                    );
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("tls-versions"));
+                    for (id, version) in [
+                        ("workspace_min_tls", &mut workspace.settings.min_tls_version),
+                        ("workspace_max_tls", &mut workspace.settings.max_tls_version),
+                    ] {
+                        egui::ComboBox::from_id_salt(id)
+                            .selected_text(version.label())
+                            .show_ui(ui, |ui| {
+                                for (option, label) in TlsVersion::OPTIONS {
+                                    settings_changed |=
+                                        ui.selectable_value(version, option, label).changed();
+                                }
+                            });
+                        if id == "workspace_min_tls" {
+                            ui.label(tr("to"));
+                        }
+                    }
+                });
+
+                ui.label(tr("trusted-ca-certificates"));
+                let mut to_remove = None;
+                for (i, certificate) in workspace.settings.ca_certificates.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("📜 {}", certificate.name));
+                        let count = certificate
+                            .pem
+                            .matches("-----BEGIN CERTIFICATE-----")
+                            .count();
+                        if count > 1 {
+                            let mut args = FluentArgs::new();
+                            args.set("count", count);
+                            ui.colored_label(Color32::GRAY, tr_args("ca-bundle-count", &args));
+                        }
+                        if ui.button("🗑").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = to_remove {
+                    workspace.settings.ca_certificates.remove(i);
+                    settings_changed = true;
+                }
+                if ui.button(tr("import-ca-certificate")).clicked() {
+                    import_ca = true;
+                }
+                if let Some(error) = settings_error {
+                    ui.colored_label(Color32::from_rgb(255, 0, 0), error);
+                }
+
+                ui.label(tr("certificate-pins"))
+                    .on_hover_text(tr("certificate-pins-hint"));
+                let mut to_remove = None;
+                for (i, pin) in workspace.settings.certificate_pins.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        settings_changed |= ui
+                            .add(
+                                TextEdit::singleline(&mut pin.host)
+                                    .hint_text("api.example.com")
+                                    .desired_width(150.0),
+                            )
+                            .changed();
+                        settings_changed |= ui
+                            .add(
+                                TextEdit::singleline(&mut pin.fingerprint)
+                                    .hint_text("sha256/AAAA…= or AB:CD:…")
+                                    .desired_width(300.0),
+                            )
+                            .changed();
+                        if ui.button("🗑").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = to_remove {
+                    workspace.settings.certificate_pins.remove(i);
+                    settings_changed = true;
+                }
+                if ui.button(tr("add-certificate-pin")).clicked() {
+                    workspace
+                        .settings
+                        .certificate_pins
+                        .push(CertificatePin::default());
+                    settings_changed = true;
+                }
+
+                ui.label(tr("client-certificates"))
+                    .on_hover_text(tr("client-certificates-hint"));
+                let mut to_remove = None;
+                for (i, certificate) in workspace
+                    .settings
+                    .client_certificates
+                    .iter_mut()
+                    .enumerate()
+                {
+                    ui.horizontal(|ui| {
+                        settings_changed |= ui.checkbox(&mut certificate.enabled, "").changed();
+                        settings_changed |= ui
+                            .add(
+                                TextEdit::singleline(&mut certificate.host)
+                                    .hint_text("*.internal.example.com")
+                                    .desired_width(150.0),
+                            )
+                            .changed();
+                        ui.label(format!("🔑 {}", certificate.name));
+                        if certificate.format == CertificateFormat::Pkcs12 {
+                            settings_changed |= ui
+                                .add(
+                                    TextEdit::singleline(&mut certificate.passphrase)
+                                        .password(true)
+                                        .hint_text(tr("passphrase"))
+                                        .desired_width(120.0),
+                                )
+                                .changed();
+                        }
+                        if ui.button("🗑").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = to_remove {
+                    workspace.settings.client_certificates.remove(i);
+                    settings_changed = true;
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("add-pem-client-certificate")).clicked() {
+                        import_identity = Some(CertificateFormat::Pem);
+                    }
+                    if ui.button(tr("add-pkcs12-client-certificate")).clicked() {
+                        import_identity = Some(CertificateFormat::Pkcs12);
+                    }
+                });
+
+                ui.separator();
+                ui.heading(tr("retries"));
+                settings_changed |= Self::draw_retry_policy(ui, &mut workspace.settings.retry);
+
+                ui.separator();
+                ui.heading(tr("default-headers"));
+                ui.label(tr("sent-with-every-request-unless-the"));
+                let mut to_remove = None;
+                for (i, (key, value)) in workspace.settings.default_headers.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        settings_changed |= ui
+                            .add(
+                                TextEdit::singleline(key)
+                                    .hint_text(tr("header-name-hint"))
+                                    .desired_width(150.0),
+                            )
+                            .changed();
+                        settings_changed |= ui
+                            .add(
+                                TextEdit::singleline(value)
+                                    .hint_text(tr("header-value-supports-variable"))
+                                    .desired_width(200.0),
+                            )
+                            .changed();
+                        if ui.button("🗑").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = to_remove {
+                    workspace.settings.default_headers.remove(i);
+                    settings_changed = true;
+                }
+                if ui.button(tr("add-default-header")).clicked() {
+                    workspace
+                        .settings
+                        .default_headers
+                        .push((String::new(), String::new()));
+                    settings_changed = true;
+                }
+
+                ui.separator();
+                ui.heading(tr("header-presets"));
+                ui.label(tr("header-presets-hint"));
+                let mut remove_preset = None;
+                for (p, preset) in workspace.settings.header_presets.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        settings_changed |= ui
+                            .add(
+                                TextEdit::singleline(&mut preset.name)
+                                    .hint_text(tr("preset-name"))
+                                    .desired_width(150.0),
+                            )
+                            .changed();
+                        if ui.button("🗑").clicked() {
+                            remove_preset = Some(p);
+                        }
+                    });
+                    ui.indent(("header_preset", p), |ui| {
+                        let mut to_remove = None;
+                        for (i, (key, value)) in preset.headers.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                settings_changed |= ui
+                                    .add(
+                                        TextEdit::singleline(key)
+                                            .hint_text(tr("header-name-hint"))
+                                            .desired_width(150.0),
+                                    )
+                                    .changed();
+                                settings_changed |= ui
+                                    .add(
+                                        TextEdit::singleline(value)
+                                            .hint_text(tr("header-value-supports-variable"))
+                                            .desired_width(200.0),
+                                    )
+                                    .changed();
+                                if ui.button("🗑").clicked() {
+                                    to_remove = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = to_remove {
+                            preset.headers.remove(i);
+                            settings_changed = true;
+                        }
+                        if ui.button(tr("add-header-row")).clicked() {
+                            preset.headers.push((String::new(), String::new()));
+                            settings_changed = true;
+                        }
+                    });
+                }
+                if let Some(p) = remove_preset {
+                    workspace.settings.header_presets.remove(p);
+                    settings_changed = true;
+                }
+                if ui.button(tr("add-header-preset")).clicked() {
+                    workspace
+                        .settings
+                        .header_presets
+                        .push(HeaderPreset::default());
+                    settings_changed = true;
+                }
+            });
+
+        if import_ca {
+            self.import_ca_certificate();
+        }
+        if let Some(format) = import_identity {
+            self.import_client_certificate(format);
+        }
+        if settings_changed {
+            self.auto_save_workspace();
+            self.save_cache();
+        }
+        self.workspace_settings_dialog = open;
+    }
+
+    fn draw_settings_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.settings_dialog;
+        let mut settings_changed = false;
+        let mut theme_changed = false;
+
+        egui::Window::new(tr("settings"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ScrollArea::vertical().max_height(600.0).show(ui, |ui| {
+                    egui::Grid::new("settings_grid")
+                        .num_columns(2)
+                        .spacing([20.0, 8.0])
+                        .show(ui, |ui| {
+                            ui.heading(tr("appearance"));
+                            ui.end_row();
+
+                            ui.label(tr("theme"));
+                            ui.horizontal(|ui| {
+                                for (theme, label) in [
+                                    (ThemeSetting::System, "theme-system"),
+                                    (ThemeSetting::Light, "theme-light"),
+                                    (ThemeSetting::Dark, "theme-dark"),
+                                ] {
+                                    if ui
+                                        .selectable_value(
+                                            &mut self.settings.theme,
+                                            theme,
+                                            tr(label),
+                                        )
+                                        .changed()
+                                    {
+                                        theme_changed = true;
+                                    }
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label(tr("language"));
+
+                            egui::ComboBox::from_id_salt("language")
+                                .selected_text(self.settings.language.name())
+                                .show_ui(ui, |ui| {
+                                    for language in Language::ALL {
+                                        let name = language.name();
+
+                                        if ui
+                                            .selectable_value(
+                                                &mut self.settings.language,
+                                                language,
+                                                name,
+                                            )
+                                            .changed()
+                                        {
+                                            set_language(language);
+
+                                            settings_changed = true;
+                                        }
+                                    }
+                                });
+
+                            ui.end_row();
+
+                            ui.label(tr("accent-color"));
+                            ui.horizontal(|ui| {
+                                let mut custom = self.settings.accent_color.is_some();
+                                if ui.checkbox(&mut custom, tr("custom")).changed() {
+                                    self.settings.accent_color = custom.then_some([0, 120, 215]);
+                                    theme_changed = true;
+                                }
+                                if let Some(accent) = &mut self.settings.accent_color
+                                    && ui.color_edit_button_srgb(accent).changed()
+                                {
+                                    theme_changed = true;
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label(tr("method-colors"));
+                            ui.horizontal_wrapped(|ui| {
+                                let colors = &mut self.settings.method_colors;
+                                for (label, color) in [
+                                    ("GET", &mut colors.get),
+                                    ("POST", &mut colors.post),
+                                    ("PUT", &mut colors.put),
+                                    ("PATCH", &mut colors.patch),
+                                    ("DELETE", &mut colors.delete),
+                                    ("HEAD", &mut colors.head),
+                                    ("OPTIONS", &mut colors.options),
+                                    ("Other", &mut colors.other),
+                                ] {
+                                    if ui.color_edit_button_srgb(color).changed() {
+                                        settings_changed = true;
+                                    }
+                                    ui.label(label);
+                                }
+                                if ui.button(tr("reset")).clicked() {
+                                    *colors = MethodColors::default();
+                                    settings_changed = true;
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label(tr("status-colors"));
+                            ui.horizontal_wrapped(|ui| {
+                                let colors = &mut self.settings.status_colors;
+                                for (label, color) in [
+                                    ("2xx", &mut colors.success),
+                                    ("3xx", &mut colors.redirect),
+                                    ("4xx", &mut colors.client_error),
+                                    ("5xx", &mut colors.server_error),
+                                    ("ERR", &mut colors.error),
+                                ] {
+                                    if ui.color_edit_button_srgb(color).changed() {
+                                        settings_changed = true;
+                                    }
+                                    ui.label(label);
+                                }
+                                if ui.button(tr("colorblind-friendly")).clicked() {
+                                    *colors = StatusColors::colorblind();
+                                    settings_changed = true;
+                                }
+                                if ui.button(tr("reset")).clicked() {
+                                    *colors = StatusColors::default();
+                                    settings_changed = true;
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label("");
+                            if ui
+                                .checkbox(&mut self.settings.status_badges, tr("status-badges"))
+                                .on_hover_text(tr("status-badges-hint"))
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.heading(tr("new-requests"));
+                            ui.end_row();
+
+                            ui.label(tr("default-method"));
+                            egui::ComboBox::from_id_salt("default_method")
+                                .selected_text(&self.settings.default_method)
+                                .show_ui(ui, |ui| {
+                                    for method in HTTP_METHODS {
+                                        if ui
+                                            .selectable_value(
+                                                &mut self.settings.default_method,
+                                                method.to_string(),
+                                                method,
+                                            )
+                                            .changed()
+                                        {
+                                            settings_changed = true;
+                                        }
+                                    }
+                                });
+                            ui.end_row();
+
+                            ui.label(tr("default-url"));
+                            if ui
+                                .text_edit_singleline(&mut self.settings.default_url)
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.heading(tr("network"));
+                            ui.end_row();
+
+                            ui.label(tr("offline-mode"));
+                            if ui
+                                .checkbox(&mut self.settings.offline_mode, tr("offline-mode-hint"))
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.label("User-Agent");
+                            ui.horizontal(|ui| {
+                                for (user_agent, label) in [
+                                    (UserAgentSetting::App, "Send"),
+                                    (UserAgentSetting::Browser, "Browser"),
+                                    (UserAgentSetting::Custom, "Custom"),
+                                    (UserAgentSetting::None, "None"),
+                                ] {
+                                    if ui
+                                        .selectable_value(
+                                            &mut self.settings.user_agent,
+                                            user_agent,
+                                            label,
+                                        )
+                                        .changed()
+                                    {
+                                        settings_changed = true;
+                                    }
+                                }
+                            });
+                            ui.end_row();
+
+                            if self.settings.user_agent == UserAgentSetting::Custom {
+                                ui.label("");
+                                if ui
+                                    .add(
+                                        TextEdit::singleline(&mut self.settings.custom_user_agent)
+                                            .hint_text(tr("custom-user-agent")),
+                                    )
+                                    .changed()
+                                {
+                                    settings_changed = true;
+                                }
+                                ui.end_row();
+                            }
+
+                            ui.label(tr("timeout-seconds-0-none"));
+                            if ui
+                                .add(egui::DragValue::new(
+                                    &mut self.settings.request_timeout_secs,
+                                ))
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.label(tr("follow-redirects"));
+                            if ui
+                                .checkbox(&mut self.settings.follow_redirects, "")
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.label(tr("max-redirects"));
+                            if ui
+                                .add_enabled(
+                                    self.settings.follow_redirects,
+                                    egui::DragValue::new(&mut self.settings.max_redirects)
+                                        .range(1..=100),
+                                )
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.heading(tr("editor"));
+                            ui.end_row();
+
+                            ui.label(tr("max-response-size-mb-0-unlimited"));
+                            if ui
+                                .add(egui::DragValue::new(
+                                    &mut self.settings.max_response_size_mb,
+                                ))
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.label(tr("larger-responses"));
+                            if ui
+                                .checkbox(
+                                    &mut self.settings.spool_large_responses,
+                                    tr("stream-to-temporary-file"),
+                                )
+                                .on_hover_text(tr("stream-to-temporary-file-hint"))
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.label(tr("body-editor-rows"));
+                            if ui
+                                .add(
+                                    egui::DragValue::new(&mut self.settings.body_editor_rows)
+                                        .range(4..=100),
+                                )
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+
+                            ui.label("");
+                            settings_changed |= ui
+                                .checkbox(&mut self.settings.body_soft_wrap, tr("soft-wrap"))
+                                .changed();
+                            ui.end_row();
+
+                            ui.heading(tr("history"));
+                            ui.end_row();
+
+                            ui.label(tr("max-history-entries"));
+                            if ui
+                                .add(
+                                    egui::DragValue::new(&mut self.settings.history_limit)
+                                        .range(0..=10000),
+                                )
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            ui.end_row();
+                        });
+
+                    ui.separator();
+                    ui.heading(tr("proxy"));
+                    if Self::draw_proxy_settings(
+                        ui,
+                        &mut self.settings.proxy,
+                        &mut self.system_proxy,
+                    ) {
+                        settings_changed = true;
+                    }
+
+                    ui.separator();
+                    ui.heading(tr("keyboard-shortcuts"));
+                    if self.draw_shortcut_settings(ui) {
+                        settings_changed = true;
+                    }
+
+                    ui.separator();
+                    ui.heading(tr("confirmations"));
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                !self.settings.skip_confirmations.is_empty(),
+                                egui::Button::new(tr("ask-again-for-all")),
+                            )
+                            .clicked()
+                        {
+                            self.settings.skip_confirmations.clear();
+                            settings_changed = true;
+                        }
+                        ui.label(tr("ask-again-for-all-hint"));
+                    });
+
+                    ui.separator();
+                    ui.heading(tr("links"));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("register-send-links")).clicked() {
+                            self.deep_link_message = Some(match Self::register_url_scheme() {
+                                Ok(()) => tr("send-links-registered"),
+                                Err(e) => e,
+                            });
+                        }
+                        ui.label(tr("right-click-a-request-to-copy"));
+                    });
+
+                    ui.separator();
+                    if ui.button(tr("restore-defaults")).clicked() {
+                        self.settings = AppSettings::default();
+                        settings_changed = true;
+                        theme_changed = true;
+                    }
+                });
+            });
+
+        if theme_changed {
+            self.apply_theme(ctx);
+            settings_changed = true;
+        }
+        if settings_changed {
+            self.history.truncate(self.settings.history_limit);
+            self.save_settings();
+        }
+        self.settings_dialog = open;
+    }
+
+    // Requests of a folder in run order: its own requests, then each subfolder's
+    fn collect_folder_requests(folder: &Folder, requests: &mut Vec<HttpRequest>) {
+        requests.extend(folder.requests.iter().cloned());
+        for subfolder in &folder.folders {
+            Self::collect_folder_requests(subfolder, requests);
+        }
+    }
+
+    // Name and requests of the selected collection or folder
+    // Groups requests by method and URL, without the query string
+    fn endpoint_label(method: &str, url: &str) -> String {
+        format!(
+            "{} {}",
+            method,
+            url.split(['?', '#']).next().unwrap_or_default()
+        )
+    }
+
+    fn trim_run_records(&mut self) {
+        const MAX_RUN_RECORDS: usize = 5000;
+        if self.run_records.len() > MAX_RUN_RECORDS {
+            let excess = self.run_records.len() - MAX_RUN_RECORDS;
+            self.run_records.drain(..excess);
+        }
+    }
+
+    fn runner_target(&self) -> Option<(String, Vec<HttpRequest>)> {
+        let workspace = self.current_workspace();
+        let collection = workspace
+            .selected_collection
+            .and_then(|idx| workspace.collections.get(idx))?;
+        let folder = Self::get_folder_by_path(collection, &workspace.selected_folder_path)?;
+        let name = if workspace.selected_folder_path.is_empty() {
+            collection.name.clone()
+        } else {
+            format!("{} / {}", collection.name, folder.name)
+        };
+        let mut requests = Vec::new();
+        Self::collect_folder_requests(folder, &mut requests);
+        Some((name, requests))
+    }
+
+    fn start_runner(&mut self) {
+        let Some((target, requests)) = self.runner_target() else {
+            return;
+        };
+        let requests = Self::repeat_requests(requests, self.settings.runner_iterations);
+        self.run_requests(
+            target,
+            requests,
+            self.settings.runner_max_concurrency.max(1),
+            self.settings.runner_requests_per_second,
+            Duration::from_millis(self.settings.runner_delay_ms),
+        );
+    }
+
+    // Sends the current request over and over with the runner's pacing
+    fn send_request_repeatedly(&mut self) {
+        let request = self.current_request.clone();
+        let target = if request.name.is_empty() {
+            request.url.clone()
+        } else {
+            request.name.clone()
+        };
+        let requests = Self::repeat_requests(vec![request], self.settings.runner_iterations);
+        self.run_requests(
+            target,
+            requests,
+            self.settings.runner_max_concurrency.max(1),
+            self.settings.runner_requests_per_second,
+            Duration::from_millis(self.settings.runner_delay_ms),
+        );
+        self.runner_dialog = true;
+    }
+
+    fn repeat_requests(requests: Vec<HttpRequest>, iterations: usize) -> Vec<HttpRequest> {
+        if iterations <= 1 {
+            return requests;
+        }
+        (1..=iterations)
+            .flat_map(|iteration| {
+                requests.iter().cloned().map(move |mut request| {
+                    request.name = format!("{} [{}]", request.name, iteration);
+                    request
+                })
+            })
+            .collect()
+    }
+
+    fn selected_tree_requests(&self) -> Vec<HttpRequest> {
+        self.tree_selection
+            .iter()
+            .filter_map(|id| {
+                self.current_workspace()
+                    .collections
+                    .iter()
+                    .find_map(|collection| Self::find_request_by_id(&collection.root_folder, id))
+                    .cloned()
+            })
+            .collect()
+    }
+
+    // Sends every Ctrl+clicked request at once and shows them in the runner
+    fn send_selected_requests(&mut self) {
+        let requests = self.selected_tree_requests();
+        if requests.is_empty() {
+            return;
+        }
+        let mut args = FluentArgs::new();
+        args.set("count", requests.len());
+        let target = tr_args("selected-requests", &args);
+        let max_concurrency = requests.len();
+        self.run_requests(target, requests, max_concurrency, 0.0, Duration::ZERO);
+        self.runner_dialog = true;
+    }
+
+    fn run_requests(
+        &mut self,
+        target: String,
+        requests: Vec<HttpRequest>,
+        max_concurrency: usize,
+        requests_per_second: f64,
+        delay: Duration,
+    ) {
+        let workspace = self.current_workspace();
+        let collection = workspace
+            .selected_collection
+            .and_then(|idx| workspace.collections.get(idx))
+            .map(|collection| collection.name.clone())
+            .unwrap_or_default();
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let skip: Arc<Vec<AtomicBool>> =
+            Arc::new(requests.iter().map(|_| AtomicBool::new(false)).collect());
+        let mut runner = RunnerState {
+            target,
+            collection,
+            results: requests
+                .iter()
+                .map(|request| RunnerResult {
+                    name: request.name.clone(),
+                    method: request.method.clone(),
+                    url: request.url.clone(),
+                    outcome: None,
+                    time: 0,
+                    failures: vec![],
+                    task: None,
+                    retry_wait: None,
+                })
+                .collect(),
+            receiver: rx,
+            cancel: cancel.clone(),
+            skip: skip.clone(),
+            finished: false,
+            max_concurrency,
+            cookie_jar: self.send_cookie_jar(),
+        };
+
+        if self.settings.offline_mode {
+            for (entry, request) in runner.results.iter_mut().zip(&requests) {
+                entry.outcome = Some(match &request.example {
+                    Some(example) => Ok(example.status),
+                    None => Err(tr("offline-no-saved-example")),
+                });
+                if let Some(example) = &request.example {
+                    entry.failures =
+                        HeaderAssertion::check_all(&self.resolved_assertions(request), |name| {
+                            example
+                                .headers
+                                .iter()
+                                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                                .map(|(_, value)| value.clone())
+                        })
+                        .into_iter()
+                        .filter_map(|(description, result)| {
+                            result.err().map(|e| format!("{}: {}", description, e))
+                        })
+                        .collect();
+                }
+            }
+            runner.finished = true;
+            self.runner = Some(runner);
+            return;
+        }
+
+        let cookie_jar = runner.cookie_jar.clone();
+        self.runner = Some(runner);
+
+        // Each request gets a client built from its own TLS, version, proxy and connection
+        // overrides, not the open request's
+        let mut resolved_requests = Vec::new();
+        for request in &requests {
+            let client = if request.settings.fresh_connection {
+                self.fresh_client(request)
+            } else {
+                self.shared_client(request)
+            };
+            let retry_policy = self.effective_retry_policy(request);
+            let mut resolved = self.resolve_request(request);
+            if resolved.cookie_jar.is_some() {
+                resolved.cookie_jar = Some(cookie_jar.clone());
+            }
+            resolved_requests.push((
+                client,
+                resolved,
+                self.resolved_assertions(request),
+                retry_policy,
+            ));
+        }
+        let pins = Arc::new(self.current_workspace().settings.certificate_pins.clone());
+        let count = resolved_requests.len();
+
+        self.runtime.spawn(async move {
+            let semaphore = Arc::new(tokio::sync::Semaphore::new(max_concurrency));
+            let mut ticker = (requests_per_second > 0.0).then(|| {
+                let mut interval =
+                    tokio::time::interval(Duration::from_secs_f64(1.0 / requests_per_second));
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                interval
+            });
+
+            for (index, (client, resolved, assertions, retry_policy)) in
+                resolved_requests.into_iter().enumerate()
+            {
+                let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                    break;
+                };
+                if let Some(ticker) = &mut ticker {
+                    ticker.tick().await;
+                }
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                if skip[index].load(Ordering::Relaxed) {
+                    continue;
+                }
+                let client = match client {
+                    Ok(client) => client,
+                    Err(e) => {
+                        let _ = tx.send(RunnerEvent::Completed {
+                            index,
+                            result: Err(e),
+                            time: 0,
+                            failures: vec![],
+                        });
+                        continue;
+                    }
+                };
+
+                let tx = tx.clone();
+                let started_tx = tx.clone();
+                let pins = pins.clone();
+                // Hold the slot for the delay so the next request waits behind it
+                let delay = if index + 1 < count {
+                    delay
+                } else {
+                    Duration::ZERO
+                };
+                let task = tokio::spawn(async move {
+                    let _permit = permit;
+                    let mut start_time = Instant::now();
+                    let mut failures = vec![];
+                    let mut attempt = 0;
+                    let send_result = loop {
+                        let send_result = Self::send_resolved(&client, &resolved).await;
+                        let Some(delay) = retry_policy.retry_delay(attempt, &send_result) else {
+                            break send_result;
+                        };
+                        let wait = retry_policy.wait(attempt, delay, &send_result);
+                        let _ = tx.send(RunnerEvent::Waiting { index, wait });
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        start_time = Instant::now();
+                    };
+                    let result = match send_result {
+                        Ok(response)
+                            if let Err(message) = CertificatePin::check(&response, &pins) =>
+                        {
+                            Err(message)
+                        }
+                        Ok(mut response) => {
+                            failures = HeaderAssertion::failures(&assertions, response.headers());
+                            let status = response.status().as_u16();
+                            // Drain the body without keeping it around
+                            while let Ok(Some(_)) = response.chunk().await {}
+                            Ok(status)
+                        }
+                        Err(e) => Err(tr_error("request-failed", e)),
+                    };
+                    let _ = tx.send(RunnerEvent::Completed {
+                        index,
+                        result,
+                        time: start_time.elapsed().as_millis(),
+                        failures,
+                    });
+                    tokio::time::sleep(delay).await;
+                });
+                let _ = started_tx.send(RunnerEvent::Started {
+                    index,
+                    task: task.abort_handle(),
+                });
+            }
+
+            // Wait for in-flight requests before reporting completion
+            let _ = semaphore.acquire_many(max_concurrency as u32).await;
+            let _ = tx.send(RunnerEvent::Finished);
+        });
+    }
+
+    fn draw_runner_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.runner_dialog;
+        let mut settings_changed = false;
+        let mut start = false;
+        let target = self.runner_target();
+        let running = self.runner.as_ref().is_some_and(|runner| !runner.finished);
+
+        egui::Window::new(tr("collection-runner"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                match &target {
+                    Some((name, requests)) => {
+                        let mut args = FluentArgs::new();
+                        args.set("name", name.clone());
+                        args.set("count", requests.len());
+                        ui.label(tr_args("load-test-target", &args));
+                    }
+                    None => {
+                        ui.label(tr("select-a-collection-or-folder-to"));
+                    }
+                }
+
+                egui::Grid::new("runner_settings_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("requests-per-second-0-unlimited"));
+                        if ui
+                            .add_enabled(
+                                !running,
+                                egui::DragValue::new(&mut self.settings.runner_requests_per_second)
+                                    .speed(0.1)
+                                    .range(0.0..=1000.0),
+                            )
+                            .changed()
+                        {
+                            settings_changed = true;
+                        }
+                        ui.end_row();
+
+                        ui.label(tr("max-concurrent-requests"));
+                        if ui
+                            .add_enabled(
+                                !running,
+                                egui::DragValue::new(&mut self.settings.runner_max_concurrency)
+                                    .range(1..=64),
+                            )
+                            .changed()
+                        {
+                            settings_changed = true;
+                        }
+                        ui.end_row();
+
+                        ui.label(tr("delay-between-requests"));
+                        if ui
+                            .add_enabled(
+                                !running,
+                                egui::DragValue::new(&mut self.settings.runner_delay_ms)
+                                    .suffix(" ms")
+                                    .speed(10.0)
+                                    .range(0..=600_000),
+                            )
+                            .changed()
+                        {
+                            settings_changed = true;
+                        }
+                        ui.end_row();
+
+                        ui.label(tr("iterations"));
+                        if ui
+                            .add_enabled(
+                                !running,
+                                egui::DragValue::new(&mut self.settings.runner_iterations)
+                                    .range(1..=10_000),
+                            )
+                            .changed()
+                        {
+                            settings_changed = true;
+                        }
+                        ui.end_row();
+                    });
+
+                ui.horizontal(|ui| {
+                    let can_start = !running
+                        && target
+                            .as_ref()
+                            .is_some_and(|(_, requests)| !requests.is_empty());
+                    if ui
+                        .add_enabled(can_start, egui::Button::new(tr("run")))
+                        .clicked()
+                    {
+                        start = true;
+                    }
+                    if let Some(runner) = &self.runner
+                        && running
+                        && ui.button(tr("stop")).clicked()
+                    {
+                        runner.cancel.store(true, Ordering::Relaxed);
+                    }
+                });
+
+                let Some(runner) = &self.runner else {
+                    return;
+                };
+                ui.separator();
+
+                let completed = runner
+                    .results
+                    .iter()
+                    .filter(|entry| entry.outcome.is_some())
+                    .count();
+                let passed = runner.results.iter().filter(|entry| entry.passed()).count();
+                ui.horizontal(|ui| {
+                    if !runner.finished {
+                        ui.spinner();
+                    }
+                    ui.label(format!(
+                        "{}: {}/{} completed, {} succeeded, {} failed",
+                        runner.target,
+                        completed,
+                        runner.results.len(),
+                        passed,
+                        completed - passed
+                    ));
+                });
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("runner_results_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for entry in &runner.results {
+                                ui.label(RichText::new(&entry.method).strong());
+                                ui.label(&entry.name);
+                                match &entry.outcome {
+                                    Some(Ok(status)) => {
+                                        let status_text =
+                                            self.status_text(*status, status.to_string());
+                                        ui.horizontal(|ui| {
+                                            ui.label(status_text);
+                                            if !entry.failures.is_empty() {
+                                                ui.colored_label(
+                                                    Color32::from_rgb(255, 0, 0),
+                                                    format!("✖ {}", entry.failures.len()),
+                                                )
+                                                .on_hover_text(entry.failures.join("\n"));
+                                            }
+                                        });
+                                        ui.label(format!("{} ms", entry.time));
+                                    }
+                                    Some(Err(e)) => {
+                                        ui.label(self.status_text(0, "ERR")).on_hover_text(e);
+                                        ui.label("");
+                                    }
+                                    None if runner.finished => {
+                                        ui.label(tr("skipped"));
+                                        ui.label("");
+                                    }
+                                    None if let Some(wait) = &entry.retry_wait => {
+                                        ui.colored_label(
+                                            Color32::from_rgb(255, 165, 0),
+                                            wait.label(),
+                                        );
+                                        ui.label("");
+                                    }
+                                    None => {
+                                        ui.label("...");
+                                        ui.label("");
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if settings_changed {
+            self.save_settings();
+        }
+        if start {
+            self.start_runner();
+        }
+        self.runner_dialog = open;
+    }
+
+    fn find_request_by_id<'a>(folder: &'a Folder, id: &str) -> Option<&'a HttpRequest> {
+        folder
+            .requests
+            .iter()
+            .find(|request| request.id == id)
+            .or_else(|| {
+                folder
+                    .folders
+                    .iter()
+                    .find_map(|subfolder| Self::find_request_by_id(subfolder, id))
+            })
+    }
+
+    // Time until the monitor should next run, or an error for a bad cron expression
+    fn monitor_delay(monitor: &Monitor) -> Result<Duration, String> {
+        match monitor.schedule {
+            MonitorSchedule::Interval => Ok(Duration::from_secs(monitor.interval_secs.max(1))),
+            MonitorSchedule::Cron => {
+                let schedule = cron::Schedule::from_str(&monitor.cron)
+                    .map_err(|e| format!("Invalid cron expression: {}", e))?;
+                let next = schedule
+                    .upcoming(chrono::Local)
+                    .next()
+                    .ok_or_else(|| "Cron expression never fires".to_string())?;
+                Ok((next - chrono::Local::now()).to_std().unwrap_or_default())
+            }
+        }
+    }
+
+    fn poll_monitors(&mut self, ctx: &egui::Context) {
+        let mut results_changed = false;
+        while let Ok(event) = self.monitor_receiver.try_recv() {
+            match event {
+                MonitorEvent::Result { monitor_id, result } => {
+                    let results = self.monitor_results.entry(monitor_id).or_default();
+                    results.insert(0, result);
+                    results.truncate(500);
+                    results_changed = true;
+                }
+                MonitorEvent::Record(record) => {
+                    self.run_records.push(record);
+                    self.trim_run_records();
+                    results_changed = true;
+                }
+                MonitorEvent::Finished(monitor_id) => {
+                    self.monitor_tasks.remove(&monitor_id);
+                    self.monitors_running.remove(&monitor_id);
+                }
+            }
+        }
+        if results_changed {
+            self.save_cache();
+        }
+        if self.monitors_paused {
+            // Reschedule from the moment monitors are resumed
+            self.monitor_next_run.clear();
+            return;
+        }
+
+        let monitors: Vec<Monitor> = self
+            .workspaces
+            .iter()
+            .flat_map(|workspace| workspace.settings.monitors.iter())
+            .filter(|monitor| monitor.enabled)
+            .cloned()
+            .collect();
+        if monitors.is_empty() {
+            return;
+        }
+
+        let now = Instant::now();
+        for monitor in monitors {
+            let Ok(delay) = Self::monitor_delay(&monitor) else {
+                continue;
+            };
+            let next_run = *self
+                .monitor_next_run
+                .entry(monitor.id.clone())
+                .or_insert(now + delay);
+            if now < next_run {
+                continue;
+            }
+            self.monitor_next_run
+                .insert(monitor.id.clone(), now + delay);
+            if !self.settings.offline_mode && !self.monitors_running.contains(&monitor.id) {
+                self.run_monitor(&monitor);
+            }
+        }
+        ctx.request_repaint_after(Duration::from_secs(1));
+    }
+
+    fn has_scheduled_monitors(&self) -> bool {
+        self.workspaces
+            .iter()
+            .flat_map(|workspace| &workspace.settings.monitors)
+            .any(|monitor| monitor.enabled)
+    }
+
+    // Closing with background mode on minimizes instead, so monitors keep running.
+    // Without the tray there is no way back to a minimized window, so it just closes
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        if !ctx.input(|input| input.viewport().close_requested()) {
+            return;
+        }
+        if self.quitting
+            || !cfg!(feature = "tray")
+            || !self.settings.run_in_background
+            || self.monitors_paused
+            || !self.has_scheduled_monitors()
+        {
+            // Keeps unsaved edits as drafts for the next start
+            self.save_cache();
+            return;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+        self.notify(ToastKind::Info, tr("running-in-background"));
+    }
+
+    fn run_monitor(&mut self, monitor: &Monitor) {
+        let Some(index) = self
+            .workspaces
+            .iter()
+            .position(|workspace| workspace.settings.monitors.contains(monitor))
+        else {
+            return;
+        };
+        let found: Vec<_> = monitor
+            .request_ids
+            .iter()
+            .map(|id| {
+                self.workspaces[index]
+                    .collections
+                    .iter()
+                    .find_map(|collection| {
+                        Self::find_request_by_id(&collection.root_folder, id)
+                            .map(|request| (collection.name.clone(), request.clone()))
+                    })
+                    .ok_or_else(|| {
+                        let mut args = FluentArgs::new();
+                        args.set("id", id.clone());
+                        tr_args("missing-request-name", &args)
+                    })
+            })
+            .collect();
+        // Name, resolved request with its assertions and client, and the collection
+        // and endpoint for run metrics
+        let requests: Vec<_> = found
+            .into_iter()
+            .map(|found| match found {
+                Ok((collection, request)) => {
+                    let prepared = self.as_open(index, &request, |app| {
+                        app.shared_client(&request).map(|client| {
+                            (
+                                app.resolve_request(&request),
+                                app.resolved_assertions(&request),
+                                client,
+                            )
+                        })
+                    });
+                    (
+                        request.name.clone(),
+                        Some(prepared),
+                        collection,
+                        Self::endpoint_label(&request.method, &request.url),
+                    )
+                }
+                Err(name) => (name, None, String::new(), String::new()),
+            })
+            .collect();
+
+        let monitor_id = monitor.id.clone();
+        self.monitors_running.insert(monitor_id.clone());
+        let monitor = monitor.clone();
+        let tx = self.monitor_sender.clone();
+        let pins = self.workspaces[index].settings.certificate_pins.clone();
+        let task = self.runtime.spawn(async move {
+            let mut failures = Vec::new();
+            for (request_name, resolved, collection, endpoint) in requests {
+                let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                let start_time = Instant::now();
+                let (status, failure) = match resolved {
+                    None => (0, Some(tr("request-no-longer-exists"))),
+                    Some(Err(e)) => (0, Some(e)),
+                    Some(Ok((resolved, assertions, client))) => {
+                        match Self::send_resolved(&client, &resolved).await {
+                            Ok(response)
+                                if let Err(message) = CertificatePin::check(&response, &pins) =>
+                            {
+                                (0, Some(message))
+                            }
+                            Ok(mut response) => {
+                                let status = response.status();
+                                let failures =
+                                    HeaderAssertion::failures(&assertions, response.headers());
+                                while let Ok(Some(_)) = response.chunk().await {}
+                                let latency = start_time.elapsed().as_millis();
+                                let failure = if monitor.expect_success && !status.is_success() {
+                                    let mut args = FluentArgs::new();
+                                    args.set("status", status.as_u16());
+                                    Some(tr_args("monitor-expected-success", &args))
+                                } else if monitor.max_latency_ms > 0
+                                    && latency > monitor.max_latency_ms as u128
+                                {
+                                    let mut args = FluentArgs::new();
+                                    args.set("time", latency as u64);
+                                    args.set("limit", monitor.max_latency_ms);
+                                    Some(tr_args("monitor-too-slow", &args))
+                                } else if !failures.is_empty() {
+                                    Some(failures.join("; "))
+                                } else {
+                                    None
+                                };
+                                (status.as_u16(), failure)
+                            }
+                            Err(e) => (0, Some(tr_error("request-failed", e))),
+                        }
+                    }
+                };
+                if let Some(failure) = &failure {
+                    failures.push(format!("{}: {}", request_name, failure));
+                }
+                if !endpoint.is_empty() {
+                    let _ = tx.send(MonitorEvent::Record(RunRecord {
+                        timestamp: chrono::Local::now().timestamp(),
+                        source: RunSource::Monitor,
+                        collection,
+                        endpoint,
+                        status,
+                        time: start_time.elapsed().as_millis(),
+                        passed: failure.is_none(),
+                    }));
+                }
+                let _ = tx.send(MonitorEvent::Result {
+                    monitor_id: monitor.id.clone(),
+                    result: MonitorResult {
+                        timestamp,
+                        request_name,
+                        status,
+                        time: start_time.elapsed().as_millis(),
+                        failure,
+                    },
+                });
+            }
+
+            if monitor.notify && !failures.is_empty() {
+                let mut args = FluentArgs::new();
+                args.set("name", monitor.name.clone());
+                let summary = tr_args("monitor-failed-notification", &args);
+                let body = failures.join("\n");
+                let _ = tokio::task::spawn_blocking(move || {
+                    notify_rust::Notification::new()
+                        .summary(&summary)
+                        .body(&body)
+                        .show()
+                })
+                .await;
+            }
+            let _ = tx.send(MonitorEvent::Finished(monitor.id));
+        });
+        self.monitor_tasks.insert(monitor_id, task.abort_handle());
+    }
+
+    fn add_monitor(&mut self, name: String, request_ids: Vec<String>) {
+        self.current_workspace_mut()
+            .settings
+            .monitors
+            .push(Monitor {
+                name,
+                request_ids,
+                ..Default::default()
+            });
+        self.auto_save_workspace();
+        self.save_cache();
+    }
+
+    fn draw_monitors_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.monitors_dialog;
+        let mut changed = false;
+        let mut add_request = false;
+        let mut add_folder = false;
+        let mut run_now = None;
+        let mut delete = None;
+        let mut changed_settings = false;
+        let current_request_saved = self
+            .current_workspace()
+            .collections
+            .iter()
+            .any(|collection| {
+                Self::find_request_by_id(&collection.root_folder, &self.current_request.id)
+                    .is_some()
+            });
+
+        egui::Window::new(tr("monitors"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(550.0)
+            .show(ctx, |ui| {
+                ui.label(tr("monitors-run-while-the-app-is"));
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            current_request_saved,
+                            egui::Button::new(tr("monitor-current-request")),
+                        )
+                        .on_disabled_hover_text(tr("save-request-to-collection-first"))
+                        .clicked()
+                    {
+                        add_request = true;
+                    }
+                    if ui.button(tr("monitor-selected-folder")).clicked() {
+                        add_folder = true;
+                    }
+                });
+                if self.settings.offline_mode {
+                    ui.colored_label(
+                        Color32::from_rgb(255, 165, 0),
+                        tr("monitors-paused-offline"),
+                    );
+                }
+                ui.horizontal(|ui| {
+                    let label = if self.monitors_paused {
+                        tr("resume-monitors")
+                    } else {
+                        tr("pause-monitors")
+                    };
+                    if ui.button(label).clicked() {
+                        self.monitors_paused = !self.monitors_paused;
+                    }
+                    if cfg!(feature = "tray")
+                        && ui
+                            .checkbox(
+                                &mut self.settings.run_in_background,
+                                tr("run-in-background"),
+                            )
+                            .on_hover_text(tr("run-in-background-hint"))
+                            .changed()
+                    {
+                        changed_settings = true;
+                    }
+                });
+                ui.separator();
+
+                ScrollArea::vertical().max_height(450.0).show(ui, |ui| {
+                    let workspace = &mut self.workspaces[self.current_workspace];
+                    if workspace.settings.monitors.is_empty() {
+                        ui.label(tr("no-monitors-yet"));
+                    }
+                    for (idx, monitor) in workspace.settings.monitors.iter_mut().enumerate() {
+                        let results = self.monitor_results.get(&monitor.id);
+                        let last = results.and_then(|results| results.first());
+                        ui.horizontal(|ui| {
+                            changed |= ui.checkbox(&mut monitor.enabled, "").changed();
+                            ui.label(RichText::new(&monitor.name).strong());
+                            match last {
+                                Some(result) if result.failure.is_some() => {
+                                    ui.colored_label(
+                                        Color32::from_rgb(255, 0, 0),
+                                        tr("monitor-failing"),
+                                    )
+                                    .on_hover_text(result.failure.clone().unwrap_or_default());
+                                }
+                                Some(_) => {
+                                    ui.colored_label(
+                                        Color32::from_rgb(0, 128, 0),
+                                        tr("monitor-passing"),
+                                    );
+                                }
+                                None => {
+                                    ui.colored_label(Color32::GRAY, tr("monitor-not-run-yet"));
+                                }
+                            }
+                            if self.monitors_running.contains(&monitor.id) {
+                                ui.spinner();
+                            }
+                        });
+
+                        egui::CollapsingHeader::new("Details")
+                            .id_salt(&monitor.id)
+                            .show(ui, |ui| {
+                                egui::Grid::new(format!("monitor_grid_{}", monitor.id))
+                                    .num_columns(2)
+                                    .spacing([20.0, 6.0])
+                                    .show(ui, |ui| {
+                                        ui.label(tr("name"));
+                                        changed |=
+                                            ui.text_edit_singleline(&mut monitor.name).changed();
+                                        ui.end_row();
+
+                                        ui.label(tr("schedule"));
+                                        ui.horizontal(|ui| {
+                                            changed |= ui
+                                                .selectable_value(
+                                                    &mut monitor.schedule,
+                                                    MonitorSchedule::Interval,
+                                                    "Interval",
+                                                )
+                                                .changed();
+                                            changed |= ui
+                                                .selectable_value(
+                                                    &mut monitor.schedule,
+                                                    MonitorSchedule::Cron,
+                                                    "Cron",
+                                                )
+                                                .changed();
+                                        });
+                                        ui.end_row();
+
+                                        match monitor.schedule {
+                                            MonitorSchedule::Interval => {
+                                                ui.label(tr("every-seconds"));
+                                                changed |= ui
+                                                    .add(
+                                                        egui::DragValue::new(
+                                                            &mut monitor.interval_secs,
+                                                        )
+                                                        .range(10..=86400),
+                                                    )
+                                                    .changed();
+                                            }
+                                            MonitorSchedule::Cron => {
+                                                ui.label(tr("cron-sec-min-hour-day-month"));
+                                                changed |= ui
+                                                    .text_edit_singleline(&mut monitor.cron)
+                                                    .changed();
+                                            }
+                                        }
+                                        ui.end_row();
+                                        if let Err(e) = Self::monitor_delay(monitor) {
+                                            ui.label("");
+                                            ui.colored_label(Color32::from_rgb(255, 0, 0), e);
+                                            ui.end_row();
+                                        }
+
+                                        ui.label(tr("fail-unless-status-is-2xx"));
+                                        changed |=
+                                            ui.checkbox(&mut monitor.expect_success, "").changed();
+                                        ui.end_row();
+
+                                        ui.label(tr("latency-limit-ms-0-none"));
+                                        changed |= ui
+                                            .add(egui::DragValue::new(&mut monitor.max_latency_ms))
+                                            .changed();
+                                        ui.end_row();
+
+                                        ui.label(tr("desktop-notification-on-failure"));
+                                        changed |= ui.checkbox(&mut monitor.notify, "").changed();
+                                        ui.end_row();
+                                    });
+
+                                ui.horizontal(|ui| {
+                                    if ui.button(tr("run-now")).clicked() {
+                                        run_now = Some(idx);
+                                    }
+                                    if ui.button(tr("delete")).clicked() {
+                                        delete = Some(idx);
+                                    }
+                                });
+
+                                if let Some(results) = results {
+                                    let mut args = FluentArgs::new();
+                                    args.set("count", results.len());
+                                    ui.label(tr_args("monitor-last-results", &args));
+                                    egui::Grid::new(format!("monitor_results_{}", monitor.id))
+                                        .num_columns(4)
+                                        .striped(true)
+                                        .show(ui, |ui| {
+                                            for result in results.iter().take(50) {
+                                                ui.label(&result.timestamp);
+                                                ui.label(&result.request_name);
+                                                match &result.failure {
+                                                    Some(failure) => {
+                                                        ui.colored_label(
+                                                            Color32::from_rgb(255, 0, 0),
+                                                            result.status.to_string(),
+                                                        )
+                                                        .on_hover_text(failure);
+                                                    }
+                                                    None => {
+                                                        ui.colored_label(
+                                                            Color32::from_rgb(0, 128, 0),
+                                                            result.status.to_string(),
+                                                        );
+                                                    }
+                                                }
+                                                ui.label(format!("{} ms", result.time));
+                                                ui.end_row();
+                                            }
+                                        });
+                                }
+                            });
+                        ui.separator();
+                    }
+                });
+            });
+
+        if add_request {
+            let request = &self.current_request;
+            self.add_monitor(request.name.clone(), vec![request.id.clone()]);
+        }
+        if add_folder && let Some((name, requests)) = self.runner_target() {
+            self.add_monitor(
+                name,
+                requests.into_iter().map(|request| request.id).collect(),
+            );
+        }
+        if let Some(idx) = run_now {
+            let monitor = self.current_workspace().settings.monitors[idx].clone();
+            if !self.monitors_running.contains(&monitor.id) {
+                self.run_monitor(&monitor);
+            }
+        }
+        if let Some(idx) = delete {
+            let monitor = self.current_workspace_mut().settings.monitors.remove(idx);
+            self.monitor_results.remove(&monitor.id);
+            self.monitor_next_run.remove(&monitor.id);
+            changed = true;
+        }
+        if changed {
+            // Reschedule from now so edited intervals take effect
+            self.monitor_next_run.clear();
+            self.auto_save_workspace();
+            self.save_cache();
+        }
+        if changed_settings {
+            self.save_settings();
+        }
+        self.monitors_dialog = open;
+    }
+
+    fn start_load_test(&mut self) {
+        if self.settings.offline_mode {
+            self.load_test_error = Some(tr("offline-mode-is-on"));
+            return;
+        }
+        let (target, requests) = if self.load_test_folder {
+            match self.runner_target() {
+                Some(target) => target,
+                None => return,
+            }
+        } else {
+            (
+                self.current_request.name.clone(),
+                vec![self.current_request.clone()],
+            )
+        };
+        if requests.is_empty() {
+            return;
+        }
+        // Each request is sent with a client built from its own settings
+        let mut resolved_requests = Vec::new();
+        for request in &requests {
+            match self.shared_client(request) {
+                Ok(client) => resolved_requests.push((self.resolve_request(request), client)),
+                Err(e) => {
+                    self.load_test_error = Some(e);
+                    return;
+                }
+            }
+        }
+        let resolved_requests = Arc::new(resolved_requests);
+
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let started = Instant::now();
+        self.load_test = Some(LoadTestState {
+            target,
+            started,
+            finished_at: None,
+            receiver: rx,
+            cancel: cancel.clone(),
+            latencies: vec![],
+            errors: 0,
+            per_second: vec![],
+        });
+
+        let concurrency = self.load_test_concurrency.max(1);
+        let deadline = (self.load_test_limit == LoadTestLimit::Duration)
+            .then(|| started + Duration::from_secs(self.load_test_duration_secs));
+        let max_requests =
+            (self.load_test_limit == LoadTestLimit::Count).then_some(self.load_test_request_count);
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        self.runtime.spawn(async move {
+            let mut workers = Vec::new();
+            for _ in 0..concurrency {
+                let resolved_requests = resolved_requests.clone();
+                let counter = counter.clone();
+                let cancel = cancel.clone();
+                let tx = tx.clone();
+                workers.push(tokio::spawn(async move {
+                    loop {
+                        if cancel.load(Ordering::Relaxed)
+                            || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                        {
+                            break;
+                        }
+                        let index = counter.fetch_add(1, Ordering::Relaxed);
+                        if max_requests.is_some_and(|max_requests| index >= max_requests) {
+                            break;
+                        }
+                        let (resolved, client) =
+                            &resolved_requests[index % resolved_requests.len()];
+                        let start_time = Instant::now();
+                        let failed = match Self::send_resolved(client, resolved).await {
+                            Ok(mut response) => {
+                                let status = response.status();
+                                while let Ok(Some(_)) = response.chunk().await {}
+                                status.is_client_error() || status.is_server_error()
+                            }
+                            Err(_) => true,
+                        };
+                        let _ = tx.send(LoadTestEvent::Completed {
+                            elapsed_ms: started.elapsed().as_millis(),
+                            latency: start_time.elapsed().as_millis(),
+                            failed,
+                        });
+                    }
+                }));
+            }
+            for worker in workers {
+                let _ = worker.await;
+            }
+            let _ = tx.send(LoadTestEvent::Finished);
+        });
+    }
+
+    // Nearest-rank percentile of already sorted values
+    fn percentile(sorted: &[u128], percentile: f64) -> u128 {
+        if sorted.is_empty() {
+            return 0;
+        }
+        let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    fn load_test_summary(load_test: &LoadTestState) -> serde_json::Value {
+        let mut sorted = load_test.latencies.clone();
+        sorted.sort_unstable();
+        let elapsed = load_test
+            .finished_at
+            .unwrap_or_else(Instant::now)
+            .duration_since(load_test.started)
+            .as_secs_f64();
+        let total = sorted.len();
+        serde_json::json!({
+            "target": load_test.target,
+            "duration_secs": elapsed,
+            "requests": total,
+            "errors": load_test.errors,
+            "error_rate": if total > 0 { load_test.errors as f64 / total as f64 } else { 0.0 },
+            "throughput_rps": if elapsed > 0.0 { total as f64 / elapsed } else { 0.0 },
+            "latency_ms": {
+                "min": sorted.first().copied().unwrap_or(0),
+                "p50": Self::percentile(&sorted, 50.0),
+                "p95": Self::percentile(&sorted, 95.0),
+                "p99": Self::percentile(&sorted, 99.0),
+                "max": sorted.last().copied().unwrap_or(0),
+            },
+            "requests_per_second": load_test.per_second,
+        })
+    }
+
+    fn export_load_test_summary(&mut self) {
+        if let Some(load_test) = &self.load_test
+            && let Some(path) = rfd::FileDialog::new()
+                .set_title(tr("export-load-test-summary-title"))
+                .add_filter("JSON", &["json"])
+                .save_file()
+            && let Ok(json) = serde_json::to_string_pretty(&Self::load_test_summary(load_test))
+        {
+            self.export_file(
+                path,
+                json,
+                "file-kind-load-test-summary",
+                tr("load-test-summary-exported"),
+            );
+        }
+    }
+
+    fn draw_throughput_chart(ui: &mut Ui, per_second: &[usize]) {
+        let (rect, _) = ui.allocate_exact_size(
+            egui::Vec2::new(ui.available_width(), 100.0),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_stroke(rect, 2.0, ui.visuals().widgets.noninteractive.bg_stroke);
+        let Some(&max) = per_second.iter().max().filter(|max| **max > 0) else {
+            return;
+        };
+        let bar_width = rect.width() / per_second.len().max(30) as f32;
+        for (second, &count) in per_second.iter().enumerate() {
+            let height = rect.height() * count as f32 / max as f32;
+            let left = rect.left() + second as f32 * bar_width;
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::Pos2::new(left, rect.bottom() - height),
+                    egui::Pos2::new(left + (bar_width - 1.0).max(1.0), rect.bottom()),
+                ),
+                0.0,
+                Color32::from_rgb(0, 128, 0),
+            );
+        }
+        painter.text(
+            rect.left_top() + egui::Vec2::new(4.0, 2.0),
+            egui::Align2::LEFT_TOP,
+            format!("{} req/s peak", max),
+            egui::FontId::proportional(11.0),
+            ui.visuals().text_color(),
+        );
+    }
+
+    fn draw_metrics_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.metrics_dialog;
+        let mut clear = false;
+        let since = self
+            .metrics_range
+            .seconds()
+            .map(|seconds| chrono::Local::now().timestamp() - seconds);
+        let records: Vec<RunRecord> = self
+            .run_records
+            .iter()
+            .filter(|record| since.is_none_or(|since| record.timestamp >= since))
+            .filter(|record| {
+                self.metrics_collection
+                    .as_ref()
+                    .is_none_or(|collection| record.collection == *collection)
+            })
+            .cloned()
+            .collect();
+        let mut collections: Vec<String> = self
+            .run_records
+            .iter()
+            .map(|record| record.collection.clone())
+            .collect();
+        collections.sort_unstable();
+        collections.dedup();
+
+        egui::Window::new(tr("run-metrics"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("metrics_range")
+                        .selected_text(self.metrics_range.label())
+                        .show_ui(ui, |ui| {
+                            for range in MetricsRange::ALL {
+                                ui.selectable_value(&mut self.metrics_range, range, range.label());
+                            }
+                        });
+                    egui::ComboBox::from_id_salt("metrics_collection")
+                        .selected_text(
+                            self.metrics_collection
+                                .clone()
+                                .unwrap_or_else(|| tr("all-collections")),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.metrics_collection,
+                                None,
+                                tr("all-collections"),
+                            );
+                            for collection in &collections {
+                                ui.selectable_value(
+                                    &mut self.metrics_collection,
+                                    Some(collection.clone()),
+                                    collection,
+                                );
+                            }
+                        });
+                    if ui.button(tr("clear")).clicked() {
+                        clear = true;
+                    }
+                });
+                ui.label(RichText::new(tr("run-metrics-source")).weak().small());
+                ui.separator();
+
+                if records.is_empty() {
+                    ui.label(tr("no-runs-in-range"));
+                    return;
+                }
+
+                let total = records.len();
+                let passed = records.iter().filter(|record| record.passed).count();
+                let average =
+                    records.iter().map(|record| record.time).sum::<u128>() / total as u128;
+                let by_source = |source| {
+                    records
+                        .iter()
+                        .filter(|record| record.source == source)
+                        .count()
+                };
+                egui::Grid::new("metrics_summary_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("executions"));
+                        ui.label(format!(
+                            "{} ({} runner, {} monitor)",
+                            total,
+                            by_source(RunSource::Runner),
+                            by_source(RunSource::Monitor)
+                        ));
+                        ui.end_row();
+                        ui.label(tr("pass-rate"));
+                        ui.label(format!("{:.1}%", passed as f64 * 100.0 / total as f64));
+                        ui.end_row();
+                        ui.label(tr("average-latency"));
+                        ui.label(format!("{} ms", average));
+                        ui.end_row();
+                    });
+                ui.separator();
+
+                // Per endpoint: runs, passes and total latency
+                let mut endpoints: Vec<(&str, usize, usize, u128)> = vec![];
+                for record in &records {
+                    match endpoints
+                        .iter_mut()
+                        .find(|(endpoint, ..)| *endpoint == record.endpoint)
+                    {
+                        Some((_, runs, passes, time)) => {
+                            *runs += 1;
+                            *passes += record.passed as usize;
+                            *time += record.time;
+                        }
+                        None => endpoints.push((
+                            &record.endpoint,
+                            1,
+                            record.passed as usize,
+                            record.time,
+                        )),
+                    }
+                }
+                endpoints.sort_by_key(|(_, runs, ..)| std::cmp::Reverse(*runs));
+
+                ui.heading(tr("endpoints"));
+                ScrollArea::vertical()
+                    .id_salt("metrics_endpoints")
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("metrics_endpoints_grid")
+                            .num_columns(4)
+                            .striped(true)
+                            .spacing([16.0, 4.0])
+                            .show(ui, |ui| {
+                                ui.strong(tr("endpoint"));
+                                ui.strong(tr("runs"));
+                                ui.strong(tr("pass-rate"));
+                                ui.strong(tr("average-latency"));
+                                ui.end_row();
+                                for (endpoint, runs, passes, time) in &endpoints {
+                                    let method = endpoint.split(' ').next().unwrap_or_default();
+                                    ui.label(
+                                        RichText::new(*endpoint)
+                                            .monospace()
+                                            .color(self.method_color(method)),
+                                    );
+                                    ui.label(runs.to_string());
+                                    let rate = *passes as f64 * 100.0 / *runs as f64;
+                                    let color = if rate >= 99.0 {
+                                        Color32::from_rgb(46, 160, 67)
+                                    } else if rate >= 90.0 {
+                                        Color32::from_rgb(255, 165, 0)
+                                    } else {
+                                        Color32::from_rgb(215, 58, 73)
+                                    };
+                                    ui.colored_label(color, format!("{:.1}%", rate));
+                                    ui.label(format!("{} ms", time / *runs as u128));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.separator();
+
+                let mut errors: Vec<(u16, usize)> = vec![];
+                for record in records.iter().filter(|record| !record.passed) {
+                    match errors
+                        .iter_mut()
+                        .find(|(status, _)| *status == record.status)
+                    {
+                        Some((_, count)) => *count += 1,
+                        None => errors.push((record.status, 1)),
+                    }
+                }
+                errors.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+                ui.heading(tr("errors-by-status"));
+                if errors.is_empty() {
+                    ui.label(tr("no-failures"));
+                }
+                let failures = total - passed;
+                egui::Grid::new("metrics_errors_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        for (status, count) in &errors {
+                            ui.label(if *status == 0 {
+                                tr("no-response")
+                            } else {
+                                status.to_string()
+                            });
+                            ui.label(count.to_string());
+                            let share = *count as f32 / failures as f32;
+                            ui.add(
+                                egui::ProgressBar::new(share)
+                                    .desired_width(200.0)
+                                    .text(format!("{:.0}%", share * 100.0)),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if clear {
+            self.run_records.clear();
+            self.save_cache();
+        }
+        self.metrics_dialog = open;
+    }
+
+    fn token_status(token: &NamedToken) -> (String, Color32) {
+        if !token.expires.trim().is_empty() && token.parsed_expiry().is_none() {
+            return (tr("token-invalid-date"), Color32::from_rgb(255, 0, 0));
+        }
+        let Some(expires_at) = token.expires_at() else {
+            return (tr("token-no-expiry"), Color32::GRAY);
+        };
+        let remaining = expires_at - chrono::Local::now().timestamp();
+        let mut args = FluentArgs::new();
+        if remaining <= 0 {
+            (tr("token-expired"), Color32::from_rgb(255, 0, 0))
+        } else if remaining < 300 {
+            args.set("minutes", (remaining + 59) / 60);
+            (
+                tr_args("token-expires-soon", &args),
+                Color32::from_rgb(255, 165, 0),
+            )
+        } else {
+            let until = chrono::DateTime::from_timestamp(expires_at, 0)
+                .map(|time| {
+                    time.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            args.set("time", until);
+            (
+                tr_args("token-valid-until", &args),
+                Color32::from_rgb(0, 160, 0),
+            )
+        }
+    }
+
+    fn rename_token_in_folder(folder: &mut Folder, old: &str, new: &str) {
+        for request in &mut folder.requests {
+            if request.token.as_deref() == Some(old) {
+                request.token = Some(new.to_string());
+            }
+        }
+        for subfolder in &mut folder.folders {
+            Self::rename_token_in_folder(subfolder, old, new);
+        }
+    }
+
+    // Keeps requests and collections pointing at a token when it is renamed
+    fn rename_token_references(&mut self, old: &str, new: &str) {
+        self.collections_generation += 1;
+        for collection in &mut self.current_workspace_mut().collections {
+            if collection.token.as_deref() == Some(old) {
+                collection.token = Some(new.to_string());
+            }
+            Self::rename_token_in_folder(&mut collection.root_folder, old, new);
+        }
+        for request in self
+            .drafts
+            .values_mut()
+            .chain(std::iter::once(&mut self.current_request))
+        {
+            if request.token.as_deref() == Some(old) {
+                request.token = Some(new.to_string());
+            }
+        }
+    }
+
+    fn draw_sessions_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.sessions_dialog;
+        let mut changed = false;
+        let mut to_remove = None;
+        let token_names: Vec<String> = self
+            .current_workspace()
+            .settings
+            .tokens
+            .iter()
+            .map(|token| token.name.clone())
+            .collect();
+
+        egui::Window::new(tr("sessions"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(700.0)
+            .show(ctx, |ui| {
+                ui.label(tr("sessions-hint"));
+                ui.separator();
+                let workspace = &mut self.workspaces[self.current_workspace];
+                ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                    for (idx, session) in workspace.settings.sessions.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut workspace.active_session, Some(idx), "")
+                                .on_hover_text(tr("use-session"));
+                            changed |= ui
+                                .add(TextEdit::singleline(&mut session.name).desired_width(200.0))
+                                .changed();
+                            if ui.button("🗑").clicked() {
+                                to_remove = Some(idx);
+                            }
+                        });
+                        ui.indent(idx, |ui| {
+                            ui.collapsing(tr("session-tokens"), |ui| {
+                                if token_names.is_empty() {
+                                    ui.colored_label(Color32::GRAY, tr("no-tokens"));
+                                }
+                                egui::Grid::new(("session_tokens", idx)).show(ui, |ui| {
+                                    for name in &token_names {
+                                        let position = session
+                                            .tokens
+                                            .iter()
+                                            .position(|(token_name, _)| token_name == name)
+                                            .unwrap_or_else(|| {
+                                                session.tokens.push((name.clone(), String::new()));
+                                                session.tokens.len() - 1
+                                            });
+                                        ui.label(name);
+                                        changed |= ui
+                                            .add(
+                                                TextEdit::singleline(
+                                                    &mut session.tokens[position].1,
+                                                )
+                                                .password(true)
+                                                .hint_text(tr("session-token-shared"))
+                                                .desired_width(300.0),
+                                            )
+                                            .changed();
+                                        ui.end_row();
+                                    }
+                                });
+                            });
+                            let mut args = FluentArgs::new();
+                            args.set("count", session.cookies.len());
+                            ui.collapsing(tr_args("session-cookies", &args), |ui| {
+                                let mut remove_cookie = None;
+                                egui::Grid::new(("session_cookies", idx))
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for (cookie_idx, cookie) in
+                                            session.cookies.iter_mut().enumerate()
+                                        {
+                                            changed |= ui
+                                                .add(
+                                                    TextEdit::singleline(&mut cookie.domain)
+                                                        .hint_text(tr("cookie-domain"))
+                                                        .desired_width(140.0),
+                                                )
+                                                .changed();
+                                            changed |= ui
+                                                .add(
+                                                    TextEdit::singleline(&mut cookie.path)
+                                                        .desired_width(60.0),
+                                                )
+                                                .changed();
+                                            changed |= ui
+                                                .add(
+                                                    TextEdit::singleline(&mut cookie.name)
+                                                        .hint_text(tr("cookie-name"))
+                                                        .desired_width(120.0),
+                                                )
+                                                .changed();
+                                            changed |= ui
+                                                .add(
+                                                    TextEdit::singleline(&mut cookie.value)
+                                                        .hint_text(tr("cookie-value"))
+                                                        .desired_width(200.0),
+                                                )
+                                                .changed();
+                                            if ui.button("🗑").clicked() {
+                                                remove_cookie = Some(cookie_idx);
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                                if let Some(cookie_idx) = remove_cookie {
+                                    session.cookies.remove(cookie_idx);
+                                    changed = true;
+                                }
+                                ui.horizontal(|ui| {
+                                    if ui.button(tr("add-cookie")).clicked() {
+                                        session.cookies.push(SessionCookie {
+                                            domain: String::new(),
+                                            path: "/".to_string(),
+                                            name: String::new(),
+                                            value: String::new(),
+                                            expires: None,
+                                            host_only: false,
+                                            secure: false,
+                                            http_only: false,
+                                        });
+                                        changed = true;
+                                    }
+                                    if ui
+                                        .add_enabled(
+                                            !session.cookies.is_empty(),
+                                            egui::Button::new(tr("clear-cookies")),
+                                        )
+                                        .clicked()
+                                    {
+                                        session.cookies.clear();
+                                        changed = true;
+                                    }
+                                });
+                            });
+                        });
+                        ui.separator();
+                    }
+                });
+                if workspace.settings.sessions.is_empty() {
+                    ui.colored_label(Color32::GRAY, tr("no-sessions"));
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("add-session")).clicked() {
+                        workspace.settings.sessions.push(Session {
+                            name: format!("session-{}", workspace.settings.sessions.len() + 1),
+                            ..Default::default()
+                        });
+                        changed = true;
+                    }
+                    if ui
+                        .add_enabled(
+                            workspace.active_session.is_some(),
+                            egui::Button::new(tr("no-session")),
+                        )
+                        .clicked()
+                    {
+                        workspace.active_session = None;
+                    }
+                });
+            });
+
+        if let Some(idx) = to_remove {
+            let workspace = self.current_workspace_mut();
+            workspace.settings.sessions.remove(idx);
+            workspace.active_session = match workspace.active_session {
+                Some(active) if active == idx => None,
+                Some(active) if active > idx => Some(active - 1),
+                active => active,
+            };
+            changed = true;
+        }
+        if changed {
+            self.auto_save_workspace();
+        }
+        if !open {
+            self.save_cache();
+        }
+        self.sessions_dialog = open;
+    }
+
+    fn draw_cookie_manager_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.cookie_manager_dialog;
+        let mut changed = false;
+        let mut new_domain = std::mem::take(&mut self.new_cookie_domain);
+        let mut args = FluentArgs::new();
+        args.set(
+            "jar",
+            self.active_session()
+                .map_or_else(|| tr("workspace"), |session| session.name.clone()),
+        );
+        let title = tr_args("cookie-jar-of", &args);
+        let jar = self.cookie_jar_mut();
+        let mut domains: Vec<String> = jar.iter().map(|cookie| cookie.domain.clone()).collect();
+        domains.sort();
+        domains.dedup();
+
+        egui::Window::new(tr("cookie-manager"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(700.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(title).strong());
+                ui.label(tr("cookie-manager-hint"));
+                ui.separator();
+                let mut remove_cookie = None;
+                let mut clear_domain = None;
+                let mut add_to_domain = None;
+                ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                    for domain in &domains {
+                        let mut args = FluentArgs::new();
+                        args.set("domain", domain.clone());
+                        args.set(
+                            "count",
+                            jar.iter().filter(|cookie| &cookie.domain == domain).count(),
+                        );
+                        egui::CollapsingHeader::new(tr_args("cookie-domain-count", &args))
+                            .id_salt(("cookie_domain", domain))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                egui::Grid::new(("cookie_manager", domain))
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for (idx, cookie) in jar
+                                            .iter_mut()
+                                            .enumerate()
+                                            .filter(|(_, cookie)| &cookie.domain == domain)
+                                        {
+                                            changed |= ui
+                                                .add(
+                                                    TextEdit::singleline(&mut cookie.name)
+                                                        .hint_text(tr("cookie-name"))
+                                                        .desired_width(120.0),
+                                                )
+                                                .changed();
+                                            changed |= ui
+                                                .add(
+                                                    TextEdit::singleline(&mut cookie.value)
+                                                        .hint_text(tr("cookie-value"))
+                                                        .desired_width(220.0),
+                                                )
+                                                .changed();
+                                            changed |= ui
+                                                .add(
+                                                    TextEdit::singleline(&mut cookie.path)
+                                                        .desired_width(80.0),
+                                                )
+                                                .changed();
+                                            match cookie.expires {
+                                                _ if cookie.is_expired() => {
+                                                    ui.colored_label(
+                                                        Color32::GRAY,
+                                                        tr("cookie-expired"),
+                                                    );
+                                                }
+                                                Some(expires) => {
+                                                    ui.label(
+                                                        chrono::DateTime::from_timestamp(
+                                                            expires, 0,
+                                                        )
+                                                        .map(|time| {
+                                                            time.with_timezone(&chrono::Local)
+                                                                .format("%Y-%m-%d %H:%M")
+                                                                .to_string()
+                                                        })
+                                                        .unwrap_or_default(),
+                                                    );
+                                                }
+                                                None => {
+                                                    ui.colored_label(
+                                                        Color32::GRAY,
+                                                        tr("cookie-session"),
+                                                    );
+                                                }
+                                            }
+                                            if ui.button("🗑").clicked() {
+                                                remove_cookie = Some(idx);
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                                ui.horizontal(|ui| {
+                                    if ui.button(tr("add-cookie")).clicked() {
+                                        add_to_domain = Some(domain.clone());
+                                    }
+                                    if ui.button(tr("clear-domain-cookies")).clicked() {
+                                        clear_domain = Some(domain.clone());
+                                    }
+                                });
+                            });
+                    }
+                    if domains.is_empty() {
+                        ui.colored_label(Color32::GRAY, tr("cookie-jar-empty"));
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut new_domain)
+                            .hint_text(tr("cookie-domain"))
+                            .desired_width(200.0),
+                    );
+                    if ui
+                        .add_enabled(
+                            !new_domain.trim().is_empty(),
+                            egui::Button::new(tr("add-cookie")),
+                        )
+                        .clicked()
+                    {
+                        add_to_domain = Some(new_domain.trim().to_lowercase());
+                        new_domain.clear();
+                    }
+                    if ui
+                        .add_enabled(!jar.is_empty(), egui::Button::new(tr("clear-cookies")))
+                        .clicked()
+                    {
+                        jar.clear();
+                        changed = true;
+                    }
+                });
+
+                if let Some(idx) = remove_cookie {
+                    jar.remove(idx);
+                    changed = true;
+                }
+                if let Some(domain) = clear_domain {
+                    jar.retain(|cookie| cookie.domain != domain);
+                    changed = true;
+                }
+                if let Some(domain) = add_to_domain {
+                    jar.push(SessionCookie {
+                        domain,
+                        path: "/".to_string(),
+                        name: String::new(),
+                        value: String::new(),
+                        expires: None,
+                        host_only: false,
+                        secure: false,
+                        http_only: false,
+                    });
+                    changed = true;
+                }
+            });
+
+        self.new_cookie_domain = new_domain;
+        if changed {
+            self.auto_save_workspace();
+        }
+        self.cookie_manager_dialog = open;
+    }
+
+    fn draw_token_manager_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.token_manager_dialog;
+        let mut changed = false;
+        let mut renamed = None;
+        let mut to_remove = None;
+
+        egui::Window::new(tr("token-manager"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(760.0)
+            .show(ctx, |ui| {
+                ui.label(tr("token-manager-hint"));
+                ui.separator();
+                let tokens = &mut self.current_workspace_mut().settings.tokens;
+                egui::Grid::new("token_manager_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("token-name"));
+                        ui.strong(tr("token-scheme"));
+                        ui.strong(tr("token-value"));
+                        ui.strong(tr("token-expires"));
+                        ui.strong(tr("token-status"));
+                        ui.label("");
+                        ui.end_row();
+
+                        for (i, token) in tokens.iter_mut().enumerate() {
+                            let old_name = token.name.clone();
+                            if ui
+                                .add(TextEdit::singleline(&mut token.name).desired_width(120.0))
+                                .changed()
+                            {
+                                renamed = Some((old_name, token.name.clone()));
+                                changed = true;
+                            }
+                            changed |= ui
+                                .add(TextEdit::singleline(&mut token.scheme).desired_width(60.0))
+                                .changed();
+                            changed |= ui
+                                .add(
+                                    TextEdit::singleline(&mut token.value)
+                                        .password(true)
+                                        .desired_width(220.0),
+                                )
+                                .on_hover_text(tr("token-value-hint"))
+                                .changed();
+                            changed |= ui
+                                .add(
+                                    TextEdit::singleline(&mut token.expires)
+                                        .hint_text("YYYY-MM-DD HH:MM")
+                                        .desired_width(120.0),
+                                )
+                                .on_hover_text(tr("token-expires-hint"))
+                                .changed();
+                            let (status, color) = Self::token_status(token);
+                            ui.colored_label(color, status);
+                            if ui.button("🗑").clicked() {
+                                to_remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if tokens.is_empty() {
+                    ui.colored_label(Color32::GRAY, tr("no-tokens"));
+                }
+                if ui.button(tr("add-token")).clicked() {
+                    tokens.push(NamedToken {
+                        name: format!("token-{}", tokens.len() + 1),
+                        ..Default::default()
+                    });
+                    changed = true;
+                }
+            });
+
+        if let Some(i) = to_remove {
+            self.current_workspace_mut().settings.tokens.remove(i);
+            changed = true;
+        }
+        if let Some((old, new)) = renamed {
+            self.rename_token_references(&old, &new);
+        }
+        if changed {
+            self.auto_save_workspace();
+        }
+        self.token_manager_dialog = open;
+    }
+
+    fn draw_load_test_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.load_test_dialog;
+        let mut start = false;
+        let mut export = false;
+        let running = self
+            .load_test
+            .as_ref()
+            .is_some_and(|load_test| load_test.finished_at.is_none());
+
+        egui::Window::new(tr("load-test"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                ui.add_enabled_ui(!running, |ui| {
+                    egui::Grid::new("load_test_settings_grid")
+                        .num_columns(2)
+                        .spacing([20.0, 8.0])
+                        .show(ui, |ui| {
+                            ui.label(tr("target"));
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(
+                                    &mut self.load_test_folder,
+                                    false,
+                                    tr("load-test-current-request"),
+                                );
+                                ui.selectable_value(
+                                    &mut self.load_test_folder,
+                                    true,
+                                    tr("load-test-selected-folder"),
+                                );
+                            });
+                            ui.end_row();
+
+                            ui.label(tr("concurrent-users"));
+                            ui.add(
+                                egui::DragValue::new(&mut self.load_test_concurrency)
+                                    .range(1..=500),
+                            );
+                            ui.end_row();
+
+                            ui.label(tr("stop-after"));
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(
+                                    &mut self.load_test_limit,
+                                    LoadTestLimit::Duration,
+                                    tr("load-test-limit-duration"),
+                                );
+                                ui.selectable_value(
+                                    &mut self.load_test_limit,
+                                    LoadTestLimit::Count,
+                                    tr("load-test-limit-count"),
+                                );
+                            });
+                            ui.end_row();
+
+                            match self.load_test_limit {
+                                LoadTestLimit::Duration => {
+                                    ui.label(tr("duration-seconds"));
+                                    ui.add(
+                                        egui::DragValue::new(&mut self.load_test_duration_secs)
+                                            .range(1..=3600),
+                                    );
+                                }
+                                LoadTestLimit::Count => {
+                                    ui.label(tr("total-requests"));
+                                    ui.add(
+                                        egui::DragValue::new(&mut self.load_test_request_count)
+                                            .range(1..=1_000_000),
+                                    );
+                                }
+                            }
+                            ui.end_row();
+                        });
+                });
+
+                ui.horizontal(|ui| {
+                    if running {
+                        if let Some(load_test) = &self.load_test
+                            && ui.button(tr("stop")).clicked()
+                        {
+                            load_test.cancel.store(true, Ordering::Relaxed);
+                        }
+                        ui.spinner();
+                    } else if ui.button(tr("start")).clicked() {
+                        start = true;
+                    }
+                    if let Some(error) = &self.load_test_error {
+                        ui.colored_label(Color32::from_rgb(255, 0, 0), error);
+                    }
+                });
+
+                let Some(load_test) = &self.load_test else {
+                    return;
+                };
+                ui.separator();
+
+                let summary = Self::load_test_summary(load_test);
+                let number = |key: &str| summary[key].as_f64().unwrap_or(0.0);
+                let latency = |key: &str| summary["latency_ms"][key].as_u64().unwrap_or(0);
+                ui.label(RichText::new(&load_test.target).strong());
+                egui::Grid::new("load_test_results_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("requests"));
+                        ui.label(format!("{}", load_test.latencies.len()));
+                        ui.end_row();
+                        ui.label(tr("elapsed"));
+                        ui.label(format!("{:.1} s", number("duration_secs")));
+                        ui.end_row();
+                        ui.label(tr("throughput"));
+                        ui.label(format!("{:.1} req/s", number("throughput_rps")));
+                        ui.end_row();
+                        ui.label(tr("error-rate"));
+                        let error_rate = number("error_rate") * 100.0;
+                        let error_color = if load_test.errors > 0 {
+                            Color32::from_rgb(255, 0, 0)
+                        } else {
+                            Color32::from_rgb(0, 128, 0)
+                        };
+                        ui.colored_label(
+                            error_color,
+                            format!("{:.1}% ({} errors)", error_rate, load_test.errors),
+                        );
+                        ui.end_row();
+                        ui.label(tr("latency-p50-p95-p99"));
+                        ui.label(format!(
+                            "{} / {} / {} ms",
+                            latency("p50"),
+                            latency("p95"),
+                            latency("p99")
+                        ));
+                        ui.end_row();
+                        ui.label(tr("latency-min-max"));
+                        ui.label(format!("{} / {} ms", latency("min"), latency("max")));
+                        ui.end_row();
+                    });
+
+                ui.add_space(4.0);
+                Self::draw_throughput_chart(ui, &load_test.per_second);
+                ui.add_space(4.0);
+
+                if ui
+                    .add_enabled(
+                        load_test.finished_at.is_some(),
+                        egui::Button::new(tr("export-summary")),
+                    )
+                    .clicked()
+                {
+                    export = true;
+                }
+            });
+
+        if start {
+            self.load_test_error = None;
+            self.start_load_test();
+        }
+        if export {
+            self.export_load_test_summary();
+        }
+        self.load_test_dialog = open;
+    }
+
+    fn start_capture(&mut self) {
+        if self.settings.offline_mode {
+            self.capture_error = Some(tr("offline-capture-skipped"));
+            return;
+        }
+        let port = self.capture_port;
+        let listener = match std::net::TcpListener::bind(("127.0.0.1", port)).and_then(|listener| {
+            listener.set_nonblocking(true)?;
+            Ok(listener)
+        }) {
+            Ok(listener) => listener,
+            Err(e) => {
+                let mut args = FluentArgs::new();
+                args.set("port", port);
+                args.set("error", e.to_string());
+                self.capture_error = Some(tr_args("capture-listen-failed", &args));
+                return;
+            }
+        };
+        let _guard = self.runtime.enter();
+        let listener = match tokio::net::TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(e) => {
+                let mut args = FluentArgs::new();
+                args.set("port", port);
+                args.set("error", e.to_string());
+                self.capture_error = Some(tr_args("capture-listen-failed", &args));
+                return;
+            }
+        };
+        // Forward exactly what the app sent: no redirects, no upstream proxy
+        let client = match reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy()
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                self.capture_error = Some(tr_error("build-client-failed", e));
+                return;
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let handle = self.runtime.spawn(async move {
+            // Owned by this task, so aborting it on stop aborts every open connection too
+            let mut connections = tokio::task::JoinSet::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                while connections.try_join_next().is_some() {}
+                let client = client.clone();
+                let tx = tx.clone();
+                connections.spawn(async move {
+                    let _ = Self::handle_capture_connection(stream, client, tx).await;
+                });
+            }
+        });
+        self.capture = Some(CaptureState {
+            port,
+            receiver: rx,
+            handle,
+        });
+        self.capture_error = None;
+    }
+
+    // Stops listening and drops the connections still being served
+    fn stop_capture(&mut self) {
+        if let Some(capture) = self.capture.take() {
+            capture.handle.abort();
+        }
+    }
+
+    async fn write_capture_error(
+        stream: &mut tokio::net::TcpStream,
+        status: &str,
+        message: &str,
+    ) -> std::io::Result<()> {
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 {}\r\ncontent-type: text/plain\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    message.len(),
+                    message
+                )
+                .as_bytes(),
+            )
+            .await
+    }
+
+    // Decodes a chunked request body, reading from the stream as the chunks need it.
+    // Trailers are left unread; the connection is closed after the response.
+    async fn read_chunked_body(
+        stream: &mut tokio::net::TcpStream,
+        mut pending: Vec<u8>,
+    ) -> std::io::Result<Vec<u8>> {
+        async fn fill(
+            stream: &mut tokio::net::TcpStream,
+            pending: &mut Vec<u8>,
+            needed: usize,
+        ) -> std::io::Result<()> {
+            let mut chunk = [0u8; 8192];
+            while pending.len() < needed {
+                let read = stream.read(&mut chunk).await?;
+                if read == 0 {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                pending.extend_from_slice(&chunk[..read]);
+            }
+            Ok(())
+        }
+        let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "bad chunk size");
+
+        let mut body = Vec::new();
+        loop {
+            let line_end = loop {
+                if let Some(end) = pending.windows(2).position(|window| window == b"\r\n") {
+                    break end;
+                }
+                if pending.len() > 1024 {
+                    return Err(invalid());
+                }
+                let needed = pending.len() + 1;
+                fill(stream, &mut pending, needed).await?;
+            };
+            // The size may be followed by ";extension"
+            let size = std::str::from_utf8(&pending[..line_end])
+                .ok()
+                .and_then(|line| line.split(';').next())
+                .and_then(|size| usize::from_str_radix(size.trim(), 16).ok())
+                .ok_or_else(invalid)?;
+            pending.drain(..line_end + 2);
+            if size == 0 {
+                return Ok(body);
+            }
+            fill(stream, &mut pending, size + 2).await?;
+            body.extend_from_slice(&pending[..size]);
+            pending.drain(..size + 2);
+        }
+    }
+
+    // Serve a single proxied request; HTTPS is tunnelled untouched since it can't be read
+    async fn handle_capture_connection(
+        mut stream: tokio::net::TcpStream,
+        client: reqwest::Client,
+        tx: mpsc::Sender<CapturedExchange>,
+    ) -> std::io::Result<()> {
+        const HOP_BY_HOP_HEADERS: [&str; 11] = [
+            "connection",
+            "proxy-connection",
+            "keep-alive",
+            "proxy-authorization",
+            "proxy-authenticate",
+            "te",
+            "trailer",
+            "transfer-encoding",
+            "upgrade",
+            "host",
+            "content-length",
+        ];
+
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 8192];
+        let header_end = loop {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break position + 4;
+            }
+            if buffer.len() > 64 * 1024 {
+                return Self::write_capture_error(
+                    &mut stream,
+                    "431 Request Header Fields Too Large",
+                    "Request head too large",
+                )
+                .await;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let target = request_line.next().unwrap_or_default().to_string();
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        if method.eq_ignore_ascii_case("CONNECT") {
+            let mut upstream = match tokio::net::TcpStream::connect(&target).await {
+                Ok(upstream) => upstream,
+                Err(e) => {
+                    return Self::write_capture_error(
+                        &mut stream,
+                        "502 Bad Gateway",
+                        &format!("Failed to connect to {}: {}", target, e),
+                    )
+                    .await;
+                }
+            };
+            stream
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+            upstream.write_all(&buffer[header_end..]).await?;
+            tokio::io::copy_bidirectional(&mut stream, &mut upstream).await?;
+            return Ok(());
+        }
+
+        let Ok(request_method) = Method::from_bytes(method.as_bytes()) else {
+            return Self::write_capture_error(&mut stream, "400 Bad Request", "Invalid method")
+                .await;
+        };
+        if !target.starts_with("http://") {
+            return Self::write_capture_error(
+                &mut stream,
+                "400 Bad Request",
+                "Configure this address as an HTTP proxy",
+            )
+            .await;
+        }
+
+        let chunked = headers.iter().any(|(key, value)| {
+            key.eq_ignore_ascii_case("transfer-encoding")
+                && value.to_lowercase().contains("chunked")
+        });
+        let body = if chunked {
+            match Self::read_chunked_body(&mut stream, buffer[header_end..].to_vec()).await {
+                Ok(body) => body,
+                Err(e) => {
+                    return Self::write_capture_error(
+                        &mut stream,
+                        "400 Bad Request",
+                        &format!("Invalid chunked body: {}", e),
+                    )
+                    .await;
+                }
+            }
+        } else {
+            let content_length = headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+                .unwrap_or(0);
+            let mut body = buffer[header_end..].to_vec();
+            while body.len() < content_length {
+                let read = stream.read(&mut chunk).await?;
+                if read == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..read]);
+            }
+            body.truncate(content_length);
+            body
+        };
+
+        let forwarded_headers: Vec<(String, String)> = headers
+            .into_iter()
+            .filter(|(key, _)| !HOP_BY_HOP_HEADERS.contains(&key.to_lowercase().as_str()))
+            .collect();
+        let mut request = client.request(request_method, &target);
+        for (key, value) in &forwarded_headers {
+            request = request.header(key, value);
+        }
+        let response = match request.body(body.clone()).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return Self::write_capture_error(
+                    &mut stream,
+                    "502 Bad Gateway",
+                    &format!("Proxy error: {}", e),
+                )
+                .await;
+            }
+        };
+
+        let status = response.status();
+        let status_text = status.canonical_reason().unwrap_or("Unknown").to_string();
+        let response_headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .filter(|(key, _)| !HOP_BY_HOP_HEADERS.contains(&key.as_str()))
+            .map(|(key, value)| (key.to_string(), value.to_str().unwrap_or("").to_string()))
+            .collect();
+        let response_body = response.bytes().await.unwrap_or_default();
+
+        let mut response_head = format!("HTTP/1.1 {} {}\r\n", status.as_u16(), status_text);
+        for (key, value) in &response_headers {
+            response_head.push_str(&format!("{}: {}\r\n", key, value));
+        }
+        response_head.push_str(&format!(
+            "content-length: {}\r\nconnection: close\r\n\r\n",
+            response_body.len()
+        ));
+        stream.write_all(response_head.as_bytes()).await?;
+        stream.write_all(&response_body).await?;
+        stream.flush().await?;
+
+        let _ = tx.send(CapturedExchange {
+            method,
+            url: target,
+            headers: forwarded_headers,
+            body: String::from_utf8_lossy(&body).to_string(),
+            response: SavedExample {
+                status: status.as_u16(),
+                status_text,
+                headers: response_headers.into_iter().collect(),
+                body: String::from_utf8_lossy(&response_body).to_string(),
+            },
+        });
+        Ok(())
+    }
+
+    // Host and path of an absolute http:// URL, without the query string
+    fn split_captured_url(url: &str) -> (&str, &str) {
+        let rest = url.strip_prefix("http://").unwrap_or(url);
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        match rest.find('/') {
+            Some(idx) => (&rest[..idx], &rest[idx..]),
+            None => (rest, "/"),
+        }
+    }
+
+    fn record_captured_exchange(&mut self, exchange: CapturedExchange) {
+        let (host, path) = Self::split_captured_url(&exchange.url);
+        let host = host.to_lowercase();
+        let filters: Vec<String> = self
+            .capture_host_filter
+            .split(',')
+            .map(|filter| filter.trim().to_lowercase())
+            .filter(|filter| !filter.is_empty())
+            .collect();
+        if !filters.is_empty() && !filters.iter().any(|filter| host.contains(filter)) {
+            return;
+        }
+
+        // Keep one request per method and endpoint
+        let duplicate = self.captured.iter().any(|existing| {
+            existing.method == exchange.method
+                && Self::split_captured_url(&existing.url) == (host.as_str(), path)
+        });
+        if !duplicate {
+            self.captured.push(exchange);
+        }
+    }
+
+    fn save_captured_collection(&mut self) {
+        let requests = self
+            .captured
+            .iter()
+            .map(|exchange| {
+                let (_, path) = Self::split_captured_url(&exchange.url);
+                let is_json = exchange.headers.iter().any(|(key, value)| {
+                    key.eq_ignore_ascii_case("content-type") && value.contains("json")
+                });
+                HttpRequest {
+                    id: Uuid::new_v4().to_string(),
+                    name: format!("{} {}", exchange.method, path),
+                    method: exchange.method.clone(),
+                    url: exchange.url.clone(),
+                    headers: exchange
+                        .headers
+                        .iter()
+                        .map(|(key, value)| KeyValue::new(key.clone(), value.clone()))
+                        .collect(),
+                    body: exchange.body.clone(),
+                    body_type: if exchange.body.is_empty() {
+                        BodyType::None
+                    } else if is_json {
+                        BodyType::Json
+                    } else {
+                        BodyType::Raw
+                    },
+                    form_data: vec![],
+                    url_encoded_data: vec![],
+                    query_params: HttpRequest::query_pairs(&exchange.url),
+                    query_in_url: true,
+                    path_variables: vec![],
+                    settings: RequestSettings::default(),
+                    example: Some(exchange.response.clone()),
+                    body_schema: None,
+                    token: None,
+                    assertions: vec![],
+                    captures: vec![],
+                    auth: RequestAuth::Inherit,
+                    binary_file: String::new(),
+                    body_file: None,
+                }
+            })
+            .collect();
+
+        self.current_workspace_mut().collections.push(Collection {
+            id: Uuid::new_v4().to_string(),
+            name: "Captured Traffic".to_string(),
+            root_folder: Folder {
+                id: Uuid::new_v4().to_string(),
+                name: "Root".to_string(),
+                requests,
+                folders: vec![],
+                auth: RequestAuth::Inherit,
+            },
+            token: None,
+            auth: RequestAuth::None,
+            signer: None,
+        });
+        self.captured.clear();
+        self.collections_generation += 1;
+        self.auto_save_workspace();
+    }
+
+    fn draw_capture_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.capture_dialog;
+        let mut start = false;
+        let mut stop = false;
+        let mut save = false;
+
+        egui::Window::new(tr("capture-traffic"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let running_port = self.capture.as_ref().map(|capture| capture.port);
+                ui.label(tr("capture-traffic-hint"));
+
+                egui::Grid::new("capture_settings_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("listen-on-127-0-0-1"));
+                        ui.add_enabled(
+                            running_port.is_none(),
+                            egui::DragValue::new(&mut self.capture_port).range(1..=65535),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("only-hosts-containing"));
+                        ui.add(
+                            TextEdit::singleline(&mut self.capture_host_filter)
+                                .hint_text("api.example.com, localhost"),
+                        );
+                        ui.end_row();
+                    });
+
+                ui.horizontal(|ui| match running_port {
+                    Some(port) => {
+                        if ui.button(tr("stop")).clicked() {
+                            stop = true;
+                        }
+                        ui.spinner();
+                        let mut args = FluentArgs::new();
+                        args.set("port", port);
+                        ui.label(tr_args("capture-listening", &args));
+                    }
+                    None => {
+                        if ui.button(tr("start")).clicked() {
+                            start = true;
+                        }
+                    }
+                });
+                if let Some(error) = &self.capture_error {
+                    ui.colored_label(Color32::from_rgb(255, 0, 0), error);
+                }
+
+                ui.separator();
+                let mut args = FluentArgs::new();
+                args.set("count", self.captured.len());
+                ui.label(tr_args("requests-captured", &args));
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for exchange in &self.captured {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(&exchange.method).strong());
+                            ui.label(&exchange.url);
+                            ui.label(exchange.response.status.to_string());
+                        });
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !self.captured.is_empty(),
+                            egui::Button::new(tr("save-as-collection")),
+                        )
+                        .clicked()
+                    {
+                        save = true;
+                    }
+                    if ui.button(tr("clear")).clicked() {
+                        self.captured.clear();
+                    }
+                });
+            });
+
+        if start {
+            self.start_capture();
+        }
+        if stop {
+            self.stop_capture();
+        }
+        if save {
+            self.save_captured_collection();
+        }
+        self.capture_dialog = open;
+    }
+
+    // Parse `send://request?id=...` and `send://import?url=...`
+    fn parse_deep_link(link: &str) -> Result<DeepLink, String> {
+        let rest = link.strip_prefix("send://").ok_or_else(|| {
+            let mut args = FluentArgs::new();
+            args.set("link", link.to_string());
+            tr_args("deep-link-not-send", &args)
+        })?;
+        let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| {
+                    urlencoding::decode(value)
+                        .map(|value| value.into_owned())
+                        .unwrap_or_else(|_| value.to_string())
+                })
+        };
+        match action.trim_end_matches('/') {
+            "request" => param("id")
+                .map(DeepLink::Request)
+                .ok_or_else(|| tr("deep-link-missing-id")),
+            "import" => param("url")
+                .map(DeepLink::Import)
+                .ok_or_else(|| tr("deep-link-missing-url")),
+            action => {
+                let mut args = FluentArgs::new();
+                args.set("action", action.to_string());
+                Err(tr_args("deep-link-unknown-action", &args))
+            }
+        }
+    }
+
+    // Folder path and index of a request within a folder tree
+    fn find_request_path(folder: &Folder, id: &str) -> Option<(Vec<usize>, usize)> {
+        if let Some(idx) = folder.requests.iter().position(|request| request.id == id) {
+            return Some((vec![], idx));
+        }
+        folder
+            .folders
+            .iter()
+            .enumerate()
+            .find_map(|(folder_idx, subfolder)| {
+                Self::find_request_path(subfolder, id).map(|(mut path, request_idx)| {
+                    path.insert(0, folder_idx);
+                    (path, request_idx)
+                })
+            })
+    }
+
+    fn open_request_by_id(&mut self, id: &str) -> bool {
+        self.stash_draft();
+        for (workspace_idx, workspace) in self.workspaces.iter_mut().enumerate() {
+            for (collection_idx, collection) in workspace.collections.iter().enumerate() {
+                if let Some((folder_path, request_idx)) =
+                    Self::find_request_path(&collection.root_folder, id)
+                {
+                    let request = Self::get_folder_by_path(collection, &folder_path)
+                        .map(|folder| folder.requests[request_idx].clone());
+                    workspace.selected_collection = Some(collection_idx);
+                    workspace.selected_folder_path = folder_path;
+                    workspace.selected_request = Some(request_idx);
+                    self.current_workspace = workspace_idx;
+                    if let Some(request) = request {
+                        self.current_request = self.drafts.remove(id).unwrap_or(request);
+                    }
+                    self.selected_sidebar_item = Some(SidebarItem::Collections);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn import_from_url(&mut self, url: String) {
+        if self.settings.offline_mode {
+            self.notify(ToastKind::Info, tr("offline-import-skipped"));
+            return;
+        }
+        // Workspace defaults only, so the open request's overrides (skipping TLS
+        // verification, say) don't apply to a download a link started
+        let defaults = Self::new_default_request(&self.settings);
+        let client = match self.shared_client(&defaults) {
+            Ok(client) => client,
+            Err(e) => {
+                self.notify(ToastKind::Error, e);
+                return;
+            }
+        };
+        let client = client.for_url(&url).clone();
+        let (tx, rx) = mpsc::channel();
+        self.import_receiver = Some(rx);
+        self.runtime.spawn(async move {
+            let failed = |id: &str, e: String| {
+                let mut args = FluentArgs::new();
+                args.set("url", url.clone());
+                args.set("error", e);
+                tr_args(id, &args)
+            };
+            let result = async {
+                let response = client
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(|e| failed("download-url-failed", e.to_string()))?;
+                let content = response
+                    .text()
+                    .await
+                    .map_err(|e| failed("download-url-failed", e.to_string()))?;
+                let mut collection = serde_json::from_str::<Collection>(&content)
+                    .map_err(|e| failed("url-not-a-collection", e.to_string()))?;
+                collection.id = Uuid::new_v4().to_string();
+                Ok(collection)
+            }
+            .await;
+            let _ = tx.send(result);
+        });
+    }
+
+    fn open_launch_args(&mut self, launch: LaunchArgs) {
+        for path in launch.workspaces {
+            // A file association may hand us an exported collection rather than a workspace
+            let is_workspace = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<AppStorage>(&content).ok())
+                .is_some();
+            if is_workspace {
+                self.open_workspace_file(path);
+            } else {
+                self.import_collection_file(&path);
+            }
+        }
+        for path in launch.imports {
+            self.import_collection_file(&path);
+        }
+        if let Some(name) = launch.environment {
+            let workspace = self.current_workspace_mut();
+            match workspace
+                .environments
+                .iter()
+                .position(|env| env.name == name)
+            {
+                Some(idx) => {
+                    workspace.selected_environment = Some(idx);
+                    self.save_cache();
+                }
+                None => {
+                    let mut args = FluentArgs::new();
+                    args.set("name", name);
+                    self.notify(ToastKind::Error, tr_args("environment-not-found", &args));
+                }
+            }
+        }
+    }
+
+    fn handle_deep_link(&mut self, link: &str) {
+        match Self::parse_deep_link(link) {
+            Ok(DeepLink::Request(id)) => {
+                if self.open_request_by_id(&id) {
+                    self.save_cache();
+                } else {
+                    let mut args = FluentArgs::new();
+                    args.set("id", id);
+                    self.deep_link_message = Some(tr_args("deep-link-request-not-found", &args));
+                }
+            }
+            Ok(DeepLink::Import(url)) => {
+                let mut args = FluentArgs::new();
+                args.set("url", url.clone());
+                self.confirm(
+                    ConfirmKind::ImportFromUrl,
+                    tr_args("confirm-import-from-url", &args),
+                    PendingAction::ImportFromUrl(url),
+                );
+            }
+            Err(e) => self.deep_link_message = Some(e),
+        }
+    }
+
+    // Receive links opened while this instance is running; fails if another instance owns the port
+    fn start_deep_link_listener(&mut self, ctx: egui::Context) {
+        let Ok(listener) = std::net::TcpListener::bind(("127.0.0.1", DEEP_LINK_PORT)) else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        self.deep_link_receiver = Some(rx);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // One line per connection; a client that never sends one can't hold up the rest
+                let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+                let mut reader = std::io::BufReader::new(std::io::Read::take(stream, 8192));
+                let mut link = String::new();
+                if std::io::BufRead::read_line(&mut reader, &mut link).is_ok()
+                    && link.starts_with("send://")
+                {
+                    let _ = tx.send(link.trim().to_string());
+                    ctx.request_repaint();
+                }
+            }
+        });
+    }
+
+    // Started the first time background mode is on and kept until the app exits
+    #[cfg(feature = "tray")]
+    fn start_tray(&mut self, ctx: &egui::Context) {
+        let (tx, rx) = mpsc::channel();
+        self.tray_receiver = Some(rx);
+        let menu_tx = tx.clone();
+        let menu_ctx = ctx.clone();
+        tray_icon::menu::MenuEvent::set_event_handler(Some(
+            move |event: tray_icon::menu::MenuEvent| {
+                if let Some(tray_event) = TrayEvent::from_menu_id(&event.id.0) {
+                    let _ = menu_tx.send(tray_event);
+                    menu_ctx.request_repaint();
+                }
+            },
+        ));
+        // Not sent on Linux, where clicking the icon opens the menu
+        let click_tx = tx.clone();
+        let click_ctx = ctx.clone();
+        tray_icon::TrayIconEvent::set_event_handler(Some(move |event| {
+            if let tray_icon::TrayIconEvent::Click {
+                button: tray_icon::MouseButton::Left,
+                button_state: tray_icon::MouseButtonState::Up,
+                ..
+            } = event
+            {
+                let _ = click_tx.send(TrayEvent::Open);
+                click_ctx.request_repaint();
+            }
+        }));
+        let labels = [
+            tr("open-send"),
+            tr("pause-monitors"),
+            tr("resume-monitors"),
+            tr("quit"),
+        ];
+        // The icon has to be created on a thread running GTK's main loop
+        #[cfg(target_os = "linux")]
+        {
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let icon = gtk::init()
+                    .map_err(|e| e.to_string())
+                    .and_then(|()| Self::build_tray_icon(&labels));
+                match icon {
+                    Ok(_icon) => gtk::main(),
+                    Err(e) => {
+                        let _ = tx.send(TrayEvent::Failed(e));
+                        ctx.request_repaint();
+                    }
+                }
+            });
+        }
+        #[cfg(not(target_os = "linux"))]
+        match Self::build_tray_icon(&labels) {
+            Ok(icon) => self.tray_icon = Some(icon),
+            Err(e) => {
+                let _ = tx.send(TrayEvent::Failed(e));
+            }
+        }
+    }
+
+    #[cfg(feature = "tray")]
+    fn build_tray_icon(labels: &[String; 4]) -> Result<tray_icon::TrayIcon, String> {
+        use tray_icon::menu::{Menu, MenuItem, PredefinedMenuItem};
+        let [open, pause, resume, quit] = labels;
+        let open = MenuItem::with_id("open", open, true, None);
+        let pause = MenuItem::with_id("pause-monitors", pause, true, None);
+        let resume = MenuItem::with_id("resume-monitors", resume, true, None);
+        let quit = MenuItem::with_id("quit", quit, true, None);
+        let menu = Menu::with_items(&[
+            &open,
+            &PredefinedMenuItem::separator(),
+            &pause,
+            &resume,
+            &PredefinedMenuItem::separator(),
+            &quit,
+        ])
+        .map_err(|e| e.to_string())?;
+        // A filled circle in egui's default accent blue
+        const SIZE: u32 = 32;
+        let rgba = (0..SIZE * SIZE)
+            .flat_map(|i| {
+                let (x, y) = ((i % SIZE) as f32 + 0.5, (i / SIZE) as f32 + 0.5);
+                let center = SIZE as f32 / 2.0;
+                if (x - center).hypot(y - center) <= center - 1.0 {
+                    [0, 92, 128, 255]
+                } else {
+                    [0, 0, 0, 0]
+                }
+            })
+            .collect();
+        let icon = tray_icon::Icon::from_rgba(rgba, SIZE, SIZE).map_err(|e| e.to_string())?;
+        tray_icon::TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip("Send")
+            .with_icon(icon)
+            .build()
+            .map_err(|e| e.to_string())
+    }
+
+    #[cfg(feature = "tray")]
+    fn handle_tray_events(&mut self, ctx: &egui::Context) {
+        if self.settings.run_in_background && self.tray_receiver.is_none() {
+            self.start_tray(ctx);
+        }
+        let events: Vec<TrayEvent> = self
+            .tray_receiver
+            .as_ref()
+            .map(|receiver| receiver.try_iter().collect())
+            .unwrap_or_default();
+        for event in events {
+            match event {
+                TrayEvent::Open => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                }
+                TrayEvent::PauseMonitors => self.monitors_paused = true,
+                TrayEvent::ResumeMonitors => self.monitors_paused = false,
+                TrayEvent::Quit => {
+                    self.quitting = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+                TrayEvent::Failed(e) => self.notify(ToastKind::Error, e),
+            }
+        }
+    }
+
+    // Hand a link to an already running instance
+    fn forward_deep_link(link: &str) -> bool {
+        std::net::TcpStream::connect(("127.0.0.1", DEEP_LINK_PORT))
+            .and_then(|mut stream| {
+                std::io::Write::write_all(&mut stream, format!("{}\n", link).as_bytes())
+            })
+            .is_ok()
+    }
+
+    fn register_url_scheme() -> Result<(), String> {
+        let exe = std::env::current_exe()
+            .map_err(|e| format!("Failed to locate the executable: {}", e))?;
+
+        #[cfg(target_os = "linux")]
+        {
+            let applications = dirs::data_dir()
+                .ok_or_else(|| "No data directory found".to_string())?
+                .join("applications");
+            std::fs::create_dir_all(&applications)
+                .map_err(|e| format!("Failed to create {}: {}", applications.display(), e))?;
+            let desktop_file = applications.join("send-url-handler.desktop");
+            let entry = format!(
+                "[Desktop Entry]\nType=Application\nName=Send\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/send;\n",
+                exe.display()
+            );
+            std::fs::write(&desktop_file, entry)
+                .map_err(|e| format!("Failed to write {}: {}", desktop_file.display(), e))?;
+            let status = std::process::Command::new("xdg-mime")
+                .args([
+                    "default",
+                    "send-url-handler.desktop",
+                    "x-scheme-handler/send",
+                ])
+                .status()
+                .map_err(|e| format!("Failed to run xdg-mime: {}", e))?;
+            if !status.success() {
+                return Err("xdg-mime could not register the send:// handler".to_string());
+            }
+            Ok(())
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let command = format!("\"{}\" \"%1\"", exe.display());
+            for args in [
+                vec!["/ve", "/d", "URL:Send Protocol"],
+                vec!["/v", "URL Protocol", "/d", ""],
+            ] {
+                Self::reg_add(r"HKCU\Software\Classes\send", &args)?;
+            }
+            Self::reg_add(
+                r"HKCU\Software\Classes\send\shell\open\command",
+                &["/ve", "/d", &command],
+            )
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            let _ = exe;
+            Err(
+                "send:// links are registered by the app bundle (CFBundleURLTypes) on this platform"
+                    .to_string(),
+            )
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn reg_add(key: &str, args: &[&str]) -> Result<(), String> {
+        let status = std::process::Command::new("reg")
+            .arg("add")
+            .arg(key)
+            .args(args)
+            .arg("/f")
+            .status()
+            .map_err(|e| format!("Failed to run reg: {}", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("Failed to write registry key {}", key))
+        }
+    }
+
+    fn undo_snapshot(&self) -> UndoSnapshot {
+        let workspace = self.current_workspace();
+        let collections = match &self.undo_baseline {
+            Some(baseline)
+                if baseline.workspace == self.current_workspace
+                    && baseline.collections_generation == self.collections_generation =>
+            {
+                baseline.collections.clone()
+            }
+            _ => Arc::new(workspace.collections.clone()),
+        };
+        UndoSnapshot {
+            workspace: self.current_workspace,
+            request: self.current_request.clone(),
+            collections,
+            collections_generation: self.collections_generation,
+            selected_collection: workspace.selected_collection,
+            selected_folder_path: workspace.selected_folder_path.clone(),
+            selected_request: workspace.selected_request,
+        }
+    }
+
+    fn has_pending_edit(&self) -> bool {
+        self.undo_baseline.as_ref().is_some_and(|baseline| {
+            baseline.workspace == self.current_workspace
+                && (baseline.collections_generation != self.collections_generation
+                    || baseline.request != self.current_request)
+        })
+    }
+
+    // Record an undo step once edits settle, so a burst of typing undoes as one
+    fn track_undo(&mut self, ctx: &egui::Context) {
+        if ctx.input(|input| !input.events.is_empty()) {
+            self.last_input = Instant::now();
+        }
+        let baseline_stale = self
+            .undo_baseline
+            .as_ref()
+            .is_none_or(|baseline| baseline.workspace != self.current_workspace);
+        if baseline_stale {
+            self.undo_baseline = Some(self.undo_snapshot());
+            return;
+        }
+        if !self.has_pending_edit() {
+            return;
+        }
+        let settle = Duration::from_millis(700);
+        if self.last_input.elapsed() >= settle {
+            self.commit_pending_edit();
+        } else {
+            ctx.request_repaint_after(settle);
+        }
+    }
+
+    fn commit_pending_edit(&mut self) {
+        if !self.has_pending_edit() {
+            return;
+        }
+        if let Some(baseline) = self.undo_baseline.replace(self.undo_snapshot()) {
+            self.undo_stack.push(baseline);
+            if self.undo_stack.len() > 100 {
+                self.undo_stack.remove(0);
+            }
+            self.redo_stack.clear();
+        }
+    }
+
+    fn restore_snapshot(&mut self, snapshot: UndoSnapshot) {
+        if snapshot.workspace >= self.workspaces.len() {
+            return;
+        }
+        self.current_workspace = snapshot.workspace;
+        self.current_request = snapshot.request.clone();
+        let workspace = self.current_workspace_mut();
+        workspace.collections = snapshot.collections.as_ref().clone();
+        workspace.selected_collection = snapshot.selected_collection;
+        workspace.selected_folder_path = snapshot.selected_folder_path.clone();
+        workspace.selected_request = snapshot.selected_request;
+        self.collections_generation += 1;
+        self.undo_baseline = Some(UndoSnapshot {
+            collections_generation: self.collections_generation,
+            ..snapshot
+        });
+        self.auto_save_workspace();
+        self.save_cache();
+    }
+
+    fn undo(&mut self) {
+        self.commit_pending_edit();
+        if let Some(snapshot) = self.undo_stack.pop() {
+            self.redo_stack.push(self.undo_snapshot());
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        self.commit_pending_edit();
+        if let Some(snapshot) = self.redo_stack.pop() {
+            self.undo_stack.push(self.undo_snapshot());
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    fn key_binding(&self, action: ShortcutAction) -> KeyBinding {
+        self.settings
+            .shortcuts
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_binding())
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        for action in ShortcutAction::ALL {
+            let Some(shortcut) = self.key_binding(action).to_shortcut() else {
+                continue;
+            };
+            if ctx.input_mut(|input| input.consume_shortcut(&shortcut)) {
+                self.run_shortcut_action(action);
+            }
+        }
+    }
+
+    fn run_shortcut_action(&mut self, action: ShortcutAction) {
+        match action {
+            ShortcutAction::Send => {
+                if !self.is_loading {
+                    self.send_request();
                 }
-                if ui
-                    .selectable_value(&mut self.raw_body_type, RawBodyType::JSON, "JSON")
-                    .changed()
-                {
-                    raw_type_changed = true;
+            }
+            ShortcutAction::Save => {
+                // Unsaved requests go through the New Request dialog to pick a name
+                if self.current_workspace().selected_request.is_some() {
+                    self.save_current_request();
+                } else {
+                    self.open_new_request_dialog();
                 }
-                if ui
-                    .selectable_value(&mut self.raw_body_type, RawBodyType::HTML, "HTML")
-                    .changed()
-                {
-                    raw_type_changed = true;
+            }
+            ShortcutAction::NewRequest => self.open_new_request_dialog(),
+            ShortcutAction::SwitchEnvironment => self.environment_switcher = true,
+            ShortcutAction::CloseRequest => {
+                self.stash_draft();
+                self.current_request = Self::new_default_request(&self.settings);
+                self.current_response = None;
+                self.request_error = None;
+                self.current_workspace_mut().selected_request = None;
+            }
+            ShortcutAction::ShowShortcuts => self.shortcuts_dialog = true,
+            ShortcutAction::CommandPalette => {
+                self.command_palette = true;
+                self.command_palette_query.clear();
+                self.command_palette_selected = 0;
+            }
+            ShortcutAction::Undo => self.undo(),
+            ShortcutAction::Redo => self.redo(),
+        }
+    }
+
+    // Case-insensitive subsequence match; higher is better, favouring
+    // consecutive characters and matches at word starts
+    fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+        let text: Vec<char> = text.to_lowercase().chars().collect();
+        let mut score = 0;
+        let mut position = 0;
+        let mut previous_match: Option<usize> = None;
+        for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+            let found = (position..text.len()).find(|&idx| text[idx] == query_char)?;
+            score += 1;
+            if previous_match.is_some_and(|previous| previous + 1 == found) {
+                score += 5;
+            }
+            if found == 0 || !text[found - 1].is_alphanumeric() {
+                score += 3;
+            }
+            previous_match = Some(found);
+            position = found + 1;
+        }
+        Some(score - text.len() as i32 / 10)
+    }
+
+    fn collect_palette_requests(
+        folder: &Folder,
+        prefix: &str,
+        commands: &mut Vec<(String, PaletteCommand)>,
+    ) {
+        for request in &folder.requests {
+            let mut args = FluentArgs::new();
+            args.set(
+                "request",
+                format!("{}{} {}", prefix, request.method, request.name),
+            );
+            commands.push((
+                tr_args("palette-open-request", &args),
+                PaletteCommand::OpenRequest(request.id.clone()),
+            ));
+        }
+        for subfolder in &folder.folders {
+            Self::collect_palette_requests(
+                subfolder,
+                &format!("{}{} / ", prefix, subfolder.name),
+                commands,
+            );
+        }
+    }
+
+    fn palette_commands(&self) -> Vec<(String, PaletteCommand)> {
+        let mut commands: Vec<(String, PaletteCommand)> = ShortcutAction::ALL
+            .iter()
+            .filter(|action| **action != ShortcutAction::CommandPalette)
+            .map(|action| (action.label(), PaletteCommand::Shortcut(*action)))
+            .collect();
+        let offline_toggle = if self.settings.offline_mode {
+            "palette-offline-mode-off"
+        } else {
+            "palette-offline-mode-on"
+        };
+        commands.extend(
+            [
+                ("palette-new-collection", PaletteCommand::NewCollection),
+                ("palette-new-folder", PaletteCommand::NewFolder),
+                ("palette-new-environment", PaletteCommand::NewEnvironment),
+                ("palette-new-workspace", PaletteCommand::NewWorkspace),
+                (
+                    "palette-show-collections",
+                    PaletteCommand::ShowSidebar(SidebarItem::Collections),
+                ),
+                (
+                    "palette-show-environment",
+                    PaletteCommand::ShowSidebar(SidebarItem::Environment),
+                ),
+                (
+                    "palette-show-history",
+                    PaletteCommand::ShowSidebar(SidebarItem::History),
+                ),
+                (
+                    "palette-show-tools",
+                    PaletteCommand::ShowSidebar(SidebarItem::Tools),
+                ),
+                ("palette-run-collection", PaletteCommand::RunCollection),
+                ("palette-load-test", PaletteCommand::LoadTest),
+                ("palette-monitors", PaletteCommand::Monitors),
+                ("palette-capture-traffic", PaletteCommand::CaptureTraffic),
+                ("palette-save-workspace", PaletteCommand::SaveWorkspace),
+                ("palette-load-workspace", PaletteCommand::LoadWorkspace),
+                (
+                    "palette-export-collection",
+                    PaletteCommand::ExportCollection,
+                ),
+                (
+                    "palette-import-collection",
+                    PaletteCommand::ImportCollection,
+                ),
+                (
+                    "palette-export-for-sharing",
+                    PaletteCommand::ExportForSharing,
+                ),
+                (offline_toggle, PaletteCommand::ToggleOfflineMode),
+                (
+                    "palette-workspace-settings",
+                    PaletteCommand::WorkspaceSettings,
+                ),
+                ("palette-settings", PaletteCommand::Settings),
+            ]
+            .map(|(id, command)| (tr(id), command)),
+        );
+        let mut args = FluentArgs::new();
+        args.set("name", tr("no-environment"));
+        commands.push((
+            tr_args("palette-environment", &args),
+            PaletteCommand::SelectEnvironment(None),
+        ));
+
+        let workspace = self.current_workspace();
+        for (idx, environment) in workspace.environments.iter().enumerate() {
+            args.set("name", environment.name.clone());
+            commands.push((
+                tr_args("palette-environment", &args),
+                PaletteCommand::SelectEnvironment(Some(idx)),
+            ));
+        }
+        for (idx, workspace) in self.workspaces.iter().enumerate() {
+            args.set("name", workspace.name.clone());
+            commands.push((
+                tr_args("palette-workspace", &args),
+                PaletteCommand::SelectWorkspace(idx),
+            ));
+        }
+        for collection in &workspace.collections {
+            Self::collect_palette_requests(
+                &collection.root_folder,
+                &format!("{} / ", collection.name),
+                &mut commands,
+            );
+        }
+        commands
+    }
+
+    fn run_palette_command(&mut self, command: PaletteCommand) {
+        match command {
+            PaletteCommand::Shortcut(action) => self.run_shortcut_action(action),
+            PaletteCommand::NewCollection => self.new_collection_dialog = true,
+            PaletteCommand::NewFolder => self.new_folder_dialog = true,
+            PaletteCommand::NewEnvironment => self.new_environment_dialog = true,
+            PaletteCommand::NewWorkspace => self.new_workspace_dialog = true,
+            PaletteCommand::SelectEnvironment(environment) => {
+                self.current_workspace_mut().selected_environment = environment;
+                self.save_cache();
+            }
+            PaletteCommand::SelectWorkspace(idx) => {
+                self.current_workspace = idx;
+                self.save_cache();
+            }
+            PaletteCommand::OpenRequest(id) => {
+                if self.open_request_by_id(&id) {
+                    self.save_cache();
+                }
+            }
+            PaletteCommand::ShowSidebar(item) => {
+                self.selected_sidebar_item = Some(item);
+                self.save_cache();
+            }
+            PaletteCommand::RunCollection => self.runner_dialog = true,
+            PaletteCommand::LoadTest => self.load_test_dialog = true,
+            PaletteCommand::Monitors => self.monitors_dialog = true,
+            PaletteCommand::CaptureTraffic => self.capture_dialog = true,
+            PaletteCommand::SaveWorkspace => self.save_to_file(),
+            PaletteCommand::LoadWorkspace => self.load_from_file(),
+            PaletteCommand::ExportCollection => self.export_collection(),
+            PaletteCommand::ImportCollection => self.import_collection(),
+            PaletteCommand::ExportForSharing => self.share_export_dialog = true,
+            PaletteCommand::ToggleOfflineMode => {
+                self.settings.offline_mode = !self.settings.offline_mode;
+                self.save_settings();
+            }
+            PaletteCommand::WorkspaceSettings => self.workspace_settings_dialog = true,
+            PaletteCommand::Settings => self.settings_dialog = true,
+        }
+    }
+
+    fn draw_command_palette(&mut self, ctx: &egui::Context) {
+        let mut matches: Vec<(i32, String, PaletteCommand)> = self
+            .palette_commands()
+            .into_iter()
+            .filter_map(|(label, command)| {
+                Self::fuzzy_score(&self.command_palette_query, &label)
+                    .map(|score| (score, label, command))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+        matches.truncate(15);
+
+        let (up, down, enter, escape) = ctx.input_mut(|input| {
+            (
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if up {
+            self.command_palette_selected = self.command_palette_selected.saturating_sub(1);
+        }
+        if down {
+            self.command_palette_selected += 1;
+        }
+        self.command_palette_selected = self
+            .command_palette_selected
+            .min(matches.len().saturating_sub(1));
+
+        let mut chosen = None;
+        egui::Window::new(tr("command-palette"))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .fixed_size([500.0, 0.0])
+            .anchor(egui::Align2::CENTER_TOP, egui::Vec2::new(0.0, 60.0))
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.command_palette_query)
+                        .hint_text(tr("type-a-command-or-request-name"))
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.command_palette_selected = 0;
+                }
+                ui.separator();
+                if matches.is_empty() {
+                    ui.label(tr("no-matching-commands"));
+                }
+                for (idx, (_, label, command)) in matches.iter().enumerate() {
+                    if ui
+                        .selectable_label(idx == self.command_palette_selected, label)
+                        .clicked()
+                    {
+                        chosen = Some(command.clone());
+                    }
                 }
+            });
+
+        if enter && let Some((_, _, command)) = matches.get(self.command_palette_selected) {
+            chosen = Some(command.clone());
+        }
+        if chosen.is_some() || escape {
+            self.command_palette = false;
+        }
+        if let Some(command) = chosen {
+            self.run_palette_command(command);
+        }
+    }
+
+    fn draw_shortcuts_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.shortcuts_dialog;
+        egui::Window::new(tr("keyboard-shortcuts"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcuts_grid")
+                    .num_columns(2)
+                    .spacing([30.0, 6.0])
+                    .show(ui, |ui| {
+                        for action in ShortcutAction::ALL {
+                            ui.label(action.label());
+                            ui.label(RichText::new(self.key_binding(action).label()).monospace());
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+                ui.label(tr("bindings-can-be-changed-in-settings"));
+            });
+        self.shortcuts_dialog = open;
+    }
+
+    fn draw_environment_switcher(&mut self, ctx: &egui::Context) {
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let workspace = &mut self.workspaces[self.current_workspace];
+        egui::Window::new(tr("switch-environment"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::Vec2::new(0.0, 80.0))
+            .show(ctx, |ui| {
                 if ui
-                    .selectable_value(&mut self.raw_body_type, RawBodyType::XML, "XML")
-                    .changed()
+                    .selectable_label(
+                        workspace.selected_environment.is_none(),
+                        tr("no-environment"),
+                    )
+                    .clicked()
                 {
-                    raw_type_changed = true;
+                    workspace.selected_environment = None;
+                    close = true;
+                }
+                for (idx, environment) in workspace.environments.iter().enumerate() {
+                    if ui
+                        .selectable_label(
+                            workspace.selected_environment == Some(idx),
+                            &environment.name,
+                        )
+                        .clicked()
+                    {
+                        workspace.selected_environment = Some(idx);
+                        close = true;
+                    }
+                }
+            });
+        if close {
+            self.environment_switcher = false;
+            self.save_cache();
+        }
+    }
+
+    // Remappable bindings; returns true when a binding changed
+    fn draw_shortcut_settings(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+
+        if let Some(action) = self.recording_shortcut {
+            let pressed = ui.input(|input| {
+                input.events.iter().find_map(|event| match event {
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some((*key, *modifiers)),
+                    _ => None,
+                })
+            });
+            match pressed {
+                Some((egui::Key::Escape, _)) => self.recording_shortcut = None,
+                Some((key, modifiers)) => {
+                    let binding = KeyBinding {
+                        command: modifiers.command,
+                        shift: modifiers.shift,
+                        alt: modifiers.alt,
+                        key: key.name().to_string(),
+                    };
+                    if binding == action.default_binding() {
+                        self.settings.shortcuts.remove(&action);
+                    } else {
+                        self.settings.shortcuts.insert(action, binding);
+                    }
+                    self.recording_shortcut = None;
+                    changed = true;
+                }
+                None => {}
+            }
+        }
+
+        egui::Grid::new("shortcut_settings_grid")
+            .num_columns(3)
+            .spacing([20.0, 6.0])
+            .show(ui, |ui| {
+                for action in ShortcutAction::ALL {
+                    ui.label(action.label());
+                    let text = if self.recording_shortcut == Some(action) {
+                        tr("press-keys")
+                    } else {
+                        self.key_binding(action).label()
+                    };
+                    if ui
+                        .button(RichText::new(text).monospace())
+                        .on_hover_text(tr("click-then-press-the-new-key"))
+                        .clicked()
+                    {
+                        self.recording_shortcut = Some(action);
+                    }
+                    if ui
+                        .add_enabled(
+                            self.settings.shortcuts.contains_key(&action),
+                            egui::Button::new(tr("reset")),
+                        )
+                        .clicked()
+                    {
+                        self.settings.shortcuts.remove(&action);
+                        changed = true;
+                    }
+                    ui.end_row();
+                }
+            });
+        changed
+    }
+
+    fn draw_retry_headers_dialog(&mut self, ctx: &egui::Context) {
+        let Some(headers) = &mut self.retry_headers else {
+            return;
+        };
+        let mut open = true;
+        let mut retry = false;
+        let mut cancel = false;
+        egui::Window::new(tr("retry-with-modified-headers-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr("these-headers-are-used-for-this"));
+                ui.separator();
+                let mut remove = None;
+                egui::Grid::new("retry_headers_grid")
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        for (idx, row) in headers.iter_mut().enumerate() {
+                            ui.checkbox(&mut row.enabled, "")
+                                .on_hover_text(tr("send-this-row"));
+                            ui.add(TextEdit::singleline(&mut row.key).hint_text(tr("header")));
+                            ui.add(TextEdit::singleline(&mut row.value).hint_text(tr("value")));
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(idx);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(idx) = remove {
+                    headers.remove(idx);
+                }
+                if ui.button(tr("add-header-row")).clicked() {
+                    headers.push(KeyValue::new("", ""));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("retry")).clicked() {
+                        retry = true;
+                    }
+                    if ui.button(tr("cancel")).clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if retry && let Some(headers) = self.retry_headers.take() {
+            let mut request = self.current_request.clone();
+            request.headers = headers
+                .into_iter()
+                .filter(|row| !row.key.trim().is_empty())
+                .collect();
+            if !self.is_loading {
+                self.send_request_as(request, false);
+            }
+        } else if cancel || !open {
+            self.retry_headers = None;
+        }
+    }
+
+    fn draw_signer_dialog(&mut self, ctx: &egui::Context) {
+        let Some((collection_idx, signer)) = &mut self.signer_dialog else {
+            return;
+        };
+        let Some(name) = self.workspaces[self.current_workspace]
+            .collections
+            .get(*collection_idx)
+            .map(|collection| collection.name.clone())
+        else {
+            self.signer_dialog = None;
+            return;
+        };
+        let mut args = FluentArgs::new();
+        args.set("name", name);
+        let mut open = true;
+        let mut save = false;
+        let mut remove = false;
+        let mut cancel = false;
+        egui::Window::new(tr_args("signer-dialog-title", &args))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(tr("signer-hint"));
+                ui.separator();
+                ui.checkbox(&mut signer.enabled, tr("signer-enabled"));
+                egui::Grid::new("signer_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("signer-algorithm"));
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt("signer_algorithm")
+                                .selected_text(signer.algorithm.label())
+                                .show_ui(ui, |ui| {
+                                    for algorithm in SignatureAlgorithm::ALL {
+                                        ui.selectable_value(
+                                            &mut signer.algorithm,
+                                            algorithm,
+                                            algorithm.label(),
+                                        );
+                                    }
+                                });
+                            ui.radio_value(&mut signer.encoding, SignatureEncoding::Hex, "Hex");
+                            ui.radio_value(
+                                &mut signer.encoding,
+                                SignatureEncoding::Base64,
+                                "Base64",
+                            );
+                        });
+                        ui.end_row();
+                        ui.label(tr("signer-secret"));
+                        ui.add(
+                            TextEdit::singleline(&mut signer.secret)
+                                .password(true)
+                                .hint_text(tr("header-value-supports-variable"))
+                                .desired_width(360.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("signer-string-to-sign"));
+                        ui.add(
+                            TextEdit::multiline(&mut signer.string_to_sign)
+                                .code_editor()
+                                .desired_rows(4)
+                                .desired_width(360.0),
+                        );
+                        ui.end_row();
+                    });
+                ui.label(tr("signer-headers"));
+                let mut remove_header = None;
+                for (idx, (key, value)) in signer.headers.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(key)
+                                .hint_text(tr("header-name-hint"))
+                                .desired_width(160.0),
+                        );
+                        ui.add(
+                            TextEdit::singleline(value)
+                                .hint_text("{signature}")
+                                .desired_width(280.0),
+                        );
+                        if ui.small_button("🗑").clicked() {
+                            remove_header = Some(idx);
+                        }
+                    });
+                }
+                if let Some(idx) = remove_header {
+                    signer.headers.remove(idx);
+                }
+                if ui.button(tr("add-header-row")).clicked() {
+                    signer.headers.push((String::new(), String::new()));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("save")).clicked() {
+                        save = true;
+                    }
+                    if ui.button(tr("signer-remove")).clicked() {
+                        remove = true;
+                    }
+                    if ui.button(tr("cancel")).clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if (save || remove)
+            && let Some((collection_idx, signer)) = self.signer_dialog.take()
+        {
+            if let Some(collection) = self
+                .current_workspace_mut()
+                .collections
+                .get_mut(collection_idx)
+            {
+                collection.signer = save.then_some(signer);
+            }
+            self.collections_generation += 1;
+            self.auto_save_workspace();
+        } else if cancel || !open {
+            self.signer_dialog = None;
+        }
+    }
+
+    fn draw_auth_dialog(&mut self, ctx: &egui::Context) {
+        let Some((node, auth)) = &mut self.auth_dialog else {
+            return;
+        };
+        let workspace = &self.workspaces[self.current_workspace];
+        let (name, is_collection) = match node {
+            TreeNode::Collection(collection_idx) => (
+                workspace
+                    .collections
+                    .get(*collection_idx)
+                    .map(|c| c.name.clone()),
+                true,
+            ),
+            TreeNode::Folder(collection_idx, path) => (
+                workspace
+                    .collections
+                    .get(*collection_idx)
+                    .and_then(|collection| Self::get_folder_by_path(collection, path))
+                    .map(|folder| folder.name.clone()),
+                false,
+            ),
+            TreeNode::Request(..) => (None, false),
+        };
+        let Some(name) = name else {
+            self.auth_dialog = None;
+            return;
+        };
+        let mut args = FluentArgs::new();
+        args.set("name", name);
+        let mut open = true;
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new(tr_args("auth-dialog-title", &args))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr("auth-dialog-hint"));
+                ui.separator();
+                Self::draw_auth_type(ui, "tree_auth", auth, !is_collection);
+                ui.separator();
+                Self::draw_auth_fields(ui, auth);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("save")).clicked() {
+                        save = true;
+                    }
+                    if ui.button(tr("cancel")).clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if save && let Some((node, auth)) = self.auth_dialog.take() {
+            let workspace = self.current_workspace_mut();
+            match node {
+                TreeNode::Collection(collection_idx) => {
+                    if let Some(collection) = workspace.collections.get_mut(collection_idx) {
+                        collection.auth = auth;
+                    }
+                }
+                TreeNode::Folder(collection_idx, path) => {
+                    if let Some(folder) = workspace
+                        .collections
+                        .get_mut(collection_idx)
+                        .and_then(|collection| Self::get_folder_by_path_mut(collection, &path))
+                    {
+                        folder.auth = auth;
+                    }
                 }
+                TreeNode::Request(..) => {}
+            }
+            self.collections_generation += 1;
+            self.auto_save_workspace();
+        } else if cancel || !open {
+            self.auth_dialog = None;
+        }
+    }
+
+    fn draw_dialogs(&mut self, ctx: &egui::Context) {
+        // Command Palette
+        if self.command_palette {
+            self.draw_command_palette(ctx);
+        }
+
+        // Keyboard Shortcuts Dialog
+        if self.shortcuts_dialog {
+            self.draw_shortcuts_dialog(ctx);
+        }
+
+        // Environment Switcher
+        if self.environment_switcher {
+            self.draw_environment_switcher(ctx);
+        }
+
+        // Retry With Modified Headers Dialog
+        if self.retry_headers.is_some() {
+            self.draw_retry_headers_dialog(ctx);
+        }
+
+        // Collection and folder auth
+        if self.auth_dialog.is_some() {
+            self.draw_auth_dialog(ctx);
+        }
+
+        // Collection request signing
+        if self.signer_dialog.is_some() {
+            self.draw_signer_dialog(ctx);
+        }
+
+        // Request Queue
+        if self.request_queue_dialog {
+            self.draw_request_queue_dialog(ctx);
+        }
+
+        // Raw Request Preview
+        if self.request_preview.is_some() {
+            self.draw_request_preview(ctx);
+        }
+
+        // Import curl Command Dialog
+        if self.curl_import.is_some() {
+            self.draw_curl_import_dialog(ctx);
+        }
+
+        // Confirmation for destructive operations
+        if self.confirmation.is_some() {
+            self.draw_confirmation(ctx);
+        }
+
+        // Deep Link Message
+        if let Some(message) = self.deep_link_message.clone() {
+            egui::Window::new(tr("send"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                .show(ctx, |ui| {
+                    ui.label(message);
+                    if ui.button(tr("ok")).clicked() {
+                        self.deep_link_message = None;
+                    }
+                });
+        }
+
+        // Collection Runner Dialog
+        if self.runner_dialog {
+            self.draw_runner_dialog(ctx);
+        }
+
+        // Monitors Dialog
+        if self.monitors_dialog {
+            self.draw_monitors_dialog(ctx);
+        }
+
+        // Run Metrics Dashboard
+        if self.metrics_dialog {
+            self.draw_metrics_dialog(ctx);
+        }
+
+        // Token Manager
+        if self.token_manager_dialog {
+            self.draw_token_manager_dialog(ctx);
+        }
+
+        // Named Sessions
+        if self.sessions_dialog {
+            self.draw_sessions_dialog(ctx);
+        }
+
+        // Cookie Manager
+        if self.cookie_manager_dialog {
+            self.draw_cookie_manager_dialog(ctx);
+        }
+
+        // Load Test Dialog
+        if self.load_test_dialog {
+            self.draw_load_test_dialog(ctx);
+        }
+
+        // Capture Traffic Dialog
+        if self.capture_dialog {
+            self.draw_capture_dialog(ctx);
+        }
+
+        // Settings Dialog
+        if self.settings_dialog {
+            self.draw_settings_dialog(ctx);
+        }
+
+        // Workspace Settings Dialog
+        if self.workspace_settings_dialog {
+            self.draw_workspace_settings_dialog(ctx);
+        }
+
+        // Export for Sharing Dialog
+        if self.share_export_dialog {
+            egui::Window::new(tr("export-for-sharing"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(
+                            &mut self.share_export_scope,
+                            ShareExportScope::Workspace,
+                            tr("workspace"),
+                        );
+                        ui.selectable_value(
+                            &mut self.share_export_scope,
+                            ShareExportScope::Collection,
+                            tr("selected-collection"),
+                        );
+                    });
+                    ui.separator();
+                    ui.label(tr("secret-variables-are-blanked"));
+                    ui.label(tr("sensitive-headers-are-removed"));
+                    ui.label(tr("local-file-paths-in-form-data"));
+                    if self.share_export_scope == ShareExportScope::Workspace {
+                        ui.checkbox(
+                            &mut self.share_export_strip_values,
+                            tr("remove-all-variable-values"),
+                        );
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("export")).clicked() {
+                            self.export_for_sharing();
+                            self.share_export_dialog = false;
+                        }
+                        if ui.button(tr("cancel")).clicked() {
+                            self.share_export_dialog = false;
+                        }
+                    });
+                });
+        }
 
-                if raw_type_changed {
-                    // Update Content-Type header when raw body type changes
-                    let content_type = self.raw_body_type.get_content_type();
-                    self.set_content_type_header(content_type);
-                    self.save_current_request();
-                    self.save_cache();
+        // New Collection Dialog
+        if self.new_collection_dialog {
+            egui::Window::new(tr("new-collection"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(tr("collection-name"));
+                    ui.text_edit_singleline(&mut self.new_collection_name);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("create")).clicked()
+                            && !self.new_collection_name.trim().is_empty()
+                        {
+                            let collection_name = self.new_collection_name.clone();
+                            self.current_workspace_mut().collections.push(Collection {
+                                id: Uuid::new_v4().to_string(),
+                                name: collection_name,
+                                root_folder: Folder {
+                                    id: Uuid::new_v4().to_string(),
+                                    name: "Root".to_string(),
+                                    requests: vec![],
+                                    folders: vec![],
+                                    auth: RequestAuth::Inherit,
+                                },
+                                token: None,
+                                auth: RequestAuth::None,
+                                signer: None,
+                            });
+                            self.new_collection_name.clear();
+                            self.new_collection_dialog = false;
+                            self.collections_generation += 1;
+                            self.auto_save_workspace();
+                        }
+                        if ui.button(tr("cancel")).clicked() {
+                            self.new_collection_name.clear();
+                            self.new_collection_dialog = false;
+                        }
+                    });
+                });
+        }
+
+        // New Request Dialog
+        if self.new_request_dialog {
+            egui::Window::new(tr("new-request"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(tr("request-name"));
+                    ui.text_edit_singleline(&mut self.new_request_name);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("create")).clicked()
+                            && !self.new_request_name.trim().is_empty()
+                        {
+                            let request_name = self.new_request_name.clone();
+                            let current_request = self.current_request.clone();
+                            let current_workspace_idx = self.current_workspace;
+                            let collection_idx =
+                                self.workspaces[current_workspace_idx].selected_collection;
+                            let folder_path = self.workspaces[current_workspace_idx]
+                                .selected_folder_path
+                                .clone();
+
+                            if let Some(collection_idx) = collection_idx
+                                && collection_idx
+                                    < self.workspaces[current_workspace_idx].collections.len()
+                                && let Some(folder) = Self::get_folder_by_path_mut(
+                                    &mut self.workspaces[current_workspace_idx].collections
+                                        [collection_idx],
+                                    &folder_path,
+                                )
+                            {
+                                let mut new_request = current_request;
+                                new_request.id = Uuid::new_v4().to_string();
+                                new_request.name = request_name;
+                                folder.requests.push(new_request);
+                                self.new_request_name.clear();
+                                self.new_request_dialog = false;
+                                self.collections_generation += 1;
+                                self.auto_save_workspace();
+                            }
+                        }
+                        if ui.button(tr("cancel")).clicked() {
+                            self.new_request_name.clear();
+                            self.new_request_dialog = false;
+                        }
+                    });
+                });
+        }
+
+        // New Workspace Dialog
+        if self.new_workspace_dialog {
+            egui::Window::new(tr("new-workspace"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(tr("workspace-name"));
+                    ui.text_edit_singleline(&mut self.new_workspace_name);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("create")).clicked()
+                            && !self.new_workspace_name.trim().is_empty()
+                        {
+                            let new_workspace = Workspace {
+                                name: self.new_workspace_name.clone(),
+                                file_path: None,
+                                collections: vec![Collection {
+                                    id: Uuid::new_v4().to_string(),
+                                    name: "Default Collection".to_string(),
+                                    root_folder: Folder {
+                                        id: Uuid::new_v4().to_string(),
+                                        name: "Root".to_string(),
+                                        requests: vec![],
+                                        folders: vec![],
+                                        auth: RequestAuth::Inherit,
+                                    },
+                                    token: None,
+                                    auth: RequestAuth::None,
+                                    signer: None,
+                                }],
+                                environments: vec![Environment {
+                                    name: "Default".to_string(),
+                                    variables: vec![],
+                                    secret_keys: vec![],
+                                }],
+                                selected_collection: Some(0),
+                                selected_folder_path: vec![],
+                                selected_request: None,
+                                selected_environment: Some(0),
+                                settings: WorkspaceSettings::default(),
+                                active_session: None,
+                            };
+                            self.workspaces.push(new_workspace);
+                            self.current_workspace = self.workspaces.len() - 1;
+                            self.new_workspace_name.clear();
+                            self.new_workspace_dialog = false;
+                            self.save_cache();
+                        }
+                        if ui.button(tr("cancel")).clicked() {
+                            self.new_workspace_name.clear();
+                            self.new_workspace_dialog = false;
+                        }
+                    });
+                });
+        }
+
+        // New Environment Dialog
+        if self.new_environment_dialog {
+            egui::Window::new(tr("new-environment"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(tr("environment-name"));
+                    ui.text_edit_singleline(&mut self.new_environment_name);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("create")).clicked()
+                            && !self.new_environment_name.trim().is_empty()
+                        {
+                            let new_environment = Environment {
+                                name: self.new_environment_name.clone(),
+                                variables: vec![],
+                                secret_keys: vec![],
+                            };
+                            self.current_workspace_mut()
+                                .environments
+                                .push(new_environment);
+                            // Set the new environment as selected
+                            let new_env_index = self.current_workspace().environments.len() - 1;
+                            self.current_workspace_mut().selected_environment = Some(new_env_index);
+                            self.new_environment_name.clear();
+                            self.new_environment_dialog = false;
+                            self.auto_save_workspace();
+                        }
+                        if ui.button(tr("cancel")).clicked() {
+                            self.new_environment_name.clear();
+                            self.new_environment_dialog = false;
+                        }
+                    });
+                });
+        }
+
+        // New Folder Dialog
+        if self.new_folder_dialog {
+            egui::Window::new(tr("new-folder"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(tr("folder-name"));
+                    ui.text_edit_singleline(&mut self.new_folder_name);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("create")).clicked()
+                            && !self.new_folder_name.trim().is_empty()
+                        {
+                            let folder_name = self.new_folder_name.clone();
+                            let current_workspace_idx = self.current_workspace;
+                            let collection_idx =
+                                self.workspaces[current_workspace_idx].selected_collection;
+                            let folder_path = self.workspaces[current_workspace_idx]
+                                .selected_folder_path
+                                .clone();
+
+                            if let Some(collection_idx) = collection_idx
+                                && collection_idx
+                                    < self.workspaces[current_workspace_idx].collections.len()
+                                && let Some(folder) = Self::get_folder_by_path_mut(
+                                    &mut self.workspaces[current_workspace_idx].collections
+                                        [collection_idx],
+                                    &folder_path,
+                                )
+                            {
+                                folder.folders.push(Folder {
+                                    id: Uuid::new_v4().to_string(),
+                                    name: folder_name,
+                                    requests: vec![],
+                                    folders: vec![],
+                                    auth: RequestAuth::Inherit,
+                                });
+                                self.new_folder_name.clear();
+                                self.new_folder_dialog = false;
+                                self.collections_generation += 1;
+                                self.auto_save_workspace();
+                            }
+                        }
+                        if ui.button(tr("cancel")).clicked() {
+                            self.new_folder_name.clear();
+                            self.new_folder_dialog = false;
+                        }
+                    });
+                });
+        }
+    }
+
+    // Workspace default headers that the request doesn't override
+    fn inherited_default_headers<'a>(
+        &'a self,
+        request: &HttpRequest,
+    ) -> impl Iterator<Item = &'a (String, String)> {
+        let overridden: Vec<String> = KeyValue::enabled(&request.headers)
+            .map(|(key, _)| key.trim().to_lowercase())
+            .collect();
+        self.current_workspace()
+            .settings
+            .default_headers
+            .iter()
+            .filter(move |(key, _)| {
+                !key.trim().is_empty() && !overridden.contains(&key.trim().to_lowercase())
+            })
+    }
+
+    // The request's own auth, or else the nearest folder's or the collection's
+    fn effective_auth(&self, request: &HttpRequest) -> RequestAuth {
+        if request.auth != RequestAuth::Inherit {
+            return request.auth.clone();
+        }
+        self.current_workspace()
+            .collections
+            .iter()
+            .find_map(|collection| {
+                Self::inherited_auth(&collection.root_folder, &request.id, &collection.auth)
+            })
+            .filter(|auth| **auth != RequestAuth::Inherit)
+            .cloned()
+            .unwrap_or(RequestAuth::None)
+    }
+
+    fn inherited_auth<'a>(
+        folder: &'a Folder,
+        request_id: &str,
+        parent: &'a RequestAuth,
+    ) -> Option<&'a RequestAuth> {
+        let auth = match folder.auth {
+            RequestAuth::Inherit => parent,
+            _ => &folder.auth,
+        };
+        if folder
+            .requests
+            .iter()
+            .any(|request| request.id == request_id)
+        {
+            return Some(auth);
+        }
+        folder
+            .folders
+            .iter()
+            .find_map(|child| Self::inherited_auth(child, request_id, auth))
+    }
+
+    // The request's own token, or else the one its collection sends
+    fn effective_token_name<'a>(&'a self, request: &'a HttpRequest) -> Option<&'a String> {
+        request.token.as_ref().or_else(|| {
+            self.current_workspace()
+                .collections
+                .iter()
+                .find(|collection| {
+                    Self::find_request_by_id(&collection.root_folder, &request.id).is_some()
+                })
+                .and_then(|collection| collection.token.as_ref())
+        })
+    }
+
+    fn active_session(&self) -> Option<&Session> {
+        let workspace = self.current_workspace();
+        workspace
+            .active_session
+            .and_then(|idx| workspace.settings.sessions.get(idx))
+    }
+
+    // The token to send, with the active session's value in place of the shared one
+    fn effective_token(&self, request: &HttpRequest) -> Option<NamedToken> {
+        let name = self.effective_token_name(request)?;
+        let mut token = self
+            .current_workspace()
+            .settings
+            .tokens
+            .iter()
+            .find(|token| token.name == *name)?
+            .clone();
+        if let Some((_, value)) = self.active_session().and_then(|session| {
+            session
+                .tokens
+                .iter()
+                .find(|(token_name, value)| *token_name == *name && !value.trim().is_empty())
+        }) {
+            token.value = value.clone();
+            token.expires.clear();
+        }
+        Some(token)
+    }
+
+    // The active session's cookies, or the workspace's own jar without one
+    fn cookie_jar(&self) -> &[SessionCookie] {
+        match self.active_session() {
+            Some(session) => &session.cookies,
+            None => &self.current_workspace().settings.cookies,
+        }
+    }
+
+    fn cookie_jar_mut(&mut self) -> &mut Vec<SessionCookie> {
+        let workspace = self.current_workspace_mut();
+        match workspace
+            .active_session
+            .and_then(|idx| workspace.settings.sessions.get_mut(idx))
+        {
+            Some(session) => &mut session.cookies,
+            None => &mut workspace.settings.cookies,
+        }
+    }
+
+    // A jar for a send, holding the active session's cookies
+    fn send_cookie_jar(&self) -> CookieJar {
+        CookieJar::new(
+            CookieOwner {
+                workspace: self.current_workspace,
+                workspace_name: self.current_workspace().name.clone(),
+                session: self.active_session().map(|session| session.name.clone()),
+            },
+            self.cookie_jar().to_vec(),
+        )
+    }
+
+    // Merges the cookies a send's responses set into the jar the send started from, even
+    // if another workspace or session is open now; true when anything changed
+    fn keep_cookies(&mut self, jar: &CookieJar) -> bool {
+        let changes = jar.take_changes();
+        if changes.is_empty() {
+            return false;
+        }
+        let owner = &jar.owner;
+        let Some(workspace) = self
+            .workspaces
+            .get_mut(owner.workspace)
+            .filter(|workspace| workspace.name == owner.workspace_name)
+        else {
+            return false;
+        };
+        let target = match &owner.session {
+            Some(name) => match workspace
+                .settings
+                .sessions
+                .iter_mut()
+                .find(|session| session.name == *name)
+            {
+                Some(session) => &mut session.cookies,
+                None => return false,
+            },
+            None => &mut workspace.settings.cookies,
+        };
+        for cookie in changes {
+            SessionCookie::store(target, cookie);
+        }
+        true
+    }
+
+    // Runs the sent request's capture rules and stores the values in the active environment
+    fn apply_capture_rules(&mut self, response: &HttpResponse) {
+        let rules: Vec<CaptureRule> = self
+            .pending_request
+            .iter()
+            .flat_map(|request| &request.captures)
+            .filter(|rule| rule.enabled && !rule.variable.trim().is_empty())
+            .cloned()
+            .collect();
+        self.capture_results = rules
+            .iter()
+            .map(|rule| {
+                let value = rule.apply(&response.body, |name| {
+                    response
+                        .headers
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                        .map(|(_, value)| value.clone())
+                });
+                (rule.describe(), value)
+            })
+            .collect();
+        if self.capture_results.is_empty() {
+            return;
+        }
+
+        let workspace = &mut self.workspaces[self.current_workspace];
+        let Some(environment) = workspace
+            .selected_environment
+            .and_then(|idx| workspace.environments.get_mut(idx))
+        else {
+            for (_, result) in &mut self.capture_results {
+                if result.is_ok() {
+                    *result = Err(tr("capture-no-environment"));
                 }
-            });
+            }
+            return;
+        };
+        for (rule, (_, result)) in rules.iter().zip(&self.capture_results) {
+            let Ok(value) = result else {
+                continue;
+            };
+            let name = rule.variable.trim();
+            match environment
+                .variables
+                .iter_mut()
+                .find(|(key, _)| key == name)
+            {
+                Some((_, existing)) => *existing = value.clone(),
+                None => environment
+                    .variables
+                    .push((name.to_string(), value.clone())),
+            }
         }
+        self.auto_save_workspace();
+    }
 
-        ui.separator();
+    // Enabled assertions with variables in the expected values resolved
+    fn resolved_assertions(&self, request: &HttpRequest) -> Vec<HeaderAssertion> {
+        request
+            .assertions
+            .iter()
+            .filter(|assertion| assertion.enabled && !assertion.header.trim().is_empty())
+            .map(|assertion| HeaderAssertion {
+                expected: self.resolve_value(&assertion.expected),
+                ..assertion.clone()
+            })
+            .collect()
+    }
 
-        // Body content based on type
-        match self.current_request.body_type {
-            BodyType::None => {
-                ui.label("This request does not have a body");
+    fn resolve_request(&self, request: &HttpRequest) -> ResolvedRequest {
+        let mut resolved = self.resolve_unsigned(request);
+        if let Some(signer) = self.request_signer(request) {
+            signer.sign(&mut resolved, |value| self.resolve_value(value));
+        }
+        resolved
+    }
+
+    // The resolved request before the collection's signer runs
+    fn resolve_unsigned(&self, request: &HttpRequest) -> ResolvedRequest {
+        let auth = self.effective_auth(request);
+        let mut resolved_url = self.resolve_url(&request.url_with_path_variables(|value| {
+            urlencoding::encode(&self.resolve_value(value)).into_owned()
+        }));
+
+        // Requests from older files keep their query params out of the URL
+        if !request.query_in_url {
+            let mut params = Vec::new();
+            for (key, value) in KeyValue::enabled(&request.query_params) {
+                if !key.trim().is_empty() {
+                    let resolved_key = self.resolve_value(key);
+                    let resolved_value = self.resolve_value(value);
+                    params.push(format!(
+                        "{}={}",
+                        urlencoding::encode(&resolved_key),
+                        urlencoding::encode(&resolved_value)
+                    ));
+                }
             }
-            BodyType::FormData => {
-                self.draw_form_data_panel(ui);
+            if !params.is_empty() {
+                let separator = if resolved_url.contains('?') { "&" } else { "?" };
+                resolved_url = format!("{}{}{}", resolved_url, separator, params.join("&"));
             }
-            BodyType::UrlEncoded => {
-                self.draw_url_encoded_panel(ui);
+        }
+        if let Some((key, value)) = auth.api_key(ApiKeyPlacement::Query) {
+            let separator = if resolved_url.contains('?') { "&" } else { "?" };
+            resolved_url = format!(
+                "{}{}{}={}",
+                resolved_url,
+                separator,
+                urlencoding::encode(&self.resolve_value(key)),
+                urlencoding::encode(&self.resolve_value(value))
+            );
+        }
+
+        let mut resolved_headers = Vec::new();
+        for (k, v) in self.inherited_default_headers(request) {
+            resolved_headers.push((self.resolve_value(k), self.resolve_value(v)));
+        }
+        for (k, v) in KeyValue::enabled(&request.headers) {
+            resolved_headers.push((self.resolve_value(k), self.resolve_value(v)));
+        }
+        // An Authorization header set by hand wins over the Auth tab, which wins over the token
+        let has_authorization = |headers: &[(String, String)]| {
+            headers
+                .iter()
+                .any(|(key, _)| key.trim().eq_ignore_ascii_case("authorization"))
+        };
+        if let RequestAuth::Basic { username, password } = &auth
+            && !has_authorization(&resolved_headers)
+        {
+            resolved_headers.push((
+                "Authorization".to_string(),
+                RequestAuth::basic_header(
+                    &self.resolve_value(username),
+                    &self.resolve_value(password),
+                ),
+            ));
+        }
+        if let RequestAuth::Bearer { token } = &auth
+            && !has_authorization(&resolved_headers)
+        {
+            resolved_headers.push((
+                "Authorization".to_string(),
+                format!("Bearer {}", self.resolve_value(token)),
+            ));
+        }
+        let mut credential_headers = Vec::new();
+        if let Some((key, value)) = auth.api_key(ApiKeyPlacement::Header)
+            && let key = self.resolve_value(key)
+            && !resolved_headers
+                .iter()
+                .any(|(name, _)| name.trim().eq_ignore_ascii_case(key.trim()))
+        {
+            credential_headers.push(key.trim().to_string());
+            resolved_headers.push((key, self.resolve_value(value)));
+        }
+        let oauth = self
+            .client_credentials(&auth)
+            .filter(|_| !has_authorization(&resolved_headers));
+        let ntlm = match &auth {
+            RequestAuth::Ntlm {
+                username,
+                password,
+                domain,
+                negotiate,
+            } if !has_authorization(&resolved_headers) => Some(NtlmCredentials::new(
+                &self.resolve_value(username),
+                &self.resolve_value(password),
+                &self.resolve_value(domain),
+                *negotiate,
+            )),
+            _ => None,
+        };
+        if let Some(token) = self.effective_token(request)
+            && !auth.sets_authorization()
+            && !has_authorization(&resolved_headers)
+        {
+            resolved_headers.push((
+                "Authorization".to_string(),
+                token.header_value(&self.resolve_value(&token.value)),
+            ));
+        }
+
+        if let Some(encoding) = request.settings.accept_encoding.header_value()
+            && !resolved_headers
+                .iter()
+                .any(|(key, _)| key.trim().eq_ignore_ascii_case("accept-encoding"))
+        {
+            resolved_headers.push(("Accept-Encoding".to_string(), encoding.to_string()));
+        }
+
+        ResolvedRequest {
+            method: request.method.clone(),
+            url: Self::normalize_url(&resolved_url),
+            headers: resolved_headers,
+            credential_headers,
+            // The inline text is hidden while a raw body comes from a file
+            body: if request.body_file.is_some() {
+                String::new()
+            } else {
+                self.resolve_value(&request.body)
+            },
+            body_type: request.body_type.clone(),
+            form_data: request
+                .form_data
+                .iter()
+                .filter(|entry| entry.is_enabled())
+                .map(|entry| self.resolve_form_entry(entry))
+                .collect(),
+            url_encoded_data: KeyValue::enabled(&request.url_encoded_data)
+                .map(|(key, value)| (self.resolve_value(key), self.resolve_value(value)))
+                .collect(),
+            body_file: match request.body_type {
+                BodyType::Binary => self.resolve_value(&request.binary_file),
+                BodyType::Raw | BodyType::Json => request
+                    .body_file
+                    .as_deref()
+                    .map(|path| self.resolve_value(path))
+                    .unwrap_or_default(),
+                _ => String::new(),
+            },
+            oauth,
+            ntlm,
+            max_redirects: self.effective_redirects(request),
+            http_version: self.effective_http_version(request),
+            upload: Arc::default(),
+            chunked: request.settings.chunked,
+            expect_continue: request.settings.expect_continue,
+            cookie_jar: (!request.settings.ignore_cookie_jar).then(|| self.send_cookie_jar()),
+        }
+    }
+
+    fn resolve_form_entry(&self, entry: &FormDataEntry) -> FormDataEntry {
+        let mut resolved = match entry {
+            FormDataEntry::Text { key, value, .. } => {
+                FormDataEntry::text(self.resolve_value(key), self.resolve_value(value))
             }
-            BodyType::Raw => {
-                // Raw body editor with syntax highlighting based on sub-type
-                let (lang, hint, use_code_editor) = match self.raw_body_type {
-                    RawBodyType::Text => ("text", "Enter plain text...", false),
-                    RawBodyType::JavaScript => ("javascript", "Enter JavaScript code...", true),
-                    RawBodyType::JSON => ("json", "Enter JSON data...", true),
-                    RawBodyType::HTML => ("html", "Enter HTML content...", true),
-                    RawBodyType::XML => ("xml", "Enter XML content...", true),
-                };
+            // The file name sent is taken from the resolved path
+            FormDataEntry::File { key, file_path, .. } => {
+                FormDataEntry::file(self.resolve_value(key), self.resolve_value(file_path))
+            }
+        };
+        let (content_type, headers) = entry.part_options();
+        let (resolved_type, resolved_headers) = resolved.part_options_mut();
+        *resolved_type = self.resolve_value(content_type);
+        *resolved_headers = headers
+            .iter()
+            .map(|(key, value)| (self.resolve_value(key), self.resolve_value(value)))
+            .collect();
+        resolved
+    }
 
-                let mut code = self.current_request.body.clone();
+    fn effective_http_version(&self, request: &HttpRequest) -> HttpVersion {
+        request
+            .settings
+            .http_version
+            .unwrap_or(self.current_workspace().settings.http_version)
+    }
+
+    fn effective_redirects(&self, request: &HttpRequest) -> Option<usize> {
+        request
+            .settings
+            .follow_redirects
+            .unwrap_or(self.settings.follow_redirects)
+            .then(|| {
+                request
+                    .settings
+                    .max_redirects
+                    .unwrap_or(self.settings.max_redirects)
+            })
+    }
+
+    // The enabled signer of the collection holding the request
+    fn request_signer(&self, request: &HttpRequest) -> Option<&RequestSigner> {
+        self.current_workspace()
+            .collections
+            .iter()
+            .find(|collection| {
+                Self::find_request_by_id(&collection.root_folder, &request.id).is_some()
+            })
+            .and_then(|collection| collection.signer.as_ref())
+            .filter(|signer| signer.enabled)
+    }
+
+    fn client_credentials(&self, auth: &RequestAuth) -> Option<ClientCredentials> {
+        let RequestAuth::OAuth2 {
+            token_url,
+            client_id,
+            client_secret,
+            scopes,
+        } = auth
+        else {
+            return None;
+        };
+        Some(ClientCredentials {
+            token_url: self.resolve_value(token_url).trim().to_string(),
+            client_id: self.resolve_value(client_id),
+            client_secret: self.resolve_value(client_secret),
+            scopes: self.resolve_value(scopes),
+            cache: self.oauth_tokens.clone(),
+        })
+    }
+
+    // Sends a resolved request, following redirects by hand so each hop can be shown. Like
+    // browsers, 303 and a POST's 301/302 continue as GET without a body, and credentials
+    // are dropped when the redirect leaves the origin.
+    async fn send_resolved(
+        clients: &HttpClients,
+        request: &ResolvedRequest,
+    ) -> reqwest::Result<reqwest::Response> {
+        let mut response = Self::send_once(clients.for_url(&request.url), request).await?;
+        if let Some(jar) = &request.cookie_jar {
+            jar.store(&response);
+        }
+        let mut connection = clients.note_connection(&response);
+        let Some(max_redirects) = request.max_redirects else {
+            if let Some(connection) = connection {
+                response.extensions_mut().insert(connection);
+            }
+            return Ok(response);
+        };
+        let mut current = request.clone();
+        let mut chain = RedirectChain::default();
+        loop {
+            let status = response.status();
+            let Some(next_url) = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| response.url().join(location).ok())
+                .filter(|_| status.is_redirection())
+            else {
+                break;
+            };
+            if chain.hops.len() >= max_redirects {
+                chain.limit_reached = true;
+                break;
+            }
+            chain.hops.push(RedirectHop {
+                method: current.method.clone(),
+                url: response.url().to_string(),
+                status: status.as_u16(),
+                headers: response
+                    .headers()
+                    .iter()
+                    .map(|(key, value)| {
+                        (
+                            key.to_string(),
+                            String::from_utf8_lossy(value.as_bytes()).to_string(),
+                        )
+                    })
+                    .collect(),
+            });
 
-                if use_code_editor {
-                    let theme = CodeTheme::default();
-                    let _job = highlight(ui.ctx(), ui.style(), &theme, &code, lang);
+            let mut next = current.clone();
+            let as_get = status == reqwest::StatusCode::SEE_OTHER
+                || (matches!(status.as_u16(), 301 | 302) && current.method == "POST");
+            if as_get {
+                next.method = if current.method == "HEAD" {
+                    "HEAD"
+                } else {
+                    "GET"
                 }
+                .to_string();
+                next.body.clear();
+                next.body_type = BodyType::None;
+                next.form_data.clear();
+                next.url_encoded_data.clear();
+                next.headers.retain(|(key, _)| {
+                    !key.trim().eq_ignore_ascii_case("content-type")
+                        && !key.trim().eq_ignore_ascii_case("content-length")
+                });
+            }
+            if next_url.origin() != response.url().origin() {
+                next.headers.retain(|(key, _)| {
+                    let key = key.trim();
+                    !Self::is_sensitive_header(key)
+                        && !next
+                            .credential_headers
+                            .iter()
+                            .any(|name| name.eq_ignore_ascii_case(key))
+                });
+                next.oauth = None;
+                next.ntlm = None;
+            }
+            next.url = next_url.to_string();
+            // Read the redirect's body so its connection goes back to the pool
+            response.bytes().await?;
+            response = Self::send_once(clients.for_url(&next.url), &next).await?;
+            if let Some(jar) = &next.cookie_jar {
+                jar.store(&response);
+            }
+            connection = clients.note_connection(&response);
+            current = next;
+        }
+        if let Some(connection) = connection {
+            response.extensions_mut().insert(connection);
+        }
+        if !chain.hops.is_empty() || chain.limit_reached {
+            response.extensions_mut().insert(chain);
+        }
+        Ok(response)
+    }
+
+    // One request without following redirects. A 401 with a cached OAuth token retries once
+    // with a new token, in case the server revoked it before it expired.
+    async fn send_once(
+        client: &reqwest::Client,
+        request: &ResolvedRequest,
+    ) -> reqwest::Result<reqwest::Response> {
+        if let Some(ntlm) = &request.ntlm {
+            let response = Self::build_request(client, request)
+                .await?
+                .header(
+                    reqwest::header::AUTHORIZATION,
+                    ntlm.header(&ntlm.negotiate_message()),
+                )
+                .send()
+                .await?;
+            let Some(message) = ntlm
+                .challenge(&response)
+                .and_then(|challenge| ntlm.authenticate_message(&challenge))
+            else {
+                return Ok(response);
+            };
+            // Read the 401 to the end so the pool hands back the same connection
+            response.bytes().await?;
+            return Self::build_request(client, request)
+                .await?
+                .header(reqwest::header::AUTHORIZATION, ntlm.header(&message))
+                .send()
+                .await;
+        }
+        let response = Self::build_request(client, request).await?.send().await?;
+        match &request.oauth {
+            Some(oauth) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                let (retry_client, retry) =
+                    Self::build_request(client, request).await?.build_split();
+                let mut retry = retry?;
+                // build_request added the old token, and RequestBuilder::header appends
+                retry.headers_mut().remove(reqwest::header::AUTHORIZATION);
+                let authorization = oauth.authorization(client, true).await?;
+                reqwest::RequestBuilder::from_parts(retry_client, retry)
+                    .header(reqwest::header::AUTHORIZATION, authorization)
+                    .send()
+                    .await
+            }
+            _ => Ok(response),
+        }
+    }
 
-                let text_edit = TextEdit::multiline(&mut code)
-                    .desired_rows(12)
-                    .desired_width(ui.available_width())
-                    .hint_text(hint);
+    // Trims the URL, punycode-encodes IDN hosts and percent-encodes characters that
+    // aren't allowed in the path or query. Unparseable URLs are only trimmed.
+    fn normalize_url(url: &str) -> String {
+        let url = url.trim();
+        reqwest::Url::parse(url).map_or_else(|_| url.to_string(), |parsed| parsed.to_string())
+    }
 
-                let body_response = if use_code_editor {
-                    ui.add(text_edit.code_editor())
-                } else {
-                    ui.add(text_edit)
-                };
+    // Approximate bytes on the wire as (request line and headers, body), following build_request
+    fn estimate_request_size(
+        &self,
+        request: &HttpRequest,
+        resolved: &ResolvedRequest,
+    ) -> (usize, usize) {
+        let header_line = |key: &str, value: &str| key.len() + value.len() + 4;
+        let without_scheme = resolved
+            .url
+            .split_once("://")
+            .map_or(resolved.url.as_str(), |(_, rest)| rest);
+        let (host, path) = without_scheme
+            .find('/')
+            .map_or((without_scheme, "/"), |idx| without_scheme.split_at(idx));
+        let mut head = resolved.method.len() + path.len() + " HTTP/1.1\r\n".len();
+        head += header_line("host", host) + header_line("accept", "*/*");
+        let user_agent = self.effective_user_agent(request);
+        if !user_agent.is_empty() {
+            head += header_line("user-agent", &user_agent);
+        }
+        let multipart = resolved.body_type == BodyType::FormData && !resolved.form_data.is_empty();
+        let has_content_type = resolved
+            .headers
+            .iter()
+            .any(|(key, _)| key.trim().eq_ignore_ascii_case("content-type"));
+        // Multipart bodies replace the Content-Type set by hand with one carrying the boundary
+        head += resolved
+            .headers
+            .iter()
+            .filter(|(key, value)| !key.trim().is_empty() && !value.trim().is_empty())
+            .filter(|(key, _)| !multipart || !key.trim().eq_ignore_ascii_case("content-type"))
+            .map(|(key, value)| header_line(key, value))
+            .sum::<usize>();
 
-                if code != self.current_request.body {
-                    self.current_request.body = code;
-                    if body_response.changed() {
-                        self.save_current_request();
-                    }
+        let body = match resolved.body_type {
+            BodyType::FormData if multipart => {
+                // reqwest's boundary is four 16-digit hex groups
+                const BOUNDARY: usize = 67;
+                head += header_line("content-type", "multipart/form-data; boundary=") + BOUNDARY;
+                let parts: usize = resolved
+                    .form_data
+                    .iter()
+                    .map(|entry| match entry {
+                        FormDataEntry::Text { key, value, .. } if !key.trim().is_empty() => {
+                            BOUNDARY
+                                + header_line("content-disposition", "form-data; name=\"\"")
+                                + entry.part_header_lines().len()
+                                + key.len()
+                                + value.len()
+                                + 8
+                        }
+                        FormDataEntry::File {
+                            key,
+                            file_path,
+                            file_name,
+                            ..
+                        } if !key.trim().is_empty() && !file_path.trim().is_empty() => {
+                            let size = std::fs::metadata(file_path).map_or(0, |m| m.len() as usize);
+                            BOUNDARY
+                                + header_line(
+                                    "content-disposition",
+                                    "form-data; name=\"\"; filename=\"\"",
+                                )
+                                + entry.part_header_lines().len()
+                                + key.len()
+                                + file_name.len()
+                                + size
+                                + 8
+                        }
+                        _ => 0,
+                    })
+                    .sum();
+                parts + BOUNDARY + 6
+            }
+            BodyType::UrlEncoded if !resolved.url_encoded_data.is_empty() => {
+                // RequestBuilder::form keeps a Content-Type set by hand
+                if !has_content_type {
+                    head += header_line("content-type", "application/x-www-form-urlencoded");
                 }
+                let pairs: Vec<String> = resolved
+                    .url_encoded_data
+                    .iter()
+                    .filter(|(key, _)| !key.trim().is_empty())
+                    .map(|(key, value)| {
+                        format!(
+                            "{}={}",
+                            urlencoding::encode(key),
+                            urlencoding::encode(value)
+                        )
+                    })
+                    .collect();
+                pairs.join("&").len()
             }
-            BodyType::Json => {
-                // This should not be reached anymore, but keeping for backwards compatibility
-                ui.label(RichText::new("JSON").color(Color32::from_rgb(0, 150, 255)));
+            BodyType::Binary | BodyType::Raw | BodyType::Json
+                if !resolved.body_file.trim().is_empty() =>
+            {
+                std::fs::metadata(&resolved.body_file).map_or(0, |m| m.len() as usize)
+            }
+            _ if resolved.body.trim().is_empty() => 0,
+            _ => resolved.body.len(),
+        };
+        if body > 0 {
+            head += header_line("content-length", &body.to_string());
+        }
+        (head + 2, body)
+    }
 
-                let mut code = self.current_request.body.clone();
+    // Runs every frame, so the signer signs an empty body instead of reading a body file.
+    // Its headers only change size when a template includes {body}.
+    fn draw_request_size(&self, ui: &mut Ui) {
+        let mut resolved = self.resolve_unsigned(&self.current_request);
+        if let Some(signer) = self.request_signer(&self.current_request) {
+            let body_file = std::mem::take(&mut resolved.body_file);
+            signer.sign(&mut resolved, |value| self.resolve_value(value));
+            resolved.body_file = body_file;
+        }
+        let (head, body) = self.estimate_request_size(&self.current_request, &resolved);
+        let total = head + body;
+        let color = if total >= 10 * 1024 * 1024 {
+            Color32::from_rgb(215, 58, 73)
+        } else if total >= 1024 * 1024 {
+            Color32::from_rgb(255, 165, 0)
+        } else {
+            Color32::GRAY
+        };
+        let mut args = FluentArgs::new();
+        args.set("headers", Self::format_size(head));
+        args.set("body", Self::format_size(body));
+        let mut hint = format!(
+            "{}\n{}\n{}",
+            tr("request-size-hint"),
+            tr_args("request-size-headers", &args),
+            tr_args("request-size-body", &args)
+        );
+        let files: Vec<String> = resolved
+            .form_data
+            .iter()
+            .filter(|_| resolved.body_type == BodyType::FormData)
+            .filter_map(|entry| match entry {
+                FormDataEntry::File {
+                    file_path,
+                    file_name,
+                    ..
+                } if !file_path.trim().is_empty() => Some(match std::fs::metadata(file_path) {
+                    Ok(metadata) => format!(
+                        "{}: {}",
+                        file_name,
+                        Self::format_size(metadata.len() as usize)
+                    ),
+                    Err(_) => format!("{}: {}", file_name, tr("file-not-found")),
+                }),
+                _ => None,
+            })
+            .collect();
+        if !files.is_empty() {
+            hint = format!("{}\n{}", hint, files.join("\n"));
+        }
+        ui.colored_label(color, format!("≈ {}", Self::format_size(total)))
+            .on_hover_text(hint);
+    }
 
-                let theme = CodeTheme::default();
-                let lang = "json";
-                let _job = highlight(ui.ctx(), ui.style(), &theme, &code, lang);
+    async fn build_request(
+        client: &reqwest::Client,
+        request: &ResolvedRequest,
+    ) -> reqwest::Result<reqwest::RequestBuilder> {
+        // The method editor only accepts valid tokens; imports could still bring in others
+        let method = Method::from_bytes(request.method.as_bytes()).unwrap_or(Method::GET);
 
-                let json_response = ui.add(
-                    TextEdit::multiline(&mut code)
-                        .code_editor()
-                        .desired_rows(12)
-                        .desired_width(ui.available_width())
-                        .hint_text("Enter JSON data..."),
-                );
+        let mut req_builder = client.request(method, &request.url);
+        if request.http_version == HttpVersion::Http2 {
+            req_builder = req_builder.version(reqwest::Version::HTTP_2);
+        }
 
-                if code != self.current_request.body {
-                    self.current_request.body = code;
-                    if json_response.changed() {
-                        self.save_current_request();
-                    }
-                }
+        // Jar cookies go in the same Cookie header as any set by hand
+        let mut headers = request.headers.clone();
+        if let Some(cookies) = request
+            .cookie_jar
+            .as_ref()
+            .and_then(|jar| jar.header(&request.url))
+        {
+            match headers.iter_mut().find(|(key, value)| {
+                key.trim().eq_ignore_ascii_case("cookie") && !value.trim().is_empty()
+            }) {
+                Some((_, value)) => *value = format!("{}; {}", value, cookies),
+                None => headers.push(("Cookie".to_string(), cookies)),
             }
         }
-    }
+        for (key, value) in &headers {
+            // Multipart bodies set their own Content-Type with the boundary
+            if key.trim().is_empty()
+                || value.trim().is_empty()
+                || (request.body_type == BodyType::FormData
+                    && !request.form_data.is_empty()
+                    && key.trim().eq_ignore_ascii_case("content-type"))
+            {
+                continue;
+            }
+            req_builder = req_builder.header(key, value);
+        }
 
-    fn draw_form_data_panel(&mut self, ui: &mut Ui) {
-        ScrollArea::vertical().show(ui, |ui| {
-            let mut to_remove = Vec::new();
-            let mut form_data_changed = false;
+        // Handle body based on type
+        match request.body_type {
+            BodyType::FormData if !request.form_data.is_empty() => {
+                let mut form = reqwest::multipart::Form::new();
+                request.upload.sent.store(0, Ordering::Relaxed);
+                request.upload.total.store(0, Ordering::Relaxed);
 
-            for (i, entry) in self.current_request.form_data.iter_mut().enumerate() {
-                ui.horizontal(|ui| {
+                for entry in &request.form_data {
                     match entry {
-                        FormDataEntry::Text { key, value } => {
-                            ui.label("Text");
-                            let key_response = ui.add(
-                                TextEdit::singleline(key)
-                                    .hint_text("Key")
-                                    .desired_width(150.0),
-                            );
-                            let value_response = ui.add(
-                                TextEdit::singleline(value)
-                                    .hint_text("Value")
-                                    .desired_width(200.0),
-                            );
-                            if key_response.changed() || value_response.changed() {
-                                form_data_changed = true;
+                        FormDataEntry::Text { key, value, .. } => {
+                            if !key.trim().is_empty() {
+                                let part = reqwest::multipart::Part::text(value.clone());
+                                form = form.part(key.clone(), entry.apply_part_options(part)?);
                             }
                         }
                         FormDataEntry::File {
                             key,
                             file_path,
                             file_name,
+                            ..
                         } => {
-                            ui.label("File");
-                            let key_response = ui.add(
-                                TextEdit::singleline(key)
-                                    .hint_text("Key")
-                                    .desired_width(150.0),
-                            );
-                            ui.label(if file_name.is_empty() {
-                                "No file selected"
-                            } else {
-                                file_name.as_str()
-                            });
-                            if ui.button("Browse...").clicked() {
-                                if let Some(path) =
-                                    rfd::FileDialog::new().set_title("Select File").pick_file()
-                                {
-                                    *file_path = path.to_string_lossy().to_string();
-                                    *file_name = path
-                                        .file_name()
-                                        .unwrap_or_default()
-                                        .to_string_lossy()
-                                        .to_string();
-                                    form_data_changed = true;
-                                }
-                            }
-                            if key_response.changed() {
-                                form_data_changed = true;
-                            }
-                        }
-                    }
-
-                    // Type toggle button
-                    let current_is_text = matches!(entry, FormDataEntry::Text { .. });
-                    let toggle_text = if current_is_text {
-                        "→File"
-                    } else {
-                        "→Text"
-                    };
-                    if ui.button(toggle_text).clicked() {
-                        if current_is_text {
-                            if let FormDataEntry::Text { key, .. } = entry {
-                                *entry = FormDataEntry::File {
-                                    key: key.clone(),
-                                    file_path: String::new(),
-                                    file_name: String::new(),
+                            if !key.trim().is_empty() && !file_path.trim().is_empty() {
+                                // Streamed from disk; a file that can't be opened is skipped
+                                let Ok(file) = tokio::fs::File::open(file_path).await else {
+                                    continue;
                                 };
-                            }
-                        } else {
-                            if let FormDataEntry::File { key, .. } = entry {
-                                *entry = FormDataEntry::Text {
-                                    key: key.clone(),
-                                    value: String::new(),
+                                let Ok(metadata) = file.metadata().await else {
+                                    continue;
+                                };
+                                request
+                                    .upload
+                                    .total
+                                    .fetch_add(metadata.len(), Ordering::Relaxed);
+                                let reader = ProgressReader {
+                                    inner: file,
+                                    progress: request.upload.clone(),
                                 };
+                                let body = reqwest::Body::wrap_stream(
+                                    tokio_util::io::ReaderStream::new(reader),
+                                );
+                                let part = reqwest::multipart::Part::stream_with_length(
+                                    body,
+                                    metadata.len(),
+                                )
+                                .file_name(file_name.clone());
+                                form = form.part(key.clone(), entry.apply_part_options(part)?);
                             }
                         }
-                        form_data_changed = true;
                     }
+                }
 
-                    if ui.button("🗑").clicked() {
-                        to_remove.push(i);
+                req_builder = req_builder.multipart(form);
+            }
+            BodyType::UrlEncoded if !request.url_encoded_data.is_empty() => {
+                // Create URL-encoded form data
+                let mut form_params = Vec::new();
+                for (key, value) in &request.url_encoded_data {
+                    if !key.trim().is_empty() {
+                        form_params.push((key.as_str(), value.as_str()));
                     }
-                });
+                }
+
+                req_builder = req_builder.form(&form_params);
+            }
+            BodyType::Binary | BodyType::Raw | BodyType::Json
+                if !request.body_file.trim().is_empty() =>
+            {
+                request.upload.sent.store(0, Ordering::Relaxed);
+                request.upload.total.store(0, Ordering::Relaxed);
+                // Streamed from disk like file parts; a file that can't be opened sends no body
+                if let Ok(file) = tokio::fs::File::open(&request.body_file).await
+                    && let Ok(metadata) = file.metadata().await
+                {
+                    request
+                        .upload
+                        .total
+                        .store(metadata.len(), Ordering::Relaxed);
+                    let reader = ProgressReader {
+                        inner: file,
+                        progress: request.upload.clone(),
+                    };
+                    // A stream has no length of its own, so hyper would otherwise send it chunked
+                    req_builder = req_builder
+                        .header(reqwest::header::CONTENT_LENGTH, metadata.len())
+                        .body(reqwest::Body::wrap_stream(
+                            tokio_util::io::ReaderStream::new(reader),
+                        ));
+                }
+            }
+            _ => {
+                // Set body for non-form requests
+                if !request.body.trim().is_empty() {
+                    req_builder = req_builder.body(request.body.clone());
+                }
             }
+        }
 
-            // Remove entries
-            if !to_remove.is_empty() {
-                for &i in to_remove.iter().rev() {
-                    self.current_request.form_data.remove(i);
+        if let Some(oauth) = &request.oauth {
+            let authorization = oauth.authorization(client, false).await?;
+            req_builder = req_builder.header(reqwest::header::AUTHORIZATION, authorization);
+        }
+
+        if request.chunked || request.expect_continue != ExpectContinue::Headers {
+            let (client, built) = req_builder.build_split();
+            let mut built = built?;
+            match request.expect_continue {
+                ExpectContinue::Headers => {}
+                ExpectContinue::Send => {
+                    built.headers_mut().insert(
+                        reqwest::header::EXPECT,
+                        reqwest::header::HeaderValue::from_static("100-continue"),
+                    );
+                }
+                ExpectContinue::Never => {
+                    built.headers_mut().remove(reqwest::header::EXPECT);
+                }
+            }
+            // Without a known length hyper falls back to chunked transfer encoding
+            if request.chunked {
+                built.headers_mut().remove(reqwest::header::CONTENT_LENGTH);
+                if let Some(bytes) = built.body().and_then(|body| body.as_bytes()) {
+                    let reader = std::io::Cursor::new(bytes.to_vec());
+                    *built.body_mut() = Some(reqwest::Body::wrap_stream(
+                        tokio_util::io::ReaderStream::new(reader),
+                    ));
                 }
-                form_data_changed = true;
             }
+            req_builder = reqwest::RequestBuilder::from_parts(client, built);
+        }
+        Ok(req_builder)
+    }
 
-            // Add new entry button
-            ui.horizontal(|ui| {
-                if ui.button("Add Text Field").clicked() {
-                    self.current_request.form_data.push(FormDataEntry::Text {
-                        key: String::new(),
-                        value: String::new(),
-                    });
-                    form_data_changed = true;
+    // The request as it goes on the wire, built the same way send_request_as builds it.
+    // Nothing is sent: OAuth tokens and the NTLM handshake are shown as placeholders.
+    fn raw_request_preview(&self, request: &HttpRequest) -> Result<(String, String), String> {
+        let mut resolved = self.resolve_request(request);
+        // Built with a length so the body can be shown; the framing is fixed up below
+        let chunked = std::mem::take(&mut resolved.chunked);
+        let oauth = resolved.oauth.take();
+        let ntlm = resolved.ntlm.take();
+        // Only used to build the request, so the request's proxy and TLS settings don't matter
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let built = self
+            .runtime
+            .block_on(Self::build_request(&client, &resolved))
+            .and_then(|builder| builder.build())
+            .map_err(|e| e.to_string())?;
+        let url = built.url();
+        let headers = built.headers();
+
+        let mut target = url.path().to_string();
+        if let Some(query) = url.query() {
+            target = format!("{}?{}", target, query);
+        }
+        let mut lines = vec![format!("{} {} HTTP/1.1", built.method(), target)];
+        let host = url.host_str().unwrap_or_default();
+        lines.push(match url.port() {
+            Some(port) => format!("host: {}:{}", host, port),
+            None => format!("host: {}", host),
+        });
+        // The client adds these when the request doesn't set them
+        let user_agent = self.effective_user_agent(request);
+        if !headers.contains_key(reqwest::header::USER_AGENT) && !user_agent.trim().is_empty() {
+            lines.push(format!("user-agent: {}", user_agent));
+        }
+        if !headers.contains_key(reqwest::header::ACCEPT) {
+            lines.push("accept: */*".to_string());
+        }
+        for (name, value) in headers {
+            lines.push(format!(
+                "{}: {}",
+                name,
+                String::from_utf8_lossy(value.as_bytes())
+            ));
+        }
+        if oauth.is_some() {
+            lines.push(format!(
+                "authorization: Bearer {}",
+                tr("preview-oauth-token")
+            ));
+        }
+        if let Some(ntlm) = &ntlm {
+            lines.push(format!(
+                "authorization: {} {}",
+                ntlm.scheme,
+                tr("preview-ntlm-handshake")
+            ));
+        }
+
+        let body = match built.body() {
+            Some(body) => match body.as_bytes() {
+                Some(bytes) => {
+                    if !headers.contains_key(reqwest::header::CONTENT_LENGTH) {
+                        lines.push(format!("content-length: {}", bytes.len()));
+                    }
+                    match std::str::from_utf8(bytes) {
+                        Ok(text) => text.to_string(),
+                        Err(_) => format!("<{} bytes of binary data>", bytes.len()),
+                    }
+                }
+                // Streamed bodies are described instead
+                None if !resolved.body_file.is_empty() => {
+                    let mut args = FluentArgs::new();
+                    args.set("file", resolved.body_file.clone());
+                    tr_args("binary-body-preview", &args)
+                }
+                None => Self::multipart_preview(&resolved, headers),
+            },
+            None => String::new(),
+        };
+        if chunked && built.body().is_some() {
+            lines.retain(|line| !line.starts_with("content-length:"));
+            lines.push("transfer-encoding: chunked".to_string());
+        }
+        Ok((url.to_string(), format!("{}\n\n{}", lines.join("\n"), body)))
+    }
+
+    fn multipart_preview(
+        request: &ResolvedRequest,
+        headers: &reqwest::header::HeaderMap,
+    ) -> String {
+        let boundary = headers
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_once("boundary="))
+            .map(|(_, boundary)| boundary.to_string())
+            .unwrap_or_default();
+        let mut body = String::new();
+        for entry in &request.form_data {
+            match entry {
+                FormDataEntry::Text { key, value, .. } if !key.trim().is_empty() => {
+                    body.push_str(&format!(
+                        "--{}\ncontent-disposition: form-data; name=\"{}\"\n{}\n{}\n",
+                        boundary,
+                        key,
+                        entry.part_header_lines(),
+                        value
+                    ));
                 }
-                if ui.button("Add File").clicked() {
-                    self.current_request.form_data.push(FormDataEntry::File {
-                        key: String::new(),
-                        file_path: String::new(),
-                        file_name: String::new(),
+                FormDataEntry::File {
+                    key,
+                    file_path,
+                    file_name,
+                    ..
+                } if !key.trim().is_empty() && !file_path.trim().is_empty() => {
+                    let contents = match std::fs::metadata(file_path) {
+                        Ok(metadata) => format!("<{} bytes from {}>", metadata.len(), file_path),
+                        Err(_) => format!("<{}: {}>", tr("file-not-found"), file_path),
+                    };
+                    body.push_str(&format!(
+                        "--{}\ncontent-disposition: form-data; name=\"{}\"; filename=\"{}\"\n{}\n{}\n",
+                        boundary,
+                        key,
+                        file_name,
+                        entry.part_header_lines(),
+                        contents
+                    ));
+                }
+                _ => {}
+            }
+        }
+        body.push_str(&format!("--{}--", boundary));
+        body
+    }
+
+    fn draw_request_preview(&mut self, ctx: &egui::Context) {
+        let Some(preview) = &self.request_preview else {
+            return;
+        };
+        let mut open = true;
+        let mut send = false;
+        let mut refresh = false;
+        egui::Window::new(tr("preview-request"))
+            .open(&mut open)
+            .default_width(600.0)
+            .show(ctx, |ui| match preview {
+                Ok((url, raw)) => {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("preview-url"));
+                        ui.label(RichText::new(url).monospace());
+                    });
+                    ui.separator();
+                    ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                        ui.add(
+                            TextEdit::multiline(&mut raw.as_str())
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("copy")).clicked() {
+                            ui.ctx().copy_text(raw.clone());
+                        }
+                        if ui.button(tr("refresh")).clicked() {
+                            refresh = true;
+                        }
+                        if ui
+                            .add_enabled(!self.is_loading, egui::Button::new(tr("send")))
+                            .clicked()
+                        {
+                            send = true;
+                        }
                     });
-                    form_data_changed = true;
+                }
+                Err(error) => {
+                    ui.colored_label(Color32::from_rgb(255, 0, 0), error);
                 }
             });
 
-            if form_data_changed {
-                self.save_current_request();
+        if refresh {
+            let request = self.current_request.clone();
+            self.request_preview = Some(self.raw_request_preview(&request));
+        }
+        if send {
+            self.send_request();
+            open = false;
+        }
+        if !open {
+            self.request_preview = None;
+        }
+    }
+
+    // The client settings for sending `request`, its own overrides over the workspace's
+    fn client_config(&self, request: &HttpRequest) -> ClientConfig {
+        let (min_tls_version, max_tls_version) = self.effective_tls_versions(request);
+        ClientConfig {
+            timeout_secs: self.settings.request_timeout_secs,
+            proxy: self.effective_proxy(request),
+            system_proxy: self.effective_system_proxy(request),
+            skip_tls_verification: self.effective_skip_tls_verification(request),
+            ca_certificates: self.current_workspace().settings.ca_certificates.clone(),
+            client_certificates: self
+                .current_workspace()
+                .settings
+                .client_certificates
+                .iter()
+                .filter(|certificate| certificate.enabled && !certificate.host.trim().is_empty())
+                .cloned()
+                .collect(),
+            min_tls_version,
+            max_tls_version,
+            user_agent: self.effective_user_agent(request),
+            ip_preference: request
+                .settings
+                .ip_preference
+                .clone()
+                .unwrap_or(self.current_workspace().settings.ip_preference.clone()),
+            http_version: self.effective_http_version(request),
+            local_address: self.current_workspace().settings.local_address.clone(),
+            interface: self.current_workspace().settings.interface.clone(),
+        }
+    }
+
+    fn build_clients(config: &ClientConfig) -> Result<HttpClients, String> {
+        let connections = Arc::default();
+        Ok(HttpClients {
+            default: Self::build_client(config, None, &connections)?,
+            identities: config
+                .client_certificates
+                .iter()
+                .map(|certificate| {
+                    Ok((
+                        certificate.host.clone(),
+                        Self::build_client(config, Some(certificate), &connections)?,
+                    ))
+                })
+                .collect::<Result<_, String>>()?,
+            connections,
+        })
+    }
+
+    fn build_client(
+        config: &ClientConfig,
+        identity: Option<&ClientCertificate>,
+        connections: &Arc<std::sync::Mutex<ConnectionLog>>,
+    ) -> Result<reqwest::Client, String> {
+        // send_resolved follows redirects itself so it can record each hop
+        let mut client_builder = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .danger_accept_invalid_certs(config.skip_tls_verification)
+            .tls_info(true)
+            .dns_resolver(Arc::new(IpPreferenceResolver {
+                preference: config.ip_preference.clone(),
+                connections: connections.clone(),
+            }))
+            .connector_layer(ConnectTimingLayer {
+                connections: connections.clone(),
+            })
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT);
+        if config.timeout_secs > 0 {
+            client_builder = client_builder.timeout(Duration::from_secs(config.timeout_secs));
+        }
+        if !config.user_agent.trim().is_empty() {
+            client_builder = client_builder.user_agent(config.user_agent.clone());
+        }
+        match config.http_version {
+            HttpVersion::Http1 => client_builder = client_builder.http1_only(),
+            HttpVersion::Http2PriorKnowledge => {
+                client_builder = client_builder.http2_prior_knowledge()
+            }
+            HttpVersion::Auto | HttpVersion::Http2 => {}
+        }
+        if !config.local_address.trim().is_empty() {
+            let local_address = config.local_address.trim().parse::<IpAddr>().map_err(|e| {
+                let mut args = FluentArgs::new();
+                args.set("address", config.local_address.clone());
+                args.set("error", e.to_string());
+                tr_args("invalid-source-address", &args)
+            })?;
+            client_builder = client_builder.local_address(local_address);
+        }
+        if !config.interface.trim().is_empty() {
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            {
+                client_builder = client_builder.interface(config.interface.trim());
+            }
+            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+            return Err(tr("interface-binding-unsupported"));
+        }
+        if let Some(version) = config.min_tls_version.min_version() {
+            client_builder = client_builder.min_tls_version(version);
+        }
+        if let Some(version) = config.max_tls_version.max_version() {
+            client_builder = client_builder.max_tls_version(version);
+        }
+        for ca_certificate in &config.ca_certificates {
+            let certificates = reqwest::Certificate::from_pem_bundle(ca_certificate.pem.as_bytes())
+                .map_err(|e| {
+                    let mut args = FluentArgs::new();
+                    args.set("name", ca_certificate.name.clone());
+                    args.set("error", e.to_string());
+                    tr_args("invalid-ca-certificate", &args)
+                })?;
+            for certificate in certificates {
+                client_builder = client_builder.add_root_certificate(certificate);
+            }
+        }
+        if let Some(proxy_settings) = &config.proxy {
+            let proxy = Self::build_proxy(proxy_settings)
+                .map_err(|e| tr_error("invalid-proxy-configuration", e))?;
+            client_builder = client_builder.proxy(proxy);
+        } else if let Some(system_proxy) = &config.system_proxy {
+            let no_proxy = reqwest::NoProxy::from_string(&system_proxy.no_proxy);
+            let invalid = |e: reqwest::Error| tr_error("invalid-system-proxy", e);
+            if let Some(url) = &system_proxy.http {
+                let proxy = reqwest::Proxy::http(url).map_err(invalid)?;
+                client_builder = client_builder.proxy(proxy.no_proxy(no_proxy.clone()));
+            }
+            if let Some(url) = &system_proxy.https {
+                let proxy = reqwest::Proxy::https(url).map_err(invalid)?;
+                client_builder = client_builder.proxy(proxy.no_proxy(no_proxy));
+            }
+        } else {
+            // Detection already covered the environment and OS settings reqwest would read
+            client_builder = client_builder.no_proxy();
+        }
+        if let Some(certificate) = identity {
+            let identity = certificate.identity().map_err(|e| {
+                let mut args = FluentArgs::new();
+                args.set("name", certificate.name.clone());
+                args.set("error", e);
+                tr_args("invalid-client-certificate", &args)
+            })?;
+            client_builder = client_builder.identity(identity);
+        }
+        client_builder
+            .build()
+            .map_err(|e| tr_error("build-client-failed", e))
+    }
+
+    // Run `f` as if `request` were open in the workspace at `index`, so variables,
+    // client settings and pins come from there rather than from what is on screen
+    fn as_open<T>(
+        &mut self,
+        index: usize,
+        request: &HttpRequest,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let workspace = std::mem::replace(&mut self.current_workspace, index);
+        let open = std::mem::replace(&mut self.current_request, request.clone());
+        let result = f(self);
+        self.current_request = open;
+        self.current_workspace = workspace;
+        result
+    }
+
+    // Reuse the workspace's pooled clients for each set of settings that affect them, keeping
+    // the few used last
+    fn shared_client(&mut self, request: &HttpRequest) -> Result<HttpClients, String> {
+        let config = self.client_config(request);
+        let cached = self.http_clients.entry(self.current_workspace).or_default();
+        if let Some(index) = cached.iter().position(|cached| cached.config == config) {
+            let entry = cached.remove(index);
+            let clients = entry.clients.clone();
+            cached.push(entry);
+            return Ok(clients);
+        }
+        let _guard = self.runtime.enter();
+        let mut build_config = config.clone();
+        let mut relay = None;
+        if let Some(proxy) = &config.proxy
+            && proxy.auth == ProxyAuth::Ntlm
+            && !proxy.username.is_empty()
+        {
+            let started = match cached
+                .iter()
+                .filter_map(|cached| cached.relay.as_ref())
+                .find(|relay| relay.settings == *proxy)
+            {
+                Some(relay) => relay.clone(),
+                None => Arc::new(NtlmProxyRelay::start(proxy)?),
+            };
+            build_config.proxy = Some(started.proxy_settings(proxy));
+            relay = Some(started);
+        }
+        let clients = Self::build_clients(&build_config)?;
+        cached.push(CachedClients {
+            config,
+            clients: clients.clone(),
+            relay,
+        });
+        if cached.len() > CACHED_CLIENTS_PER_WORKSPACE {
+            cached.remove(0);
+        }
+        Ok(clients)
+    }
+
+    // Clients sharing nothing with the pooled ones, for timing a cold request
+    fn fresh_client(&mut self, request: &HttpRequest) -> Result<HttpClients, String> {
+        // Starts the NTLM relay if the proxy needs one; the relay keeps no connections
+        self.shared_client(request)?;
+        let cached = self
+            .http_clients
+            .get(&self.current_workspace)
+            .and_then(|cached| cached.last())
+            .ok_or("No client for the workspace")?;
+        let mut config = cached.config.clone();
+        if let (Some(relay), Some(proxy)) = (&cached.relay, &config.proxy) {
+            config.proxy = Some(relay.proxy_settings(proxy));
+        }
+        let _guard = self.runtime.enter();
+        Self::build_clients(&config)
+    }
+
+    fn is_repeatable_method(method: &str) -> bool {
+        method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD")
+    }
+
+    // Fetch the rest of a truncated response with a Range request, falling back
+    // to the full body if the server ignores the range
+    fn download_remainder(&mut self) {
+        let Some(response) = &self.current_response else {
+            return;
+        };
+        let Some(prefix) = response.truncated_bytes.clone() else {
+            return;
+        };
+        let header = |name: &str| {
+            response
+                .headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim().to_string())
+        };
+        let resumable = header("accept-ranges").is_some_and(|value| value == "bytes");
+        let validator = header("etag").or_else(|| header("last-modified"));
+        let Some((request, clients)) = self
+            .last_resolved_request
+            .clone()
+            .filter(|request| Self::is_repeatable_method(&request.method))
+            .zip(self.last_request_clients.clone())
+        else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("download-response-title"))
+            .save_file()
+        else {
+            return;
+        };
+
+        // Continue a partial file left by an earlier attempt at the same download,
+        // otherwise seed it with the bytes already received
+        let (part, meta) = PartialDownload::paths(&path);
+        let partial = PartialDownload {
+            url: request.url.clone(),
+            validator,
+        };
+        let existing = std::fs::metadata(&part).map_or(0, |metadata| metadata.len());
+        let continue_partial = resumable
+            && PartialDownload::load(&path).as_ref() == Some(&partial)
+            && existing >= prefix.len() as u64;
+        if !continue_partial {
+            let written = std::fs::write(&part, prefix.as_slice()).and_then(|_| {
+                std::fs::write(&meta, serde_json::to_string(&partial).unwrap_or_default())
+            });
+            if let Err(e) = written {
+                let (_, receiver) = mpsc::channel();
+                self.download = Some(DownloadState {
+                    path,
+                    receiver,
+                    downloaded: 0,
+                    total: None,
+                    result: Some(Err(tr_error("write-file-failed", e))),
+                    request,
+                    clients,
+                    resumable,
+                    pause: Arc::new(AtomicBool::new(false)),
+                    paused: false,
+                });
+                return;
             }
+        }
+        self.start_download(path, request, clients, resumable);
+    }
+
+    // Copies the temporary file of a large response, reporting through the download status
+    fn save_spooled_body(&mut self) {
+        let Some(spooled) = self
+            .current_response
+            .as_ref()
+            .and_then(|response| response.spooled.clone())
+        else {
+            return;
+        };
+        let Some((request, clients)) = self
+            .last_resolved_request
+            .clone()
+            .zip(self.last_request_clients.clone())
+        else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("save-response-body-title"))
+            .save_file()
+        else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        self.download = Some(DownloadState {
+            path: path.clone(),
+            receiver: rx,
+            downloaded: 0,
+            total: Some(spooled.size),
+            result: None,
+            request,
+            clients,
+            resumable: false,
+            pause: Arc::new(AtomicBool::new(false)),
+            paused: false,
+        });
+        self.runtime.spawn(async move {
+            let result = tokio::fs::copy(&spooled.path, &path)
+                .await
+                .map_err(|e| tr_error("write-file-failed", e));
+            let _ = tx.send(DownloadEvent::Finished(result));
         });
     }
 
-    fn draw_url_encoded_panel(&mut self, ui: &mut Ui) {
-        ScrollArea::vertical().show(ui, |ui| {
-            let mut to_remove = Vec::new();
-            let mut url_encoded_changed = false;
+    fn resume_download(&mut self) {
+        if let Some(download) = self.download.take() {
+            self.start_download(
+                download.path,
+                download.request,
+                download.clients,
+                download.resumable,
+            );
+        }
+    }
 
-            for (i, (key, value)) in self.current_request.url_encoded_data.iter_mut().enumerate() {
-                ui.horizontal(|ui| {
-                    let key_response = ui.add(
-                        TextEdit::singleline(key)
-                            .hint_text("Key")
-                            .desired_width(200.0),
-                    );
-                    let value_response = ui.add(
-                        TextEdit::singleline(value)
-                            .hint_text("Value")
-                            .desired_width(250.0),
-                    );
+    // Fetches the rest of the response into "<file>.part" with a Range request and moves it
+    // into place once complete
+    fn start_download(
+        &mut self,
+        path: std::path::PathBuf,
+        request: ResolvedRequest,
+        clients: HttpClients,
+        resumable: bool,
+    ) {
+        let (tx, rx) = mpsc::channel();
+        let pause = Arc::new(AtomicBool::new(false));
+        if self.settings.offline_mode {
+            self.download = Some(DownloadState {
+                path,
+                receiver: rx,
+                downloaded: 0,
+                total: None,
+                result: Some(Err(tr("offline-mode-is-on"))),
+                request,
+                clients,
+                resumable,
+                pause,
+                paused: false,
+            });
+            return;
+        }
+        let client = clients.clone();
+        self.download = Some(DownloadState {
+            path: path.clone(),
+            receiver: rx,
+            downloaded: 0,
+            total: None,
+            result: None,
+            request: request.clone(),
+            clients,
+            resumable,
+            pause: pause.clone(),
+            paused: false,
+        });
+
+        let progress_tx = tx.clone();
+        self.runtime.spawn(async move {
+            let result: Result<Option<u64>, String> = async {
+                let (part, meta) = PartialDownload::paths(&path);
+                let offset = tokio::fs::metadata(&part)
+                    .await
+                    .map_or(0, |metadata| metadata.len());
+                let mut request = request.clone();
+                request
+                    .headers
+                    .push(("Range".to_string(), format!("bytes={}-", offset)));
+                if let Some(validator) = PartialDownload::load(&path).and_then(|p| p.validator) {
+                    request.headers.push(("If-Range".to_string(), validator));
+                }
+                let mut response = Self::send_resolved(&client, &request)
+                    .await
+                    .map_err(|e| tr_error("request-failed", e))?;
+                let status = response.status();
+                if !status.is_success() {
+                    let mut args = FluentArgs::new();
+                    args.set("status", status.to_string());
+                    return Err(tr_args("server-responded-with", &args));
+                }
 
-                    if key_response.changed() || value_response.changed() {
-                        url_encoded_changed = true;
-                    }
+                // A full response means the server ignored the range, so start over
+                let mut downloaded = 0u64;
+                let mut total = response.content_length();
+                let mut file = if status == reqwest::StatusCode::PARTIAL_CONTENT {
+                    downloaded = offset;
+                    total = total.map(|length| length + offset);
+                    tokio::fs::OpenOptions::new().append(true).open(&part).await
+                } else {
+                    tokio::fs::File::create(&part).await
+                }
+                .map_err(|e| tr_error("open-file-failed", e))?;
+                let _ = progress_tx.send(DownloadEvent::Progress { downloaded, total });
 
-                    if ui.button("🗑").clicked() {
-                        to_remove.push(i);
+                while let Some(chunk) = response
+                    .chunk()
+                    .await
+                    .map_err(|e| tr_error("download-interrupted-error", e))?
+                {
+                    file.write_all(&chunk)
+                        .await
+                        .map_err(|e| tr_error("write-file-failed", e))?;
+                    downloaded += chunk.len() as u64;
+                    let _ = progress_tx.send(DownloadEvent::Progress { downloaded, total });
+                    if pause.load(Ordering::Relaxed) {
+                        file.flush()
+                            .await
+                            .map_err(|e| tr_error("write-file-failed", e))?;
+                        return Ok(None);
                     }
-                });
-            }
-
-            // Remove entries
-            if !to_remove.is_empty() {
-                for &i in to_remove.iter().rev() {
-                    self.current_request.url_encoded_data.remove(i);
                 }
-                url_encoded_changed = true;
+                file.flush()
+                    .await
+                    .map_err(|e| tr_error("write-file-failed", e))?;
+                drop(file);
+                tokio::fs::rename(&part, &path)
+                    .await
+                    .map_err(|e| tr_error("move-file-failed", e))?;
+                let _ = tokio::fs::remove_file(&meta).await;
+                Ok(Some(downloaded))
             }
+            .await;
 
-            // Add new entry button
-            if ui.button("Add Parameter").clicked() {
-                self.current_request
-                    .url_encoded_data
-                    .push((String::new(), String::new()));
-                url_encoded_changed = true;
-            }
-
-            if url_encoded_changed {
-                self.save_current_request();
-            }
+            let _ = tx.send(match result {
+                Ok(Some(size)) => DownloadEvent::Finished(Ok(size)),
+                Ok(None) => DownloadEvent::Paused,
+                Err(e) => DownloadEvent::Finished(Err(e)),
+            });
         });
     }
 
-    fn draw_query_params_panel(&mut self, ui: &mut Ui) {
-        ScrollArea::vertical().show(ui, |ui| {
-            let mut to_remove = Vec::new();
-            let mut query_params_changed = false;
-
-            // Table header
-            ui.horizontal(|ui| {
-                ui.label("Parameter Name");
-                ui.add_space(150.0);
-                ui.label("Parameter Value");
-            });
-            ui.separator();
-
-            for (i, (key, value)) in self.current_request.query_params.iter_mut().enumerate() {
+    fn draw_download_status(
+        ui: &mut Ui,
+        download: &DownloadState,
+        pause_clicked: &mut bool,
+        resume_clicked: &mut bool,
+    ) {
+        let file_name = download
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        match &download.result {
+            None => {
+                let progress = download
+                    .total
+                    .filter(|total| *total > 0)
+                    .map(|total| download.downloaded as f32 / total as f32);
+                let text = match download.total {
+                    Some(total) => format!(
+                        "{} / {}",
+                        Self::format_size(download.downloaded as usize),
+                        Self::format_size(total as usize)
+                    ),
+                    None => Self::format_size(download.downloaded as usize),
+                };
                 ui.horizontal(|ui| {
-                    let key_response = ui.add(
-                        TextEdit::singleline(key)
-                            .hint_text("Parameter name")
-                            .desired_width(200.0),
-                    );
-                    let value_response = ui.add(
-                        TextEdit::singleline(value)
-                            .hint_text("Parameter value (supports {{variable}})")
-                            .desired_width(300.0),
-                    );
-
-                    if key_response.changed() || value_response.changed() {
-                        query_params_changed = true;
+                    let mut args = FluentArgs::new();
+                    args.set("file", file_name.to_string());
+                    if download.paused {
+                        ui.label(tr_args("download-paused", &args));
+                    } else {
+                        ui.label(tr_args("download-downloading", &args));
                     }
-
-                    if ui.button("🗑").clicked() {
-                        to_remove.push(i);
+                    match progress {
+                        Some(progress) => {
+                            ui.add(egui::ProgressBar::new(progress).text(text));
+                        }
+                        None => {
+                            if !download.paused {
+                                ui.spinner();
+                            }
+                            ui.label(text);
+                        }
+                    }
+                    if download.paused {
+                        if ui.button(tr("resume")).clicked() {
+                            *resume_clicked = true;
+                        }
+                    } else if download.resumable {
+                        let pausing = download.pause.load(Ordering::Relaxed);
+                        if ui
+                            .add_enabled(!pausing, egui::Button::new(tr("pause")))
+                            .clicked()
+                        {
+                            *pause_clicked = true;
+                        }
                     }
                 });
             }
-
-            // Remove entries
-            if !to_remove.is_empty() {
-                for &i in to_remove.iter().rev() {
-                    self.current_request.query_params.remove(i);
-                }
-                query_params_changed = true;
+            Some(Ok(size)) => {
+                let mut args = FluentArgs::new();
+                args.set("size", Self::format_size(*size as usize));
+                args.set("path", download.path.display().to_string());
+                ui.colored_label(
+                    Color32::from_rgb(0, 128, 0),
+                    tr_args("download-saved", &args),
+                );
             }
-
-            // Add new entry button
-            if ui.button("Add Query Parameter").clicked() {
-                self.current_request
-                    .query_params
-                    .push((String::new(), String::new()));
-                query_params_changed = true;
+            Some(Err(e)) => {
+                ui.horizontal(|ui| {
+                    let mut args = FluentArgs::new();
+                    args.set("error", e.clone());
+                    ui.colored_label(
+                        Color32::from_rgb(255, 0, 0),
+                        tr_args("download-failed", &args),
+                    );
+                    // The partial file is kept, so a dropped connection can pick up where it stopped
+                    if download.resumable && ui.button(tr("resume")).clicked() {
+                        *resume_clicked = true;
+                    }
+                });
             }
+        }
+    }
 
-            if query_params_changed {
-                self.save_current_request();
-            }
-        });
+    // Retry-After holds either a number of seconds or an HTTP date
+    fn parse_retry_after(value: &str) -> Option<Duration> {
+        let value = value.trim();
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        Some(
+            (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+                .to_std()
+                .unwrap_or_default(),
+        )
     }
 
-    fn draw_response_panel(&mut self, ui: &mut Ui) {
-        ui.horizontal(|ui| {
-            ui.heading("Response");
-            if self.is_loading {
-                ui.spinner();
-            }
-        });
-        ui.separator();
+    fn send_request(&mut self) {
+        self.send_request_as(self.current_request.clone(), false);
+    }
 
-        // Response tabs first to avoid borrowing issues
-        let mut response_tab_changed = false;
-        if self.current_response.is_some() {
-            ui.horizontal(|ui| {
-                if ui
-                    .selectable_value(&mut self.response_tab, ResponseTab::Body, "Body")
-                    .changed()
-                {
-                    response_tab_changed = true;
-                }
-                if ui
-                    .selectable_value(&mut self.response_tab, ResponseTab::Headers, "Headers")
-                    .changed()
-                {
-                    response_tab_changed = true;
-                }
-                if ui
-                    .selectable_value(&mut self.response_tab, ResponseTab::Cookies, "Cookies")
-                    .changed()
-                {
-                    response_tab_changed = true;
-                }
-            });
-            ui.separator();
+    // Sends the current request and writes a successful body to a file picked once the
+    // headers are in, so the Content-Disposition name can be offered
+    fn send_and_download(&mut self) {
+        if self
+            .download
+            .as_ref()
+            .is_some_and(|download| download.result.is_none())
+        {
+            self.notify(ToastKind::Error, tr("download-in-progress"));
+            return;
         }
+        self.send_request_as(self.current_request.clone(), true);
+    }
 
-        if let Some(response) = &self.current_response {
-            // Status and time
-            ui.horizontal(|ui| {
-                let status_color = if response.status >= 200 && response.status < 300 {
-                    Color32::from_rgb(0, 128, 0)
-                } else if response.status >= 400 {
-                    Color32::from_rgb(255, 0, 0)
-                } else {
-                    Color32::from_rgb(255, 165, 0)
-                };
-                ui.label(
-                    RichText::new(format!(
-                        "Status: {} {}",
-                        response.status, response.status_text
-                    ))
-                    .color(status_color),
-                );
-                ui.label(format!("Time: {}ms", response.time));
-                ui.label(format!(
-                    "Size: {}",
-                    Self::format_size(response.body_size + response.headers_size)
-                ));
-                ui.label(format!("Body: {}", Self::format_size(response.body_size)));
-                ui.label(format!(
-                    "Headers: {}",
-                    Self::format_size(response.headers_size)
-                ));
-            });
-            ui.separator();
-
-            // Response content
-            ScrollArea::vertical().show(ui, |ui| match self.response_tab {
-                ResponseTab::Body => {
-                    let mut body_text = response.body.clone();
-                    ui.add(
-                        TextEdit::multiline(&mut body_text)
-                            .desired_rows(15)
-                            .desired_width(ui.available_width())
-                            .interactive(false),
-                    );
-                }
-                ResponseTab::Headers => {
-                    for (key, value) in &response.headers {
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new(key).strong());
-                            ui.label(value);
-                        });
+    // Content-Disposition filename (RFC 6266, filename* first), else the last URL segment
+    fn download_file_name(content_disposition: Option<&str>, url: &reqwest::Url) -> String {
+        let mut name = None;
+        for param in content_disposition.unwrap_or_default().split(';') {
+            let Some((key, value)) = param.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "filename*" => {
+                    let encoded = value.splitn(3, '\'').nth(2).unwrap_or(value);
+                    if let Ok(decoded) = urlencoding::decode(encoded) {
+                        name = Some(decoded.into_owned());
+                        break;
                     }
                 }
-                ResponseTab::Cookies => {
-                    ui.label("Cookie support coming soon...");
+                "filename" if name.is_none() => {
+                    name = Some(value.trim_matches('"').to_string());
                 }
-            });
-        } else {
-            ui.centered_and_justified(|ui| {
-                ui.label("No response yet. Send a request to see the response here.");
-            });
-        }
-
-        if response_tab_changed {
-            self.save_cache();
+                _ => {}
+            }
         }
+        let name = name.or_else(|| {
+            url.path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| {
+                    urlencoding::decode(segment)
+                        .map(|decoded| decoded.into_owned())
+                        .unwrap_or_else(|_| segment.to_string())
+                })
+        });
+        // Never let the server pick a directory
+        name.as_deref()
+            .and_then(|name| name.rsplit(['/', '\\']).next())
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty() && name != "." && name != "..")
+            .unwrap_or_else(|| "download".to_string())
     }
 
-    fn draw_dialogs(&mut self, ctx: &egui::Context) {
-        // New Collection Dialog
-        if self.new_collection_dialog {
-            egui::Window::new("New Collection")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.label("Collection Name:");
-                    ui.text_edit_singleline(&mut self.new_collection_name);
-                    ui.horizontal(|ui| {
-                        if ui.button("Create").clicked() {
-                            if !self.new_collection_name.trim().is_empty() {
-                                let collection_name = self.new_collection_name.clone();
-                                self.current_workspace_mut().collections.push(Collection {
-                                    id: Uuid::new_v4().to_string(),
-                                    name: collection_name,
-                                    root_folder: Folder {
-                                        id: Uuid::new_v4().to_string(),
-                                        name: "Root".to_string(),
-                                        requests: vec![],
-                                        folders: vec![],
-                                    },
-                                });
-                                self.new_collection_name.clear();
-                                self.new_collection_dialog = false;
-                                self.auto_save_workspace();
-                            }
-                        }
-                        if ui.button("Cancel").clicked() {
-                            self.new_collection_name.clear();
-                            self.new_collection_dialog = false;
-                        }
-                    });
-                });
+    fn send_request_as(&mut self, request: HttpRequest, download: bool) {
+        self.is_loading = true;
+        self.current_response = None;
+        self.request_error = None;
+        if self
+            .download
+            .as_ref()
+            .is_some_and(|download| download.result.is_some())
+        {
+            self.download = None;
+        }
+        self.pending_request = Some(request.clone());
+        if let Some(token) = self.effective_token(&request)
+            && token.is_expired()
+        {
+            let mut args = FluentArgs::new();
+            args.set("name", token.name.clone());
+            self.notify(ToastKind::Error, tr_args("token-expired-toast", &args));
         }
+        let skip_tls_verification = self.effective_skip_tls_verification(&request);
+        let retry_policy = self.effective_retry_policy(&request);
+        let max_body_size = self.settings.max_response_size_mb as usize * 1024 * 1024;
+        let spool_large_responses = self.settings.spool_large_responses;
+        let (tx, rx) = mpsc::channel();
+        self.response_receiver = Some(rx);
+        let (wait_tx, wait_rx) = mpsc::channel();
+        self.retry_wait_receiver = Some(wait_rx);
+        self.retry_wait = None;
+        let (progress_tx, progress_rx) = mpsc::channel();
+        self.response_progress_receiver = Some(progress_rx);
+        self.response_progress = None;
 
-        // New Request Dialog
-        if self.new_request_dialog {
-            egui::Window::new("New Request")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.label("Request Name:");
-                    ui.text_edit_singleline(&mut self.new_request_name);
-                    ui.horizontal(|ui| {
-                        if ui.button("Create").clicked() {
-                            if !self.new_request_name.trim().is_empty() {
-                                let request_name = self.new_request_name.clone();
-                                let current_request = self.current_request.clone();
-                                let current_workspace_idx = self.current_workspace;
-                                let collection_idx =
-                                    self.workspaces[current_workspace_idx].selected_collection;
-                                let folder_path = self.workspaces[current_workspace_idx]
-                                    .selected_folder_path
-                                    .clone();
-
-                                if let Some(collection_idx) = collection_idx {
-                                    if collection_idx
-                                        < self.workspaces[current_workspace_idx].collections.len()
-                                    {
-                                        if let Some(folder) = Self::get_folder_by_path_mut(
-                                            &mut self.workspaces[current_workspace_idx].collections
-                                                [collection_idx],
-                                            &folder_path,
-                                        ) {
-                                            let mut new_request = current_request;
-                                            new_request.id = Uuid::new_v4().to_string();
-                                            new_request.name = request_name;
-                                            folder.requests.push(new_request);
-                                            self.new_request_name.clear();
-                                            self.new_request_dialog = false;
-                                            self.auto_save_workspace();
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        if ui.button("Cancel").clicked() {
-                            self.new_request_name.clear();
-                            self.new_request_dialog = false;
-                        }
-                    });
-                });
+        if self.settings.offline_mode {
+            self.last_resolved_request = None;
+            self.last_request_clients = None;
+            let _ = tx.send(match &request.example {
+                Some(example) => Ok(example.to_response()),
+                None => Err(RequestError::new(
+                    RequestErrorKind::Offline,
+                    tr("offline-no-example"),
+                )),
+            });
+            return;
         }
 
-        // New Workspace Dialog
-        if self.new_workspace_dialog {
-            egui::Window::new("New Workspace")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.label("Workspace Name:");
-                    ui.text_edit_singleline(&mut self.new_workspace_name);
-                    ui.horizontal(|ui| {
-                        if ui.button("Create").clicked() {
-                            if !self.new_workspace_name.trim().is_empty() {
-                                let new_workspace = Workspace {
-                                    name: self.new_workspace_name.clone(),
-                                    file_path: None,
-                                    collections: vec![Collection {
-                                        id: Uuid::new_v4().to_string(),
-                                        name: "Default Collection".to_string(),
-                                        root_folder: Folder {
-                                            id: Uuid::new_v4().to_string(),
-                                            name: "Root".to_string(),
-                                            requests: vec![],
-                                            folders: vec![],
-                                        },
-                                    }],
-                                    environments: vec![Environment {
-                                        name: "Default".to_string(),
-                                        variables: vec![],
-                                    }],
-                                    selected_collection: Some(0),
-                                    selected_folder_path: vec![],
-                                    selected_request: None,
-                                    selected_environment: Some(0),
-                                };
-                                self.workspaces.push(new_workspace);
-                                self.current_workspace = self.workspaces.len() - 1;
-                                self.new_workspace_name.clear();
-                                self.new_workspace_dialog = false;
-                                self.save_cache();
-                            }
-                        }
-                        if ui.button("Cancel").clicked() {
-                            self.new_workspace_name.clear();
-                            self.new_workspace_dialog = false;
-                        }
+        let client = if request.settings.fresh_connection {
+            self.fresh_client(&request)
+        } else {
+            self.shared_client(&request)
+        };
+        let client = match client {
+            Ok(client) => client,
+            Err(e) => {
+                let _ = tx.send(Err(RequestError::new(RequestErrorKind::Client, e)));
+                return;
+            }
+        };
+        let resolved = self.resolve_request(&request);
+        self.last_resolved_request = Some(resolved.clone());
+        self.last_request_clients = Some(client.clone());
+        self.upload_progress = Some(resolved.upload.clone());
+        let pins = self.current_workspace().settings.certificate_pins.clone();
+        let download_tx = download.then(|| {
+            let (download_tx, download_rx) = mpsc::channel();
+            self.download = Some(DownloadState {
+                path: std::path::PathBuf::new(),
+                receiver: download_rx,
+                downloaded: 0,
+                total: None,
+                result: None,
+                request: resolved.clone(),
+                clients: client.clone(),
+                resumable: false,
+                pause: Arc::new(AtomicBool::new(false)),
+                paused: false,
+            });
+            download_tx
+        });
+
+        let task = self.runtime.spawn(async move {
+            let mut attempts = Vec::new();
+            let mut attempt = 0;
+            let result = loop {
+                let start_time = Instant::now();
+                let send_result = Self::send_resolved(&client, &resolved).await;
+                if let Ok(response) = &send_result
+                    && let Err(message) = CertificatePin::check(response, &pins)
+                {
+                    let mut error = RequestError::new(RequestErrorKind::CertificatePin, message);
+                    error.attempts = attempt + 1;
+                    break Err(error);
+                }
+                if let Some(delay) = retry_policy.retry_delay(attempt, &send_result) {
+                    let _ = wait_tx.send(retry_policy.wait(attempt, delay, &send_result));
+                    attempts.push(match &send_result {
+                        Ok(response) => AttemptRecord {
+                            status: response.status().as_u16(),
+                            time: start_time.elapsed().as_millis(),
+                            error: None,
+                        },
+                        Err(e) => AttemptRecord {
+                            status: 0,
+                            time: start_time.elapsed().as_millis(),
+                            error: Some(e.to_string()),
+                        },
                     });
-                });
-        }
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
 
-        // New Environment Dialog
-        if self.new_environment_dialog {
-            egui::Window::new("New Environment")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.label("Environment Name:");
-                    ui.text_edit_singleline(&mut self.new_environment_name);
-                    ui.horizontal(|ui| {
-                        if ui.button("Create").clicked() {
-                            if !self.new_environment_name.trim().is_empty() {
-                                let new_environment = Environment {
-                                    name: self.new_environment_name.clone(),
-                                    variables: vec![],
-                                };
-                                self.current_workspace_mut()
-                                    .environments
-                                    .push(new_environment);
-                                // Set the new environment as selected
-                                let new_env_index = self.current_workspace().environments.len() - 1;
-                                self.current_workspace_mut().selected_environment =
-                                    Some(new_env_index);
-                                self.new_environment_name.clear();
-                                self.new_environment_dialog = false;
-                                self.auto_save_workspace();
+                break match send_result {
+                    Ok(response) => {
+                        let headers_time = start_time.elapsed();
+                        let remote_addr = response.remote_addr().map(|addr| addr.ip().to_string());
+                        let version = HttpVersion::negotiated(response.version());
+                        let connection = response.extensions().get::<ConnectionInfo>().copied();
+                        let connection_reused = connection.map(|connection| connection.reused);
+                        let secure = response.url().scheme() == "https";
+                        let status = response.status().as_u16();
+                        let status_text = response
+                            .status()
+                            .canonical_reason()
+                            .unwrap_or("Unknown")
+                            .to_string();
+                        let redirects = response
+                            .extensions()
+                            .get::<RedirectChain>()
+                            .cloned()
+                            .unwrap_or_default();
+                        let mut headers = HashMap::new();
+                        let mut headers_size = 0;
+                        let mut set_cookies = Vec::new();
+                        for (key, value) in response.headers() {
+                            let key_str = key.to_string();
+                            let value_str = value.to_str().unwrap_or("").to_string();
+                            headers_size += key_str.len() + value_str.len() + 4; // +4 for ": " and "\r\n"
+                            if key == reqwest::header::SET_COOKIE {
+                                set_cookies.push(value_str.clone());
                             }
+                            headers.insert(key_str, value_str);
                         }
-                        if ui.button("Cancel").clicked() {
-                            self.new_environment_name.clear();
-                            self.new_environment_dialog = false;
+                        let mut response = response;
+                        let content_length = response.content_length();
+                        let mut body_bytes = Vec::new();
+                        let mut truncated = false;
+                        let mut read_error = None;
+                        let mut spool: Option<ResponseSpool> = None;
+                        let mut spool_error = None;
+                        let mut download_file = None;
+                        if let Some(download_tx) = &download_tx
+                            && response.status().is_success()
+                        {
+                            let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+                            let _ = download_tx.send(DownloadEvent::ChooseFile {
+                                suggested: Self::download_file_name(
+                                    response
+                                        .headers()
+                                        .get(reqwest::header::CONTENT_DISPOSITION)
+                                        .and_then(|value| value.to_str().ok()),
+                                    response.url(),
+                                ),
+                                reply: reply_tx,
+                            });
+                            if let Ok(Some(path)) = reply_rx.await {
+                                match tokio::fs::File::create(&path).await {
+                                    Ok(file) => download_file = Some(file),
+                                    Err(e) => {
+                                        let _ = download_tx.send(DownloadEvent::Finished(Err(
+                                            tr_error("open-file-failed", e),
+                                        )));
+                                    }
+                                }
+                            }
                         }
-                    });
-                });
-        }
-
-        // New Folder Dialog
-        if self.new_folder_dialog {
-            egui::Window::new("New Folder")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.label("Folder Name:");
-                    ui.text_edit_singleline(&mut self.new_folder_name);
-                    ui.horizontal(|ui| {
-                        if ui.button("Create").clicked() {
-                            if !self.new_folder_name.trim().is_empty() {
-                                let folder_name = self.new_folder_name.clone();
-                                let current_workspace_idx = self.current_workspace;
-                                let collection_idx =
-                                    self.workspaces[current_workspace_idx].selected_collection;
-                                let folder_path = self.workspaces[current_workspace_idx]
-                                    .selected_folder_path
-                                    .clone();
-
-                                if let Some(collection_idx) = collection_idx {
-                                    if collection_idx
-                                        < self.workspaces[current_workspace_idx].collections.len()
+                        let downloading = download_file.is_some();
+                        let mut downloaded = 0u64;
+                        let mut download_error = None;
+                        loop {
+                            match response.chunk().await {
+                                Ok(Some(chunk)) => {
+                                    if let (Some(file), Some(download_tx)) =
+                                        (&mut download_file, &download_tx)
                                     {
-                                        if let Some(folder) = Self::get_folder_by_path_mut(
-                                            &mut self.workspaces[current_workspace_idx].collections
-                                                [collection_idx],
-                                            &folder_path,
-                                        ) {
-                                            folder.folders.push(Folder {
-                                                id: Uuid::new_v4().to_string(),
-                                                name: folder_name,
-                                                requests: vec![],
-                                                folders: vec![],
-                                            });
-                                            self.new_folder_name.clear();
-                                            self.new_folder_dialog = false;
-                                            self.auto_save_workspace();
+                                        if let Err(e) = file.write_all(&chunk).await {
+                                            download_error = Some(e);
+                                            break;
+                                        }
+                                        downloaded += chunk.len() as u64;
+                                        let _ = download_tx.send(DownloadEvent::Progress {
+                                            downloaded,
+                                            total: content_length,
+                                        });
+                                        continue;
+                                    }
+                                    if let Some(spool) = &mut spool {
+                                        if let Err(e) = spool.write(&chunk).await {
+                                            spool_error = Some(e);
+                                            break;
+                                        }
+                                        let _ = progress_tx.send((spool.body.size, content_length));
+                                        continue;
+                                    }
+                                    if max_body_size > 0
+                                        && body_bytes.len() + chunk.len() > max_body_size
+                                        && spool_large_responses
+                                    {
+                                        body_bytes.extend_from_slice(&chunk);
+                                        match ResponseSpool::create(&body_bytes).await {
+                                            Ok(created) => {
+                                                let _ = progress_tx
+                                                    .send((created.body.size, content_length));
+                                                spool = Some(created);
+                                            }
+                                            Err(e) => {
+                                                spool_error = Some(e);
+                                                break;
+                                            }
                                         }
+                                        body_bytes.truncate(SPOOL_PREVIEW_HEAD);
+                                        continue;
+                                    }
+                                    if max_body_size > 0
+                                        && body_bytes.len() + chunk.len() > max_body_size
+                                    {
+                                        let remaining = max_body_size - body_bytes.len();
+                                        body_bytes.extend_from_slice(&chunk[..remaining]);
+                                        truncated = true;
+                                        break;
                                     }
+                                    body_bytes.extend_from_slice(&chunk);
+                                }
+                                Ok(None) => break,
+                                Err(e) => {
+                                    read_error = Some(e);
+                                    break;
                                 }
                             }
                         }
-                        if ui.button("Cancel").clicked() {
-                            self.new_folder_name.clear();
-                            self.new_folder_dialog = false;
+                        if let (Some(mut file), Some(download_tx)) = (download_file, &download_tx) {
+                            let result = match (download_error, &read_error) {
+                                (Some(e), _) => Err(tr_error("write-file-failed", e)),
+                                (None, Some(e)) => Err(e.to_string()),
+                                (None, None) => file
+                                    .flush()
+                                    .await
+                                    .map(|_| downloaded)
+                                    .map_err(|e| tr_error("write-file-failed", e)),
+                            };
+                            let _ = download_tx.send(DownloadEvent::Finished(result));
                         }
-                    });
-                });
-        }
+                        if let Some(e) = read_error {
+                            break Err(RequestError::from_reqwest(&e, attempt + 1));
+                        }
+                        let spooled = match spool {
+                            Some(spool) if spool_error.is_none() => {
+                                match spool.finish(&body_bytes).await {
+                                    Ok((preview, spooled)) => {
+                                        body_bytes = preview.into_bytes();
+                                        Some(Arc::new(spooled))
+                                    }
+                                    Err(e) => {
+                                        spool_error = Some(e);
+                                        None
+                                    }
+                                }
+                            }
+                            _ => None,
+                        };
+                        if let Some(e) = spool_error {
+                            let mut error = RequestError::new(
+                                RequestErrorKind::Other,
+                                tr_error("spool-file-failed", e),
+                            );
+                            error.attempts = attempt + 1;
+                            break Err(error);
+                        }
+                        let content_encoding = headers
+                            .iter()
+                            .find(|(key, _)| key.eq_ignore_ascii_case("content-encoding"))
+                            .map(|(_, value)| value.clone())
+                            .filter(|value| !value.eq_ignore_ascii_case("identity"));
+                        // A truncated stream can't be decompressed, so it stays as received
+                        let mut encoded_body = None;
+                        if !truncated
+                            && spooled.is_none()
+                            && let Some(encoding) = &content_encoding
+                            && let Some(decoded) =
+                                AcceptEncoding::decode(encoding, &body_bytes, max_body_size)
+                        {
+                            encoded_body =
+                                Some(Arc::new(std::mem::replace(&mut body_bytes, decoded)));
+                        }
+                        let body = String::from_utf8_lossy(&body_bytes).to_string();
+                        let body_size = if downloading {
+                            downloaded as usize
+                        } else {
+                            spooled
+                                .as_ref()
+                                .map_or(body_bytes.len(), |spooled| spooled.size as usize)
+                        };
+                        let truncated_bytes = truncated.then(|| Arc::new(body_bytes));
+                        let timing = ResponseTiming::new(
+                            headers_time,
+                            start_time.elapsed(),
+                            connection.as_ref(),
+                            secure,
+                        );
+
+                        Ok(HttpResponse {
+                            status,
+                            status_text,
+                            headers,
+                            body,
+                            timing,
+                            body_size,
+                            headers_size,
+                            tls_verification_disabled: skip_tls_verification,
+                            attempts: std::mem::take(&mut attempts),
+                            remote_addr,
+                            truncated_bytes,
+                            from_example: false,
+                            set_cookies,
+                            redirects,
+                            version,
+                            connection_reused,
+                            content_encoding,
+                            encoded_body,
+                            spooled,
+                            reformatted_body: None,
+                        })
+                    }
+                    Err(e) => Err(RequestError::from_reqwest(&e, attempt + 1)),
+                };
+            };
+            let result = result.map_err(|error| RequestError {
+                retried: attempts,
+                ..error
+            });
+
+            let _ = tx.send(result);
+        });
+        self.request_task = Some(task.abort_handle());
+        self.request_started = Some(Instant::now());
     }
+}
 
-    fn send_request(&mut self) {
-        self.is_loading = true;
-        self.current_response = None;
-        let request = self.current_request.clone();
-        let (tx, rx) = mpsc::channel();
-        self.response_receiver = Some(rx);
+// Outcome of one request in a headless run
+struct CliResult {
+    collection: String,
+    name: String,
+    time: u128,
+    failure: Option<String>,
+}
 
-        let mut resolved_url = self.resolve_value(&request.url);
+const CLI_USAGE: &str = "Usage: send run <workspace-or-collection.json> [--env NAME] [--data FILE.json|FILE.csv] [--report junit.xml] [--bail]";
+const LAUNCH_USAGE: &str = "Usage: send [workspace.json ...] [--import collection.json] [--env NAME]\n       send run --help";
 
-        // Add query parameters to URL
-        if !request.query_params.is_empty() {
-            let mut params = Vec::new();
-            for (key, value) in &request.query_params {
-                if !key.trim().is_empty() {
-                    let resolved_key = self.resolve_value(key);
-                    let resolved_value = self.resolve_value(value);
-                    params.push(format!(
-                        "{}={}",
-                        urlencoding::encode(&resolved_key),
-                        urlencoding::encode(&resolved_value)
-                    ));
+// Files to open when the app starts, e.g. from a file association or a script
+#[derive(Debug, Default)]
+struct LaunchArgs {
+    workspaces: Vec<std::path::PathBuf>,
+    imports: Vec<std::path::PathBuf>,
+    environment: Option<String>,
+}
+
+impl LaunchArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut launch = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--import" => launch.imports.push(
+                    args.next()
+                        .ok_or("--import needs a collection file")?
+                        .into(),
+                ),
+                "--env" => {
+                    launch.environment = Some(
+                        args.next()
+                            .ok_or("--env needs an environment name")?
+                            .clone(),
+                    )
                 }
-            }
-            if !params.is_empty() {
-                let separator = if resolved_url.contains('?') { "&" } else { "?" };
-                resolved_url = format!("{}{}{}", resolved_url, separator, params.join("&"));
+                _ if !arg.starts_with('-') => launch.workspaces.push(arg.into()),
+                _ => return Err(format!("Unexpected argument '{}'", arg)),
             }
         }
+        Ok(launch)
+    }
+}
 
-        let mut resolved_headers = Vec::new();
-        for (k, v) in &request.headers {
-            resolved_headers.push((k.clone(), self.resolve_value(v)));
+impl SendApp {
+    // Data file rows: a JSON array of objects, or CSV with a header row
+    fn load_data_rows(path: &str) -> Result<Vec<Vec<(String, String)>>, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read data file '{}': {}", path, e))?;
+        if path.to_lowercase().ends_with(".json") {
+            let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+                serde_json::from_str(&content)
+                    .map_err(|e| format!("Invalid data file '{}': {}", path, e))?;
+            return Ok(rows
+                .into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|(key, value)| match value {
+                            serde_json::Value::String(value) => (key, value),
+                            value => (key, value.to_string()),
+                        })
+                        .collect()
+                })
+                .collect());
         }
-        let resolved_body = self.resolve_value(&request.body);
-
-        self.runtime.spawn(async move {
-            let start_time = Instant::now();
-            let method = match request.method.as_str() {
-                "GET" => Method::GET,
-                "POST" => Method::POST,
-                "PUT" => Method::PUT,
-                "DELETE" => Method::DELETE,
-                "PATCH" => Method::PATCH,
-                "HEAD" => Method::HEAD,
-                "OPTIONS" => Method::OPTIONS,
-                _ => Method::GET,
-            };
 
-            let client = reqwest::Client::new();
-            let mut req_builder = client.request(method, &resolved_url);
+        // RFC 4180, so quoted fields may hold commas, quotes and line breaks
+        let invalid = |e: csv::Error| format!("Invalid data file '{}': {}", path, e);
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(content.as_bytes());
+        let header = reader.headers().map_err(invalid)?.clone();
+        reader
+            .records()
+            .map(|record| {
+                let record = record.map_err(invalid)?;
+                Ok(header
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect())
+            })
+            .collect()
+    }
 
-            // Handle body based on type
-            match request.body_type {
-                BodyType::FormData if !request.form_data.is_empty() => {
-                    let mut form = reqwest::multipart::Form::new();
+    fn escape_xml(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
 
-                    for entry in &request.form_data {
-                        match entry {
-                            FormDataEntry::Text { key, value } => {
-                                if !key.trim().is_empty() {
-                                    form = form.text(key.clone(), value.clone());
-                                }
-                            }
-                            FormDataEntry::File {
-                                key,
-                                file_path,
-                                file_name,
-                            } => {
-                                if !key.trim().is_empty() && !file_path.trim().is_empty() {
-                                    match tokio::fs::read(file_path).await {
-                                        Ok(file_data) => {
-                                            let part = reqwest::multipart::Part::bytes(file_data)
-                                                .file_name(file_name.clone());
-                                            form = form.part(key.clone(), part);
-                                        }
-                                        Err(_) => {
-                                            // If file can't be read, skip this entry
-                                            continue;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+    fn write_junit_report(path: &str, results: &[CliResult]) -> std::io::Result<()> {
+        let mut suites: Vec<(&str, Vec<&CliResult>)> = Vec::new();
+        for result in results {
+            match suites
+                .iter_mut()
+                .find(|(name, _)| *name == result.collection)
+            {
+                Some((_, suite)) => suite.push(result),
+                None => suites.push((&result.collection, vec![result])),
+            }
+        }
 
-                    req_builder = req_builder.multipart(form);
+        let failures = results.iter().filter(|result| result.failure.is_some());
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites tests=\"{}\" failures=\"{}\">\n",
+            results.len(),
+            failures.count()
+        );
+        for (name, suite) in suites {
+            let time: u128 = suite.iter().map(|result| result.time).sum();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+                Self::escape_xml(name),
+                suite.len(),
+                suite
+                    .iter()
+                    .filter(|result| result.failure.is_some())
+                    .count(),
+                time as f64 / 1000.0
+            ));
+            for result in suite {
+                xml.push_str(&format!(
+                    "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                    Self::escape_xml(name),
+                    Self::escape_xml(&result.name),
+                    result.time as f64 / 1000.0
+                ));
+                match &result.failure {
+                    Some(failure) => xml.push_str(&format!(
+                        ">\n      <failure message=\"{}\"/>\n    </testcase>\n",
+                        Self::escape_xml(failure)
+                    )),
+                    None => xml.push_str("/>\n"),
                 }
-                BodyType::UrlEncoded if !request.url_encoded_data.is_empty() => {
-                    // Set headers for URL-encoded requests
-                    for (key, value) in &resolved_headers {
-                        if !key.trim().is_empty() && !value.trim().is_empty() {
-                            req_builder = req_builder.header(key, value);
-                        }
-                    }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        std::fs::write(path, xml)
+    }
 
-                    // Create URL-encoded form data
-                    let mut form_params = Vec::new();
-                    for (key, value) in &request.url_encoded_data {
-                        if !key.trim().is_empty() {
-                            form_params.push((key.as_str(), value.as_str()));
+    // `send run ...`: execute collections without the GUI, returning the exit code
+    fn run_headless(args: &[String]) -> i32 {
+        let mut file = None;
+        let mut env_name = None;
+        let mut data_path = None;
+        let mut report_path = None;
+        let mut bail = false;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--env" | "--data" | "--report" => {
+                    let target = match arg.as_str() {
+                        "--env" => &mut env_name,
+                        "--data" => &mut data_path,
+                        _ => &mut report_path,
+                    };
+                    match args.next() {
+                        Some(value) if !value.starts_with("--") => *target = Some(value.clone()),
+                        _ => {
+                            eprintln!("{} needs a value\n{}", arg, CLI_USAGE);
+                            return 2;
                         }
                     }
-
-                    req_builder = req_builder.form(&form_params);
                 }
+                "--bail" => bail = true,
+                "-h" | "--help" => {
+                    println!("{}", CLI_USAGE);
+                    return 0;
+                }
+                _ if file.is_none() && !arg.starts_with('-') => file = Some(arg.clone()),
                 _ => {
-                    // Set headers for other request types
-                    for (key, value) in &resolved_headers {
-                        if !key.trim().is_empty() && !value.trim().is_empty() {
-                            req_builder = req_builder.header(key, value);
-                        }
-                    }
+                    eprintln!("Unexpected argument '{}'\n{}", arg, CLI_USAGE);
+                    return 2;
+                }
+            }
+        }
+        let Some(file) = file else {
+            eprintln!("{}", CLI_USAGE);
+            return 2;
+        };
 
-                    // Set body for non-form requests
-                    if !resolved_body.trim().is_empty() {
-                        req_builder = req_builder.body(resolved_body);
-                    }
+        let content = match std::fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Failed to read '{}': {}", file, e);
+                return 2;
+            }
+        };
+        // Accept a saved workspace or a single exported collection
+        let storage = match serde_json::from_str::<AppStorage>(&content) {
+            Ok(storage) => storage,
+            Err(_) => match serde_json::from_str::<Collection>(&content) {
+                Ok(collection) => AppStorage {
+                    collections: vec![collection],
+                    environments: vec![],
+                    settings: WorkspaceSettings::default(),
+                },
+                Err(e) => {
+                    eprintln!("'{}' is not a workspace or collection: {}", file, e);
+                    return 2;
+                }
+            },
+        };
+
+        let mut environment = match &env_name {
+            Some(name) => match storage.environments.iter().find(|env| &env.name == name) {
+                Some(environment) => environment.clone(),
+                None => {
+                    eprintln!("Environment '{}' not found in '{}'", name, file);
+                    return 2;
+                }
+            },
+            None => Environment {
+                name: "CLI".to_string(),
+                variables: vec![],
+                secret_keys: vec![],
+            },
+        };
+        let base_variables = environment.variables.clone();
+        let rows = match &data_path {
+            Some(path) => match Self::load_data_rows(path) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return 2;
+                }
+            },
+            None => vec![vec![]],
+        };
+
+        // Only what the file says applies, not the preferences or state of the GUI user
+        let mut app = SendApp::with_settings(AppSettings::default());
+        environment.variables.clear();
+        app.workspaces = vec![Workspace {
+            name: "CLI".to_string(),
+            file_path: None,
+            collections: storage.collections,
+            environments: vec![environment],
+            selected_collection: None,
+            selected_folder_path: vec![],
+            selected_request: None,
+            selected_environment: Some(0),
+            settings: storage.settings,
+            active_session: None,
+        }];
+        app.current_workspace = 0;
+
+        let mut results = Vec::new();
+        'rows: for (iteration, row) in rows.iter().enumerate() {
+            // Data file values take precedence over the environment
+            let mut variables = base_variables.clone();
+            for (key, value) in row {
+                match variables.iter_mut().find(|(existing, _)| existing == key) {
+                    Some((_, existing)) => *existing = value.clone(),
+                    None => variables.push((key.clone(), value.clone())),
                 }
             }
+            app.workspaces[0].environments[0].variables = variables;
+            if rows.len() > 1 {
+                println!("Iteration {}/{}", iteration + 1, rows.len());
+            }
 
-            let result = match req_builder.send().await {
-                Ok(response) => {
-                    let status = response.status().as_u16();
-                    let status_text = response
-                        .status()
-                        .canonical_reason()
-                        .unwrap_or("Unknown")
-                        .to_string();
-                    let mut headers = HashMap::new();
-                    let mut headers_size = 0;
-                    for (key, value) in response.headers() {
-                        let key_str = key.to_string();
-                        let value_str = value.to_str().unwrap_or("").to_string();
-                        headers_size += key_str.len() + value_str.len() + 4; // +4 for ": " and "\r\n"
-                        headers.insert(key_str, value_str);
-                    }
-                    let body = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|e| format!("Error reading body: {}", e));
-                    let body_size = body.len();
+            let collections = app.workspaces[0].collections.clone();
+            let pins = app.workspaces[0].settings.certificate_pins.clone();
+            for collection in &collections {
+                println!("{}", collection.name);
+                let mut requests = Vec::new();
+                Self::collect_folder_requests(&collection.root_folder, &mut requests);
+                for request in requests {
+                    // Each request's own client settings and retry policy apply
+                    let (client, resolved, assertions, retry_policy) =
+                        app.as_open(0, &request, |app| {
+                            (
+                                app.shared_client(&request),
+                                app.resolve_request(&request),
+                                app.resolved_assertions(&request),
+                                app.effective_retry_policy(&request),
+                            )
+                        });
+                    let client = match client {
+                        Ok(client) => client,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            return 2;
+                        }
+                    };
+                    let start_time = Instant::now();
+                    let mut attempt = 0;
+                    let (status, failure) = app.runtime.block_on(async {
+                        let send_result = loop {
+                            let send_result = Self::send_resolved(&client, &resolved).await;
+                            match retry_policy.retry_delay(attempt, &send_result) {
+                                Some(delay) => {
+                                    tokio::time::sleep(delay).await;
+                                    attempt += 1;
+                                }
+                                None => break send_result,
+                            }
+                        };
+                        match send_result {
+                            Ok(response)
+                                if let Err(message) = CertificatePin::check(&response, &pins) =>
+                            {
+                                ("ERR".to_string(), Some(message))
+                            }
+                            Ok(mut response) => {
+                                let status = response.status();
+                                let failures =
+                                    HeaderAssertion::failures(&assertions, response.headers());
+                                while let Ok(Some(_)) = response.chunk().await {}
+                                let failure = if !status.is_success() {
+                                    Some(format!("Expected 2xx, got {}", status.as_u16()))
+                                } else if !failures.is_empty() {
+                                    Some(failures.join("; "))
+                                } else {
+                                    None
+                                };
+                                (status.as_u16().to_string(), failure)
+                            }
+                            Err(e) => ("ERR".to_string(), Some(format!("Request failed: {}", e))),
+                        }
+                    });
+                    if let Some(jar) = &resolved.cookie_jar {
+                        app.keep_cookies(jar);
+                    }
                     let time = start_time.elapsed().as_millis();
-
-                    Ok(HttpResponse {
-                        status,
-                        status_text,
-                        headers,
-                        body,
+                    let mark = if failure.is_some() { "✖" } else { "✔" };
+                    println!(
+                        "  {} {} {} {} ({} ms)",
+                        mark, request.method, request.name, status, time
+                    );
+                    if attempt > 0 {
+                        println!("      {} retries", attempt);
+                    }
+                    if let Some(failure) = &failure {
+                        println!("      {}", failure);
+                    }
+                    let failed = failure.is_some();
+                    results.push(CliResult {
+                        collection: collection.name.clone(),
+                        name: if rows.len() > 1 {
+                            format!("{} [{}]", request.name, iteration + 1)
+                        } else {
+                            request.name.clone()
+                        },
                         time,
-                        body_size,
-                        headers_size,
-                    })
+                        failure,
+                    });
+                    if failed && bail {
+                        break 'rows;
+                    }
                 }
-                Err(e) => Err(format!("Request failed: {}", e)),
-            };
+            }
+        }
 
-            let _ = tx.send(result);
-        });
+        let failed = results
+            .iter()
+            .filter(|result| result.failure.is_some())
+            .count();
+        println!(
+            "\n{} requests, {} passed, {} failed",
+            results.len(),
+            results.len() - failed,
+            failed
+        );
+        if let Some(path) = &report_path
+            && let Err(e) = Self::write_junit_report(path, &results)
+        {
+            eprintln!("Failed to write report '{}': {}", path, e);
+            return 2;
+        }
+        if failed > 0 { 1 } else { 0 }
     }
 }
 
 fn main() -> EframeResult<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("run") {
+        std::process::exit(SendApp::run_headless(&args[2..]));
+    }
+    let deep_link = args
+        .get(1)
+        .filter(|arg| arg.starts_with("send://"))
+        .cloned();
+    if let Some(link) = &deep_link
+        && SendApp::forward_deep_link(link)
+    {
+        return Ok(());
+    }
+    let launch = if deep_link.is_some() {
+        LaunchArgs::default()
+    } else if matches!(args.get(1).map(String::as_str), Some("-h" | "--help")) {
+        println!("{}", LAUNCH_USAGE);
+        return Ok(());
+    } else {
+        match LaunchArgs::parse(&args[1..]) {
+            Ok(launch) => launch,
+            Err(e) => {
+                eprintln!("{}\n{}", e, LAUNCH_USAGE);
+                std::process::exit(2);
+            }
+        }
+    };
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
@@ -2097,6 +20684,665 @@ fn main() -> EframeResult<()> {
     eframe::run_native(
         "Send - HTTP Client",
         options,
-        Box::new(|_cc| Ok(Box::new(SendApp::default()))),
+        Box::new(|cc| {
+            let mut app = SendApp::default();
+            app.apply_theme(&cc.egui_ctx);
+            app.start_deep_link_listener(cc.egui_ctx.clone());
+            if let Some(link) = deep_link {
+                app.handle_deep_link(&link);
+            }
+            app.open_launch_args(launch);
+            Ok(Box::new(app))
+        }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // MS-NLMP 4.2.4: user "User", domain "Domain", password "Password"
+    fn ntlm_example() -> NtlmCredentials {
+        NtlmCredentials::new("User", "Password", "Domain", false)
+    }
+
+    const SERVER_CHALLENGE: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    const CLIENT_CHALLENGE: [u8; 8] = [0xaa; 8];
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    // MsvAvNbDomainName "Domain", MsvAvNbComputerName "Server", MsvAvEOL
+    fn ntlm_target_info() -> Vec<u8> {
+        let mut info = vec![0x02, 0x00, 0x0c, 0x00];
+        info.extend(NtlmCredentials::utf16("Domain"));
+        info.extend([0x01, 0x00, 0x0c, 0x00]);
+        info.extend(NtlmCredentials::utf16("Server"));
+        info.extend([0x00; 4]);
+        info
+    }
+
+    #[test]
+    fn ntlm_nt_hash() {
+        assert_eq!(
+            hex(&NtlmCredentials::nt_hash("Password")),
+            "a4f49c406510bdcab6824ee7c30fd852"
+        );
+    }
+
+    #[test]
+    fn ntlm_response_key() {
+        assert_eq!(
+            hex(&ntlm_example().response_key()),
+            "0c868a403bfd7a93a3001ef22ef02e3f"
+        );
+    }
+
+    #[test]
+    fn ntlm_proof_and_session_key() {
+        let key = ntlm_example().response_key();
+        let response = NtlmCredentials::nt_response(
+            &key,
+            &SERVER_CHALLENGE,
+            &CLIENT_CHALLENGE,
+            &[0; 8],
+            &ntlm_target_info(),
+        );
+        let proof = &response[..16];
+        assert_eq!(hex(proof), "68cd0ab851e51c96aabc927bebef6a1c");
+        assert_eq!(
+            hex(&NtlmCredentials::hmac_md5(&key, proof)),
+            "8de40ccadbc14a82f15cb0ad0de95ca3"
+        );
+    }
+
+    #[test]
+    fn ntlm_lm_response() {
+        let key = ntlm_example().response_key();
+        let proof = NtlmCredentials::hmac_md5(&key, &[SERVER_CHALLENGE, CLIENT_CHALLENGE].concat());
+        assert_eq!(hex(&proof), "86c35097ac9cec102554764a57cccc19");
+    }
+
+    #[test]
+    fn md5_tool_matches_rfc_1321() {
+        for (input, digest) in [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "c3fcd3d76192e4007dfb496cca67e13b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "d174ab98d277d9f5a5611c2c9f419d9f",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ] {
+            assert_eq!(Tool::Md5.run(input), Ok(digest.to_string()));
+        }
+    }
+
+    #[test]
+    fn csv_data_rows_keep_quoted_commas_and_quotes() {
+        let path = std::env::temp_dir().join(format!("send-data-{}.csv", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "name,note\n\"Doe, Jane\",\"said \"\"hi\"\"\"\nplain, spaced \n",
+        )
+        .unwrap();
+        let rows = SendApp::load_data_rows(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+        let pair = |key: &str, value: &str| (key.to_string(), value.to_string());
+        assert_eq!(
+            rows.unwrap(),
+            vec![
+                vec![pair("name", "Doe, Jane"), pair("note", "said \"hi\"")],
+                vec![pair("name", "plain"), pair("note", "spaced")],
+            ]
+        );
+    }
+
+    #[test]
+    fn cookie_date_reads_rfc_1123() {
+        assert_eq!(
+            SessionCookie::parse_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784111777)
+        );
+    }
+
+    #[test]
+    fn cookie_date_reads_netscape_dashes() {
+        assert_eq!(
+            SessionCookie::parse_date("Thu, 01-Jan-1970 00:00:01 GMT"),
+            Some(1)
+        );
+        assert_eq!(
+            SessionCookie::parse_date("Sunday, 06-Nov-94 08:49:37 GMT"),
+            Some(784111777)
+        );
+    }
+
+    #[test]
+    fn cookie_date_reads_asctime() {
+        assert_eq!(
+            SessionCookie::parse_date("Sun Nov  6 08:49:37 1994"),
+            Some(784111777)
+        );
+    }
+
+    #[test]
+    fn cookie_date_rejects_incomplete_or_invalid_dates() {
+        assert_eq!(SessionCookie::parse_date("Thu, 01-Jan-1970"), None);
+        assert_eq!(
+            SessionCookie::parse_date("Thu, 31-Feb-2024 00:00:00 GMT"),
+            None
+        );
+        assert_eq!(
+            SessionCookie::parse_date("Thu, 01-Jan-1970 24:00:00 GMT"),
+            None
+        );
+    }
+
+    #[test]
+    fn expired_netscape_cookie_deletes() {
+        let url = reqwest::Url::parse("https://example.com/").unwrap();
+        let cookie = SessionCookie::parse(
+            "session=; expires=Thu, 01-Jan-1970 00:00:01 GMT; path=/",
+            &url,
+        )
+        .unwrap();
+        assert!(cookie.is_expired());
+    }
+
+    fn resolved_request(method: &str, url: &str) -> ResolvedRequest {
+        ResolvedRequest {
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: vec![],
+            credential_headers: vec![],
+            body: String::new(),
+            body_type: BodyType::None,
+            form_data: vec![],
+            url_encoded_data: vec![],
+            body_file: String::new(),
+            oauth: None,
+            ntlm: None,
+            max_redirects: None,
+            http_version: HttpVersion::Auto,
+            upload: Arc::default(),
+            chunked: false,
+            expect_continue: ExpectContinue::Headers,
+            cookie_jar: None,
+        }
+    }
+
+    fn canned_response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+        let headers: String = headers
+            .iter()
+            .map(|(key, value)| format!("{}: {}\r\n", key, value))
+            .collect();
+        format!(
+            "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n{}\r\n{}",
+            status,
+            body.len(),
+            headers,
+            body
+        )
+    }
+
+    // Answers one request per connection with the next response, in order. Returns the
+    // base URL and the raw requests received so far.
+    async fn serve(responses: Vec<String>) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 4096];
+                loop {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    let Some(end) = text.find("\r\n\r\n") else {
+                        if read == 0 {
+                            break;
+                        }
+                        continue;
+                    };
+                    let length = text[..end]
+                        .lines()
+                        .filter_map(|line| line.split_once(':'))
+                        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if read == 0 || request.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+                log.lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&request).to_string());
+                stream.write_all(response.as_bytes()).await.unwrap();
+                let _ = stream.shutdown().await;
+            }
+        });
+        (url, received)
+    }
+
+    fn header_values(raw_request: &str, name: &str) -> Vec<String> {
+        raw_request
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_string())
+            .collect()
+    }
+
+    // Redirects are left to send_resolved, as with the app's clients
+    fn test_client() -> reqwest::Client {
+        reqwest::Client::builder()
+            .no_proxy()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn oauth_retry_sends_one_authorization_header() {
+        let token = |value: &str| {
+            canned_response(
+                "200 OK",
+                &[("content-type", "application/json")],
+                &format!("{{\"access_token\":\"{}\",\"expires_in\":3600}}", value),
+            )
+        };
+        let (url, received) = serve(vec![
+            token("old"),
+            canned_response("401 Unauthorized", &[], ""),
+            token("new"),
+            canned_response("200 OK", &[], ""),
+        ])
+        .await;
+        let mut request = resolved_request("GET", &format!("{}/resource", url));
+        request.oauth = Some(ClientCredentials {
+            token_url: format!("{}/token", url),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            scopes: String::new(),
+            cache: OAuthTokenCache::default(),
+        });
+        let response = SendApp::send_once(&test_client(), &request).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let received = received.lock().unwrap();
+        assert_eq!(header_values(&received[1], "authorization"), ["Bearer old"]);
+        assert_eq!(header_values(&received[3], "authorization"), ["Bearer new"]);
+    }
+
+    fn test_app() -> SendApp {
+        SendApp::with_settings(AppSettings::default())
+    }
+
+    fn test_request(app: &SendApp, method: &str, url: &str) -> HttpRequest {
+        let mut request = SendApp::new_default_request(&app.settings);
+        request.method = method.to_string();
+        request.url = url.to_string();
+        request
+    }
+
+    #[test]
+    fn preview_shows_oauth_token_without_fetching_it() {
+        let app = test_app();
+        let mut request = test_request(&app, "GET", "http://127.0.0.1:9/resource");
+        // Nothing listens on the discard port, so a token fetch would fail the preview
+        request.auth = RequestAuth::OAuth2 {
+            token_url: "http://127.0.0.1:9/token".to_string(),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            scopes: String::new(),
+        };
+        let (_, raw) = app.raw_request_preview(&request).unwrap();
+        assert!(raw.contains("authorization: Bearer <fetched when sent>"));
+        assert_eq!(raw.matches("authorization:").count(), 1);
+    }
+
+    #[test]
+    fn preview_shows_ntlm_handshake_placeholder() {
+        let app = test_app();
+        let mut request = test_request(&app, "GET", "http://127.0.0.1:9/resource");
+        request.auth = RequestAuth::Ntlm {
+            username: "DOMAIN\\user".to_string(),
+            password: "password".to_string(),
+            domain: String::new(),
+            negotiate: false,
+        };
+        let (_, raw) = app.raw_request_preview(&request).unwrap();
+        assert!(raw.contains("authorization: NTLM <from the NTLM handshake when sent>"));
+    }
+
+    fn estimate(app: &SendApp, request: &HttpRequest) -> (usize, usize) {
+        app.estimate_request_size(request, &app.resolve_request(request))
+    }
+
+    #[test]
+    fn estimate_counts_headers_for_form_bodies() {
+        let app = test_app();
+        for body_type in [BodyType::UrlEncoded, BodyType::FormData] {
+            let mut request = test_request(&app, "POST", "http://example.com/form");
+            request.body_type = body_type;
+            request.url_encoded_data = vec![KeyValue::new("a", "1")];
+            request.form_data = vec![FormDataEntry::text("a".to_string(), "1".to_string())];
+            let (without, _) = estimate(&app, &request);
+            request.headers.push(KeyValue::new("X-Custom", "value"));
+            let (with, _) = estimate(&app, &request);
+            assert_eq!(with - without, "X-Custom: value\r\n".len());
+        }
+    }
+
+    #[test]
+    fn estimate_uses_the_estimated_requests_user_agent() {
+        let app = test_app();
+        let mut short = test_request(&app, "GET", "http://example.com/");
+        short.settings.user_agent = Some("agent/1".to_string());
+        let mut long = short.clone();
+        long.settings.user_agent = Some("a-longer-agent/1.0".to_string());
+        assert_eq!(
+            estimate(&app, &long).0 - estimate(&app, &short).0,
+            "a-longer-agent/1.0".len() - "agent/1".len()
+        );
+    }
+
+    #[test]
+    fn estimate_takes_body_file_size_from_metadata() {
+        let path = std::env::temp_dir().join(format!("send-body-{}.bin", Uuid::new_v4()));
+        std::fs::write(&path, vec![0u8; 1000]).unwrap();
+        let app = test_app();
+        let mut request = test_request(&app, "PUT", "http://example.com/upload");
+        request.body_type = BodyType::Binary;
+        request.binary_file = path.to_string_lossy().to_string();
+        let (_, body) = estimate(&app, &request);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(body, 1000);
+    }
+
+    #[tokio::test]
+    async fn cross_origin_redirect_drops_credentials() {
+        let (target, target_received) = serve(vec![canned_response("200 OK", &[], "")]).await;
+        let location = format!("{}/landed", target);
+        let (origin, origin_received) = serve(vec![canned_response(
+            "302 Found",
+            &[("location", location.as_str())],
+            "",
+        )])
+        .await;
+        let mut request = resolved_request("GET", &format!("{}/start", origin));
+        let header = |key: &str, value: &str| (key.to_string(), value.to_string());
+        request.headers = vec![
+            header("Authorization", "Bearer abc"),
+            header("X-Api-Key", "key"),
+            header("X-Session-Token", "token"),
+            header("X-Signature", "signed"),
+            header("X-Trace", "kept"),
+        ];
+        request.credential_headers = vec!["X-Signature".to_string()];
+        request.max_redirects = Some(5);
+        let clients = HttpClients {
+            default: test_client(),
+            identities: vec![],
+            connections: Arc::default(),
+        };
+        let response = SendApp::send_resolved(&clients, &request).await.unwrap();
+        assert_eq!(response.url().as_str(), location);
+
+        let sent = &origin_received.lock().unwrap()[0];
+        assert_eq!(header_values(sent, "x-signature"), ["signed"]);
+        let redirected = &target_received.lock().unwrap()[0];
+        for name in [
+            "authorization",
+            "x-api-key",
+            "x-session-token",
+            "x-signature",
+        ] {
+            assert!(
+                header_values(redirected, name).is_empty(),
+                "{} was sent",
+                name
+            );
+        }
+        assert_eq!(header_values(redirected, "x-trace"), ["kept"]);
+    }
+
+    #[test]
+    fn api_key_header_counts_as_a_credential() {
+        let app = test_app();
+        let mut request = test_request(&app, "GET", "http://example.com/");
+        request.auth = RequestAuth::ApiKey {
+            key: "X-Client".to_string(),
+            value: "abc".to_string(),
+            placement: ApiKeyPlacement::Header,
+        };
+        assert_eq!(
+            app.resolve_request(&request).credential_headers,
+            ["X-Client"]
+        );
+    }
+
+    async fn relay_exchange(relay: &NtlmProxyRelay, request: &str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let address = relay.url.trim_start_matches("http://");
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = Vec::new();
+        let _ =
+            tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+        String::from_utf8_lossy(&response).to_string()
+    }
+
+    #[tokio::test]
+    async fn ntlm_relay_requires_its_session_credentials() {
+        let (upstream, received) = serve(vec![canned_response("200 OK", &[], "")]).await;
+        let relay = NtlmProxyRelay::start(&ProxySettings {
+            enabled: true,
+            url: upstream,
+            username: "DOMAIN\\user".to_string(),
+            password: "password".to_string(),
+            auth: ProxyAuth::Ntlm,
+            ..ProxySettings::default()
+        })
+        .unwrap();
+
+        let anonymous = relay_exchange(
+            &relay,
+            "GET http://example.com/ HTTP/1.1\r\nHost: example.com\r\n\r\n",
+        )
+        .await;
+        assert!(anonymous.starts_with("HTTP/1.1 407"));
+        assert!(received.lock().unwrap().is_empty());
+
+        let settings = relay.proxy_settings(&ProxySettings::default());
+        let authorization = RequestAuth::basic_header(&settings.username, &settings.password);
+        relay_exchange(
+            &relay,
+            &format!(
+                "GET http://example.com/ HTTP/1.1\r\nHost: example.com\r\n\
+                 Proxy-Authorization: {}\r\n\r\n",
+                authorization
+            ),
+        )
+        .await;
+        let received = received.lock().unwrap();
+        let probe = header_values(&received[0], "proxy-authorization");
+        assert_eq!(probe.len(), 1);
+        assert!(probe[0].starts_with("NTLM "));
+    }
+
+    #[test]
+    fn params_follow_the_url_query_and_keep_switched_off_rows() {
+        let app = test_app();
+        let mut request = test_request(&app, "GET", "http://example.com/?a=1&b=two+words");
+        request.query_params = vec![KeyValue {
+            enabled: false,
+            ..KeyValue::new("off", "x")
+        }];
+        request.sync_query_params();
+        assert_eq!(
+            request.query_params,
+            vec![
+                KeyValue {
+                    enabled: false,
+                    ..KeyValue::new("off", "x")
+                },
+                KeyValue::new("a", "1"),
+                KeyValue::new("b", "two words"),
+            ]
+        );
+    }
+
+    #[test]
+    fn url_query_keeps_the_text_of_unchanged_params() {
+        let app = test_app();
+        let mut request = test_request(&app, "GET", "http://example.com/?q=a+b&flag#top");
+        request.sync_query_params();
+        request.query_params.push(KeyValue::new("c", "x y&{{id}}"));
+        request.sync_url_query();
+        assert_eq!(
+            request.url,
+            "http://example.com/?q=a+b&flag&c=x%20y%26{{id}}#top"
+        );
+    }
+
+    #[test]
+    fn path_variables_follow_the_url() {
+        let app = test_app();
+        let mut request = test_request(&app, "GET", "http://{{host}}:8080/users/:id/posts/{post}");
+        request.path_variables = vec![
+            ("id".to_string(), "7".to_string()),
+            ("gone".to_string(), "1".to_string()),
+        ];
+        request.sync_path_variables();
+        assert_eq!(
+            request.path_variables,
+            vec![
+                ("id".to_string(), "7".to_string()),
+                ("post".to_string(), String::new()),
+            ]
+        );
+        assert_eq!(
+            request.url_with_path_variables(str::to_string),
+            "http://{{host}}:8080/users/7/posts/{post}"
+        );
+    }
+
+    #[test]
+    fn capture_rule_takes_the_first_group_or_whole_match() {
+        let body = r#"{"token": "abc123", "id": 42}"#;
+        let no_headers = |_: &str| None;
+        let rule = CaptureRule {
+            pattern: r#""token": "(\w+)""#.to_string(),
+            ..CaptureRule::default()
+        };
+        assert_eq!(rule.apply(body, no_headers), Ok("abc123".to_string()));
+        let rule = CaptureRule {
+            pattern: r"\d+".to_string(),
+            ..CaptureRule::default()
+        };
+        assert_eq!(rule.apply(body, no_headers), Ok("123".to_string()));
+        let rule = CaptureRule {
+            pattern: "missing".to_string(),
+            ..CaptureRule::default()
+        };
+        assert!(rule.apply(body, no_headers).is_err());
+    }
+
+    #[test]
+    fn capture_rule_reads_a_header() {
+        let rule = CaptureRule {
+            source: CaptureSource::Header,
+            header: " Location ".to_string(),
+            pattern: r"/orders/(\d+)".to_string(),
+            ..CaptureRule::default()
+        };
+        let header = |name: &str| (name == "Location").then(|| "/orders/99".to_string());
+        assert_eq!(rule.apply("", header), Ok("99".to_string()));
+        assert!(rule.apply("", |_: &str| None).is_err());
+    }
+
+    #[test]
+    fn retry_delay_backs_off_and_caps_retry_after() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            backoff_ms: 100,
+            max_retry_after_secs: 5,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.delay_for(0, None), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2, None), Duration::from_millis(400));
+        assert_eq!(
+            policy.delay_for(0, Some(Duration::from_secs(120))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_delay_follows_the_policy() {
+        let (url, _) = serve(vec![
+            canned_response("503 Service Unavailable", &[("retry-after", "2")], ""),
+            canned_response("503 Service Unavailable", &[], ""),
+            canned_response("404 Not Found", &[], ""),
+        ])
+        .await;
+        let policy = RetryPolicy {
+            max_retries: 1,
+            backoff_ms: 100,
+            ..RetryPolicy::default()
+        };
+        let client = test_client();
+        let unavailable = client.get(&url).send().await;
+        assert_eq!(
+            policy.retry_delay(0, &unavailable),
+            Some(Duration::from_secs(2))
+        );
+        let unavailable = client.get(&url).send().await;
+        assert_eq!(policy.retry_delay(1, &unavailable), None);
+        let not_found = client.get(&url).send().await;
+        assert_eq!(policy.retry_delay(0, &not_found), None);
+    }
+
+    #[test]
+    fn junit_report_groups_by_collection_and_escapes() {
+        let result = |collection: &str, name: &str, failure: Option<&str>| CliResult {
+            collection: collection.to_string(),
+            name: name.to_string(),
+            time: 1500,
+            failure: failure.map(str::to_string),
+        };
+        let path = std::env::temp_dir().join(format!("send-junit-{}.xml", Uuid::new_v4()));
+        SendApp::write_junit_report(
+            path.to_str().unwrap(),
+            &[
+                result("API", "List <users>", None),
+                result("Other", "Ping", None),
+                result("API", "Create", Some("expected \"201\" & got 500")),
+            ],
+        )
+        .unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(xml.contains("<testsuites tests=\"3\" failures=\"1\">"));
+        assert!(xml.contains("<testsuite name=\"API\" tests=\"2\" failures=\"1\" time=\"3.000\">"));
+        assert!(
+            xml.contains("<testsuite name=\"Other\" tests=\"1\" failures=\"0\" time=\"1.500\">")
+        );
+        assert!(xml.contains("name=\"List &lt;users&gt;\" time=\"1.500\"/>"));
+        assert!(xml.contains("<failure message=\"expected &quot;201&quot; &amp; got 500\"/>"));
+    }
+}